@@ -469,6 +469,7 @@ impl Repo {
         let mut class_count = 0;
         let total = filez.len();
         let mut impl_relationships = Vec::new();
+        let mut class_dependencies = Vec::new();
 
         info!("=> get_classes...");
 
@@ -506,6 +507,11 @@ impl Repo {
                     },
                 ));
             }
+
+            if let Some(dep_query) = lang.lang().class_dependency_query() {
+                let q = lang.q(&dep_query, &NodeType::Class);
+                class_dependencies.extend(lang.collect_class_dependencies(&q, code, filename)?);
+            }
         }
 
         let mut stats = HashMap::new();
@@ -516,8 +522,38 @@ impl Repo {
         info!("=> got {} classes", class_count);
         graph.class_inherits();
         graph.class_includes();
+        self.add_class_dependencies(graph, class_dependencies);
         Ok(impl_relationships)
     }
+    fn add_class_dependencies<G: Graph>(
+        &self,
+        graph: &mut G,
+        dependencies: Vec<(String, String, String)>,
+    ) {
+        let mut edge_count = 0;
+        for (class_name, dependency, file) in dependencies {
+            let Some(class) = graph.find_node_by_name_in_file(NodeType::Class, &class_name, &file)
+            else {
+                continue;
+            };
+            if let Some(target) = graph
+                .find_nodes_by_name(NodeType::Class, &dependency)
+                .into_iter()
+                .find(|t| t.name != class.name || t.file != class.file)
+            {
+                graph.add_edge(&Edge::depends_on(
+                    NodeType::Class,
+                    &class,
+                    NodeType::Class,
+                    &target,
+                ));
+                edge_count += 1;
+            }
+        }
+        if edge_count > 0 {
+            info!("=> created {} class DEPENDS_ON edges", edge_count);
+        }
+    }
     fn resolve_implements_edges<G: Graph>(
         &self,
         graph: &mut G,
@@ -2,8 +2,17 @@ use super::utils::*;
 use crate::lang::call_finder::{parse_imports_for_file, IMPORT_CACHE};
 use crate::lang::{
     asg::{NodeData, TestRecord},
-    graphs::{Graph, NodeType},
+    channels::add_channel_nodes,
+    commands::add_command_nodes,
+    config_keys::add_config_key_nodes,
+    errors::add_error_edges,
+    fields::type_references,
+    graphs::{Edge, Graph, NodeType},
+    jobs::add_job_nodes,
     linker::link_tests,
+    modules::add_module_nodes,
+    overrides::add_override_edges,
+    queues::add_queue_nodes,
 };
 use crate::repo::Repo;
 use shared::error::Result;
@@ -269,12 +278,80 @@ impl Repo {
             }
         }
 
+        self.add_repository_models(graph);
+        self.add_field_type_links(graph);
+
         let mut stats = HashMap::new();
         stats.insert("data_models".to_string(), datamodel_count);
         info!("=> got {} data models", datamodel_count);
         runner.finish(stats);
         Ok(())
     }
+    // data-access interfaces (e.g. Spring Data repositories) CONTAINS the model they manage
+    fn add_repository_models<G: Graph>(&self, graph: &mut G) {
+        let mut edge_count = 0;
+        for repository in graph.find_nodes_by_type(NodeType::Trait) {
+            let Some(model) = self.lang.lang().repository_model(&repository.body) else {
+                continue;
+            };
+            let models = graph.find_nodes_by_name(NodeType::DataModel, &model);
+            if let Some(dm) = models.first() {
+                graph.add_edge(&Edge::contains(
+                    NodeType::Trait,
+                    &repository,
+                    NodeType::DataModel,
+                    dm,
+                ));
+                edge_count += 1;
+            }
+        }
+        if edge_count > 0 {
+            info!("=> linked {} repositories to data models", edge_count);
+        }
+    }
+    // a DataModel CONTAINS the DataModels its fields are typed with, e.g. `address: Option<Address>`
+    fn add_field_type_links<G: Graph>(&self, graph: &mut G) {
+        let mut edge_count = 0;
+        for dm in graph.find_nodes_by_type(NodeType::DataModel) {
+            let mut linked = HashSet::new();
+            for field in dm.fields() {
+                let Some(type_name) = &field.type_name else {
+                    continue;
+                };
+                for reference in type_references(type_name) {
+                    if reference == dm.name || !linked.insert(reference.clone()) {
+                        continue;
+                    }
+                    let targets = graph.find_nodes_by_name(NodeType::DataModel, &reference);
+                    // a name declared in several files only links when the model's own file
+                    // or its imports say which one is meant
+                    let target = targets
+                        .iter()
+                        .find(|t| t.file == dm.file)
+                        .or_else(|| targets.first().filter(|_| targets.len() == 1))
+                        .or_else(|| {
+                            let imports = parse_imports_for_file(&dm.file, &self.lang, graph)?;
+                            let (path, _) = imports
+                                .iter()
+                                .find(|(_, names)| names.contains(&reference))?;
+                            targets.iter().find(|t| t.file.contains(path.as_str()))
+                        });
+                    if let Some(target) = target {
+                        graph.add_edge(&Edge::contains(
+                            NodeType::DataModel,
+                            &dm,
+                            NodeType::DataModel,
+                            target,
+                        ));
+                        edge_count += 1;
+                    }
+                }
+            }
+        }
+        if edge_count > 0 {
+            info!("=> linked {} data model fields to data models", edge_count);
+        }
+    }
     pub async fn process_functions_and_tests<G: Graph + Sync>(
         &self,
         graph: &mut G,
@@ -550,6 +627,18 @@ impl Repo {
         info!("=> got {} import edges", import_edges_count);
         log_stage_timing("finalize_import_edges", sub_start, Some(&format!("import_edges={}", import_edges_count)));
 
+        let override_count = add_override_edges(graph, &self.lang.kind);
+        if override_count > 0 {
+            stats.insert("overrides".to_string(), override_count);
+            info!("=> got {} method overrides", override_count);
+        }
+
+        let module_count = add_module_nodes(graph, &self.lang.kind, filez);
+        if module_count > 0 {
+            stats.insert("modules".to_string(), module_count);
+            info!("=> got {} modules", module_count);
+        }
+
         let sub_start = Instant::now();
         let registry = crate::lang::registry::build(&self.lang, &*graph, filez);
         log_stage_timing("type_registry_build", sub_start, Some(&format!("built={}", registry.is_some())));
@@ -621,6 +710,42 @@ impl Repo {
             log_stage_timing("finalize_function_calls", sub_start, Some(&format!("function_calls={}", function_call_count)));
         }
 
+        let queue_count = add_queue_nodes(graph, &self.lang.kind, filez);
+        if queue_count > 0 {
+            stats.insert("queues".to_string(), queue_count);
+            info!("=> got {} queues", queue_count);
+        }
+
+        let channel_count = add_channel_nodes(graph, &self.lang.kind, filez);
+        if channel_count > 0 {
+            stats.insert("channels".to_string(), channel_count);
+            info!("=> got {} realtime channels", channel_count);
+        }
+
+        let job_count = add_job_nodes(graph, &self.lang.kind, filez);
+        if job_count > 0 {
+            stats.insert("jobs".to_string(), job_count);
+            info!("=> got {} scheduled jobs", job_count);
+        }
+
+        let command_count = add_command_nodes(graph, &self.lang.kind, filez);
+        if command_count > 0 {
+            stats.insert("commands".to_string(), command_count);
+            info!("=> got {} cli commands", command_count);
+        }
+
+        let config_key_count = add_config_key_nodes(graph, &self.lang.kind, filez);
+        if config_key_count > 0 {
+            stats.insert("config_keys".to_string(), config_key_count);
+            info!("=> got {} config keys", config_key_count);
+        }
+
+        let error_edge_count = add_error_edges(graph, &self.lang.kind, filez);
+        if error_edge_count > 0 {
+            stats.insert("error_edges".to_string(), error_edge_count);
+            info!("=> got {} throws/catches edges", error_edge_count);
+        }
+
         link_tests(graph)?;
 
         graph.prune_orphan_functions(&self.lang);
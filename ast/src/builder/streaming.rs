@@ -34,11 +34,12 @@ impl GraphStreamingUploader {
         Ok(())
     }
 
-    pub async fn flush_edges_stage(
+    pub async fn flush_edges_stage<G: Graph>(
         &mut self,
         neo: &Neo4jGraph,
         stage: &str,
         edges: &BTreeSet<(String, String, EdgeType)>,
+        graph: &G,
     ) -> Result<()> {
         if edges.is_empty() {
             return Ok(());
@@ -53,6 +54,8 @@ impl GraphStreamingUploader {
                     e.1.clone(),
                     e.2.clone(),
                     Uuid::new_v4().to_string(),
+                    graph.get_call_resolution(&e.0, &e.1, &e.2),
+                    graph.get_call_sites(&e.0, &e.1, &e.2),
                 )
             }),
             256,
@@ -123,7 +126,7 @@ pub async fn flush_stage_nodes_and_edges<G: Graph>(
     let all_edges = graph.get_edge_keys();
     let new_edges: BTreeSet<_> = all_edges.iter().skip(ctx.flushed_edge_count).cloned().collect();
     if !new_edges.is_empty() {
-        ctx.uploader.flush_edges_stage(&ctx.neo, stage, &new_edges).await?;
+        ctx.uploader.flush_edges_stage(&ctx.neo, stage, &new_edges, graph).await?;
         ctx.flushed_edge_count = all_edges.len();
     }
     Ok(())
@@ -0,0 +1,928 @@
+use super::yaml::{self, Yaml};
+use super::{file_name, last_line, normalize, slice_lines, InfraFile};
+use crate::lang::graphs::{Edge, Graph, NodeType};
+use crate::lang::NodeData;
+use lsp::strip_tmp;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// GitLab top-level keys that configure the pipeline rather than declare a job.
+const GITLAB_RESERVED: [&str; 10] = [
+    "stages",
+    "variables",
+    "include",
+    "default",
+    "workflow",
+    "image",
+    "services",
+    "before_script",
+    "after_script",
+    "cache",
+];
+
+/// Commands that run a test suite, matched against the start of a shell command.
+const TEST_RUNNERS: [&str; 24] = [
+    "cargo test",
+    "cargo nextest",
+    "go test",
+    "pytest",
+    "python -m pytest",
+    "python -m unittest",
+    "jest",
+    "vitest",
+    "mocha",
+    "npm test",
+    "npm run test",
+    "yarn test",
+    "pnpm test",
+    "bun test",
+    "deno test",
+    "rspec",
+    "bundle exec rspec",
+    "phpunit",
+    "dotnet test",
+    "mvn test",
+    "gradle test",
+    "./gradlew test",
+    "mix test",
+    "swift test",
+];
+
+/// Prefixes that wrap the real command (`npx jest`, `poetry run pytest`).
+const COMMAND_WRAPPERS: [&str; 7] = [
+    "npx ",
+    "poetry run ",
+    "uv run ",
+    "pipenv run ",
+    "bundle exec ",
+    "yarn ",
+    "pnpm exec ",
+];
+
+const SCRIPT_EXTS: [&str; 9] = ["sh", "bash", "py", "js", "mjs", "ts", "rb", "pl", "ps1"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunRef {
+    File(String),
+    Dir(String),
+    Make { dir: String, target: String },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StepSpec {
+    pub name: String,
+    pub start: usize,
+    pub end: usize,
+    pub run: Option<String>,
+    pub uses: Option<String>,
+    pub test_runner: Option<String>,
+    pub refs: Vec<RunRef>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JobSpec {
+    pub name: String,
+    pub start: usize,
+    pub end: usize,
+    pub runs_on: Option<String>,
+    pub stage: Option<String>,
+    pub needs: Vec<String>,
+    pub steps: Vec<StepSpec>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowSpec {
+    pub name: String,
+    pub file: String,
+    pub platform: String,
+    pub triggers: Vec<String>,
+    pub end: usize,
+    pub jobs: Vec<JobSpec>,
+}
+
+pub fn is_github_workflow(path: &str) -> bool {
+    path.contains(".github/workflows/") && (path.ends_with(".yml") || path.ends_with(".yaml"))
+}
+
+pub fn is_gitlab_ci(path: &str) -> bool {
+    path.ends_with(".gitlab-ci.yml") || path.ends_with(".gitlab-ci.yaml")
+}
+
+pub fn is_makefile(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    matches!(name, "Makefile" | "makefile" | "GNUmakefile") || name.ends_with(".mk")
+}
+
+pub fn is_ci_file(path: &str) -> bool {
+    is_github_workflow(path) || is_gitlab_ci(path) || is_makefile(path)
+}
+
+pub fn parse_ci_file(file: &InfraFile) -> Option<WorkflowSpec> {
+    if is_github_workflow(&file.path) {
+        // .github/workflows/<file> lives three levels below the checkout root
+        let root = Path::new(&file.path).ancestors().nth(3)?.to_path_buf();
+        Some(parse_github_workflow(file, &root))
+    } else if is_gitlab_ci(&file.path) {
+        let root = Path::new(&file.path).parent()?.to_path_buf();
+        Some(parse_gitlab_ci(file, &root))
+    } else if is_makefile(&file.path) {
+        let dir = Path::new(&file.path).parent()?.to_path_buf();
+        Some(parse_makefile(file, &dir))
+    } else {
+        None
+    }
+}
+
+fn parse_github_workflow(file: &InfraFile, root: &Path) -> WorkflowSpec {
+    let doc = yaml::parse(&file.code);
+    let lines: Vec<&str> = file.code.lines().collect();
+    let stem = file_stem(&file.path);
+    let name = doc
+        .get("name")
+        .and_then(|n| n.as_str())
+        .unwrap_or(&stem)
+        .to_string();
+    let triggers = doc
+        .get("on")
+        .map(|o| o.keys_or_strings())
+        .unwrap_or_default();
+    let default_dir = doc
+        .get_path(&["defaults", "run", "working-directory"])
+        .and_then(|d| d.as_str());
+
+    let mut jobs = Vec::new();
+    for entry in doc.get("jobs").map(|j| j.entries()).unwrap_or(&[]) {
+        let job = &entry.value;
+        let job_dir = job
+            .get_path(&["defaults", "run", "working-directory"])
+            .and_then(|d| d.as_str())
+            .or(default_dir);
+        let mut spec = JobSpec {
+            name: entry.key.clone(),
+            start: entry.line,
+            end: last_line(job).unwrap_or(entry.line),
+            runs_on: job.get("runs-on").map(|r| r.strings().join(",")),
+            needs: job.get("needs").map(|n| n.strings()).unwrap_or_default(),
+            ..Default::default()
+        };
+        let steps = job.get("steps").map(|s| s.items()).unwrap_or(&[]);
+        for (i, step) in steps.iter().enumerate() {
+            let start = step.first_line().unwrap_or(spec.start);
+            let end = steps
+                .get(i + 1)
+                .and_then(|s| s.first_line())
+                .map(|l| l.saturating_sub(1))
+                .unwrap_or(spec.end);
+            let run = step
+                .get("run")
+                .and_then(|r| r.as_str())
+                .map(|r| r.to_string());
+            let uses = step
+                .get("uses")
+                .and_then(|u| u.as_str())
+                .map(|u| u.to_string());
+            let step_dir = step
+                .get("working-directory")
+                .and_then(|d| d.as_str())
+                .or(job_dir);
+            let cwd = resolve(root, step_dir.unwrap_or("."));
+            let label = step
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map(|n| n.to_string())
+                .or_else(|| uses.clone())
+                .or_else(|| run.as_deref().map(step_label))
+                .unwrap_or_else(|| format!("step {}", i + 1));
+            spec.steps
+                .push(step_spec(label, start, end, run, uses, &cwd));
+        }
+        jobs.push(spec);
+    }
+
+    WorkflowSpec {
+        name,
+        file: file.path.clone(),
+        platform: "github".to_string(),
+        triggers,
+        end: lines.len().saturating_sub(1),
+        jobs,
+    }
+}
+
+fn parse_gitlab_ci(file: &InfraFile, root: &Path) -> WorkflowSpec {
+    let doc = yaml::parse(&file.code);
+    let lines: Vec<&str> = file.code.lines().collect();
+    let cwd = root.display().to_string();
+    let mut jobs = Vec::new();
+    for entry in doc.entries() {
+        if entry.key.starts_with('.') || GITLAB_RESERVED.contains(&entry.key.as_str()) {
+            continue;
+        }
+        let job = &entry.value;
+        if !matches!(job, Yaml::Map(_)) {
+            continue;
+        }
+        let mut spec = JobSpec {
+            name: entry.key.clone(),
+            start: entry.line,
+            end: last_line(job).unwrap_or(entry.line),
+            stage: job
+                .get("stage")
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string()),
+            runs_on: job
+                .get("image")
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string()),
+            needs: job
+                .get("needs")
+                .or_else(|| job.get("dependencies"))
+                .map(needs_list)
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        for key in ["before_script", "script", "after_script"] {
+            let Some(script) = job.get(key) else {
+                continue;
+            };
+            let line = job
+                .entries()
+                .iter()
+                .find(|e| e.key == key)
+                .map(|e| e.line)
+                .unwrap_or(spec.start);
+            let run = script.strings().join("\n");
+            if run.is_empty() {
+                continue;
+            }
+            let end = last_line(script).unwrap_or(line);
+            spec.steps
+                .push(step_spec(key.to_string(), line, end, Some(run), None, &cwd));
+        }
+        jobs.push(spec);
+    }
+    WorkflowSpec {
+        name: file_name(&file.path).to_string(),
+        file: file.path.clone(),
+        platform: "gitlab".to_string(),
+        triggers: Vec::new(),
+        end: lines.len().saturating_sub(1),
+        jobs,
+    }
+}
+
+/// `needs: [build]` or `needs: [{job: build, artifacts: true}]`
+fn needs_list(needs: &Yaml) -> Vec<String> {
+    needs
+        .items()
+        .iter()
+        .filter_map(|n| match n {
+            Yaml::Scalar(s) => Some(s.clone()),
+            other => other
+                .get("job")
+                .and_then(|j| j.as_str())
+                .map(|j| j.to_string()),
+        })
+        .collect()
+}
+
+fn parse_makefile(file: &InfraFile, dir: &Path) -> WorkflowSpec {
+    let lines: Vec<&str> = file.code.lines().collect();
+    let cwd = dir.display().to_string();
+    let mut jobs: Vec<JobSpec> = Vec::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let Some((targets, prereqs)) = make_rule(line) else {
+            i += 1;
+            continue;
+        };
+        let start = i;
+        let mut recipe: Vec<(usize, String)> = Vec::new();
+        i += 1;
+        while i < lines.len() && (lines[i].starts_with('\t') || lines[i].trim().is_empty()) {
+            let cmd = lines[i].trim().trim_start_matches(['@', '-', '+']).trim();
+            if !cmd.is_empty() && !cmd.starts_with('#') {
+                recipe.push((i, cmd.to_string()));
+            }
+            i += 1;
+        }
+        let end = recipe.last().map(|(l, _)| *l).unwrap_or(start);
+        for target in targets {
+            let steps = recipe
+                .iter()
+                .map(|(l, cmd)| step_spec(step_label(cmd), *l, *l, Some(cmd.clone()), None, &cwd))
+                .collect();
+            jobs.push(JobSpec {
+                name: target,
+                start,
+                end,
+                needs: prereqs.clone(),
+                steps,
+                ..Default::default()
+            });
+        }
+    }
+
+    let names: Vec<String> = jobs.iter().map(|j| j.name.clone()).collect();
+    for job in &mut jobs {
+        // file prerequisites are not jobs
+        job.needs.retain(|n| names.contains(n));
+    }
+
+    WorkflowSpec {
+        name: file_name(&file.path).to_string(),
+        file: file.path.clone(),
+        platform: "make".to_string(),
+        triggers: Vec::new(),
+        end: lines.len().saturating_sub(1),
+        jobs,
+    }
+}
+
+/// `build test: deps` -> (["build", "test"], ["deps"]); skips variables, special and pattern rules.
+fn make_rule(line: &str) -> Option<(Vec<String>, Vec<String>)> {
+    if line.starts_with(['\t', ' ', '#', '.']) || line.contains('%') {
+        return None;
+    }
+    let (targets, rest) = line.split_once(':')?;
+    if rest.starts_with('=') || targets.contains('=') || targets.contains('$') {
+        return None;
+    }
+    let rest = rest.trim_start_matches(':');
+    let prereqs = rest
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .split_whitespace()
+        .map(|p| p.to_string())
+        .collect();
+    let targets: Vec<String> = targets.split_whitespace().map(|t| t.to_string()).collect();
+    if targets.is_empty() {
+        return None;
+    }
+    Some((targets, prereqs))
+}
+
+fn step_spec(
+    name: String,
+    start: usize,
+    end: usize,
+    run: Option<String>,
+    uses: Option<String>,
+    cwd: &str,
+) -> StepSpec {
+    let mut spec = StepSpec {
+        name,
+        start,
+        end,
+        run,
+        uses,
+        ..Default::default()
+    };
+    if let Some(run) = &spec.run {
+        let (refs, runner) = analyze_script(run, cwd);
+        spec.refs = refs;
+        spec.test_runner = runner;
+    }
+    spec
+}
+
+fn step_label(run: &str) -> String {
+    let first = run.lines().next().unwrap_or("").trim();
+    if first.chars().count() > 60 {
+        format!("{}...", first.chars().take(57).collect::<String>())
+    } else {
+        first.to_string()
+    }
+}
+
+/// Finds the scripts, test paths and make targets a shell snippet invokes.
+/// Paths resolve against `cwd`, which follows `cd` within the snippet.
+pub fn analyze_script(script: &str, cwd: &str) -> (Vec<RunRef>, Option<String>) {
+    let mut refs: Vec<RunRef> = Vec::new();
+    let mut runner: Option<String> = None;
+    let mut cwd = PathBuf::from(cwd);
+
+    let joined = script.replace("\\\n", " ");
+    for line in joined.lines() {
+        for command in line.split(['&', ';', '|']) {
+            let command = strip_env_prefix(command.trim());
+            if command.is_empty() || command.starts_with('#') {
+                continue;
+            }
+            let tokens: Vec<&str> = command.split_whitespace().collect();
+            match tokens.as_slice() {
+                ["cd", dir, ..] => {
+                    cwd = normalize(&cwd.join(dir));
+                    continue;
+                }
+                ["make", args @ ..] | ["gmake", args @ ..] => {
+                    refs.extend(make_refs(args, &cwd));
+                    continue;
+                }
+                _ => {}
+            }
+
+            let unwrapped = unwrap_command(command);
+            if let Some(r) = TEST_RUNNERS
+                .iter()
+                .find(|r| unwrapped == **r || unwrapped.starts_with(&format!("{} ", r)))
+            {
+                runner.get_or_insert_with(|| r.to_string());
+                let args: Vec<&str> = unwrapped[r.len()..].split_whitespace().collect();
+                let paths: Vec<&str> = args
+                    .iter()
+                    .copied()
+                    .filter(|a| !a.starts_with('-') && looks_like_path(a))
+                    .collect();
+                if paths.is_empty() {
+                    push_unique(&mut refs, RunRef::Dir(display(&cwd)));
+                }
+                for p in paths {
+                    let p = p.trim_end_matches("/...").trim_end_matches("...");
+                    let resolved = display(&normalize(&cwd.join(p)));
+                    let r = if has_ext(p) {
+                        RunRef::File(resolved)
+                    } else {
+                        RunRef::Dir(resolved)
+                    };
+                    push_unique(&mut refs, r);
+                }
+                continue;
+            }
+
+            for token in &tokens {
+                let t = token.trim_matches(|c| c == '"' || c == '\'');
+                if t.starts_with('-') || t.contains("://") || t.contains('$') || t.ends_with("...")
+                {
+                    continue;
+                }
+                let is_script = (t.starts_with("./") && t.len() > 2)
+                    || t.rsplit_once('.')
+                        .is_some_and(|(stem, ext)| !stem.is_empty() && SCRIPT_EXTS.contains(&ext));
+                if is_script {
+                    let resolved = display(&normalize(&cwd.join(t)));
+                    push_unique(&mut refs, RunRef::File(resolved));
+                }
+            }
+        }
+    }
+    (refs, runner)
+}
+
+fn make_refs(args: &[&str], cwd: &Path) -> Vec<RunRef> {
+    let mut dir = cwd.to_path_buf();
+    let mut targets = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match *arg {
+            "-C" | "--directory" => {
+                if let Some(d) = iter.next() {
+                    dir = normalize(&dir.join(d));
+                }
+            }
+            "-f" | "-j" => {
+                iter.next();
+            }
+            a if a.starts_with('-') || a.contains('=') => {}
+            a => targets.push(a.to_string()),
+        }
+    }
+    targets
+        .into_iter()
+        .map(|target| RunRef::Make {
+            dir: display(&dir),
+            target,
+        })
+        .collect()
+}
+
+fn strip_env_prefix(command: &str) -> &str {
+    let mut rest = command;
+    while let Some((first, tail)) = rest.split_once(char::is_whitespace) {
+        let is_assign = first.split_once('=').is_some_and(|(k, _)| {
+            !k.is_empty()
+                && k.chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        });
+        if !is_assign {
+            break;
+        }
+        rest = tail.trim_start();
+    }
+    rest
+}
+
+fn unwrap_command(command: &str) -> &str {
+    let mut rest = command;
+    loop {
+        let Some(wrapper) = COMMAND_WRAPPERS.iter().find(|w| rest.starts_with(**w)) else {
+            return rest;
+        };
+        rest = rest[wrapper.len()..].trim_start();
+    }
+}
+
+fn looks_like_path(arg: &str) -> bool {
+    arg.contains('/') || has_ext(arg)
+}
+
+fn has_ext(arg: &str) -> bool {
+    Path::new(arg).extension().is_some() && !arg.ends_with('.')
+}
+
+fn push_unique(refs: &mut Vec<RunRef>, r: RunRef) {
+    if !refs.contains(&r) {
+        refs.push(r);
+    }
+}
+
+fn display(path: &Path) -> String {
+    strip_tmp(path).display().to_string()
+}
+
+fn resolve(root: &Path, dir: &str) -> String {
+    display(&normalize(&root.join(dir)))
+}
+
+fn file_stem(path: &str) -> String {
+    let name = file_name(path);
+    name.rsplit_once('.')
+        .map(|(s, _)| s)
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// Reads the workflow files CI runs from: `.github/workflows/*`, `.gitlab-ci.yml`
+/// and the root Makefile. Used by callers that do not walk the whole tree.
+pub fn collect_ci_files(root: &Path) -> Vec<InfraFile> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(root.join(".github").join("workflows")) {
+        let mut wf: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+        wf.sort();
+        paths.extend(wf);
+    }
+    for name in [".gitlab-ci.yml", "Makefile", "makefile", "GNUmakefile"] {
+        paths.push(root.join(name));
+    }
+    paths
+        .into_iter()
+        .filter(|p| p.is_file())
+        .filter_map(|p| {
+            let path = display(&p);
+            if !is_ci_file(&path) {
+                return None;
+            }
+            let code = std::fs::read_to_string(&p).ok()?;
+            Some(InfraFile { path, code })
+        })
+        .collect()
+}
+
+pub fn collect_workflows(root: &Path) -> Vec<WorkflowSpec> {
+    collect_ci_files(root)
+        .iter()
+        .filter_map(parse_ci_file)
+        .collect()
+}
+
+/// A CI job whose steps run a changed file, directly or through a make target.
+#[derive(Debug, Clone)]
+pub struct AffectedJob<'a> {
+    pub workflow: &'a WorkflowSpec,
+    pub job: &'a JobSpec,
+    pub step: &'a StepSpec,
+}
+
+impl RunRef {
+    fn covers(&self, file: &str) -> bool {
+        match self {
+            RunRef::File(p) => file == p,
+            RunRef::Dir(p) => {
+                file == p || (file.starts_with(p.as_str()) && file[p.len()..].starts_with('/'))
+            }
+            RunRef::Make { .. } => false,
+        }
+    }
+}
+
+/// CI jobs (excluding make targets) that would exercise any of `files`.
+pub fn affected_jobs<'a>(workflows: &'a [WorkflowSpec], files: &[String]) -> Vec<AffectedJob<'a>> {
+    let touches = |step: &StepSpec| step.refs.iter().any(|r| files.iter().any(|f| r.covers(f)));
+
+    // make targets hit directly, then through prerequisites and nested `make` calls
+    let mut hit_targets: HashSet<(String, String)> = HashSet::new();
+    let mut make_jobs: Vec<(String, &JobSpec)> = Vec::new();
+    for wf in workflows.iter().filter(|wf| wf.platform == "make") {
+        let dir = parent_dir(&wf.file);
+        let changed = files.contains(&wf.file);
+        for job in &wf.jobs {
+            if changed || job.steps.iter().any(touches) {
+                hit_targets.insert((dir.clone(), job.name.clone()));
+            }
+            make_jobs.push((dir.clone(), job));
+        }
+    }
+    let runs_hit_target = |step: &StepSpec, hit: &HashSet<(String, String)>| {
+        step.refs.iter().any(|r| match r {
+            RunRef::Make { dir, target } => hit.contains(&(dir.clone(), target.clone())),
+            _ => false,
+        })
+    };
+    loop {
+        let before = hit_targets.len();
+        for (dir, job) in &make_jobs {
+            let key = (dir.clone(), job.name.clone());
+            if hit_targets.contains(&key) {
+                continue;
+            }
+            let via_need = job
+                .needs
+                .iter()
+                .any(|n| hit_targets.contains(&(dir.clone(), n.clone())));
+            if via_need || job.steps.iter().any(|s| runs_hit_target(s, &hit_targets)) {
+                hit_targets.insert(key);
+            }
+        }
+        if hit_targets.len() == before {
+            break;
+        }
+    }
+
+    let mut affected = Vec::new();
+    for wf in workflows.iter().filter(|wf| wf.platform != "make") {
+        for job in &wf.jobs {
+            let step = job
+                .steps
+                .iter()
+                .find(|s| touches(s) || runs_hit_target(s, &hit_targets));
+            if let Some(step) = step {
+                affected.push(AffectedJob {
+                    workflow: wf,
+                    job,
+                    step,
+                });
+            }
+        }
+    }
+    affected
+}
+
+fn parent_dir(path: &str) -> String {
+    Path::new(path)
+        .parent()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default()
+}
+
+pub fn add_ci_nodes<G: Graph>(graph: &mut G, files: &[&InfraFile]) -> usize {
+    let workflows: Vec<WorkflowSpec> = files.iter().filter_map(|f| parse_ci_file(f)).collect();
+    let lines: HashMap<&str, Vec<&str>> = files
+        .iter()
+        .map(|f| (f.path.as_str(), f.code.lines().collect()))
+        .collect();
+
+    let mut targets: Vec<(String, NodeData)> = Vec::new();
+    let mut pending: Vec<(NodeData, Vec<RunRef>)> = Vec::new();
+
+    for wf in &workflows {
+        let wf_lines = lines.get(wf.file.as_str()).cloned().unwrap_or_default();
+        let mut wf_nd = NodeData::name_file(&wf.name, &wf.file);
+        wf_nd.end = wf.end;
+        wf_nd
+            .meta
+            .insert("platform".to_string(), wf.platform.clone());
+        if !wf.triggers.is_empty() {
+            wf_nd
+                .meta
+                .insert("triggers".to_string(), wf.triggers.join(","));
+        }
+        graph.add_node_with_parent(&NodeType::Workflow, &wf_nd, &NodeType::File, &wf.file);
+
+        let mut job_nodes: BTreeMap<String, NodeData> = BTreeMap::new();
+        for job in &wf.jobs {
+            let mut job_nd = NodeData::name_file(&job.name, &wf.file);
+            job_nd.start = job.start;
+            job_nd.end = job.end;
+            job_nd.body = slice_lines(&wf_lines, job.start, job.end);
+            job_nd
+                .meta
+                .insert("platform".to_string(), wf.platform.clone());
+            job_nd.meta.insert("workflow".to_string(), wf.name.clone());
+            if let Some(runs_on) = &job.runs_on {
+                job_nd.meta.insert("runs_on".to_string(), runs_on.clone());
+            }
+            if let Some(stage) = &job.stage {
+                job_nd.meta.insert("stage".to_string(), stage.clone());
+            }
+            if !job.needs.is_empty() {
+                job_nd.meta.insert("needs".to_string(), job.needs.join(","));
+            }
+            graph.add_node(&NodeType::WorkflowJob, &job_nd);
+            graph.add_edge(&Edge::contains(
+                NodeType::Workflow,
+                &wf_nd,
+                NodeType::WorkflowJob,
+                &job_nd,
+            ));
+
+            for step in &job.steps {
+                let mut step_nd = NodeData::name_file(&step.name, &wf.file);
+                step_nd.start = step.start;
+                step_nd.end = step.end;
+                step_nd.body = slice_lines(&wf_lines, step.start, step.end);
+                if let Some(run) = &step.run {
+                    step_nd.meta.insert("run".to_string(), run.clone());
+                }
+                if let Some(uses) = &step.uses {
+                    step_nd.meta.insert("uses".to_string(), uses.clone());
+                }
+                if let Some(runner) = &step.test_runner {
+                    step_nd
+                        .meta
+                        .insert("test_runner".to_string(), runner.clone());
+                }
+                let covers: Vec<String> = step
+                    .refs
+                    .iter()
+                    .filter_map(|r| match r {
+                        RunRef::File(p) | RunRef::Dir(p) => Some(p.clone()),
+                        RunRef::Make { .. } => None,
+                    })
+                    .collect();
+                if !covers.is_empty() {
+                    step_nd.meta.insert("covers".to_string(), covers.join(","));
+                }
+                graph.add_node(&NodeType::WorkflowStep, &step_nd);
+                graph.add_edge(&Edge::contains(
+                    NodeType::WorkflowJob,
+                    &job_nd,
+                    NodeType::WorkflowStep,
+                    &step_nd,
+                ));
+                pending.push((step_nd, step.refs.clone()));
+            }
+            if wf.platform == "make" {
+                targets.push((parent_dir(&wf.file), job_nd.clone()));
+            }
+            job_nodes.insert(job.name.clone(), job_nd);
+        }
+
+        for job in &wf.jobs {
+            let Some(job_nd) = job_nodes.get(&job.name) else {
+                continue;
+            };
+            for need in &job.needs {
+                if let Some(dep) = job_nodes.get(need) {
+                    graph.add_edge(&Edge::depends_on(
+                        NodeType::WorkflowJob,
+                        job_nd,
+                        NodeType::WorkflowJob,
+                        dep,
+                    ));
+                }
+            }
+        }
+    }
+
+    let files_by_path: HashMap<String, NodeData> = graph
+        .find_nodes_by_type(NodeType::File)
+        .into_iter()
+        .map(|f| (f.file.clone(), f))
+        .collect();
+    let dirs_by_path: HashMap<String, NodeData> = graph
+        .find_nodes_by_type(NodeType::Directory)
+        .into_iter()
+        .map(|d| (d.file.clone(), d))
+        .collect();
+
+    for (source, refs) in &pending {
+        for r in refs {
+            let target = match r {
+                RunRef::File(p) => files_by_path.get(p).map(|f| (NodeType::File, f.clone())),
+                RunRef::Dir(p) => dirs_by_path
+                    .get(p)
+                    .map(|d| (NodeType::Directory, d.clone())),
+                RunRef::Make { dir, target } => targets
+                    .iter()
+                    .find(|(d, job)| d == dir && job.name == *target)
+                    .map(|(_, job)| (NodeType::WorkflowJob, job.clone())),
+            };
+            if let Some((target_type, target)) = target {
+                graph.add_edge(&Edge::runs(source, target_type, &target));
+            }
+        }
+    }
+
+    workflows.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_script() {
+        let (refs, runner) = analyze_script(
+            "npm ci\ncd web && npx jest src/app.test.ts\n./scripts/lint.sh --fix\nmake -C tools build",
+            "/repo",
+        );
+        assert_eq!(runner.as_deref(), Some("jest"));
+        assert_eq!(
+            refs,
+            vec![
+                RunRef::File("/repo/web/src/app.test.ts".to_string()),
+                RunRef::File("/repo/web/scripts/lint.sh".to_string()),
+                RunRef::Make {
+                    dir: "/repo/web/tools".to_string(),
+                    target: "build".to_string()
+                },
+            ]
+        );
+
+        let (refs, runner) = analyze_script("RUST_LOG=info cargo test --workspace", "/repo");
+        assert_eq!(runner.as_deref(), Some("cargo test"));
+        assert_eq!(refs, vec![RunRef::Dir("/repo".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_github_workflow() {
+        let file = InfraFile {
+            path: "/repo/.github/workflows/ci.yml".to_string(),
+            code: r#"name: CI
+on: [push, pull_request]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: make build
+  test:
+    needs: build
+    runs-on: ubuntu-latest
+    defaults:
+      run:
+        working-directory: api
+    steps:
+      - name: Unit tests
+        run: pytest tests/
+"#
+            .to_string(),
+        };
+        let wf = parse_ci_file(&file).unwrap();
+        assert_eq!(wf.name, "CI");
+        assert_eq!(wf.triggers, vec!["push", "pull_request"]);
+        assert_eq!(wf.jobs.len(), 2);
+        assert_eq!(wf.jobs[0].steps[1].refs.len(), 1);
+        assert_eq!(wf.jobs[1].needs, vec!["build"]);
+        let unit = &wf.jobs[1].steps[0];
+        assert_eq!(unit.name, "Unit tests");
+        assert_eq!(unit.test_runner.as_deref(), Some("pytest"));
+        assert_eq!(unit.refs, vec![RunRef::Dir("/repo/api/tests".to_string())]);
+    }
+
+    #[test]
+    fn test_affected_jobs() {
+        let makefile = InfraFile {
+            path: "/repo/Makefile".to_string(),
+            code: "test: lint\n\tpytest tests/\n\nlint:\n\t./scripts/lint.sh\n".to_string(),
+        };
+        let workflow = InfraFile {
+            path: "/repo/.github/workflows/ci.yml".to_string(),
+            code: "jobs:\n  check:\n    steps:\n      - run: make test\n  docs:\n    steps:\n      - run: ./scripts/docs.sh\n"
+                .to_string(),
+        };
+        let workflows: Vec<WorkflowSpec> = [makefile, workflow]
+            .iter()
+            .filter_map(parse_ci_file)
+            .collect();
+
+        let hits = affected_jobs(&workflows, &["/repo/scripts/lint.sh".to_string()]);
+        let names: Vec<&str> = hits.iter().map(|h| h.job.name.as_str()).collect();
+        assert_eq!(names, vec!["check"]);
+        assert_eq!(hits[0].step.name, "make test");
+
+        let hits = affected_jobs(&workflows, &["/repo/src/app.py".to_string()]);
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn test_parse_makefile() {
+        let file = InfraFile {
+            path: "/repo/Makefile".to_string(),
+            code: ".PHONY: build test\nVERSION := 1.0\n\nbuild: deps\n\tgo build ./...\n\ntest: build\n\t@go test ./pkg/...\n"
+                .to_string(),
+        };
+        let wf = parse_ci_file(&file).unwrap();
+        let names: Vec<&str> = wf.jobs.iter().map(|j| j.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "test"]);
+        assert!(wf.jobs[0].needs.is_empty());
+        assert_eq!(wf.jobs[1].needs, vec!["build"]);
+        assert_eq!(
+            wf.jobs[1].steps[0].refs,
+            vec![RunRef::Dir("/repo/pkg".to_string())]
+        );
+    }
+}
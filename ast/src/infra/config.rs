@@ -0,0 +1,250 @@
+use super::docker::{is_compose_file, is_k8s_manifest, K8S_WORKLOADS};
+use super::yaml::{self, Yaml};
+use super::InfraFile;
+use crate::lang::graphs::{Edge, Graph, NodeType};
+use crate::lang::NodeData;
+use regex::Regex;
+use std::sync::LazyLock;
+
+static DOTENV_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*(?:export\s+)?(?P<key>[A-Za-z_][A-Za-z0-9_.]*)\s*=\s*(?P<value>.*)$").unwrap()
+});
+
+/// Committed templates of a `.env` file. The real `.env` holds secrets and is not read.
+pub fn is_dotenv_template(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    matches!(
+        name,
+        ".env.example" | ".env.sample" | ".env.template" | ".env.dist" | ".env.defaults"
+    ) || name.ends_with(".env.example")
+        || name == "example.env"
+        || name == "sample.env"
+}
+
+/// A configuration key declared outside code, with the value it is given there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDefinition {
+    pub name: String,
+    pub line: usize,
+    pub value: Option<String>,
+    /// compose service or Kubernetes workload/ConfigMap declaring the key
+    pub owner: Option<String>,
+}
+
+pub fn parse_dotenv(code: &str) -> Vec<ConfigDefinition> {
+    code.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim_start().starts_with('#'))
+        .filter_map(|(i, l)| {
+            let caps = DOTENV_RE.captures(l)?;
+            let value = caps["value"]
+                .split(" #")
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .trim_matches(|c| c == '"' || c == '\'');
+            Some(ConfigDefinition {
+                name: caps["key"].to_string(),
+                line: i,
+                value: (!value.is_empty()).then(|| value.to_string()),
+                owner: None,
+            })
+        })
+        .collect()
+}
+
+pub fn compose_definitions(code: &str) -> Vec<ConfigDefinition> {
+    let lines: Vec<&str> = code.lines().collect();
+    let doc = yaml::parse(code);
+    let mut defs = Vec::new();
+    let Some(services) = doc.get("services") else {
+        return defs;
+    };
+    for svc in services.entries() {
+        let Some(env) = svc.value.entries().iter().find(|e| e.key == "environment") else {
+            continue;
+        };
+        let owner = Some(svc.key.clone());
+        match &env.value {
+            Yaml::Map(entries) => defs.extend(entries.iter().map(|e| {
+                ConfigDefinition {
+                    name: e.key.clone(),
+                    line: e.line,
+                    value: e
+                        .value
+                        .as_str()
+                        .filter(|v| !v.is_empty())
+                        .map(str::to_string),
+                    owner: owner.clone(),
+                }
+            })),
+            other => {
+                for item in other.strings() {
+                    let (name, value) = match item.split_once('=') {
+                        Some((k, v)) => (k.to_string(), Some(v.to_string())),
+                        None => (item.clone(), None),
+                    };
+                    let line = find_line(&lines, env.line, &name).unwrap_or(env.line);
+                    defs.push(ConfigDefinition {
+                        name,
+                        line,
+                        value: value.filter(|v| !v.is_empty()),
+                        owner: owner.clone(),
+                    });
+                }
+            }
+        }
+    }
+    defs
+}
+
+/// Container `env` of workloads plus the keys of ConfigMaps and Secrets. Secret values
+/// are never recorded.
+pub fn k8s_definitions(code: &str) -> Vec<ConfigDefinition> {
+    let mut defs = Vec::new();
+    for doc in yaml::parse_documents(code) {
+        let Some(kind) = doc.get("kind").and_then(|k| k.as_str()) else {
+            continue;
+        };
+        let Some(name) = doc.get_path(&["metadata", "name"]).and_then(|n| n.as_str()) else {
+            continue;
+        };
+        let owner = Some(name.to_string());
+        if kind == "ConfigMap" || kind == "Secret" {
+            for section in ["data", "stringData"] {
+                for e in doc.get(section).map(|d| d.entries()).unwrap_or(&[]) {
+                    defs.push(ConfigDefinition {
+                        name: e.key.clone(),
+                        line: e.line,
+                        value: (kind == "ConfigMap")
+                            .then(|| e.value.as_str().map(str::to_string))
+                            .flatten(),
+                        owner: owner.clone(),
+                    });
+                }
+            }
+            continue;
+        }
+        if !K8S_WORKLOADS.contains(&kind) {
+            continue;
+        }
+        let pod_spec = match kind {
+            "Pod" => doc.get("spec"),
+            "CronJob" => doc.get_path(&["spec", "jobTemplate", "spec", "template", "spec"]),
+            _ => doc.get_path(&["spec", "template", "spec"]),
+        };
+        let containers = pod_spec
+            .and_then(|s| s.get("containers"))
+            .map(|c| c.items())
+            .unwrap_or(&[]);
+        for container in containers {
+            for var in container.get("env").map(|e| e.items()).unwrap_or(&[]) {
+                let Some(key) = var.get("name").and_then(|n| n.as_str()) else {
+                    continue;
+                };
+                defs.push(ConfigDefinition {
+                    name: key.to_string(),
+                    line: var.first_line().unwrap_or(0),
+                    value: var
+                        .get("value")
+                        .and_then(|v| v.as_str())
+                        .map(str::to_string),
+                    owner: owner.clone(),
+                });
+            }
+        }
+    }
+    defs
+}
+
+fn find_line(lines: &[&str], from: usize, key: &str) -> Option<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .skip(from)
+        .find(|(_, l)| l.contains(key))
+        .map(|(i, _)| i)
+}
+
+/// Adds a ConfigKey node per key declared in `.env` templates, compose `environment`
+/// blocks and Kubernetes manifests. Keys declared for a service are CONTAINED by it.
+pub fn add_config_definitions<G: Graph>(graph: &mut G, files: &[&InfraFile]) -> usize {
+    let mut count = 0;
+    for file in files {
+        let (source, defs) = if is_dotenv_template(&file.path) {
+            ("dotenv", parse_dotenv(&file.code))
+        } else if is_compose_file(&file.path) {
+            ("compose", compose_definitions(&file.code))
+        } else if is_k8s_manifest(&file.path, &file.code) {
+            ("kubernetes", k8s_definitions(&file.code))
+        } else {
+            continue;
+        };
+        let lines: Vec<&str> = file.code.lines().collect();
+        for def in defs {
+            let mut nd = NodeData::name_file(&def.name, &file.path);
+            nd.start = def.line;
+            nd.end = def.line;
+            nd.body = match &def.value {
+                Some(_) => lines.get(def.line).unwrap_or(&"").trim().to_string(),
+                None => def.name.clone(),
+            };
+            nd.meta.insert("source".to_string(), source.to_string());
+            if let Some(value) = &def.value {
+                nd.meta.insert("default".to_string(), value.clone());
+            }
+            if let Some(owner) = &def.owner {
+                nd.meta.insert("service".to_string(), owner.clone());
+            }
+            graph.add_node_with_parent(&NodeType::ConfigKey, &nd, &NodeType::File, &file.path);
+            if let Some(service) = def
+                .owner
+                .and_then(|o| graph.find_node_by_name_in_file(NodeType::Service, &o, &file.path))
+            {
+                graph.add_edge(&Edge::contains(
+                    NodeType::Service,
+                    &service,
+                    NodeType::ConfigKey,
+                    &nd,
+                ));
+            }
+            count += 1;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let defs = parse_dotenv(
+            "# database\nDATABASE_URL=postgres://localhost/app\nexport API_KEY=\nDEBUG=\"false\" # dev only\n",
+        );
+        let names: Vec<_> = defs.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["DATABASE_URL", "API_KEY", "DEBUG"]);
+        assert_eq!(defs[0].line, 1);
+        assert_eq!(defs[1].value, None);
+        assert_eq!(defs[2].value.as_deref(), Some("false"));
+    }
+
+    #[test]
+    fn test_compose_and_k8s_definitions() {
+        let compose = "services:\n  api:\n    environment:\n      - PORT=8080\n      - SECRET\n  worker:\n    environment:\n      QUEUE_URL: amqp://mq\n";
+        let defs = compose_definitions(compose);
+        assert_eq!(defs.len(), 3);
+        assert_eq!(defs[0].line, 3);
+        assert_eq!(defs[1].value, None);
+        assert_eq!(defs[2].owner.as_deref(), Some("worker"));
+
+        let manifest = "apiVersion: v1\nkind: Secret\nmetadata:\n  name: creds\ndata:\n  TOKEN: c2VjcmV0\n---\napiVersion: apps/v1\nkind: Deployment\nmetadata:\n  name: api\nspec:\n  template:\n    spec:\n      containers:\n        - name: api\n          env:\n            - name: LOG_LEVEL\n              value: info\n";
+        let defs = k8s_definitions(manifest);
+        assert_eq!(defs.len(), 2);
+        assert_eq!(defs[0].name, "TOKEN");
+        assert_eq!(defs[0].value, None);
+        assert_eq!(defs[1].name, "LOG_LEVEL");
+        assert_eq!(defs[1].value.as_deref(), Some("info"));
+    }
+}
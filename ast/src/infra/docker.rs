@@ -0,0 +1,828 @@
+use super::yaml::{self, Yaml};
+use super::{file_name, last_line, normalize, slice_lines, InfraFile};
+use crate::lang::graphs::{Edge, EdgeType, Graph, NodeRef, NodeType};
+use crate::lang::NodeData;
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static HOST_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|://|@)([A-Za-z][A-Za-z0-9_.-]*)(?::\d+|/|$)").unwrap());
+
+pub(super) const K8S_WORKLOADS: [&str; 6] = [
+    "Deployment",
+    "StatefulSet",
+    "DaemonSet",
+    "Pod",
+    "Job",
+    "CronJob",
+];
+
+pub fn is_dockerfile(path: &str) -> bool {
+    let name = file_name(path);
+    name == "Dockerfile"
+        || name.starts_with("Dockerfile.")
+        || name.ends_with(".Dockerfile")
+        || name.ends_with(".dockerfile")
+}
+
+pub fn is_compose_file(path: &str) -> bool {
+    let name = file_name(path);
+    (name.starts_with("docker-compose") || name.starts_with("compose"))
+        && (name.ends_with(".yml") || name.ends_with(".yaml"))
+}
+
+pub fn is_k8s_manifest(path: &str, code: &str) -> bool {
+    (path.ends_with(".yml") || path.ends_with(".yaml"))
+        && code.contains("apiVersion:")
+        && code.contains("kind:")
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dockerfile {
+    pub base_images: Vec<String>,
+    pub copies: Vec<String>,
+    pub entrypoint: Vec<String>,
+    pub env: Vec<(String, String)>,
+    pub ports: Vec<String>,
+    pub workdir: Option<String>,
+    /// `RUN go build -o <out> <pkg>` pairs, used to map a binary back to its `main` package
+    pub go_builds: Vec<(String, String)>,
+}
+
+pub fn parse_dockerfile(code: &str) -> Dockerfile {
+    let mut df = Dockerfile::default();
+    let mut stages: Vec<String> = Vec::new();
+    let mut entrypoint: Vec<String> = Vec::new();
+    let mut cmd: Vec<String> = Vec::new();
+
+    for instruction in join_continuations(code) {
+        let Some((keyword, args)) = instruction.split_once(char::is_whitespace) else {
+            continue;
+        };
+        let args = args.trim();
+        match keyword.to_ascii_uppercase().as_str() {
+            "FROM" => {
+                let parts: Vec<&str> = args
+                    .split_whitespace()
+                    .filter(|p| !p.starts_with("--"))
+                    .collect();
+                if let Some(image) = parts.first() {
+                    if !stages.iter().any(|s| s == image) {
+                        df.base_images.push(image.to_string());
+                    }
+                }
+                if parts.len() >= 3 && parts[1].eq_ignore_ascii_case("as") {
+                    stages.push(parts[2].to_string());
+                }
+            }
+            "COPY" | "ADD" => {
+                if args.contains("--from=") {
+                    continue;
+                }
+                let parts: Vec<String> = split_args(args)
+                    .into_iter()
+                    .filter(|p| !p.starts_with("--"))
+                    .collect();
+                if parts.len() >= 2 {
+                    for src in &parts[..parts.len() - 1] {
+                        if !src.contains("://") && !df.copies.contains(src) {
+                            df.copies.push(src.clone());
+                        }
+                    }
+                }
+            }
+            "CMD" => cmd = split_args(args),
+            "ENTRYPOINT" => entrypoint = split_args(args),
+            "ENV" => df.env.extend(parse_env_instruction(args)),
+            "EXPOSE" => df
+                .ports
+                .extend(args.split_whitespace().map(|p| p.to_string())),
+            "WORKDIR" => df.workdir = Some(args.to_string()),
+            "RUN" => df.go_builds.extend(parse_go_builds(args)),
+            _ => {}
+        }
+    }
+    df.entrypoint = entrypoint.into_iter().chain(cmd).collect();
+    df
+}
+
+fn join_continuations(code: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    for line in code.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        if let Some(stripped) = trimmed.strip_suffix('\\') {
+            current.push_str(stripped);
+            current.push(' ');
+            continue;
+        }
+        current.push_str(trimmed);
+        if !current.trim().is_empty() {
+            out.push(current.trim().to_string());
+        }
+        current.clear();
+    }
+    if !current.trim().is_empty() {
+        out.push(current.trim().to_string());
+    }
+    out
+}
+
+/// Exec form (`["node", "index.js"]`) or shell form (`node index.js`).
+fn split_args(args: &str) -> Vec<String> {
+    if args.starts_with('[') {
+        if let Ok(list) = serde_json::from_str::<Vec<String>>(args) {
+            return list;
+        }
+    }
+    args.split_whitespace()
+        .map(|s| s.trim_matches(|c| c == '"' || c == '\'').to_string())
+        .collect()
+}
+
+fn parse_env_instruction(args: &str) -> Vec<(String, String)> {
+    if !args.contains('=') {
+        return match args.split_once(char::is_whitespace) {
+            Some((k, v)) => vec![(k.to_string(), v.trim().to_string())],
+            None => Vec::new(),
+        };
+    }
+    args.split_whitespace()
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.trim_matches('"').to_string()))
+        .collect()
+}
+
+fn parse_go_builds(run: &str) -> Vec<(String, String)> {
+    let mut builds = Vec::new();
+    for cmd in run.split("&&") {
+        let parts: Vec<&str> = cmd.split_whitespace().collect();
+        if parts.len() < 2 || parts[0] != "go" || parts[1] != "build" {
+            continue;
+        }
+        let Some(o) = parts.iter().position(|p| *p == "-o") else {
+            continue;
+        };
+        let Some(out) = parts.get(o + 1) else {
+            continue;
+        };
+        let pkg = parts[o + 2..]
+            .iter()
+            .rev()
+            .find(|p| !p.starts_with('-'))
+            .copied()
+            .unwrap_or(".");
+        builds.push((out.to_string(), pkg.to_string()));
+    }
+    builds
+}
+
+#[derive(Debug, Clone)]
+struct ServiceSpec {
+    node: NodeData,
+    depends_on: Vec<String>,
+    env: Vec<(String, String)>,
+    /// other names the service answers to (k8s Service objects selecting it)
+    aliases: Vec<String>,
+    dockerfile: Option<(String, Dockerfile)>,
+}
+
+impl ServiceSpec {
+    fn new(node: NodeData) -> Self {
+        Self {
+            node,
+            depends_on: Vec::new(),
+            env: Vec::new(),
+            aliases: Vec::new(),
+            dockerfile: None,
+        }
+    }
+}
+
+pub fn add_service_nodes<G: Graph>(graph: &mut G, files: &[&InfraFile]) -> usize {
+    let mut specs: Vec<ServiceSpec> = Vec::new();
+
+    for file in files.iter().filter(|f| is_compose_file(&f.path)) {
+        specs.extend(compose_services(file));
+    }
+    for file in files
+        .iter()
+        .filter(|f| !is_compose_file(&f.path) && is_k8s_manifest(&f.path, &f.code))
+    {
+        specs.extend(k8s_services(file));
+    }
+    for file in files.iter().filter(|f| is_dockerfile(&f.path)) {
+        attach_dockerfile(&mut specs, file);
+    }
+
+    for spec in &mut specs {
+        finish_meta(spec);
+        graph.add_node_with_parent(
+            &NodeType::Service,
+            &spec.node,
+            &NodeType::File,
+            &spec.node.file,
+        );
+    }
+
+    for spec in &specs {
+        for dep in &spec.depends_on {
+            if let Some(target) = find_service(&specs, dep, &spec.node.file) {
+                graph.add_edge(&Edge::depends_on(
+                    NodeType::Service,
+                    &spec.node,
+                    NodeType::Service,
+                    target,
+                ));
+            }
+        }
+        let mut connected: Vec<String> = Vec::new();
+        for (key, value) in &spec.env {
+            for host in env_hosts(key, value) {
+                if host == spec.node.name || connected.contains(&host) {
+                    continue;
+                }
+                if let Some(target) = find_service(&specs, &host, &spec.node.file) {
+                    graph.add_edge(&Edge::connects_to(&spec.node, target));
+                    connected.push(host);
+                }
+            }
+        }
+        if let Some((path, df)) = &spec.dockerfile {
+            link_dockerfile(graph, &spec.node, path, df);
+        }
+    }
+
+    specs.len()
+}
+
+fn compose_services(file: &InfraFile) -> Vec<ServiceSpec> {
+    let doc = yaml::parse(&file.code);
+    let dir = Path::new(&file.path)
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default();
+    let lines: Vec<&str> = file.code.lines().collect();
+    let mut specs = Vec::new();
+
+    let Some(services) = doc.get("services") else {
+        return specs;
+    };
+    for entry in services.entries() {
+        let svc = &entry.value;
+        let mut nd = NodeData::name_file(&entry.key, &file.path);
+        nd.start = entry.line;
+        nd.end = last_line(svc).unwrap_or(entry.line);
+        nd.body = slice_lines(&lines, nd.start, nd.end);
+        nd.meta
+            .insert("platform".to_string(), "compose".to_string());
+
+        if let Some(image) = svc.get("image").and_then(|i| i.as_str()) {
+            nd.meta.insert("image".to_string(), image.to_string());
+        }
+        let (context, dockerfile) = match svc.get("build") {
+            Some(Yaml::Scalar(ctx)) => (Some(ctx.clone()), None),
+            Some(build) => (
+                build
+                    .get("context")
+                    .and_then(|c| c.as_str())
+                    .map(|c| c.to_string()),
+                build
+                    .get("dockerfile")
+                    .and_then(|d| d.as_str())
+                    .map(|d| d.to_string()),
+            ),
+            None => (None, None),
+        };
+        if let Some(ctx) = context {
+            let resolved = normalize(&dir.join(ctx)).display().to_string();
+            nd.meta.insert("context".to_string(), resolved);
+        }
+        if let Some(df) = dockerfile {
+            nd.meta.insert("dockerfile".to_string(), df);
+        }
+        let ports = svc.get("ports").map(|p| p.strings()).unwrap_or_default();
+        if !ports.is_empty() {
+            nd.meta.insert("ports".to_string(), ports.join(","));
+        }
+        let networks = svc
+            .get("networks")
+            .map(|n| n.keys_or_strings())
+            .unwrap_or_default();
+        if !networks.is_empty() {
+            nd.meta.insert("networks".to_string(), networks.join(","));
+        }
+
+        let mut spec = ServiceSpec::new(nd);
+        if let Some(deps) = svc.get("depends_on") {
+            spec.depends_on.extend(deps.keys_or_strings());
+        }
+        if let Some(links) = svc.get("links") {
+            spec.depends_on.extend(
+                links
+                    .strings()
+                    .iter()
+                    .map(|l| l.split(':').next().unwrap_or(l).to_string()),
+            );
+        }
+        if let Some(env) = svc.get("environment") {
+            spec.env = env_pairs(env);
+        }
+        specs.push(spec);
+    }
+    specs
+}
+
+fn env_pairs(env: &Yaml) -> Vec<(String, String)> {
+    match env {
+        Yaml::Map(entries) => entries
+            .iter()
+            .map(|e| (e.key.clone(), e.value.as_str().unwrap_or("").to_string()))
+            .collect(),
+        other => other
+            .strings()
+            .iter()
+            .map(|s| match s.split_once('=') {
+                Some((k, v)) => (k.to_string(), v.to_string()),
+                None => (s.clone(), String::new()),
+            })
+            .collect(),
+    }
+}
+
+fn k8s_services(file: &InfraFile) -> Vec<ServiceSpec> {
+    let lines: Vec<&str> = file.code.lines().collect();
+    let docs = yaml::parse_documents(&file.code);
+    let mut specs: Vec<ServiceSpec> = Vec::new();
+    let mut selectors: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    let mut pod_labels: Vec<Vec<(String, String)>> = Vec::new();
+
+    for doc in &docs {
+        let Some(kind) = doc.get("kind").and_then(|k| k.as_str()) else {
+            continue;
+        };
+        let Some(name) = doc.get_path(&["metadata", "name"]).and_then(|n| n.as_str()) else {
+            continue;
+        };
+        if kind == "Service" {
+            let selector = doc
+                .get_path(&["spec", "selector"])
+                .map(string_pairs)
+                .unwrap_or_default();
+            selectors.push((name.to_string(), selector));
+            continue;
+        }
+        if !K8S_WORKLOADS.contains(&kind) {
+            continue;
+        }
+        let (template, pod_spec) = match kind {
+            "Pod" => (Some(doc), doc.get("spec")),
+            "CronJob" => {
+                let t = doc.get_path(&["spec", "jobTemplate", "spec", "template"]);
+                (t, t.and_then(|t| t.get("spec")))
+            }
+            _ => {
+                let t = doc.get_path(&["spec", "template"]);
+                (t, t.and_then(|t| t.get("spec")))
+            }
+        };
+
+        let mut nd = NodeData::name_file(name, &file.path);
+        nd.start = doc.first_line().unwrap_or(0);
+        nd.end = last_line(doc).unwrap_or(nd.start);
+        nd.body = slice_lines(&lines, nd.start, nd.end);
+        nd.meta
+            .insert("platform".to_string(), "kubernetes".to_string());
+        nd.meta.insert("k8s_kind".to_string(), kind.to_string());
+
+        let mut spec = ServiceSpec::new(nd);
+        let containers = pod_spec
+            .and_then(|s| s.get("containers"))
+            .map(|c| c.items().to_vec())
+            .unwrap_or_default();
+        let mut images = Vec::new();
+        let mut ports = Vec::new();
+        for container in &containers {
+            if let Some(image) = container.get("image").and_then(|i| i.as_str()) {
+                images.push(image.to_string());
+            }
+            for port in container.get("ports").map(|p| p.items()).unwrap_or(&[]) {
+                if let Some(p) = port.get("containerPort").and_then(|p| p.as_str()) {
+                    ports.push(p.to_string());
+                }
+            }
+            for var in container.get("env").map(|e| e.items()).unwrap_or(&[]) {
+                if let Some(key) = var.get("name").and_then(|n| n.as_str()) {
+                    let value = var.get("value").and_then(|v| v.as_str()).unwrap_or("");
+                    spec.env.push((key.to_string(), value.to_string()));
+                }
+            }
+        }
+        if !images.is_empty() {
+            spec.node.meta.insert("image".to_string(), images.join(","));
+        }
+        if !ports.is_empty() {
+            spec.node.meta.insert("ports".to_string(), ports.join(","));
+        }
+        pod_labels.push(
+            template
+                .and_then(|t| t.get_path(&["metadata", "labels"]))
+                .map(string_pairs)
+                .unwrap_or_default(),
+        );
+        specs.push(spec);
+    }
+
+    for (svc_name, selector) in selectors {
+        if selector.is_empty() {
+            continue;
+        }
+        for (spec, labels) in specs.iter_mut().zip(&pod_labels) {
+            if spec.node.name != svc_name && selector.iter().all(|pair| labels.contains(pair)) {
+                spec.aliases.push(svc_name.clone());
+            }
+        }
+    }
+    specs
+}
+
+/// Adds a Job node per Kubernetes CronJob, with HANDLER edges to the functions its
+/// container command runs (resolved like a Dockerfile ENTRYPOINT).
+pub fn add_cron_job_nodes<G: Graph>(graph: &mut G, files: &[&InfraFile]) -> usize {
+    let mut count = 0;
+    for file in files
+        .iter()
+        .filter(|f| !is_compose_file(&f.path) && is_k8s_manifest(&f.path, &f.code))
+    {
+        let lines: Vec<&str> = file.code.lines().collect();
+        for doc in yaml::parse_documents(&file.code) {
+            if doc.get("kind").and_then(|k| k.as_str()) != Some("CronJob") {
+                continue;
+            }
+            let Some(name) = doc.get_path(&["metadata", "name"]).and_then(|n| n.as_str()) else {
+                continue;
+            };
+            let mut nd = NodeData::name_file(name, &file.path);
+            nd.start = doc.first_line().unwrap_or(0);
+            nd.end = last_line(&doc).unwrap_or(nd.start);
+            nd.body = slice_lines(&lines, nd.start, nd.end);
+            if let Some(schedule) = doc.get_path(&["spec", "schedule"]).and_then(|s| s.as_str()) {
+                nd.meta.insert("schedule".to_string(), schedule.to_string());
+            }
+            nd.meta
+                .insert("scheduler".to_string(), "kubernetes".to_string());
+            graph.add_node_with_parent(&NodeType::Job, &nd, &NodeType::File, &file.path);
+
+            let containers = doc
+                .get_path(&["spec", "jobTemplate", "spec", "template"])
+                .and_then(|t| t.get_path(&["spec", "containers"]))
+                .map(|c| c.items().to_vec())
+                .unwrap_or_default();
+            for container in &containers {
+                let df = Dockerfile {
+                    entrypoint: ["command", "args"]
+                        .iter()
+                        .flat_map(|k| container.get(k).map(|v| v.strings()).unwrap_or_default())
+                        .collect(),
+                    ..Default::default()
+                };
+                // the image's working directory is unknown; try the manifest's directory and its parents
+                let handlers = Path::new(&file.path)
+                    .ancestors()
+                    .skip(1)
+                    .map(|dir| entrypoint_functions(graph, dir, &df))
+                    .find(|found| !found.is_empty())
+                    .unwrap_or_default();
+                for func in &handlers {
+                    graph.add_edge(&Edge::job_handler(&nd, func));
+                }
+            }
+            count += 1;
+        }
+    }
+    count
+}
+
+fn string_pairs(map: &Yaml) -> Vec<(String, String)> {
+    map.entries()
+        .iter()
+        .filter_map(|e| e.value.as_str().map(|v| (e.key.clone(), v.to_string())))
+        .collect()
+}
+
+/// Merges a Dockerfile into the compose service built from its directory,
+/// or creates a standalone Service named after that directory.
+fn attach_dockerfile(specs: &mut Vec<ServiceSpec>, file: &InfraFile) {
+    let df = parse_dockerfile(&file.code);
+    let dir = Path::new(&file.path)
+        .parent()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+    let name = file_name(&file.path);
+
+    let owner = specs.iter_mut().find(|s| {
+        s.dockerfile.is_none()
+            && s.node.meta.get("context") == Some(&dir)
+            && s.node
+                .meta
+                .get("dockerfile")
+                .map(|d| file_name(d) == name)
+                .unwrap_or(name == "Dockerfile")
+    });
+    if let Some(spec) = owner {
+        spec.env.extend(df.env.clone());
+        spec.dockerfile = Some((file.path.clone(), df));
+        return;
+    }
+
+    let dir_name = match file_name(&dir) {
+        "" => "app",
+        name => name,
+    };
+    let service_name = match name
+        .strip_prefix("Dockerfile.")
+        .or_else(|| name.strip_suffix(".Dockerfile"))
+        .or_else(|| name.strip_suffix(".dockerfile"))
+    {
+        Some(suffix) => format!("{}-{}", dir_name, suffix),
+        None => dir_name.to_string(),
+    };
+    let mut nd = NodeData::name_file(&service_name, &file.path);
+    nd.end = file.code.lines().count().saturating_sub(1);
+    nd.body = file.code.clone();
+    nd.meta.insert("platform".to_string(), "docker".to_string());
+    nd.meta.insert("context".to_string(), dir);
+    let mut spec = ServiceSpec::new(nd);
+    spec.env = df.env.clone();
+    spec.dockerfile = Some((file.path.clone(), df));
+    specs.push(spec);
+}
+
+fn finish_meta(spec: &mut ServiceSpec) {
+    let meta = &mut spec.node.meta;
+    if !spec.env.is_empty() {
+        let keys: Vec<&str> = spec.env.iter().map(|(k, _)| k.as_str()).collect();
+        meta.insert("env".to_string(), keys.join(","));
+    }
+    if let Some((path, df)) = &spec.dockerfile {
+        meta.insert("dockerfile".to_string(), path.clone());
+        if !df.base_images.is_empty() {
+            meta.insert("base_image".to_string(), df.base_images.join(","));
+        }
+        if !df.entrypoint.is_empty() {
+            meta.insert("entrypoint".to_string(), df.entrypoint.join(" "));
+        }
+        if !df.ports.is_empty() && !meta.contains_key("ports") {
+            meta.insert("ports".to_string(), df.ports.join(","));
+        }
+    }
+}
+
+fn find_service<'a>(specs: &'a [ServiceSpec], name: &str, file: &str) -> Option<&'a NodeData> {
+    let matches = |s: &&ServiceSpec| s.node.name == name || s.aliases.iter().any(|a| a == name);
+    specs
+        .iter()
+        .filter(matches)
+        .find(|s| s.node.file == file)
+        .or_else(|| specs.iter().find(matches))
+        .map(|s| &s.node)
+}
+
+/// Hostnames an env var points at: `http://api:8080`, `redis://cache:6379/0`,
+/// `db:5432`, or a bare `DB_HOST=db`. Kubernetes DNS names keep their first label.
+pub fn env_hosts(key: &str, value: &str) -> Vec<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Vec::new();
+    }
+    let key_upper = key.to_ascii_uppercase();
+    if key_upper.ends_with("HOST") || key_upper.ends_with("HOSTNAME") {
+        let host = value.split(':').next().unwrap_or(value);
+        return vec![host.split('.').next().unwrap_or(host).to_string()];
+    }
+    if !value.contains("://") && !value.contains(':') {
+        return Vec::new();
+    }
+    HOST_RE
+        .captures_iter(value)
+        .map(|c| c[1].split('.').next().unwrap_or(&c[1]).to_string())
+        .collect()
+}
+
+fn link_dockerfile<G: Graph>(graph: &mut G, service: &NodeData, path: &str, df: &Dockerfile) {
+    let context = Path::new(path)
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default();
+
+    for src in &df.copies {
+        if src == "." || src.contains('*') {
+            continue;
+        }
+        let resolved = normalize(&context.join(src)).display().to_string();
+        let target = graph
+            .find_nodes_by_type(NodeType::Directory)
+            .into_iter()
+            .map(|d| (NodeType::Directory, d))
+            .chain(
+                graph
+                    .find_nodes_by_type(NodeType::File)
+                    .into_iter()
+                    .map(|f| (NodeType::File, f)),
+            )
+            .find(|(_, n)| n.file == resolved);
+        if let Some((nt, target)) = target {
+            graph.add_edge(&Edge::new(
+                EdgeType::Imports,
+                NodeRef::from(service.into(), NodeType::Service),
+                NodeRef::from((&target).into(), nt),
+            ));
+        }
+    }
+
+    for func in entrypoint_functions(graph, &context, df) {
+        graph.add_edge(&Edge::service_handler(service, &func));
+    }
+}
+
+fn entrypoint_functions<G: Graph>(graph: &G, context: &Path, df: &Dockerfile) -> Vec<NodeData> {
+    let workdir = df
+        .workdir
+        .as_deref()
+        .map(|w| format!("{}/", w.trim_end_matches('/')));
+    let mut tokens: Vec<String> = Vec::new();
+    for arg in &df.entrypoint {
+        // `sh -c "node server.js"` carries the real command in a single argument
+        tokens.extend(arg.split_whitespace().map(|t| t.to_string()));
+    }
+
+    let mut found = Vec::new();
+    for token in tokens {
+        let mut t = token.as_str();
+        if let Some(wd) = &workdir {
+            t = t.strip_prefix(wd.as_str()).unwrap_or(t);
+        }
+        t = t.trim_start_matches("./");
+        if t.is_empty() || t.starts_with('-') {
+            continue;
+        }
+
+        if let Some((_, pkg)) = df
+            .go_builds
+            .iter()
+            .find(|(out, _)| file_name(out) == file_name(t))
+        {
+            let dir = normalize(&context.join(pkg)).display().to_string();
+            found.extend(
+                graph
+                    .find_nodes_by_name(NodeType::Function, "main")
+                    .into_iter()
+                    .filter(|f| {
+                        Path::new(&f.file).parent().map(|p| p.display().to_string())
+                            == Some(dir.clone())
+                    }),
+            );
+            continue;
+        }
+
+        if let Some((module, attr)) = t.split_once(':') {
+            // `uvicorn app.main:app` / `gunicorn pkg.wsgi:application`
+            if !module.contains('/')
+                && module
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '.' || c == '_')
+            {
+                let file = format!("{}.py", module.replace('.', "/"));
+                found.extend(entry_in_file(graph, context, &file, attr));
+            }
+            continue;
+        }
+
+        let Some((stem, ext)) = t.rsplit_once('.') else {
+            continue;
+        };
+        if !matches!(ext, "js" | "mjs" | "cjs" | "ts" | "py" | "rb" | "php") {
+            continue;
+        }
+        let mut candidates = vec![t.to_string()];
+        for out_dir in ["dist/", "build/", "lib/"] {
+            if let Some(rest) = stem.strip_prefix(out_dir) {
+                candidates.push(format!("src/{}.ts", rest));
+                candidates.push(format!("src/{}.js", rest));
+            }
+        }
+        for candidate in candidates {
+            let hits = entry_in_file(graph, context, &candidate, "main");
+            if !hits.is_empty() {
+                found.extend(hits);
+                break;
+            }
+        }
+    }
+    found
+}
+
+fn entry_in_file<G: Graph>(graph: &G, context: &Path, file: &str, name: &str) -> Vec<NodeData> {
+    let full = normalize(&context.join(file)).display().to_string();
+    graph
+        .find_nodes_by_name(NodeType::Function, name)
+        .into_iter()
+        .filter(|f| f.file == full)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dockerfile() {
+        let df = parse_dockerfile(
+            r#"
+FROM golang:1.21-alpine AS builder
+WORKDIR /app
+COPY go.mod go.sum ./
+COPY cmd/ ./cmd/
+RUN go mod download && \
+    go build -o server ./cmd/api
+FROM alpine:3.19
+COPY --from=builder /app/server /server
+ENV PORT=8080 LOG_LEVEL=info
+EXPOSE 8080
+CMD ["/server"]
+"#,
+        );
+        assert_eq!(df.base_images, vec!["golang:1.21-alpine", "alpine:3.19"]);
+        assert_eq!(df.copies, vec!["go.mod", "go.sum", "cmd/"]);
+        assert_eq!(df.entrypoint, vec!["/server"]);
+        assert_eq!(
+            df.go_builds,
+            vec![("server".to_string(), "./cmd/api".to_string())]
+        );
+        assert_eq!(df.env.len(), 2);
+        assert_eq!(df.ports, vec!["8080"]);
+    }
+
+    #[test]
+    fn test_env_hosts() {
+        assert_eq!(env_hosts("API_URL", "http://api:8080/v1"), vec!["api"]);
+        assert_eq!(
+            env_hosts("DATABASE_URL", "postgres://user:pw@db:5432/app"),
+            vec!["db"]
+        );
+        assert_eq!(env_hosts("REDIS_HOST", "cache"), vec!["cache"]);
+        assert_eq!(
+            env_hosts("USERS_URL", "http://users.default.svc.cluster.local"),
+            vec!["users"]
+        );
+        assert!(env_hosts("LOG_LEVEL", "info").is_empty());
+    }
+
+    #[test]
+    fn test_cron_job_nodes() {
+        use crate::lang::graphs::ArrayGraph;
+        use lsp::Language;
+
+        let mut graph = ArrayGraph::new(String::new(), Language::Python);
+        let main = NodeData::name_file("main", "repo/jobs/cleanup.py");
+        graph.add_node(&NodeType::Function, &main);
+
+        let manifest = InfraFile {
+            path: "repo/k8s/cleanup.yaml".to_string(),
+            code: r#"apiVersion: batch/v1
+kind: CronJob
+metadata:
+  name: nightly-cleanup
+spec:
+  schedule: "0 3 * * *"
+  jobTemplate:
+    spec:
+      template:
+        spec:
+          containers:
+            - name: cleanup
+              image: app:latest
+              command: ["python", "jobs/cleanup.py"]
+"#
+            .to_string(),
+        };
+        assert_eq!(add_cron_job_nodes(&mut graph, &[&manifest]), 1);
+
+        let jobs = graph.find_nodes_by_type(NodeType::Job);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].name, "nightly-cleanup");
+        assert_eq!(
+            jobs[0].meta.get("schedule").map(|s| s.as_str()),
+            Some("0 3 * * *")
+        );
+        assert_eq!(
+            graph.count_edges_of_type(EdgeType::Handler),
+            1,
+            "CronJob should hand off to jobs/cleanup.py main"
+        );
+    }
+}
@@ -0,0 +1,176 @@
+pub mod ci;
+pub mod config;
+pub mod docker;
+pub mod nginx;
+pub mod terraform;
+pub mod yaml;
+
+use crate::lang::graphs::Graph;
+use lsp::strip_tmp;
+use shared::error::Result;
+use std::path::{Component, Path, PathBuf};
+use tracing::info;
+use walkdir::{DirEntry, WalkDir};
+
+/// A non-source file (Terraform, Dockerfile, CI config, nginx config) read from a repo root.
+#[derive(Debug, Clone)]
+pub struct InfraFile {
+    pub path: String,
+    pub code: String,
+}
+
+pub fn add_infra_nodes<G: Graph>(graph: &mut G, root: &Path) -> Result<()> {
+    let files = collect_infra_files(root)?;
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let tf_files: Vec<&InfraFile> = files
+        .iter()
+        .filter(|f| terraform::is_terraform_file(&f.path))
+        .collect();
+    if !tf_files.is_empty() {
+        let count = terraform::add_terraform_nodes(graph, &tf_files);
+        info!("=> got {} terraform blocks", count);
+    }
+
+    let service_files: Vec<&InfraFile> = files
+        .iter()
+        .filter(|f| !ci::is_ci_file(&f.path))
+        .filter(|f| {
+            docker::is_dockerfile(&f.path)
+                || docker::is_compose_file(&f.path)
+                || docker::is_k8s_manifest(&f.path, &f.code)
+        })
+        .collect();
+    if !service_files.is_empty() {
+        let count = docker::add_service_nodes(graph, &service_files);
+        info!("=> got {} services", count);
+        let count = docker::add_cron_job_nodes(graph, &service_files);
+        if count > 0 {
+            info!("=> got {} cron jobs", count);
+        }
+    }
+
+    let config_files: Vec<&InfraFile> = files
+        .iter()
+        .filter(|f| !ci::is_ci_file(&f.path))
+        .filter(|f| {
+            config::is_dotenv_template(&f.path)
+                || docker::is_compose_file(&f.path)
+                || docker::is_k8s_manifest(&f.path, &f.code)
+        })
+        .collect();
+    if !config_files.is_empty() {
+        let count = config::add_config_definitions(graph, &config_files);
+        if count > 0 {
+            info!("=> got {} config key definitions", count);
+        }
+    }
+
+    let ci_files: Vec<&InfraFile> = files.iter().filter(|f| ci::is_ci_file(&f.path)).collect();
+    if !ci_files.is_empty() {
+        let count = ci::add_ci_nodes(graph, &ci_files);
+        info!("=> got {} workflows", count);
+    }
+
+    let nginx_files: Vec<&InfraFile> = files
+        .iter()
+        .filter(|f| nginx::is_nginx_conf(&f.path))
+        .collect();
+    if !nginx_files.is_empty() {
+        let count = nginx::add_location_endpoints(graph, &nginx_files);
+        info!("=> got {} nginx location endpoints", count);
+    }
+
+    Ok(())
+}
+
+fn collect_infra_files(root: &Path) -> Result<Vec<InfraFile>> {
+    let mut files = Vec::new();
+    let walker = WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| !is_skip_dir(e));
+    for entry in walker {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = strip_tmp(entry.path()).display().to_string();
+        if !is_infra_file(&path) {
+            continue;
+        }
+        if let Ok(code) = std::fs::read_to_string(entry.path()) {
+            files.push(InfraFile { path, code });
+        }
+    }
+    Ok(files)
+}
+
+fn is_infra_file(path: &str) -> bool {
+    terraform::is_terraform_file(path)
+        || docker::is_dockerfile(path)
+        || ci::is_makefile(path)
+        || nginx::is_nginx_conf(path)
+        || config::is_dotenv_template(path)
+        || path.ends_with(".yml")
+        || path.ends_with(".yaml")
+}
+
+fn is_skip_dir(entry: &DirEntry) -> bool {
+    if !entry.file_type().is_dir() {
+        return false;
+    }
+    let name = entry.file_name().to_str().unwrap_or("");
+    matches!(
+        name,
+        ".git"
+            | "node_modules"
+            | "target"
+            | "vendor"
+            | "build"
+            | "dist"
+            | ".venv"
+            | "venv"
+            | ".terraform"
+    )
+}
+
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for comp in path.components() {
+        match comp {
+            Component::ParentDir => {
+                out.pop();
+            }
+            Component::CurDir => {}
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+pub(crate) fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// The last line spanned by a YAML mapping or list, from its deepest entry.
+pub(crate) fn last_line(node: &yaml::Yaml) -> Option<usize> {
+    match node {
+        yaml::Yaml::Map(entries) => entries
+            .iter()
+            .map(|e| last_line(&e.value).unwrap_or(e.line).max(e.line))
+            .max(),
+        yaml::Yaml::List(items) => items.iter().filter_map(last_line).max(),
+        _ => None,
+    }
+}
+
+/// Lines `start..=end`, clamped to the end of the file.
+pub(crate) fn slice_lines(lines: &[&str], start: usize, end: usize) -> String {
+    if start >= lines.len() {
+        return String::new();
+    }
+    lines[start..=end.min(lines.len() - 1)].join("\n")
+}
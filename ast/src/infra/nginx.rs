@@ -0,0 +1,413 @@
+use super::InfraFile;
+use crate::lang::graphs::{Edge, EdgeType, Graph, NodeRef, NodeType};
+use crate::lang::NodeData;
+use crate::utils::line_of;
+use regex::Regex;
+use std::sync::LazyLock;
+
+static LOCATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*location[ \t]+(?:(=|~\*?|\^~)[ \t]+)?([^\s{]+)[ \t]*\{").unwrap()
+});
+static LIMIT_EXCEPT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*limit_except[ \t]+([A-Za-z \t]+)\{").unwrap());
+static LUA_BLOCK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bcontent_by_lua_block[ \t]*\{").unwrap());
+static LUA_FILE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bcontent_by_lua_file[ \t]+["']?([^;"'\s]+)["']?[ \t]*;"#).unwrap()
+});
+static REQUIRE_CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"require\s*\(?\s*["']([\w./-]+)["']\s*\)?\s*[.:]\s*([A-Za-z_]\w*)\s*\("#).unwrap()
+});
+static REQUIRE_ALIAS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"local\s+([A-Za-z_]\w*)\s*=\s*require\s*\(?\s*["']([\w./-]+)["']\s*\)?"#).unwrap()
+});
+static ALIAS_CALL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b([A-Za-z_]\w*)\s*[.:]\s*([A-Za-z_]\w*)\s*\(").unwrap());
+
+/// What an OpenResty `content_by_lua*` directive hands the request to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LuaHandler {
+    /// `content_by_lua_block { require("app.users").list() }`
+    Function { module: String, func: String },
+    /// `content_by_lua_file lua/health.lua;`
+    File(String),
+}
+
+impl LuaHandler {
+    fn describe(&self) -> String {
+        match self {
+            LuaHandler::Function { module, func } => format!("{}.{}", module, func),
+            LuaHandler::File(path) => path.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocationSpec {
+    pub path: String,
+    pub modifier: Option<String>,
+    pub verbs: Vec<String>,
+    pub handler: LuaHandler,
+    pub start: usize,
+    pub end: usize,
+    pub body: String,
+}
+
+pub fn is_nginx_conf(path: &str) -> bool {
+    path.ends_with(".conf")
+}
+
+/// Parses the `location` blocks of an nginx config that hand off to Lua.
+/// Locations without a `content_by_lua*` directive (proxies, static files) are skipped.
+pub fn parse_locations(code: &str) -> Vec<LocationSpec> {
+    let mut blocks = Vec::new();
+    for caps in LOCATION_RE.captures_iter(code) {
+        let whole = caps.get(0).unwrap();
+        let open = whole.end() - 1;
+        let Some(close) = block_end(code, open) else {
+            continue;
+        };
+        blocks.push((caps, whole.start(), open, close));
+    }
+
+    let mut specs = Vec::new();
+    for (caps, start, open, close) in &blocks {
+        // directives of nested locations belong to those locations
+        let mut own = String::new();
+        let mut cursor = open + 1;
+        for (_, inner_start, _, inner_close) in &blocks {
+            if inner_start > open && inner_close < close && *inner_start >= cursor {
+                own.push_str(&code[cursor..*inner_start]);
+                cursor = inner_close + 1;
+            }
+        }
+        own.push_str(&code[cursor..*close]);
+
+        let Some(handler) = lua_handler(&own) else {
+            continue;
+        };
+        let verbs = LIMIT_EXCEPT_RE
+            .captures(&own)
+            .map(|c| {
+                c[1].split_whitespace()
+                    .map(|v| v.to_uppercase())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| vec!["ANY".to_string()]);
+        specs.push(LocationSpec {
+            path: caps[2].to_string(),
+            modifier: caps.get(1).map(|m| m.as_str().to_string()),
+            verbs,
+            handler,
+            start: line_of(code, *start),
+            end: line_of(code, *close),
+            body: code[*start..=*close].to_string(),
+        });
+    }
+    specs
+}
+
+fn lua_handler(directives: &str) -> Option<LuaHandler> {
+    if let Some(m) = LUA_BLOCK_RE.find(directives) {
+        let open = m.end() - 1;
+        let close = lua_block_end(directives, open)?;
+        return lua_block_handler(&directives[open + 1..close]);
+    }
+    LUA_FILE_RE
+        .captures(directives)
+        .map(|c| LuaHandler::File(c[1].to_string()))
+}
+
+/// Finds the module function an inline Lua block calls, either directly
+/// (`require("app.users").list()`) or through a local alias (`local users = require "app.users"`).
+pub fn lua_block_handler(lua: &str) -> Option<LuaHandler> {
+    if let Some(c) = REQUIRE_CALL_RE.captures(lua) {
+        return Some(LuaHandler::Function {
+            module: c[1].to_string(),
+            func: c[2].to_string(),
+        });
+    }
+    let aliases: Vec<(String, String)> = REQUIRE_ALIAS_RE
+        .captures_iter(lua)
+        .map(|c| (c[1].to_string(), c[2].to_string()))
+        .collect();
+    ALIAS_CALL_RE.captures_iter(lua).find_map(|c| {
+        aliases
+            .iter()
+            .find(|(alias, _)| alias == &c[1])
+            .map(|(_, module)| LuaHandler::Function {
+                module: module.clone(),
+                func: c[2].to_string(),
+            })
+    })
+}
+
+/// Like `Syntax::block_end`, but for nginx: `#` only starts a comment at the start of a token
+/// (values are full of `//` urls and `path#fragment`s), and `*_by_lua_block` bodies are Lua.
+fn block_end(code: &str, open: usize) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            q @ (b'"' | b'\'') => i = quote_end(bytes, i, q),
+            b'#' if i == 0
+                || matches!(
+                    bytes[i - 1],
+                    b' ' | b'\t' | b'\n' | b'\r' | b';' | b'{' | b'}'
+                ) =>
+            {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'{' if i > open && code[..i].trim_end().ends_with("_by_lua_block") => {
+                i = lua_block_end(code, i)?;
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Finds the `}` closing a `*_by_lua_block {` body. Lua strings and `--` comments are
+/// skipped; `#` is the length operator there, not a comment.
+fn lua_block_end(code: &str, open: usize) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            q @ (b'"' | b'\'') => i = quote_end(bytes, i, q),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => match long_bracket_end(code, i + 2) {
+                Some(end) => i = end,
+                None => {
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+            },
+            b'[' => {
+                if let Some(end) = long_bracket_end(code, i) {
+                    i = end;
+                }
+            }
+            b'{' => depth += 1,
+            b'}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Index of the quote closing the string opened at `start`, or of the line end.
+fn quote_end(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() && bytes[i] != quote && bytes[i] != b'\n' {
+        if bytes[i] == b'\\' {
+            i += 1;
+        }
+        i += 1;
+    }
+    i
+}
+
+/// A Lua long bracket (`[[ ... ]]`, `[==[ ... ]==]`) opened at `start`: the index of its last `]`.
+fn long_bracket_end(code: &str, start: usize) -> Option<usize> {
+    let rest = code.get(start..)?.strip_prefix('[')?;
+    let level = rest.bytes().take_while(|b| *b == b'=').count();
+    if rest.as_bytes().get(level) != Some(&b'[') {
+        return None;
+    }
+    let close = format!("]{}]", "=".repeat(level));
+    let body = start + level + 2;
+    code[body..]
+        .find(&close)
+        .map(|p| body + p + close.len() - 1)
+}
+
+pub fn add_location_endpoints<G: Graph>(graph: &mut G, files: &[&InfraFile]) -> usize {
+    let mut count = 0;
+    for file in files {
+        for loc in parse_locations(&file.code) {
+            for verb in &loc.verbs {
+                let mut nd = NodeData::name_file(&loc.path, &file.path);
+                nd.start = loc.start;
+                nd.end = loc.end;
+                nd.body = loc.body.clone();
+                nd.add_verb(verb);
+                nd.add_handler(&loc.handler.describe());
+                if let Some(modifier) = &loc.modifier {
+                    nd.meta
+                        .insert("location_modifier".to_string(), modifier.clone());
+                }
+                graph.add_node_with_parent(&NodeType::Endpoint, &nd, &NodeType::File, &file.path);
+                link_handler(graph, &nd, &loc.handler);
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+fn link_handler<G: Graph>(graph: &mut G, endpoint: &NodeData, handler: &LuaHandler) {
+    match handler {
+        LuaHandler::Function { module, func } => {
+            let module = module.replace('.', "/");
+            let init = format!("{}/init", module);
+            let target = graph
+                .find_nodes_by_name(NodeType::Function, func)
+                .into_iter()
+                .find(|f| {
+                    let stem = f.file.strip_suffix(".lua").unwrap_or(&f.file);
+                    stem.ends_with(&module) || stem.ends_with(&init)
+                });
+            if let Some(target) = target {
+                graph.add_edge(&Edge::handler(endpoint, &target));
+            }
+        }
+        LuaHandler::File(path) => {
+            // paths are relative to the nginx prefix, so match on the longest suffix we can
+            let parts: Vec<&str> = path
+                .split('/')
+                .filter(|p| !p.is_empty() && *p != ".")
+                .collect();
+            let files = graph.find_nodes_by_type(NodeType::File);
+            let target = (0..parts.len()).find_map(|skip| {
+                let suffix = parts[skip..].join("/");
+                files
+                    .iter()
+                    .find(|f| f.file == suffix || f.file.ends_with(&format!("/{}", suffix)))
+            });
+            if let Some(target) = target {
+                graph.add_edge(&Edge::new(
+                    EdgeType::Handler,
+                    NodeRef::from(endpoint.into(), NodeType::Endpoint),
+                    NodeRef::from(target.into(), NodeType::File),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NGINX_CONF: &str = r#"
+http {
+    lua_package_path "/app/lua/?.lua;;";
+    server {
+        listen 8080;
+
+        location /api/users {
+            limit_except GET POST {
+                deny all;
+            }
+            content_by_lua_block {
+                local users = require "app.users"
+                users.list(ngx)
+            }
+        }
+
+        location = /health {
+            content_by_lua_file lua/health.lua;
+        }
+
+        location /static/ {
+            root /var/www; # } in a comment
+            location /static/docs {
+                content_by_lua_block { require("app.docs").render() }
+            }
+        }
+
+        location /upstream {
+            proxy_pass http://backend;
+        }
+    }
+}
+"#;
+
+    #[test]
+    fn test_parse_locations() {
+        let locs = parse_locations(NGINX_CONF);
+        assert_eq!(locs.len(), 3);
+
+        assert_eq!(locs[0].path, "/api/users");
+        assert_eq!(locs[0].verbs, vec!["GET", "POST"]);
+        assert_eq!(
+            locs[0].handler,
+            LuaHandler::Function {
+                module: "app.users".to_string(),
+                func: "list".to_string()
+            }
+        );
+
+        assert_eq!(locs[1].path, "/health");
+        assert_eq!(locs[1].modifier.as_deref(), Some("="));
+        assert_eq!(locs[1].verbs, vec!["ANY"]);
+        assert_eq!(
+            locs[1].handler,
+            LuaHandler::File("lua/health.lua".to_string())
+        );
+
+        // the outer /static/ block has no handler of its own
+        assert_eq!(locs[2].path, "/static/docs");
+        assert_eq!(locs[2].handler.describe(), "app.docs.render");
+    }
+
+    #[test]
+    fn test_lua_block_handler() {
+        assert_eq!(
+            lua_block_handler("require('app.orders'):create()"),
+            Some(LuaHandler::Function {
+                module: "app.orders".to_string(),
+                func: "create".to_string()
+            })
+        );
+        assert_eq!(
+            lua_block_handler("local c = require(\"ctl\")\nngx.say('x')\nc:show(1)"),
+            Some(LuaHandler::Function {
+                module: "ctl".to_string(),
+                func: "show".to_string()
+            })
+        );
+        assert_eq!(lua_block_handler("ngx.say('ok')"), None);
+    }
+
+    #[test]
+    fn test_block_end_lua_bodies() {
+        let conf = r#"location /items {
+    rewrite ^/old$ /items#top break;
+    content_by_lua_block {
+        local t = { "a", "b" } -- } closes nothing
+        --[[ neither does } this ]]
+        local sizes = { #t } ngx.say("}")
+        require("app.items").index()
+    }
+}
+# trailing"#;
+        let close = block_end(conf, conf.find('{').unwrap()).unwrap();
+        assert_eq!(&conf[close..], "}\n# trailing");
+
+        let locs = parse_locations(conf);
+        assert_eq!(locs.len(), 1);
+        assert_eq!(locs[0].end, 8);
+        assert_eq!(locs[0].handler.describe(), "app.items.index");
+    }
+}
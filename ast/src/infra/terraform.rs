@@ -0,0 +1,402 @@
+use super::{normalize, InfraFile};
+use crate::lang::graphs::{Edge, EdgeType, Graph, NodeRef, NodeType};
+use crate::lang::scan::Syntax;
+use crate::lang::NodeData;
+use crate::utils::line_of;
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+static BLOCK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^[ \t]*(resource|data|module|variable|output)((?:[ \t]+"[^"]*")+)[ \t]*\{"#)
+        .unwrap()
+});
+static LABEL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#""([^"]*)""#).unwrap());
+static ATTR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?m)^[ \t]*([a-z_]+)[ \t]*=[ \t]*"([^"\n]*)""#).unwrap());
+static REF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(data\.[\w-]+\.[\w-]+|module\.[\w-]+|var\.[\w-]+|[a-z][a-z0-9]*_[\w]+\.[\w-]+)")
+        .unwrap()
+});
+
+/// Block attributes worth keeping on the Resource node.
+const META_ATTRS: [&str; 7] = [
+    "handler",
+    "runtime",
+    "source",
+    "filename",
+    "source_dir",
+    "image",
+    "function_name",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TfBlock {
+    pub block_type: String,
+    pub labels: Vec<String>,
+    pub address: String,
+    pub start: usize,
+    pub end: usize,
+    pub body: String,
+    pub attrs: BTreeMap<String, String>,
+}
+
+impl TfBlock {
+    pub fn resource_type(&self) -> Option<&str> {
+        match self.block_type.as_str() {
+            "resource" | "data" => self.labels.first().map(|s| s.as_str()),
+            _ => None,
+        }
+    }
+}
+
+pub fn is_terraform_file(path: &str) -> bool {
+    path.ends_with(".tf")
+}
+
+/// Parses top-level `resource`, `data`, `module`, `variable` and `output` blocks.
+pub fn parse_blocks(code: &str) -> Vec<TfBlock> {
+    let mut blocks = Vec::new();
+    let mut cursor = 0;
+    while let Some(caps) = BLOCK_RE.captures_at(code, cursor) {
+        let whole = caps.get(0).unwrap();
+        let open = whole.end() - 1;
+        let Some(close) = Syntax::HCL.block_end(code, open) else {
+            break;
+        };
+        let block_type = caps[1].to_string();
+        let labels: Vec<String> = LABEL_RE
+            .captures_iter(&caps[2])
+            .map(|c| c[1].to_string())
+            .collect();
+        if let Some(address) = block_address(&block_type, &labels) {
+            let body = code[open + 1..close].to_string();
+            let attrs = ATTR_RE
+                .captures_iter(&body)
+                .filter(|c| META_ATTRS.contains(&&c[1]))
+                .map(|c| (c[1].to_string(), c[2].to_string()))
+                .collect();
+            blocks.push(TfBlock {
+                block_type,
+                labels,
+                address,
+                start: line_of(code, whole.start()),
+                end: line_of(code, close),
+                body,
+                attrs,
+            });
+        }
+        cursor = close + 1;
+    }
+    blocks
+}
+
+fn block_address(block_type: &str, labels: &[String]) -> Option<String> {
+    match (block_type, labels) {
+        ("resource", [t, n, ..]) => Some(format!("{}.{}", t, n)),
+        ("data", [t, n, ..]) => Some(format!("data.{}.{}", t, n)),
+        ("module", [n, ..]) => Some(format!("module.{}", n)),
+        ("variable", [n, ..]) => Some(format!("var.{}", n)),
+        ("output", [n, ..]) => Some(format!("output.{}", n)),
+        _ => None,
+    }
+}
+
+/// Addresses referenced from a block body, e.g. `aws_iam_role.lambda` in `aws_iam_role.lambda.arn`.
+pub fn find_references(body: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    REF_RE
+        .captures_iter(body)
+        .map(|c| c[1].to_string())
+        .filter(|r| seen.insert(r.clone()))
+        .collect()
+}
+
+pub fn add_terraform_nodes<G: Graph>(graph: &mut G, files: &[&InfraFile]) -> usize {
+    let mut parsed = Vec::new();
+    for file in files {
+        for block in parse_blocks(&file.code) {
+            let mut nd = NodeData::name_file(&block.address, &file.path);
+            nd.start = block.start;
+            nd.end = block.end;
+            nd.body = format!("{} {{{}}}", block_header(&block), block.body);
+            nd.meta
+                .insert("block_type".to_string(), block.block_type.clone());
+            if let Some(rt) = block.resource_type() {
+                nd.meta.insert("resource_type".to_string(), rt.to_string());
+            }
+            for (k, v) in &block.attrs {
+                nd.meta.insert(k.clone(), v.clone());
+            }
+            graph.add_node_with_parent(&NodeType::Resource, &nd, &NodeType::File, &file.path);
+            parsed.push((block, nd));
+        }
+    }
+
+    let declared: BTreeMap<String, NodeData> = parsed
+        .iter()
+        .map(|(b, nd)| (b.address.clone(), nd.clone()))
+        .collect();
+
+    for (block, nd) in &parsed {
+        for reference in find_references(&block.body) {
+            if reference == block.address {
+                continue;
+            }
+            if let Some(target) = declared.get(&reference) {
+                graph.add_edge(&Edge::depends_on(
+                    NodeType::Resource,
+                    nd,
+                    NodeType::Resource,
+                    target,
+                ));
+            }
+        }
+        if block.block_type == "module" {
+            link_module_source(graph, block, nd);
+        }
+        if let Some(handler) = block.attrs.get("handler") {
+            link_handler(graph, block, nd, handler);
+        }
+    }
+
+    parsed.len()
+}
+
+fn block_header(block: &TfBlock) -> String {
+    let labels: Vec<String> = block.labels.iter().map(|l| format!("\"{}\"", l)).collect();
+    format!("{} {}", block.block_type, labels.join(" "))
+}
+
+fn link_module_source<G: Graph>(graph: &mut G, block: &TfBlock, nd: &NodeData) {
+    let Some(source) = block.attrs.get("source") else {
+        return;
+    };
+    if !(source.starts_with("./") || source.starts_with("../")) {
+        return;
+    }
+    let Some(dir) = Path::new(&nd.file).parent() else {
+        return;
+    };
+    let resolved = normalize(&dir.join(source)).display().to_string();
+    let dirs = graph.find_nodes_by_type(NodeType::Directory);
+    if let Some(target) = dirs.iter().find(|d| d.file == resolved) {
+        graph.add_edge(&Edge::new(
+            EdgeType::Imports,
+            NodeRef::from(nd.into(), NodeType::Resource),
+            NodeRef::from(target.into(), NodeType::Directory),
+        ));
+    }
+}
+
+/// Splits a lambda handler string into a module path and a function name.
+/// `index.handler` -> (`index`, `handler`), `app.main.lambda_handler` -> (`app/main`, `lambda_handler`),
+/// `com.example.Handler::handleRequest` -> (`com/example/Handler`, `handleRequest`).
+pub fn split_handler(handler: &str) -> Option<(String, String)> {
+    let (module, func) = match handler.rsplit_once("::") {
+        Some(parts) => parts,
+        None => handler.rsplit_once('.')?,
+    };
+    if module.is_empty() || func.is_empty() {
+        return None;
+    }
+    let module = module.replace("::", "/").replace('.', "/");
+    Some((module, func.to_string()))
+}
+
+fn link_handler<G: Graph>(graph: &mut G, block: &TfBlock, nd: &NodeData, handler: &str) {
+    let Some((module, func)) = split_handler(handler) else {
+        return;
+    };
+    let module_stem = module.rsplit('/').next().unwrap_or(&module).to_string();
+    let source_hint = block
+        .attrs
+        .get("source_dir")
+        .map(|s| clean_path_hint(s))
+        .filter(|s| !s.is_empty());
+
+    let mut candidates: Vec<NodeData> = graph
+        .find_nodes_by_name(NodeType::Function, &func)
+        .into_iter()
+        .filter(|f| {
+            let without_ext = f.file.rsplit_once('.').map(|(p, _)| p).unwrap_or(&f.file);
+            ends_with_path(without_ext, &module) || ends_with_path(without_ext, &module_stem)
+        })
+        .collect();
+    if let Some(hint) = &source_hint {
+        if candidates.iter().any(|f| f.file.contains(hint.as_str())) {
+            candidates.retain(|f| f.file.contains(hint.as_str()));
+        }
+    }
+    // several `index.handler`s in a monorepo and nothing to choose between them
+    if let [target] = candidates.as_slice() {
+        graph.add_edge(&Edge::resource_handler(nd, target));
+    }
+}
+
+/// `path` ends with the whole path segments of `suffix`: `src/index` ends with `index`,
+/// `src/reindex` does not.
+fn ends_with_path(path: &str, suffix: &str) -> bool {
+    path == suffix
+        || path
+            .strip_suffix(suffix)
+            .is_some_and(|rest| rest.ends_with('/'))
+}
+
+fn clean_path_hint(path: &str) -> String {
+    let path = path
+        .trim_start_matches("${path.module}/")
+        .trim_start_matches("${path.root}/");
+    let mut parts: Vec<&str> = path
+        .split('/')
+        .filter(|p| !p.is_empty() && *p != "." && *p != "..")
+        .collect();
+    if parts.first().is_some_and(|p| p.contains("${")) {
+        parts.remove(0);
+    }
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN_TF: &str = r#"
+# lambda stack
+resource "aws_iam_role" "lambda" {
+  name = "lambda-role"
+}
+
+resource "aws_lambda_function" "api" {
+  function_name = "api"
+  handler       = "index.handler"
+  runtime       = "nodejs18.x"
+  role          = aws_iam_role.lambda.arn
+  environment {
+    variables = {
+      TABLE = var.table_name # "}" in a comment
+    }
+  }
+}
+
+module "vpc" {
+  source = "./modules/vpc"
+}
+
+variable "table_name" {
+  default = "users"
+}
+"#;
+
+    #[test]
+    fn test_parse_blocks() {
+        let blocks = parse_blocks(MAIN_TF);
+        let addrs: Vec<&str> = blocks.iter().map(|b| b.address.as_str()).collect();
+        assert_eq!(
+            addrs,
+            vec![
+                "aws_iam_role.lambda",
+                "aws_lambda_function.api",
+                "module.vpc",
+                "var.table_name"
+            ]
+        );
+        let lambda = &blocks[1];
+        assert_eq!(lambda.resource_type(), Some("aws_lambda_function"));
+        assert_eq!(lambda.attrs.get("handler").unwrap(), "index.handler");
+        assert_eq!(lambda.attrs.get("runtime").unwrap(), "nodejs18.x");
+        assert_eq!(blocks[2].attrs.get("source").unwrap(), "./modules/vpc");
+    }
+
+    #[test]
+    fn test_find_references() {
+        let blocks = parse_blocks(MAIN_TF);
+        let refs = find_references(&blocks[1].body);
+        assert!(refs.contains(&"aws_iam_role.lambda".to_string()));
+        assert!(refs.contains(&"var.table_name".to_string()));
+    }
+
+    #[test]
+    fn test_split_handler() {
+        assert_eq!(
+            split_handler("index.handler"),
+            Some(("index".to_string(), "handler".to_string()))
+        );
+        assert_eq!(
+            split_handler("app.main.lambda_handler"),
+            Some(("app/main".to_string(), "lambda_handler".to_string()))
+        );
+        assert_eq!(
+            split_handler("com.example.Handler::handleRequest"),
+            Some((
+                "com/example/Handler".to_string(),
+                "handleRequest".to_string()
+            ))
+        );
+        assert_eq!(split_handler("bootstrap"), None);
+    }
+
+    #[test]
+    fn test_add_terraform_nodes_links() {
+        use crate::lang::graphs::ArrayGraph;
+        use lsp::Language;
+
+        let mut graph = ArrayGraph::new(String::new(), Language::Typescript);
+        graph.add_node(
+            &NodeType::Directory,
+            &NodeData::name_file("vpc", "repo/infra/modules/vpc"),
+        );
+        for file in [
+            "repo/lambdas/api/index.ts",
+            "repo/lambdas/search/reindex.ts",
+            "repo/lambdas/jobs/index.ts",
+            "repo/lambdas/mail/index.ts",
+        ] {
+            graph.add_node(&NodeType::Function, &NodeData::name_file("handler", file));
+        }
+
+        let main = InfraFile {
+            path: "repo/infra/main.tf".to_string(),
+            code: format!(
+                "{}\nresource \"aws_lambda_function\" \"any\" {{\n  handler = \"index.handler\"\n}}\n",
+                MAIN_TF.replace(
+                    "handler       = \"index.handler\"",
+                    "handler       = \"index.handler\"\n  source_dir    = \"${path.module}/../lambdas/api\""
+                )
+            ),
+        };
+        assert_eq!(add_terraform_nodes(&mut graph, &[&main]), 5);
+
+        let depends = graph.find_nodes_with_edge_type(
+            NodeType::Resource,
+            NodeType::Resource,
+            EdgeType::DependsOn,
+        );
+        let pairs: Vec<(&str, &str)> = depends
+            .iter()
+            .map(|(s, t)| (s.name.as_str(), t.name.as_str()))
+            .collect();
+        assert!(pairs.contains(&("aws_lambda_function.api", "aws_iam_role.lambda")));
+        assert!(pairs.contains(&("aws_lambda_function.api", "var.table_name")));
+
+        let imports = graph.find_nodes_with_edge_type(
+            NodeType::Resource,
+            NodeType::Directory,
+            EdgeType::Imports,
+        );
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].0.name, "module.vpc");
+
+        // `reindex.ts` is not `index`, and `aws_lambda_function.any` has three
+        // `index.handler`s to choose from, so only the hinted lambda is linked
+        let handlers = graph.find_nodes_with_edge_type(
+            NodeType::Resource,
+            NodeType::Function,
+            EdgeType::Handler,
+        );
+        assert_eq!(handlers.len(), 1);
+        assert_eq!(handlers[0].0.name, "aws_lambda_function.api");
+        assert_eq!(handlers[0].1.file, "repo/lambdas/api/index.ts");
+    }
+}
@@ -0,0 +1,633 @@
+//! A small, forgiving YAML reader covering the subset used by compose files,
+//! Kubernetes manifests and CI workflows. It is hand-written rather than built on
+//! serde_yaml because graph nodes need the line of every key, which deserialized values
+//! do not carry. Supported:
+//!
+//! - block maps and lists, and `- key: value` maps nested in lists
+//! - single-line flow lists and maps (`[a, b]`, `{ a: 1 }`)
+//! - plain, single- and double-quoted scalars; every scalar is a string
+//! - literal (`|`) and folded (`>`) block scalars; trailing newlines are always dropped
+//! - anchors (`&name`), aliases (`*name`) and merge keys (`<<: *name`, `<<: [*a, *b]`)
+//!   within one document
+//! - `---` separated documents
+//!
+//! Tags (`!Ref`, `!!str`) are skipped. Multi-line flow collections, multi-line plain or
+//! quoted scalars, escape sequences other than `\"` and complex (`? `) keys are not
+//! supported.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Yaml {
+    Null,
+    Scalar(String),
+    List(Vec<Yaml>),
+    Map(Vec<YamlEntry>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct YamlEntry {
+    pub key: String,
+    /// zero-based line of the key in the source file
+    pub line: usize,
+    pub value: Yaml,
+}
+
+impl Yaml {
+    pub fn get(&self, key: &str) -> Option<&Yaml> {
+        match self {
+            Yaml::Map(entries) => entries.iter().find(|e| e.key == key).map(|e| &e.value),
+            _ => None,
+        }
+    }
+    pub fn get_path(&self, path: &[&str]) -> Option<&Yaml> {
+        path.iter().try_fold(self, |node, key| node.get(key))
+    }
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Yaml::Scalar(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+    pub fn entries(&self) -> &[YamlEntry] {
+        match self {
+            Yaml::Map(entries) => entries,
+            _ => &[],
+        }
+    }
+    pub fn items(&self) -> &[Yaml] {
+        match self {
+            Yaml::List(items) => items,
+            _ => &[],
+        }
+    }
+    /// Scalars of a list, or the single scalar itself (`depends_on: db` and `depends_on: [db]`).
+    pub fn strings(&self) -> Vec<String> {
+        match self {
+            Yaml::Scalar(s) => vec![s.clone()],
+            Yaml::List(items) => items
+                .iter()
+                .filter_map(|i| i.as_str().map(|s| s.to_string()))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+    /// Keys of a map, or scalars of a list (`depends_on` accepts both forms).
+    pub fn keys_or_strings(&self) -> Vec<String> {
+        match self {
+            Yaml::Map(entries) => entries.iter().map(|e| e.key.clone()).collect(),
+            other => other.strings(),
+        }
+    }
+    /// Line of the first key, for maps nested in lists (CI steps, k8s containers).
+    pub fn first_line(&self) -> Option<usize> {
+        match self {
+            Yaml::Map(entries) => entries.first().map(|e| e.line),
+            Yaml::List(items) => items.iter().find_map(|i| i.first_line()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Line {
+    num: usize,
+    indent: usize,
+    text: String,
+}
+
+/// Anchored values of the document being parsed, by name.
+type Anchors = HashMap<String, Yaml>;
+
+pub fn parse_documents(source: &str) -> Vec<Yaml> {
+    let mut docs = Vec::new();
+    let mut current: Vec<Line> = Vec::new();
+    let raw: Vec<&str> = source.lines().collect();
+    let mut n = 0;
+    while n < raw.len() {
+        let line = raw[n];
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed.starts_with("--- ") {
+            if !current.is_empty() {
+                docs.push(parse_lines(std::mem::take(&mut current)));
+            }
+            n += 1;
+            continue;
+        }
+        let text = strip_comment(trimmed);
+        if text.trim().is_empty() {
+            n += 1;
+            continue;
+        }
+        let indent = text.len() - text.trim_start().len();
+        let content = text.trim_start().to_string();
+        if let Some((head, folded)) = block_scalar_head(&content) {
+            // fold the block scalar into a single line so the structure parser never sees it
+            let mut body: Vec<&str> = Vec::new();
+            let mut k = n + 1;
+            while k < raw.len() {
+                let next = raw[k];
+                let next_indent = next.len() - next.trim_start().len();
+                if !next.trim().is_empty() && next_indent <= indent {
+                    break;
+                }
+                body.push(next.trim_end());
+                k += 1;
+            }
+            while body.last().is_some_and(|l| l.is_empty()) {
+                body.pop();
+            }
+            current.push(Line {
+                num: n,
+                indent,
+                text: format!("{}{}", head, quote(&block_scalar(&body, folded))),
+            });
+            n = k;
+            continue;
+        }
+        current.push(Line {
+            num: n,
+            indent,
+            text: content,
+        });
+        n += 1;
+    }
+    if !current.is_empty() {
+        docs.push(parse_lines(current));
+    }
+    docs
+}
+
+pub fn parse(source: &str) -> Yaml {
+    parse_documents(source)
+        .into_iter()
+        .next()
+        .unwrap_or(Yaml::Null)
+}
+
+fn parse_lines(mut lines: Vec<Line>) -> Yaml {
+    let mut i = 0;
+    let indent = lines[0].indent;
+    parse_block(&mut lines, &mut i, indent, &mut Anchors::new())
+}
+
+fn parse_block(lines: &mut [Line], i: &mut usize, indent: usize, anchors: &mut Anchors) -> Yaml {
+    if *i >= lines.len() {
+        return Yaml::Null;
+    }
+    if is_list_item(&lines[*i].text) {
+        parse_list(lines, i, indent, anchors)
+    } else if split_key(&lines[*i].text).is_some() {
+        parse_map(lines, i, indent, anchors)
+    } else {
+        let value = parse_inline(&lines[*i].text, anchors);
+        *i += 1;
+        value
+    }
+}
+
+fn parse_map(lines: &mut [Line], i: &mut usize, indent: usize, anchors: &mut Anchors) -> Yaml {
+    let mut entries: Vec<YamlEntry> = Vec::new();
+    let mut merged: Vec<YamlEntry> = Vec::new();
+    while *i < lines.len() && lines[*i].indent == indent && !is_list_item(&lines[*i].text) {
+        let Some((key, rest)) = split_key(&lines[*i].text) else {
+            *i += 1;
+            continue;
+        };
+        let line = lines[*i].num;
+        *i += 1;
+        let (anchor, rest) = split_anchor(&rest);
+        let value = if !rest.is_empty() {
+            parse_inline(rest, anchors)
+        } else if *i < lines.len() && lines[*i].indent > indent {
+            let child = lines[*i].indent;
+            parse_block(lines, i, child, anchors)
+        } else if *i < lines.len() && lines[*i].indent == indent && is_list_item(&lines[*i].text) {
+            parse_list(lines, i, indent, anchors)
+        } else {
+            Yaml::Null
+        };
+        if let Some(anchor) = anchor {
+            anchors.insert(anchor.to_string(), value.clone());
+        }
+        if key == "<<" {
+            // `<<: *base` / `<<: [*a, *b]`: earlier maps win, explicit keys win over all
+            let maps = match value {
+                Yaml::List(items) => items,
+                map => vec![map],
+            };
+            for entry in maps.into_iter().flat_map(|m| match m {
+                Yaml::Map(entries) => entries,
+                _ => Vec::new(),
+            }) {
+                if !merged.iter().any(|m| m.key == entry.key) {
+                    merged.push(entry);
+                }
+            }
+            continue;
+        }
+        entries.push(YamlEntry { key, line, value });
+    }
+    merged.retain(|m| !entries.iter().any(|e| e.key == m.key));
+    entries.extend(merged);
+    Yaml::Map(entries)
+}
+
+fn parse_list(lines: &mut [Line], i: &mut usize, indent: usize, anchors: &mut Anchors) -> Yaml {
+    let mut items = Vec::new();
+    while *i < lines.len() && lines[*i].indent == indent && is_list_item(&lines[*i].text) {
+        let rest = lines[*i].text[1..].trim_start().to_string();
+        if rest.is_empty() {
+            *i += 1;
+            if *i < lines.len() && lines[*i].indent > indent {
+                let child = lines[*i].indent;
+                items.push(parse_block(lines, i, child, anchors));
+            } else {
+                items.push(Yaml::Null);
+            }
+            continue;
+        }
+        if is_list_item(&rest) || split_key(&rest).is_some() {
+            // `- key: value` opens a map whose keys line up with `key`
+            let offset = lines[*i].text.len() - rest.len();
+            lines[*i].indent = indent + offset;
+            lines[*i].text = rest;
+            let child = lines[*i].indent;
+            items.push(parse_block(lines, i, child, anchors));
+            continue;
+        }
+        items.push(parse_inline(&rest, anchors));
+        *i += 1;
+    }
+    Yaml::List(items)
+}
+
+fn is_list_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// Splits `key: value` on the first `:` that is followed by a space or ends the line,
+/// ignoring colons inside quotes and flow collections.
+fn split_key(text: &str) -> Option<(String, String)> {
+    if text.starts_with('[') || text.starts_with('{') {
+        return None;
+    }
+    let bytes = text.as_bytes();
+    let mut quote_char: Option<u8> = None;
+    for (idx, &b) in bytes.iter().enumerate() {
+        match quote_char {
+            Some(q) if b == q => quote_char = None,
+            Some(_) => {}
+            None if (b == b'"' || b == b'\'') && idx == 0 => quote_char = Some(b),
+            None if b == b':' => {
+                let next = bytes.get(idx + 1);
+                if next.is_none() || next == Some(&b' ') || next == Some(&b'\t') {
+                    let key = unquote(text[..idx].trim());
+                    let rest = text[idx + 1..].trim().to_string();
+                    return Some((key, rest));
+                }
+            }
+            None => {}
+        }
+    }
+    None
+}
+
+fn parse_inline(text: &str, anchors: &mut Anchors) -> Yaml {
+    let (anchor, text) = split_anchor(text.trim());
+    let value = parse_value(text, anchors);
+    if let Some(anchor) = anchor {
+        anchors.insert(anchor.to_string(), value.clone());
+    }
+    value
+}
+
+fn parse_value(text: &str, anchors: &mut Anchors) -> Yaml {
+    if text.is_empty() || text == "~" || text == "null" {
+        return Yaml::Null;
+    }
+    if let Some(alias) = text.strip_prefix('*') {
+        // an unknown alias is most likely an unquoted glob such as `*.md`
+        return anchors
+            .get(alias)
+            .cloned()
+            .unwrap_or_else(|| Yaml::Scalar(text.to_string()));
+    }
+    if text.starts_with('[') && text.ends_with(']') {
+        let inner = &text[1..text.len() - 1];
+        return Yaml::List(
+            split_flow(inner)
+                .iter()
+                .filter(|s| !s.trim().is_empty())
+                .map(|s| parse_inline(s, anchors))
+                .collect(),
+        );
+    }
+    if text.starts_with('{') && text.ends_with('}') {
+        let inner = &text[1..text.len() - 1];
+        return Yaml::Map(
+            split_flow(inner)
+                .iter()
+                .filter_map(|s| split_key(s.trim()))
+                .map(|(key, value)| YamlEntry {
+                    key,
+                    line: 0,
+                    value: parse_inline(&value, anchors),
+                })
+                .collect(),
+        );
+    }
+    Yaml::Scalar(unquote(text))
+}
+
+/// Splits a leading anchor (`&name`) and any tags (`!Ref`, `!!str`) off a value.
+fn split_anchor(text: &str) -> (Option<&str>, &str) {
+    let mut anchor = None;
+    let mut rest = text.trim_start();
+    while rest.starts_with('&') || rest.starts_with('!') {
+        let (token, after) = rest.split_once([' ', '\t']).unwrap_or((rest, ""));
+        if let Some(name) = token.strip_prefix('&') {
+            anchor = Some(name);
+        }
+        rest = after.trim_start();
+    }
+    (anchor, rest)
+}
+
+fn split_flow(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut quote_char: Option<char> = None;
+    let mut current = String::new();
+    for c in text.chars() {
+        match quote_char {
+            Some(q) if c == q => quote_char = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote_char = Some(c),
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(std::mem::take(&mut current));
+                    continue;
+                }
+                _ => {}
+            },
+        }
+        current.push(c);
+    }
+    parts.push(current);
+    parts
+}
+
+fn unquote(text: &str) -> String {
+    let t = text.trim();
+    if t.len() >= 2
+        && ((t.starts_with('"') && t.ends_with('"')) || (t.starts_with('\'') && t.ends_with('\'')))
+    {
+        return t[1..t.len() - 1].replace("\\\"", "\"").replace("''", "'");
+    }
+    t.to_string()
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\\\""))
+}
+
+/// For `key: |` / `key: >-` / `- |` returns the text before the indicator, and whether
+/// the scalar is folded (`>`).
+fn block_scalar_head(content: &str) -> Option<(String, bool)> {
+    let (head, indicator) = match content.rsplit_once(' ') {
+        Some((head, ind)) => (format!("{} ", head), ind),
+        None => (String::new(), content),
+    };
+    let is_indicator = matches!(indicator, "|" | "|-" | "|+" | ">" | ">-" | ">+");
+    if !is_indicator {
+        return None;
+    }
+    let head_trim = head.trim_end();
+    if head_trim.ends_with(':') || head_trim == "-" {
+        Some((head, indicator.starts_with('>')))
+    } else {
+        None
+    }
+}
+
+/// The text of a block scalar from its lines: the indentation of the first line is
+/// removed from all of them, and folded scalars join lines with spaces, keeping blank
+/// lines and more-indented lines as line breaks.
+fn block_scalar(body: &[&str], folded: bool) -> String {
+    let indent = body
+        .iter()
+        .find(|l| !l.trim().is_empty())
+        .map_or(0, |l| l.len() - l.trim_start().len());
+    let lines: Vec<&str> = body
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or_default())
+        .collect();
+    if !folded {
+        return lines.join("\n");
+    }
+    let mut text = String::new();
+    let mut prev: Option<&str> = None;
+    for line in lines {
+        let separator = match prev {
+            None => "",
+            Some(p) if p.is_empty() || p.starts_with(' ') || line.starts_with(' ') => "\n",
+            Some(_) if line.is_empty() => "",
+            Some(_) => " ",
+        };
+        text.push_str(separator);
+        text.push_str(line);
+        prev = Some(line);
+    }
+    text
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut quote_char: Option<char> = None;
+    let mut prev = ' ';
+    for (idx, c) in line.char_indices() {
+        match quote_char {
+            Some(q) if c == q => quote_char = None,
+            Some(_) => {}
+            None => {
+                if (c == '"' || c == '\'') && (prev == ' ' || prev == ':' || prev == '-') {
+                    quote_char = Some(c);
+                } else if c == '#' && (prev == ' ' || prev == '\t' || idx == 0) {
+                    return &line[..idx];
+                }
+            }
+        }
+        prev = c;
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_compose() {
+        let src = r#"
+version: '3.8'
+services:
+  api:
+    build:
+      context: ./api
+    ports:
+      - "8080:8080"   # http
+    environment:
+      - DATABASE_URL=postgres://db:5432/app
+  web:
+    image: node:20
+    depends_on: [api]
+"#;
+        let doc = parse(src);
+        let services = doc.get("services").unwrap();
+        assert_eq!(services.entries().len(), 2);
+        let api = services.get("api").unwrap();
+        assert_eq!(
+            api.get_path(&["build", "context"]).and_then(|v| v.as_str()),
+            Some("./api")
+        );
+        assert_eq!(
+            api.get("ports").unwrap().strings(),
+            vec!["8080:8080".to_string()]
+        );
+        assert_eq!(
+            api.get("environment").unwrap().strings(),
+            vec!["DATABASE_URL=postgres://db:5432/app".to_string()]
+        );
+        assert_eq!(services.entries()[1].line, 10);
+        assert_eq!(
+            services
+                .get("web")
+                .unwrap()
+                .get("depends_on")
+                .unwrap()
+                .strings(),
+            vec!["api".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_list_of_maps_and_block_scalars() {
+        let src = r#"
+steps:
+  - uses: actions/checkout@v4
+  - name: Test
+    run: |
+      cargo build
+      cargo test
+---
+kind: Service
+"#;
+        let docs = parse_documents(src);
+        assert_eq!(docs.len(), 2);
+        let steps = docs[0].get("steps").unwrap().items();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(
+            steps[0].get("uses").and_then(|v| v.as_str()),
+            Some("actions/checkout@v4")
+        );
+        assert_eq!(
+            steps[1].get("run").and_then(|v| v.as_str()),
+            Some("cargo build\ncargo test")
+        );
+        assert_eq!(steps[1].first_line(), Some(3));
+        assert_eq!(
+            docs[1].get("kind").and_then(|v| v.as_str()),
+            Some("Service")
+        );
+    }
+
+    #[test]
+    fn test_parse_anchors_and_merges() {
+        let src = r#"
+x-env: &env
+  LOG_LEVEL: info
+  REGION: eu
+x-base: &base
+  restart: always
+  environment: *env
+services:
+  api:
+    <<: *base
+    image: api
+    restart: on-failure
+  worker:
+    <<: [*base, {image: worker}]
+    command: !reset [run, "*.md"]
+    ports: &ports ["9000"]
+  cron:
+    ports: *ports
+    paths: [*.md]
+"#;
+        let doc = parse(src);
+        let services = doc.get("services").unwrap();
+        let api = services.get("api").unwrap();
+        assert_eq!(api.get("image").and_then(|v| v.as_str()), Some("api"));
+        // explicit keys win over merged ones, wherever they appear
+        assert_eq!(
+            api.get("restart").and_then(|v| v.as_str()),
+            Some("on-failure")
+        );
+        assert_eq!(
+            api.get_path(&["environment", "REGION"])
+                .and_then(|v| v.as_str()),
+            Some("eu")
+        );
+        assert_eq!(api.get("<<"), None);
+        assert_eq!(api.entries()[0].line, 10);
+
+        let worker = services.get("worker").unwrap();
+        assert_eq!(worker.get("image").and_then(|v| v.as_str()), Some("worker"));
+        assert_eq!(
+            worker.get("restart").and_then(|v| v.as_str()),
+            Some("always")
+        );
+        assert_eq!(
+            worker.get("command").unwrap().strings(),
+            vec!["run".to_string(), "*.md".to_string()]
+        );
+        let cron = services.get("cron").unwrap();
+        assert_eq!(
+            cron.get("ports").unwrap().strings(),
+            vec!["9000".to_string()]
+        );
+        // an alias to nothing stays a plain scalar
+        assert_eq!(
+            cron.get("paths").unwrap().strings(),
+            vec!["*.md".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_literal_and_folded_scalars() {
+        let src = r#"
+literal: |
+  if true; then
+    echo "a # b"
+  fi
+
+folded: >-
+  one
+  two
+
+  three
+    indented
+  four
+"#;
+        let doc = parse(src);
+        assert_eq!(
+            doc.get("literal").and_then(|v| v.as_str()),
+            Some("if true; then\n  echo \"a # b\"\nfi")
+        );
+        assert_eq!(
+            doc.get("folded").and_then(|v| v.as_str()),
+            Some("one two\nthree\n  indented\nfour")
+        );
+    }
+}
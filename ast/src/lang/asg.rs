@@ -1,4 +1,6 @@
-use crate::lang::Edge;
+use crate::lang::{CallSite, Edge};
+use crate::lang::fields::Field;
+use crate::lang::signature::Signature;
 use crate::lang::NodeType;
 use serde::ser::{SerializeMap, Serializer};
 use serde::{Deserialize, Serialize};
@@ -225,6 +227,39 @@ impl NodeData {
     pub fn add_reexport(&mut self) {
         self.meta.insert("is_reexport".to_string(), "true".to_string());
     }
+    pub fn add_reads(&mut self, fields: &str) {
+        self.meta.insert("reads".to_string(), fields.to_string());
+    }
+    pub fn add_writes(&mut self, fields: &str) {
+        self.meta.insert("writes".to_string(), fields.to_string());
+    }
+    pub fn add_signature(&mut self, signature: &Signature) {
+        if signature.is_empty() {
+            return;
+        }
+        if let Ok(json) = serde_json::to_string(signature) {
+            self.meta.insert("signature".to_string(), json);
+        }
+    }
+    pub fn signature(&self) -> Option<Signature> {
+        self.meta
+            .get("signature")
+            .and_then(|s| serde_json::from_str(s).ok())
+    }
+    pub fn add_fields(&mut self, fields: &[Field]) {
+        if fields.is_empty() {
+            return;
+        }
+        if let Ok(json) = serde_json::to_string(fields) {
+            self.meta.insert("fields".to_string(), json);
+        }
+    }
+    pub fn fields(&self) -> Vec<Field> {
+        self.meta
+            .get("fields")
+            .and_then(|s| serde_json::from_str(s).ok())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -243,6 +278,8 @@ pub struct Calls {
     pub confidence: f32,
     #[serde(default)]
     pub strategy: String,
+    #[serde(default)]
+    pub call_site: Option<CallSite>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -299,6 +336,17 @@ impl FromStr for NodeType {
             "IntegrationTest" => Ok(NodeType::IntegrationTest),
             "E2etest" => Ok(NodeType::E2eTest),
             "Mock" => Ok(NodeType::Mock),
+            "Resource" => Ok(NodeType::Resource),
+            "Service" => Ok(NodeType::Service),
+            "Workflow" => Ok(NodeType::Workflow),
+            "WorkflowJob" => Ok(NodeType::WorkflowJob),
+            "WorkflowStep" => Ok(NodeType::WorkflowStep),
+            "Queue" => Ok(NodeType::Queue),
+            "Job" => Ok(NodeType::Job),
+            "Command" => Ok(NodeType::Command),
+            "Channel" => Ok(NodeType::Channel),
+            "ConfigKey" => Ok(NodeType::ConfigKey),
+            "Module" => Ok(NodeType::Module),
             _ => Err(Error::validation(format!("Invalid NodeType string: {}", s))),
         }
     }
@@ -327,6 +375,17 @@ impl std::fmt::Display for NodeType {
             NodeType::IntegrationTest => "IntegrationTest",
             NodeType::E2eTest => "E2etest",
             NodeType::Mock => "Mock",
+            NodeType::Resource => "Resource",
+            NodeType::Service => "Service",
+            NodeType::Workflow => "Workflow",
+            NodeType::WorkflowJob => "WorkflowJob",
+            NodeType::WorkflowStep => "WorkflowStep",
+            NodeType::Queue => "Queue",
+            NodeType::Job => "Job",
+            NodeType::Command => "Command",
+            NodeType::Channel => "Channel",
+            NodeType::ConfigKey => "ConfigKey",
+            NodeType::Module => "Module",
         };
         write!(f, "{}", s)
     }
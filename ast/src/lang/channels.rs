@@ -0,0 +1,571 @@
+use crate::lang::graphs::{Edge, Graph, NodeType};
+use crate::lang::scan::{
+    code_matches, enclosing_function, first_line, functions_by_file, next_named_function,
+    scan_files, Syntax,
+};
+use crate::lang::NodeData;
+use crate::utils::line_of;
+use lsp::Language;
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::LazyLock;
+
+const JS: &[Language] = &[Language::Typescript];
+const PY: &[Language] = &[Language::Python];
+const RB: &[Language] = &[Language::Ruby];
+const GO: &[Language] = &[Language::Go];
+const RS: &[Language] = &[Language::Rust];
+
+/// Clients opening a WebSocket or event stream count as emitters and the upgrade /
+/// stream handler as the listener, the same way a Request points at its Endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelRole {
+    Emit,
+    Listen,
+}
+
+/// A realtime call site. `name` captures the event, channel or path; detectors without
+/// one mark a WebSocket upgrade or SSE response whose path comes from the route of the
+/// enclosing handler (see ROUTE_RES).
+struct Detector {
+    protocol: &'static str,
+    role: ChannelRole,
+    langs: &'static [Language],
+    handler_name: Option<&'static str>,
+    re: Regex,
+}
+
+fn detector(
+    protocol: &'static str,
+    role: ChannelRole,
+    langs: &'static [Language],
+    pattern: &str,
+) -> Detector {
+    Detector {
+        protocol,
+        role,
+        langs,
+        handler_name: None,
+        re: Regex::new(pattern).unwrap(),
+    }
+}
+
+static DETECTORS: LazyLock<Vec<Detector>> = LazyLock::new(|| {
+    use ChannelRole::*;
+    vec![
+        // socket.io: `socket.on("chat:message", onMessage)`, `io.to(room).emit("chat:message", msg)`
+        detector(
+            "socketio",
+            Listen,
+            JS,
+            r#"\b(?:\w*[sS]ocket\w*|io)\.on\(\s*['"`](?P<name>[\w:.\-/]+)['"`]\s*,\s*(?:(?:this\.)?(?P<handler>[A-Za-z_$][\w$]*)\s*[,)])?"#,
+        ),
+        detector(
+            "socketio",
+            Emit,
+            JS,
+            r#"\b(?:\w*[sS]ocket\w*|io|nsp)(?:\.(?:to|in|of)\([^)]*\)|\.broadcast|\.volatile)*\.emit\(\s*['"`](?P<name>[\w:.\-/]+)['"`]"#,
+        ),
+        // python-socketio / flask-socketio
+        detector(
+            "socketio",
+            Listen,
+            PY,
+            r#"(?m)^[ \t]*@\w*(?:sio|socketio)\w*\.on\(\s*['"](?P<name>[\w:.\-/]+)['"][^\n]*\n(?:[ \t]*@[^\n]*\n)*[ \t]*(?:async[ \t]+)?def[ \t]+(?P<handler>\w+)"#,
+        ),
+        detector(
+            "socketio",
+            Listen,
+            PY,
+            r"(?m)^[ \t]*@\w*(?:sio|socketio)\w*\.event\b[^\n]*\n(?:[ \t]*@[^\n]*\n)*[ \t]*(?:async[ \t]+)?def[ \t]+(?P<handler>\w+)",
+        ),
+        detector(
+            "socketio",
+            Emit,
+            PY,
+            r#"\bemit\(\s*['"](?P<name>[\w:.\-/]+)['"]"#,
+        ),
+        // Phoenix channels, from the JS client: `channel.push("new_msg", ..)`, `channel.on("new_msg", ..)`
+        detector(
+            "phoenix",
+            Emit,
+            JS,
+            r#"\b\w*[cC]hannel\w*\.push\(\s*['"`](?P<name>[\w:.\-/]+)['"`]"#,
+        ),
+        detector(
+            "phoenix",
+            Listen,
+            JS,
+            r#"\b\w*[cC]hannel\w*\.on\(\s*['"`](?P<name>[\w:.\-/]+)['"`]\s*,\s*(?:(?:this\.)?(?P<handler>[A-Za-z_$][\w$]*)\s*[,)])?"#,
+        ),
+        // ActionCable: channel classes are entered through `subscribed`, streams by name
+        Detector {
+            handler_name: Some("subscribed"),
+            ..detector(
+                "actioncable",
+                Listen,
+                RB,
+                r"(?m)^[ \t]*class[ \t]+(?:\w+::)*(?P<name>\w+Channel)[ \t]*<[ \t]*ApplicationCable::Channel\b",
+            )
+        },
+        detector(
+            "actioncable",
+            Listen,
+            RB,
+            r#"\bstream_from\s*\(?\s*['"](?P<name>[^'"]+)['"]"#,
+        ),
+        detector(
+            "actioncable",
+            Emit,
+            RB,
+            r#"ActionCable\.server\.broadcast\(?\s*['"](?P<name>[^'"]+)['"]"#,
+        ),
+        detector(
+            "actioncable",
+            Emit,
+            RB,
+            r"\b(?:\w+::)*(?P<name>\w+Channel)\.broadcast_to\b",
+        ),
+        detector(
+            "actioncable",
+            Emit,
+            JS,
+            r#"subscriptions\.create\(\s*(?:\{\s*channel\s*:\s*)?['"`](?P<name>\w+Channel)['"`]"#,
+        ),
+        // raw WebSocket and Server-Sent Events clients
+        detector(
+            "websocket",
+            Emit,
+            JS,
+            r#"new\s+WebSocket\(\s*['"`](?P<name>[^'"`]+)['"`]"#,
+        ),
+        detector(
+            "sse",
+            Emit,
+            JS,
+            r#"new\s+EventSource\(\s*['"`](?P<name>[^'"`]+)['"`]"#,
+        ),
+        // WebSocket upgrades and SSE responses on the server
+        detector(
+            "websocket",
+            Listen,
+            RS,
+            r"\bWebSocketUpgrade\b|\bws::start\(|\bactix_ws::handle\(",
+        ),
+        detector("sse", Listen, RS, r"\bSse::new\(|\bSse<"),
+        detector(
+            "websocket",
+            Listen,
+            PY,
+            r#"(?m)^[ \t]*@\w+\.(?:websocket|route)\(\s*['"](?P<name>[^'"]+)['"][^\n]*\n(?:[ \t]*@[^\n]*\n)*[ \t]*(?:async[ \t]+)?def[ \t]+(?P<handler>\w+)\s*\([^)]*\b(?:WebSocket|ws)\b"#,
+        ),
+        detector(
+            "websocket",
+            Listen,
+            JS,
+            r#"new\s+WebSocketServer\(\s*\{[^}]*?\bpath\s*:\s*['"`](?P<name>[^'"`]+)['"`]"#,
+        ),
+        detector(
+            "websocket",
+            Listen,
+            GO,
+            r"(?i:\w*upgrader)\.Upgrade\(|\bwebsocket\.Accept\(",
+        ),
+        detector(
+            "sse",
+            Listen,
+            PY,
+            r#"['"]text/event-stream|\bEventSourceResponse\("#,
+        ),
+        detector("sse", Listen, JS, r#"['"`]text/event-stream"#),
+        detector("sse", Listen, GO, r#"["`]text/event-stream"#),
+    ]
+});
+
+// Routes that give a path to the handler of an upgrade / stream without one.
+// `handler` is missing for inline closures; the nearest preceding route in the file is used.
+static ROUTE_RES: LazyLock<Vec<(&'static [Language], Regex)>> = LazyLock::new(|| {
+    let route = |langs, pattern: &str| (langs, Regex::new(pattern).unwrap());
+    vec![
+        // axum `.route("/ws", get(ws_handler))`, actix `.route("/ws", web::get().to(ws))`
+        route(
+            RS,
+            r#"\.route\(\s*"(?P<path>[^"]+)"\s*,\s*(?:(?:\w+::)*(?:get|any)\(\s*|web::get\(\)\.to\(\s*)(?:\w+::)*(?P<handler>\w+)\s*\)"#,
+        ),
+        route(
+            RS,
+            r#"#\[get\(\s*"(?P<path>[^"]+)"[^\]]*\]\s*(?:pub\s+)?async\s+fn\s+(?P<handler>\w+)"#,
+        ),
+        route(
+            PY,
+            r#"(?m)^[ \t]*@\w+\.(?:route|get)\(\s*['"](?P<path>[^'"]+)['"][^\n]*\n(?:[ \t]*@[^\n]*\n)*[ \t]*(?:async[ \t]+)?def[ \t]+(?P<handler>\w+)"#,
+        ),
+        route(
+            JS,
+            r#"\.get\(\s*['"`](?P<path>/[^'"`]*)['"`]\s*,\s*(?:(?P<handler>[A-Za-z_$][\w$]*)\s*\))?"#,
+        ),
+        route(
+            GO,
+            r#"\b(?:HandleFunc|Handle|GET)\(\s*"(?P<path>[^"]+)"\s*,\s*(?:(?:\w+\.)?(?P<handler>\w+)\s*\))?"#,
+        ),
+    ]
+});
+
+// connection lifecycle events every socket.io client and server has
+const SOCKETIO_RESERVED: &[&str] = &[
+    "connect",
+    "connection",
+    "connect_error",
+    "disconnect",
+    "disconnecting",
+    "error",
+    "reconnect",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelUse {
+    pub protocol: &'static str,
+    pub role: ChannelRole,
+    /// event, channel or path; `None` when it comes from the route of the enclosing handler
+    pub name: Option<String>,
+    /// listening function, when it is not the one enclosing `line`
+    pub handler: Option<String>,
+    pub line: usize,
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteDecl {
+    pub path: String,
+    pub handler: Option<String>,
+    pub line: usize,
+}
+
+/// Finds realtime emitters and listeners in one source file, ignoring comments and strings.
+pub fn find_channel_uses(lang: &Language, code: &str) -> Vec<ChannelUse> {
+    let mask = Syntax::of(lang).mask(code);
+    let mut uses = Vec::new();
+    for det in DETECTORS.iter().filter(|d| d.langs.contains(lang)) {
+        for caps in code_matches(&det.re, code, &mask) {
+            let whole = caps.get(0).unwrap();
+            let handler = caps
+                .name("handler")
+                .map(|h| h.as_str().to_string())
+                .or_else(|| det.handler_name.map(str::to_string));
+            let name = match caps.name("name") {
+                Some(n) => Some(channel_name(det.protocol, n.as_str())),
+                // `@sio.event def chat_message(..)` listens for its own name
+                None if det.protocol == "socketio" => handler.clone(),
+                None => None,
+            };
+            let reserved = det.protocol == "socketio"
+                && name
+                    .as_deref()
+                    .is_some_and(|n| SOCKETIO_RESERVED.contains(&n));
+            if reserved || name.as_deref() == Some("") {
+                continue;
+            }
+            uses.push(ChannelUse {
+                protocol: det.protocol,
+                role: det.role,
+                name,
+                handler,
+                line: line_of(code, whole.start()),
+                snippet: first_line(whole.as_str()),
+            });
+        }
+    }
+    uses.sort_by_key(|u| u.line);
+    uses
+}
+
+pub fn find_routes(lang: &Language, code: &str) -> Vec<RouteDecl> {
+    let mask = Syntax::of(lang).mask(code);
+    let mut routes = Vec::new();
+    for (_, re) in ROUTE_RES.iter().filter(|(langs, _)| langs.contains(lang)) {
+        for caps in code_matches(re, code, &mask) {
+            routes.push(RouteDecl {
+                path: caps["path"].to_string(),
+                handler: caps.name("handler").map(|h| h.as_str().to_string()),
+                line: line_of(code, caps.get(0).unwrap().start()),
+            });
+        }
+    }
+    routes.sort_by_key(|r| r.line);
+    routes
+}
+
+/// `ws://${host}/chat?room=1` -> `/chat`, `"chat_#{room.id}"` -> `chat`
+fn channel_name(protocol: &str, raw: &str) -> String {
+    let mut name = raw;
+    let url = matches!(protocol, "websocket" | "sse");
+    if url {
+        if let Some((_, rest)) = name.split_once("://") {
+            name = rest.find('/').map(|i| &rest[i..]).unwrap_or("/");
+        }
+        name = name.split(['?', '#']).next().unwrap_or_default();
+    }
+    if name.starts_with("${") {
+        name = name
+            .split_once('}')
+            .map(|(_, rest)| rest)
+            .unwrap_or_default();
+    }
+    if let Some(at) = name.find("#{").or_else(|| name.find("${")) {
+        name = &name[..at];
+    }
+    if !url {
+        return name.trim_end_matches(['_', ':', '-', '.']).to_string();
+    }
+    let path = name.trim_end_matches('/');
+    if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    }
+}
+
+/// The path served by `func` (the handler enclosing an upgrade at `line` in `file`):
+/// a route naming it anywhere, else the nearest route before `line` in the same file.
+pub fn route_path(
+    routes: &[(String, RouteDecl)],
+    file: &str,
+    line: usize,
+    func: Option<&NodeData>,
+) -> Option<String> {
+    let named = func.and_then(|f| {
+        let mut named = routes
+            .iter()
+            .filter(|(_, r)| r.handler.as_deref() == Some(f.name.as_str()));
+        let first = named.next();
+        first
+            .into_iter()
+            .chain(named)
+            .find(|(rf, _)| rf == file)
+            .or(first)
+    });
+    named
+        .or_else(|| {
+            routes
+                .iter()
+                .filter(|(rf, r)| rf == file && r.handler.is_none() && r.line <= line)
+                .filter(|(_, r)| !matches!(func, Some(f) if r.line < f.start))
+                .max_by_key(|(_, r)| r.line)
+        })
+        .map(|(_, r)| r.path.clone())
+}
+
+/// Adds a Channel node per (protocol, name), EMITS edges from emitting functions and
+/// HANDLER edges to the functions listening on it.
+pub fn add_channel_nodes<G: Graph>(
+    graph: &mut G,
+    lang: &Language,
+    filez: &[(String, String)],
+) -> usize {
+    let found = scan_files(filez, |_, code| find_channel_uses(lang, code));
+    if found.is_empty() {
+        return 0;
+    }
+    let routes: Vec<(String, RouteDecl)> = if found
+        .iter()
+        .any(|(_, _, u)| u.iter().any(|u| u.name.is_none()))
+    {
+        filez
+            .iter()
+            .flat_map(|(file, code)| {
+                find_routes(lang, code)
+                    .into_iter()
+                    .map(move |r| (file.clone(), r))
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let functions = functions_by_file(graph);
+
+    let mut channels: BTreeMap<(&'static str, String), NodeData> = BTreeMap::new();
+    let mut edges = Vec::new();
+    let mut seen = HashSet::new();
+    for (file, _, uses) in found {
+        let funcs = functions.get(file).map(Vec::as_slice).unwrap_or_default();
+        for u in uses {
+            let func = using_function(funcs, &u);
+            let Some(name) = u
+                .name
+                .clone()
+                .or_else(|| route_path(&routes, file, u.line, func))
+                .or_else(|| func.map(|f| f.name.clone()))
+            else {
+                continue;
+            };
+            let channel = channels
+                .entry((u.protocol, name.clone()))
+                .or_insert_with(|| {
+                    let mut nd = NodeData::name_file(&name, file);
+                    nd.start = u.line;
+                    nd.end = u.line;
+                    nd.body = u.snippet.clone();
+                    nd.meta
+                        .insert("protocol".to_string(), u.protocol.to_string());
+                    nd
+                });
+            let Some(func) = func else {
+                continue;
+            };
+            if !seen.insert((
+                func.name.clone(),
+                func.file.clone(),
+                func.start,
+                u.role,
+                u.protocol,
+                name,
+            )) {
+                continue;
+            }
+            edges.push(match u.role {
+                ChannelRole::Emit => Edge::emits(func, channel),
+                ChannelRole::Listen => Edge::channel_handler(channel, func),
+            });
+        }
+    }
+
+    for channel in channels.values() {
+        graph.add_node_with_parent(&NodeType::Channel, channel, &NodeType::File, &channel.file);
+    }
+    for edge in &edges {
+        graph.add_edge(edge);
+    }
+    channels.len()
+}
+
+fn using_function<'a>(funcs: &'a [NodeData], u: &ChannelUse) -> Option<&'a NodeData> {
+    match &u.handler {
+        Some(handler) => next_named_function(funcs, handler, u.line),
+        None => enclosing_function(funcs, u.line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(uses: &[ChannelUse], role: ChannelRole) -> Vec<(&str, &str)> {
+        uses.iter()
+            .filter(|u| u.role == role)
+            .map(|u| (u.protocol, u.name.as_deref().unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_socketio_and_event_source() {
+        let code = r#"
+io.on("connection", (socket) => {
+  socket.on("chat:message", onMessage);
+  io.to(room).emit("chat:message", msg);
+});
+const ws = new WebSocket(`ws://${location.host}/live?token=1`);
+const events = new EventSource("/api/events");
+channel.push("new_msg", { body });
+"#;
+        let uses = find_channel_uses(&Language::Typescript, code);
+        assert_eq!(
+            names(&uses, ChannelRole::Listen),
+            vec![("socketio", "chat:message")]
+        );
+        assert_eq!(uses[0].handler.as_deref(), Some("onMessage"));
+        assert_eq!(
+            names(&uses, ChannelRole::Emit),
+            vec![
+                ("socketio", "chat:message"),
+                ("websocket", "/live"),
+                ("sse", "/api/events"),
+                ("phoenix", "new_msg"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flask_socketio_and_sse() {
+        let code = r#"
+@socketio.on("join")
+def on_join(data):
+    emit("joined", data, to=data["room"])
+
+@app.route("/stream")
+def stream():
+    return Response(generate(), mimetype="text/event-stream")
+"#;
+        let uses = find_channel_uses(&Language::Python, code);
+        assert_eq!(
+            names(&uses, ChannelRole::Listen),
+            vec![("socketio", "join"), ("sse", "")]
+        );
+        assert_eq!(
+            names(&uses, ChannelRole::Emit),
+            vec![("socketio", "joined")]
+        );
+
+        let routes: Vec<(String, RouteDecl)> = find_routes(&Language::Python, code)
+            .into_iter()
+            .map(|r| ("app.py".to_string(), r))
+            .collect();
+        let mut stream = NodeData::name_file("stream", "app.py");
+        stream.start = 6;
+        stream.end = 7;
+        assert_eq!(
+            route_path(&routes, "app.py", 7, Some(&stream)).as_deref(),
+            Some("/stream")
+        );
+    }
+
+    #[test]
+    fn test_actioncable() {
+        let code = r#"
+class ChatChannel < ApplicationCable::Channel
+  def subscribed
+    stream_from "chat_#{params[:room]}"
+  end
+end
+
+ActionCable.server.broadcast("chat_#{room.id}", message: text)
+"#;
+        let uses = find_channel_uses(&Language::Ruby, code);
+        assert_eq!(
+            names(&uses, ChannelRole::Listen),
+            vec![("actioncable", "ChatChannel"), ("actioncable", "chat")]
+        );
+        assert_eq!(uses[0].handler.as_deref(), Some("subscribed"));
+        assert_eq!(
+            names(&uses, ChannelRole::Emit),
+            vec![("actioncable", "chat")]
+        );
+    }
+
+    #[test]
+    fn test_axum_upgrade_route() {
+        let handler = r#"
+async fn ws_handler(ws: WebSocketUpgrade) -> impl IntoResponse {
+    ws.on_upgrade(handle_socket)
+}
+"#;
+        let main = r#"
+let app = Router::new().route("/ws", get(handlers::ws_handler));
+"#;
+        let uses = find_channel_uses(&Language::Rust, handler);
+        assert_eq!(uses.len(), 1);
+        assert_eq!(uses[0].name, None);
+
+        let routes: Vec<(String, RouteDecl)> = find_routes(&Language::Rust, main)
+            .into_iter()
+            .map(|r| ("src/main.rs".to_string(), r))
+            .collect();
+        let mut func = NodeData::name_file("ws_handler", "src/handlers.rs");
+        func.start = 1;
+        func.end = 3;
+        assert_eq!(
+            route_path(&routes, "src/handlers.rs", 1, Some(&func)).as_deref(),
+            Some("/ws")
+        );
+    }
+}
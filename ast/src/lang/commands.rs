@@ -0,0 +1,741 @@
+use crate::lang::graphs::{Edge, Graph, NodeType};
+use crate::lang::queries::skips::summary::should_skip_dir;
+use crate::lang::scan::{
+    code_matches, enclosing_function, first_line, next_named_function, CodeMask, Syntax,
+};
+use crate::lang::NodeData;
+use crate::utils::line_of;
+use lsp::Language;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::LazyLock;
+use walkdir::WalkDir;
+
+/// How a command declaration points at the function it runs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandHandler {
+    /// a function by (possibly qualified) name: `deploy`, `deploy::run`, `cmd.Deploy`
+    Named(String),
+    /// the function the declaration sits in (cobra `Run: func(...)`, inline `.action(() => ...)`)
+    Enclosing,
+    None,
+}
+
+/// One CLI command or subcommand as declared in source.
+/// `ident` is how other declarations refer to it and `parent` the ident of the
+/// group it is registered on; paths are composed across files in `compose_paths`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandDef {
+    pub framework: &'static str,
+    pub name: String,
+    pub ident: Option<String>,
+    pub parent: Option<String>,
+    pub handler: CommandHandler,
+    pub line: usize,
+    pub snippet: String,
+}
+
+fn re(pattern: &str) -> Regex {
+    Regex::new(pattern).unwrap()
+}
+
+// clap: `#[derive(Subcommand)] enum Commands { Deploy(DeployArgs), #[command(name = "db")] Database { .. } }`
+static CLAP_ENUM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(
+        r"#\[derive\([^)]*\bSubcommand\b[^)]*\)\]\s*(?:#\[[^\]]*\]\s*)*(?:pub(?:\([^)]*\))?\s+)?enum\s+(?P<name>\w+)[^{;]*\{",
+    )
+});
+static CLAP_VARIANT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(
+        r"(?m)^[ \t]*(?P<attrs>(?:#\[[^\n]*\][ \t]*\n?[ \t]*)*)(?P<variant>[A-Z]\w*)[ \t]*(?:\(\s*(?P<inner>[\w:]+)\s*\))?[ \t]*(?:\{|,|$)",
+    )
+});
+static CLAP_NAME_RE: LazyLock<Regex> = LazyLock::new(|| re(r#"\bname\s*=\s*"(?P<name>[^"]+)""#));
+// `#[command(subcommand)] command: DbCommands` inside an args struct
+static CLAP_SUBCOMMAND_FIELD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(
+        r"(?s)(?:pub\s+)?struct\s+(?P<args>\w+)\s*\{[^}]*?#\[(?:command|clap)\(subcommand\)\]\s*(?:pub\s+)?\w+\s*:\s*(?:Option<)?(?P<sub>\w+)",
+    )
+});
+// `Commands::Deploy(args) => deploy(args)`: a path, then the rest of its match arm
+static RUST_PATH_RE: LazyLock<Regex> =
+    LazyLock::new(|| re(r"\b(?:\w+::)*(?P<enum>\w+)::(?P<variant>\w+)\b"));
+static CLAP_ARM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(r"^[^=;]*?=>\s*(?:\{\s*)?(?:(?:self|Self)\.)?(?P<handler>(?:\w+::)*[a-z_]\w*)\s*\(")
+});
+static CLAP_BUILDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(
+        r#"\.subcommand\(\s*(?:clap::)?(?:Command|App|SubCommand)::(?:new|with_name)\(\s*"(?P<name>[^"]+)""#,
+    )
+});
+static CLAP_BUILDER_ARM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(
+        r#"Some\(\(\s*"(?P<name>[^"]+)"\s*,[^)]*\)\)\s*=>\s*(?:\{\s*)?(?P<handler>(?:\w+::)*[a-z_]\w*)\s*\("#,
+    )
+});
+
+// cobra: `var deployCmd = &cobra.Command{Use: "deploy [env]", RunE: runDeploy}`
+static COBRA_RE: LazyLock<Regex> =
+    LazyLock::new(|| re(r"(?:(?:var\s+)?(?P<var>\w+)\s*:?=\s*)?&cobra\.Command\s*\{"));
+static COBRA_USE_RE: LazyLock<Regex> = LazyLock::new(|| re(r#"\bUse:\s*"(?P<use>[^"]+)""#));
+static COBRA_RUN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(r"\b(?:Pre)?Run(?:E)?:\s*(?:(?P<func>func\b)|(?P<handler>[\w.]+)\s*[,\n}])")
+});
+static COBRA_ADD_RE: LazyLock<Regex> =
+    LazyLock::new(|| re(r"(?P<parent>\w+)\.AddCommand\((?P<children>[^;\n]*)\)"));
+static GO_FUNC_RE: LazyLock<Regex> =
+    LazyLock::new(|| re(r"(?m)^func\s+(?:\([^)]*\)\s*)?(?P<name>\w+)\s*\("));
+
+// click / typer: `@cli.command("deploy")` / `@click.group()` / `@app.command()`
+static CLICK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(
+        r"(?m)^[ \t]*@(?P<parent>\w+)\.(?P<kind>command|group)\((?P<args>[^)]*)\)[^\n]*\n(?:[ \t]*@[^\n]*\n)*[ \t]*(?:async[ \t]+)?def[ \t]+(?P<handler>\w+)",
+    )
+});
+
+// commander: `program.command("deploy <env>").action(deploy)` / `const db = program.command("db")`
+static COMMANDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(
+        r#"(?:(?:const|let|var)\s+(?P<ident>[A-Za-z_$][\w$]*)\s*=\s*)?(?P<parent>[A-Za-z_$][\w$]*)\s*\.command\(\s*['"`](?P<spec>[^'"`]+)['"`]"#,
+    )
+});
+static COMMANDER_ACTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(
+        r"^\s*\.action\(\s*(?:(?P<inline>async\b|function\b|\()|(?P<handler>[A-Za-z_$][\w$.]*)\s*[,)])",
+    )
+});
+static COMMANDER_CHAIN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(
+        r#"^\s*\.(?:description|option|requiredOption|alias|argument|usage)\((?:[^()]|\([^()]*\))*\)"#,
+    )
+});
+
+// Thor: `desc "deploy ENV", "..."` above `def deploy(env)`, `subcommand "db", Db`
+static THOR_CLASS_RE: LazyLock<Regex> =
+    LazyLock::new(|| re(r"(?m)^[ \t]*class\s+(?:\w+::)*(?P<name>\w+)\s*<\s*Thor\b"));
+static THOR_DESC_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(
+        r#"(?m)^[ \t]*desc\s+['"](?P<usage>[^'"]+)['"][^\n]*\n(?:[ \t]*(?:method_option|option|long_desc)\b[^\n]*\n)*[ \t]*def\s+(?P<handler>\w+)"#,
+    )
+});
+static THOR_SUBCOMMAND_RE: LazyLock<Regex> = LazyLock::new(|| {
+    re(r#"(?m)^[ \t]*subcommand\s+['"](?P<name>[^'"]+)['"]\s*,\s*(?:\w+::)*(?P<class>\w+)"#)
+});
+
+/// Finds CLI command declarations in one source file, ignoring comments and strings.
+/// Parents may live in other files.
+pub fn find_commands(lang: &Language, code: &str) -> Vec<CommandDef> {
+    let syntax = Syntax::of(lang);
+    let mask = syntax.mask(code);
+    let mut defs = match lang {
+        Language::Rust => clap_commands(code, syntax, &mask),
+        Language::Go => cobra_commands(code, syntax, &mask),
+        Language::Python => click_commands(code, &mask),
+        Language::Typescript => commander_commands(code, &mask),
+        Language::Ruby => thor_commands(code, &mask),
+        _ => Vec::new(),
+    };
+    defs.sort_by_key(|d| d.line);
+    defs
+}
+
+fn clap_commands(code: &str, syntax: Syntax, mask: &CodeMask) -> Vec<CommandDef> {
+    let mut defs = Vec::new();
+    let arg_structs: HashMap<String, String> = code_matches(&CLAP_SUBCOMMAND_FIELD_RE, code, mask)
+        .map(|c| (c["args"].to_string(), c["sub"].to_string()))
+        .collect();
+    let enums: Vec<(String, usize, usize)> = code_matches(&CLAP_ENUM_RE, code, mask)
+        .filter_map(|c| {
+            let open = c.get(0).unwrap().end() - 1;
+            let close = syntax.block_end(code, open)?;
+            Some((c["name"].to_string(), open + 1, close))
+        })
+        .collect();
+    let enum_names: HashSet<&str> = enums.iter().map(|(n, _, _)| n.as_str()).collect();
+
+    for (enum_name, start, end) in &enums {
+        let body = &code[*start..*end];
+        for caps in CLAP_VARIANT_RE.captures_iter(body) {
+            // skip fields and variants of nested braces, and words in doc comments
+            let at = start + caps.name("variant").unwrap().start();
+            if !mask.is_code(at) || brace_depth(code, *start..at, mask) != 0 {
+                continue;
+            }
+            let variant = &caps["variant"];
+            let name = CLAP_NAME_RE
+                .captures(&caps["attrs"])
+                .map(|n| n["name"].to_string())
+                .unwrap_or_else(|| kebab_case(variant));
+            let inner = caps.name("inner").map(|i| last_segment(i.as_str(), "::"));
+            let ident = inner.and_then(|i| {
+                if enum_names.contains(i) {
+                    Some(i.to_string())
+                } else {
+                    arg_structs.get(i).cloned()
+                }
+            });
+            let handler = code_matches(&RUST_PATH_RE, code, mask)
+                .filter(|p| &p["enum"] == enum_name && &p["variant"] == variant)
+                .find_map(|p| CLAP_ARM_RE.captures(&code[p.get(0).unwrap().end()..]))
+                .map(|h| CommandHandler::Named(h["handler"].to_string()))
+                .unwrap_or(CommandHandler::None);
+            defs.push(CommandDef {
+                framework: "clap",
+                name,
+                ident,
+                parent: Some(enum_name.clone()),
+                handler,
+                line: line_of(code, at),
+                snippet: first_line(&code[at..]),
+            });
+        }
+    }
+
+    let arms: HashMap<String, String> = code_matches(&CLAP_BUILDER_ARM_RE, code, mask)
+        .map(|c| (c["name"].to_string(), c["handler"].to_string()))
+        .collect();
+    for caps in code_matches(&CLAP_BUILDER_RE, code, mask) {
+        let whole = caps.get(0).unwrap();
+        let name = caps["name"].to_string();
+        defs.push(CommandDef {
+            framework: "clap",
+            handler: arms
+                .get(&name)
+                .map(|h| CommandHandler::Named(h.clone()))
+                .unwrap_or(CommandHandler::None),
+            name,
+            ident: None,
+            parent: None,
+            line: line_of(code, whole.start()),
+            snippet: first_line(whole.as_str()),
+        });
+    }
+    defs
+}
+
+fn cobra_commands(code: &str, syntax: Syntax, mask: &CodeMask) -> Vec<CommandDef> {
+    let mut defs = Vec::new();
+    for caps in code_matches(&COBRA_RE, code, mask) {
+        let whole = caps.get(0).unwrap();
+        let open = whole.end() - 1;
+        let Some(close) = syntax.block_end(code, open) else {
+            continue;
+        };
+        let block = &code[open..close];
+        let Some(usage) = COBRA_USE_RE.captures(block) else {
+            continue;
+        };
+        let name = usage["use"]
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        // `func newDeployCmd() *cobra.Command { return &cobra.Command{...} }`
+        let constructor = GO_FUNC_RE
+            .captures_iter(&code[..whole.start()])
+            .last()
+            .map(|f| f["name"].to_string());
+        let ident = caps
+            .name("var")
+            .map(|v| v.as_str().to_string())
+            .or(constructor);
+        let handler = match COBRA_RUN_RE.captures(block) {
+            Some(run) if run.name("func").is_some() => CommandHandler::Enclosing,
+            Some(run) => CommandHandler::Named(run["handler"].to_string()),
+            None => CommandHandler::None,
+        };
+        defs.push(CommandDef {
+            framework: "cobra",
+            name,
+            ident,
+            parent: None,
+            handler,
+            line: line_of(code, whole.start()),
+            snippet: first_line(whole.as_str()),
+        });
+    }
+    defs
+}
+
+/// `rootCmd.AddCommand(deployCmd, newDbCmd())` registrations, as (parent, child) idents.
+fn cobra_links(code: &str) -> Vec<(String, String)> {
+    let mask = Syntax::of(&Language::Go).mask(code);
+    let mut links = Vec::new();
+    for caps in code_matches(&COBRA_ADD_RE, code, &mask) {
+        for child in caps["children"].split(',') {
+            let child = child.trim().trim_end_matches("()");
+            if !child.is_empty() {
+                links.push((caps["parent"].to_string(), child.to_string()));
+            }
+        }
+    }
+    links
+}
+
+fn click_commands(code: &str, mask: &CodeMask) -> Vec<CommandDef> {
+    code_matches(&CLICK_RE, code, mask)
+        .map(|caps| {
+            let whole = caps.get(0).unwrap();
+            let handler = caps["handler"].to_string();
+            let name = caps["args"]
+                .split(['"', '\''])
+                .nth(1)
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| handler.replace('_', "-"));
+            let parent = &caps["parent"];
+            CommandDef {
+                framework: "click",
+                name,
+                ident: Some(handler.clone()),
+                parent: (!matches!(parent, "click" | "typer")).then(|| parent.to_string()),
+                handler: CommandHandler::Named(handler),
+                line: line_of(code, whole.start()),
+                snippet: first_line(whole.as_str()),
+            }
+        })
+        .collect()
+}
+
+fn commander_commands(code: &str, mask: &CodeMask) -> Vec<CommandDef> {
+    let mut defs = Vec::new();
+    for caps in code_matches(&COMMANDER_RE, code, mask) {
+        let whole = caps.get(0).unwrap();
+        // `.command("deploy <env>")` closes right after the spec; walk the rest of the chain
+        let mut rest = code[whole.end()..].trim_start_matches(|c: char| c != ')');
+        rest = rest.strip_prefix(')').unwrap_or(rest);
+        while let Some(m) = COMMANDER_CHAIN_RE.find(rest) {
+            rest = &rest[m.end()..];
+        }
+        let handler = match COMMANDER_ACTION_RE.captures(rest) {
+            Some(action) if action.name("inline").is_some() => CommandHandler::Enclosing,
+            Some(action) => CommandHandler::Named(action["handler"].to_string()),
+            None => CommandHandler::None,
+        };
+        let name = caps["spec"]
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        defs.push(CommandDef {
+            framework: "commander",
+            name,
+            ident: caps.name("ident").map(|i| i.as_str().to_string()),
+            parent: Some(caps["parent"].to_string()),
+            handler,
+            line: line_of(code, whole.start()),
+            snippet: first_line(whole.as_str()),
+        });
+    }
+    defs
+}
+
+fn thor_commands(code: &str, mask: &CodeMask) -> Vec<CommandDef> {
+    let classes: Vec<(usize, String)> = code_matches(&THOR_CLASS_RE, code, mask)
+        .map(|c| (c.get(0).unwrap().start(), c["name"].to_string()))
+        .collect();
+    if classes.is_empty() {
+        return Vec::new();
+    }
+    let class_at = |offset: usize| {
+        classes
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= offset)
+            .map(|(_, name)| name.clone())
+    };
+
+    let mut defs = Vec::new();
+    for caps in code_matches(&THOR_DESC_RE, code, mask) {
+        let whole = caps.get(0).unwrap();
+        let handler = caps["handler"].to_string();
+        defs.push(CommandDef {
+            framework: "thor",
+            name: handler.replace('_', "-"),
+            ident: None,
+            parent: class_at(whole.start()),
+            handler: CommandHandler::Named(handler),
+            line: line_of(code, whole.start()),
+            snippet: first_line(whole.as_str()),
+        });
+    }
+    for caps in code_matches(&THOR_SUBCOMMAND_RE, code, mask) {
+        let whole = caps.get(0).unwrap();
+        defs.push(CommandDef {
+            framework: "thor",
+            name: caps["name"].to_string(),
+            ident: Some(caps["class"].to_string()),
+            parent: class_at(whole.start()),
+            handler: CommandHandler::None,
+            line: line_of(code, whole.start()),
+            snippet: first_line(whole.as_str()),
+        });
+    }
+    defs
+}
+
+/// Full command paths ("db migrate") for every declaration that is invocable.
+/// A group with children and nothing above it is the binary itself and is left out.
+pub fn compose_paths(
+    defs: &mut [(String, CommandDef)],
+    links: &[(String, String)],
+) -> Vec<(usize, Vec<String>)> {
+    for (_, def) in defs.iter_mut() {
+        if let Some(ident) = &def.ident {
+            if let Some((parent, _)) = links.iter().find(|(_, child)| child == ident) {
+                def.parent = Some(parent.clone());
+            }
+        }
+    }
+    let has_children: HashSet<&str> = defs
+        .iter()
+        .filter_map(|(_, d)| d.parent.as_deref())
+        .collect();
+    let is_root = |d: &CommandDef| {
+        d.parent.is_none() && d.ident.as_deref().is_some_and(|i| has_children.contains(i))
+    };
+    let lookup = |framework: &str, ident: &str, file: &str| {
+        let mut found = defs
+            .iter()
+            .filter(|(_, d)| d.framework == framework && d.ident.as_deref() == Some(ident));
+        let first = found.next();
+        first
+            .into_iter()
+            .chain(found)
+            .find(|(f, _)| f == file)
+            .or(first)
+            .map(|(f, d)| (f.as_str(), d))
+    };
+
+    let mut paths = Vec::new();
+    for (i, (file, def)) in defs.iter().enumerate() {
+        if is_root(def) {
+            continue;
+        }
+        let mut path = vec![def.name.clone()];
+        let (mut file, mut cur) = (file.as_str(), def);
+        while let Some((pfile, parent)) = cur
+            .parent
+            .as_deref()
+            .and_then(|p| lookup(cur.framework, p, file))
+        {
+            if is_root(parent) || path.len() > 8 {
+                break;
+            }
+            path.insert(0, parent.name.clone());
+            (file, cur) = (pfile, parent);
+        }
+        paths.push((i, path));
+    }
+    paths
+}
+
+/// Adds a Command node per CLI (sub)command, named by its full path, with a HANDLER edge.
+pub fn add_command_nodes<G: Graph>(
+    graph: &mut G,
+    lang: &Language,
+    filez: &[(String, String)],
+) -> usize {
+    let mut defs: Vec<(String, CommandDef)> = Vec::new();
+    let mut links = Vec::new();
+    for (file, code) in filez {
+        defs.extend(
+            find_commands(lang, code)
+                .into_iter()
+                .map(|d| (file.clone(), d)),
+        );
+        if *lang == Language::Go {
+            links.extend(cobra_links(code));
+        }
+    }
+    if defs.is_empty() {
+        return 0;
+    }
+    let paths = compose_paths(&mut defs, &links);
+
+    let all_functions = graph.find_nodes_by_type(NodeType::Function);
+    let mut count = 0;
+    for (i, path) in paths {
+        let (file, def) = &defs[i];
+        let handler = match &def.handler {
+            CommandHandler::Named(name) => named_function(&all_functions, name, file, def.line),
+            CommandHandler::Enclosing => {
+                enclosing_function(all_functions.iter().filter(|f| &f.file == file), def.line)
+            }
+            CommandHandler::None => None,
+        };
+
+        let mut nd = NodeData::name_file(&path.join(" "), file);
+        nd.start = def.line;
+        nd.end = def.line;
+        nd.body = def.snippet.clone();
+        nd.meta
+            .insert("framework".to_string(), def.framework.to_string());
+        graph.add_node_with_parent(&NodeType::Command, &nd, &NodeType::File, file);
+        if let Some(handler) = handler {
+            graph.add_edge(&Edge::command_handler(&nd, handler));
+        }
+        count += 1;
+    }
+    count
+}
+
+/// Resolves `deploy`, `deploy::run` or `cmd.Deploy`: same file first (the next definition
+/// at or after `line`), then a file or directory matching the qualifier, then anywhere.
+fn named_function<'a>(
+    funcs: &'a [NodeData],
+    handler: &str,
+    file: &str,
+    line: usize,
+) -> Option<&'a NodeData> {
+    let (qualifier, name) = match handler.rsplit_once("::").or(handler.rsplit_once('.')) {
+        Some((q, n)) => (Some(last_segment(last_segment(q, "::"), ".")), n),
+        None => (None, handler),
+    };
+    let named = || funcs.iter().filter(move |f| f.name == name);
+    next_named_function(funcs.iter().filter(|f| f.file == file), name, line)
+        .or_else(|| {
+            let q = qualifier?;
+            named().find(|f| {
+                Path::new(&f.file)
+                    .with_extension("")
+                    .iter()
+                    .any(|c| c.to_str() == Some(q))
+            })
+        })
+        .or_else(|| named().next())
+}
+
+/// Commands declared anywhere under `root`, as (file, path, framework) for summaries
+/// that do not build a graph.
+pub fn collect_commands(root: &Path) -> Vec<(String, String, &'static str)> {
+    let mut defs = Vec::new();
+    let mut links = Vec::new();
+    let walker = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !should_skip_dir(&e.file_name().to_string_lossy()));
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path().display().to_string();
+        let Some(lang) = Language::from_path(&path) else {
+            continue;
+        };
+        let Ok(code) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        defs.extend(
+            find_commands(&lang, &code)
+                .into_iter()
+                .map(|d| (path.clone(), d)),
+        );
+        if lang == Language::Go {
+            links.extend(cobra_links(&code));
+        }
+    }
+    let paths = compose_paths(&mut defs, &links);
+    paths
+        .into_iter()
+        .map(|(i, path)| (defs[i].0.clone(), path.join(" "), defs[i].1.framework))
+        .collect()
+}
+
+/// Net open braces and parens in `code[range]`, not counting those in comments or literals.
+fn brace_depth(code: &str, range: Range<usize>, mask: &CodeMask) -> i32 {
+    let start = range.start;
+    code[range]
+        .char_indices()
+        .filter(|(i, _)| mask.is_code(start + i))
+        .fold(0, |depth, (_, c)| match c {
+            '{' | '(' => depth + 1,
+            '}' | ')' => depth - 1,
+            _ => depth,
+        })
+}
+
+fn kebab_case(s: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            out.push('-');
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+fn last_segment<'a>(s: &'a str, sep: &str) -> &'a str {
+    s.rsplit(sep).next().unwrap_or(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(lang: Language, files: &[(&str, &str)]) -> Vec<String> {
+        let mut defs: Vec<(String, CommandDef)> = Vec::new();
+        let mut links = Vec::new();
+        for (file, code) in files {
+            defs.extend(
+                find_commands(&lang, code)
+                    .into_iter()
+                    .map(|d| (file.to_string(), d)),
+            );
+            links.extend(cobra_links(code));
+        }
+        let mut out: Vec<String> = compose_paths(&mut defs, &links)
+            .into_iter()
+            .map(|(_, p)| p.join(" "))
+            .collect();
+        out.sort();
+        out
+    }
+
+    #[test]
+    fn test_clap_derive() {
+        let code = r#"
+#[derive(Subcommand)]
+enum Commands {
+    /// Deploy the app
+    Deploy { env: String },
+    #[command(subcommand)]
+    Db(DbCommands),
+    /*
+    Legacy,
+    */
+}
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    Migrate,
+    #[command(name = "seed-all")]
+    Seed { count: u32 },
+}
+
+fn main() {
+    match cli.command {
+        // Commands::Deploy { env } => old_deploy(&env),
+        Commands::Deploy { env } => deploy::run(&env),
+        Commands::Db(cmd) => match cmd {
+            DbCommands::Migrate => migrate(),
+            DbCommands::Seed { count } => seed(count),
+        },
+    }
+}
+"#;
+        assert_eq!(
+            paths(Language::Rust, &[("src/main.rs", code)]),
+            vec!["db", "db migrate", "db seed-all", "deploy"]
+        );
+        let defs = find_commands(&Language::Rust, code);
+        assert_eq!(
+            defs[0].handler,
+            CommandHandler::Named("deploy::run".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cobra_across_files() {
+        let root = r#"
+var rootCmd = &cobra.Command{Use: "app"}
+
+func init() {
+    rootCmd.AddCommand(deployCmd, newDbCmd())
+}
+"#;
+        let deploy = r#"
+var deployCmd = &cobra.Command{
+    Use:  "deploy [env]",
+    RunE: runDeploy,
+}
+"#;
+        let db = r#"
+func newDbCmd() *cobra.Command {
+    return &cobra.Command{
+        Use: "db",
+        Run: func(cmd *cobra.Command, args []string) {},
+    }
+}
+"#;
+        assert_eq!(
+            paths(
+                Language::Go,
+                &[
+                    ("cmd/root.go", root),
+                    ("cmd/deploy.go", deploy),
+                    ("cmd/db.go", db)
+                ]
+            ),
+            vec!["db", "deploy"]
+        );
+    }
+
+    #[test]
+    fn test_click_groups() {
+        let code = r#"
+@click.group()
+def cli():
+    pass
+
+@cli.group()
+def db():
+    pass
+
+@db.command("migrate")
+@click.option("--dry-run", is_flag=True)
+def run_migrations(dry_run):
+    pass
+
+@cli.command()
+def sync_users():
+    pass
+"#;
+        assert_eq!(
+            paths(Language::Python, &[("cli.py", code)]),
+            vec!["db", "db migrate", "sync-users"]
+        );
+    }
+
+    #[test]
+    fn test_commander_and_thor() {
+        let ts = r#"
+program
+  .command("deploy <env>")
+  .description("deploy to an environment")
+  .option("-f, --force")
+  .action(deploy);
+const db = program.command("db");
+db.command("migrate").action(async () => migrate());
+"#;
+        assert_eq!(
+            paths(Language::Typescript, &[("src/cli.ts", ts)]),
+            vec!["db", "db migrate", "deploy"]
+        );
+        let defs = find_commands(&Language::Typescript, ts);
+        assert_eq!(defs[0].handler, CommandHandler::Named("deploy".to_string()));
+        assert_eq!(defs[2].handler, CommandHandler::Enclosing);
+
+        let rb = r#"
+class Cli < Thor
+  desc "deploy ENV", "Deploy to ENV"
+  method_option :force, type: :boolean
+  def deploy(env)
+  end
+
+  subcommand "db", Db
+end
+
+class Db < Thor
+  desc "migrate", "Run migrations"
+  def migrate
+  end
+end
+"#;
+        assert_eq!(
+            paths(Language::Ruby, &[("lib/cli.rb", rb)]),
+            vec!["db", "db migrate", "deploy"]
+        );
+    }
+}
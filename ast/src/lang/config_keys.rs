@@ -0,0 +1,249 @@
+use crate::lang::graphs::{Edge, Graph, NodeType};
+use crate::lang::scan::{
+    code_matches, enclosing_function, first_line, functions_by_file, scan_files, Syntax,
+};
+use crate::lang::NodeData;
+use crate::utils::line_of;
+use lsp::Language;
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::LazyLock;
+
+const RS: &[Language] = &[Language::Rust];
+const JS: &[Language] = &[Language::Typescript, Language::Angular, Language::Svelte];
+const PY: &[Language] = &[Language::Python];
+const RB: &[Language] = &[Language::Ruby];
+const GO: &[Language] = &[Language::Go];
+const JVM: &[Language] = &[Language::Java, Language::Kotlin];
+const CS: &[Language] = &[Language::CSharp];
+const PHP: &[Language] = &[Language::Php];
+const SWIFT: &[Language] = &[Language::Swift];
+
+/// A call or annotation that reads a configuration key. `source` is `env` for environment
+/// variables and the config library otherwise.
+struct Detector {
+    source: &'static str,
+    langs: &'static [Language],
+    re: Regex,
+}
+
+fn detector(source: &'static str, langs: &'static [Language], pattern: &str) -> Detector {
+    Detector {
+        source,
+        langs,
+        re: Regex::new(pattern).unwrap(),
+    }
+}
+
+static DETECTORS: LazyLock<Vec<Detector>> = LazyLock::new(|| {
+    vec![
+        // `std::env::var("DATABASE_URL")`, `env!("CARGO_PKG_VERSION")`, `dotenvy::var(..)`
+        detector(
+            "env",
+            RS,
+            r#"\b(?:env|dotenvy|dotenv)::var(?:_os)?\(\s*"(?P<key>[\w.\-]+)""#,
+        ),
+        detector("env", RS, r#"\b(?:option_)?env!\(\s*"(?P<key>[\w.\-]+)""#),
+        // `os.getenv("KEY")`, `os.environ["KEY"]`, `os.environ.get("KEY")`
+        detector(
+            "env",
+            PY,
+            r#"\b(?:os\.getenv|(?:os\.)?environ\.get|(?:os\.)?environ\.setdefault)\(\s*['"](?P<key>[\w.\-]+)['"]"#,
+        ),
+        detector(
+            "env",
+            PY,
+            r#"\b(?:os\.)?environ\[\s*['"](?P<key>[\w.\-]+)['"]\s*\]"#,
+        ),
+        // `process.env.KEY`, `process.env["KEY"]`, `import.meta.env.VITE_KEY`
+        detector(
+            "env",
+            JS,
+            r#"\b(?:process|import\.meta)\.env\.(?P<key>[A-Za-z_$][\w$]*)"#,
+        ),
+        detector(
+            "env",
+            JS,
+            r#"\b(?:process|import\.meta)\.env\[\s*['"`](?P<key>[\w.\-]+)['"`]\s*\]"#,
+        ),
+        // `System.getenv("KEY")`, Spring `@Value("${app.timeout:30}")` and `env.getProperty("app.timeout")`
+        detector("env", JVM, r#"\bSystem\.getenv\(\s*"(?P<key>[\w.\-]+)""#),
+        detector(
+            "spring",
+            JVM,
+            r#"@Value\(\s*(?:value\s*=\s*)?"\$\{(?P<key>[\w.\-\[\]]+)"#,
+        ),
+        detector(
+            "spring",
+            JVM,
+            r#"\b(?i:\w*env\w*)\.getProperty\(\s*"(?P<key>[\w.\-]+)""#,
+        ),
+        // `ENV["KEY"]`, `ENV.fetch("KEY")`
+        detector("env", RB, r#"\bENV\[\s*['"](?P<key>[\w.\-]+)['"]\s*\]"#),
+        detector(
+            "env",
+            RB,
+            r#"\bENV\.(?:fetch|key\?)\(\s*['"](?P<key>[\w.\-]+)['"]"#,
+        ),
+        // `os.Getenv("KEY")`, `os.LookupEnv("KEY")`, `viper.GetString("db.host")`
+        detector(
+            "env",
+            GO,
+            r#"\bos\.(?:Getenv|LookupEnv)\(\s*"(?P<key>[\w.\-]+)""#,
+        ),
+        detector(
+            "viper",
+            GO,
+            r#"\bviper\.(?:Get\w*|IsSet|BindEnv)\(\s*"(?P<key>[\w.\-]+)""#,
+        ),
+        detector(
+            "env",
+            CS,
+            r#"\bEnvironment\.GetEnvironmentVariable\(\s*"(?P<key>[\w.\-]+)""#,
+        ),
+        detector(
+            "env",
+            PHP,
+            r#"\b(?:getenv|env)\(\s*['"](?P<key>[\w.\-]+)['"]"#,
+        ),
+        detector(
+            "env",
+            PHP,
+            r#"\$_(?:ENV|SERVER)\[\s*['"](?P<key>[A-Z][\w]*)['"]\s*\]"#,
+        ),
+        detector(
+            "env",
+            SWIFT,
+            r#"\.environment\[\s*"(?P<key>[\w.\-]+)"\s*\]"#,
+        ),
+    ]
+});
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigRead {
+    pub source: &'static str,
+    pub name: String,
+    pub line: usize,
+    pub snippet: String,
+}
+
+/// Finds configuration keys read in one source file, ignoring comments and strings.
+pub fn find_config_reads(lang: &Language, code: &str) -> Vec<ConfigRead> {
+    let mask = Syntax::of(lang).mask(code);
+    let mut reads = Vec::new();
+    for det in DETECTORS.iter().filter(|d| d.langs.contains(lang)) {
+        for caps in code_matches(&det.re, code, &mask) {
+            let whole = caps.get(0).unwrap();
+            reads.push(ConfigRead {
+                source: det.source,
+                name: caps["key"].to_string(),
+                line: line_of(code, whole.start()),
+                snippet: first_line(whole.as_str()),
+            });
+        }
+    }
+    reads.sort_by_key(|r| r.line);
+    reads
+}
+
+/// Environment-style spelling used to match keys across sources: Spring's relaxed binding
+/// and viper's `AutomaticEnv` both map `app.db-url` to `APP_DB_URL`.
+pub fn canonical_key(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '.' | '-' => '_',
+            c => c.to_ascii_uppercase(),
+        })
+        .filter(|c| *c != '[' && *c != ']')
+        .collect()
+}
+
+/// Adds a ConfigKey node per key read in code and READS edges from the functions reading it.
+/// Keys read outside any function (module-level settings, annotated fields) still get a node.
+pub fn add_config_key_nodes<G: Graph>(
+    graph: &mut G,
+    lang: &Language,
+    filez: &[(String, String)],
+) -> usize {
+    let found = scan_files(filez, |_, code| find_config_reads(lang, code));
+    if found.is_empty() {
+        return 0;
+    }
+    let functions = functions_by_file(graph);
+
+    let mut keys: BTreeMap<String, NodeData> = BTreeMap::new();
+    let mut edges = Vec::new();
+    let mut seen = HashSet::new();
+    for (file, _, reads) in found {
+        let funcs = functions.get(file).map(Vec::as_slice).unwrap_or_default();
+        for r in reads {
+            let key = keys.entry(r.name.clone()).or_insert_with(|| {
+                let mut nd = NodeData::name_file(&r.name, file);
+                nd.start = r.line;
+                nd.end = r.line;
+                nd.body = r.snippet.clone();
+                nd.meta.insert("source".to_string(), r.source.to_string());
+                nd
+            });
+            let Some(func) = enclosing_function(funcs, r.line) else {
+                continue;
+            };
+            if seen.insert((func.name.clone(), func.file.clone(), func.start, r.name)) {
+                edges.push(Edge::reads(func, NodeType::ConfigKey, key));
+            }
+        }
+    }
+
+    for key in keys.values() {
+        graph.add_node_with_parent(&NodeType::ConfigKey, key, &NodeType::File, &key.file);
+    }
+    for edge in &edges {
+        graph.add_edge(edge);
+    }
+    keys.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(reads: &[ConfigRead]) -> Vec<(&str, &str)> {
+        reads.iter().map(|r| (r.source, r.name.as_str())).collect()
+    }
+
+    #[test]
+    fn test_python_and_typescript_reads() {
+        let code = "import os\n\nDATABASE_URL = os.getenv(\"DATABASE_URL\")\n\ndef debug():\n    return os.environ[\"DEBUG\"] == \"1\"\n";
+        let reads = find_config_reads(&Language::Python, code);
+        assert_eq!(
+            keys(&reads),
+            vec![("env", "DATABASE_URL"), ("env", "DEBUG")]
+        );
+        assert_eq!(reads[1].line, 5);
+
+        let code =
+            "const port = process.env.PORT ?? 3000;\nconst key = process.env[\"API_KEY\"];\n";
+        let reads = find_config_reads(&Language::Typescript, code);
+        assert_eq!(keys(&reads), vec![("env", "PORT"), ("env", "API_KEY")]);
+    }
+
+    #[test]
+    fn test_spring_and_viper_reads() {
+        let code = "@Value(\"${app.mail.sender:noreply@example.com}\")\nprivate String sender;\nString home = System.getenv(\"HOME\");\n";
+        let reads = find_config_reads(&Language::Java, code);
+        assert_eq!(
+            keys(&reads),
+            vec![("spring", "app.mail.sender"), ("env", "HOME")]
+        );
+
+        let code = "func load() {\n\thost := viper.GetString(\"db.host\")\n\ttoken := os.Getenv(\"TOKEN\")\n}\n";
+        let reads = find_config_reads(&Language::Go, code);
+        assert_eq!(keys(&reads), vec![("viper", "db.host"), ("env", "TOKEN")]);
+    }
+
+    #[test]
+    fn test_canonical_key() {
+        assert_eq!(canonical_key("app.mail-sender"), "APP_MAIL_SENDER");
+        assert_eq!(canonical_key("DATABASE_URL"), "DATABASE_URL");
+    }
+}
@@ -0,0 +1,501 @@
+use crate::lang::graphs::{Edge, EdgeType, NodeType};
+use crate::lang::NodeData;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, LazyLock, Mutex};
+
+/// How a function body uses one DataModel. A function writes a model when it
+/// constructs, mutates or persists it; any other mention is a read. Field names are
+/// collected where the syntax names them (struct literals, keyword arguments,
+/// `x.field = ..` assignments, `x.field` accesses).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ModelAccess {
+    pub reads: bool,
+    pub writes: bool,
+    pub read_fields: BTreeSet<String>,
+    pub written_fields: BTreeSet<String>,
+}
+
+const WRITE_METHODS: &str = "create|insert|update|upsert|save|delete|destroy|remove|persist|merge|add|put|set|bulk_create|bulk_update|increment|decrement|touch|commit";
+const READ_METHODS: &str =
+    "find|get|filter|where|all|first|last|select|query|count|exists|load|fetch|search|pluck|read";
+
+// a method name starting with one of the verbs as a whole word: `save`, `saveAll`,
+// `bulk_create`, `Create`, but not `address` or `settings`
+static WRITE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"^(?i:{WRITE_METHODS})(?:$|[A-Z_\d])")).unwrap());
+static READ_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"^(?i:{READ_METHODS})(?:$|[A-Z_\d])")).unwrap());
+// persisting through a repository, session or ORM handle: `repo.save(user)`, `db.Create(&user)`
+static PERSIST_ARG_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\.(?P<method>\w+)\s*\(\s*&?(?:mut\s+)?(?P<var>[A-Za-z_]\w*)\s*[,)]").unwrap()
+});
+// `user.email`, `user?.email`, `user!.email`; the variable is checked against the bindings
+static MEMBER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?P<var>[A-Za-z_]\w*)(?:\?|!)?\.(?P<field>[A-Za-z_]\w*)").unwrap()
+});
+// persistence called without parentheses: `@user.save`, `user.destroy!`
+static BARE_PERSIST_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:save|destroy|delete|touch|increment|decrement)$").unwrap());
+// the same models are checked against every function body, so compile their patterns once
+static MODEL_PATTERNS: LazyLock<Mutex<HashMap<String, Arc<ModelPatterns>>>> =
+    LazyLock::new(Default::default);
+
+struct ModelPatterns {
+    // construction: `new User(..)`, `User { .. }`, `User{..}`, `User(..)`
+    construct: Regex,
+    // `User::new(..)`, `User::default()`
+    ctor: Regex,
+    // static persistence and queries: `User.create(..)`, `User.objects.filter(..)`
+    static_call: Regex,
+    // see `bindings`
+    bindings: [Regex; 4],
+}
+
+impl ModelPatterns {
+    fn new(model: &str) -> Self {
+        let m = regex::escape(model);
+        let re = |p: String| Regex::new(&p).unwrap();
+        ModelPatterns {
+            construct: re(format!(
+                r"(?:\bnew\s+)?\b{m}\s*(?:<[^>(){{}}]*>\s*)?(?P<open>[({{])"
+            )),
+            ctor: re(format!(
+                r"\b{m}::(?:new|default|from|builder|create)\w*\s*\("
+            )),
+            static_call: re(format!(
+                r"\b{m}(?:\.objects|\.query)?(?:\.|::)(?P<method>\w+)\s*\("
+            )),
+            bindings: [
+                re(format!(
+                    r"\b(?P<var>[A-Za-z_]\w*)\??\s*:\s*&?(?:mut\s+)?(?:\*\s*)?{m}\b"
+                )),
+                re(format!(r"\b{m}\s*\??\s+(?P<var>[A-Za-z_]\w*)\s*[=;,)]")),
+                re(format!(r"\b(?P<var>[A-Za-z_]\w*)\s+\*?{m}\b")),
+                re(format!(
+                    r"\b(?P<var>[A-Za-z_]\w*)\s*(?::\s*[\w<>\[\]?]+\s*)?:?=\s*(?:await\s+)?(?:new\s+|&)?{m}\b"
+                )),
+            ],
+        }
+    }
+
+    fn get(model: &str) -> Arc<ModelPatterns> {
+        let mut cache = MODEL_PATTERNS.lock().unwrap();
+        cache
+            .entry(model.to_string())
+            .or_insert_with(|| Arc::new(ModelPatterns::new(model)))
+            .clone()
+    }
+}
+
+const NOT_BINDINGS: &[&str] = &[
+    "new",
+    "return",
+    "func",
+    "fn",
+    "type",
+    "struct",
+    "class",
+    "interface",
+    "enum",
+    "impl",
+    "var",
+    "let",
+    "const",
+    "val",
+    "def",
+    "fun",
+    "await",
+    "yield",
+    "throw",
+    "case",
+    "in",
+    "of",
+    "as",
+    "is",
+    "extends",
+    "implements",
+    "import",
+    "from",
+    "public",
+    "private",
+    "protected",
+    "static",
+    "final",
+    "mut",
+    "self",
+    "this",
+];
+
+pub fn model_access(body: &str, model: &str) -> ModelAccess {
+    let mut access = ModelAccess::default();
+    if model.is_empty()
+        || !(body.contains(model)
+            || body.contains(&lower_first(model))
+            || body.contains(&snake_case(model)))
+    {
+        return access;
+    }
+    let patterns = ModelPatterns::get(model);
+
+    for c in patterns.construct.captures_iter(body) {
+        let whole = c.get(0).unwrap();
+        let open = c.name("open").unwrap();
+        let explicit_new = whole.as_str().starts_with("new");
+        if !explicit_new && !is_construction_site(body, whole.start()) {
+            continue;
+        }
+        access.writes = true;
+        let segment = balanced(&body[open.start()..]);
+        let sep = if open.as_str() == "{" { ':' } else { '=' };
+        access.written_fields.extend(top_level_keys(segment, sep));
+        // `new User({ email, name })`
+        if open.as_str() == "(" {
+            let inner = segment.trim_start_matches('(').trim_start();
+            if inner.starts_with('{') {
+                access
+                    .written_fields
+                    .extend(top_level_keys(balanced(inner), ':'));
+            }
+        }
+    }
+    if patterns.ctor.is_match(body) {
+        access.writes = true;
+    }
+
+    for c in patterns.static_call.captures_iter(body) {
+        let method = &c["method"];
+        if WRITE_RE.is_match(method) {
+            access.writes = true;
+        } else if READ_RE.is_match(method) {
+            access.reads = true;
+        }
+    }
+
+    let vars = bindings(body, model, &patterns);
+    for c in MEMBER_RE.captures_iter(body) {
+        let whole = c.get(0).unwrap();
+        if !vars.contains(&c["var"]) || is_member_access(body, whole.start()) {
+            continue;
+        }
+        let field = c["field"].to_string();
+        let rest = body[whole.end()..].trim_start();
+        if rest.starts_with('(') {
+            // bean accessors name the field: `setEmail(..)`, `getEmail()`
+            let accessor = |prefix: &str| {
+                field
+                    .strip_prefix(prefix)
+                    .filter(|f| f.starts_with(|c: char| c.is_uppercase()))
+                    .map(lower_first)
+            };
+            if let Some(f) = accessor("set") {
+                access.writes = true;
+                access.written_fields.insert(f);
+            } else if let Some(f) = accessor("get") {
+                access.reads = true;
+                access.read_fields.insert(f);
+            } else if WRITE_RE.is_match(&field) {
+                access.writes = true;
+            } else {
+                access.reads = true;
+            }
+        } else if BARE_PERSIST_RE.is_match(&field) {
+            access.writes = true;
+        } else if is_assignment(rest) {
+            access.writes = true;
+            access.written_fields.insert(field);
+        } else {
+            access.reads = true;
+            access.read_fields.insert(field);
+        }
+    }
+    if PERSIST_ARG_RE
+        .captures_iter(body)
+        .any(|c| vars.contains(&c["var"]) && WRITE_RE.is_match(&c["method"]))
+    {
+        access.writes = true;
+    }
+
+    if !access.writes {
+        access.reads = true;
+    }
+    access
+}
+
+/// Reads/Writes edges for every DataModel a function CONTAINS, with the touched
+/// fields recorded on the function as `reads` / `writes` meta (`User.email,User.name`).
+pub fn data_flow_edges(func: &mut NodeData, models: &[Edge]) -> Vec<Edge> {
+    data_flow_edges_with(func, models, |name| name.to_string())
+}
+
+/// [`data_flow_edges`] for models that code refers to by another name, e.g. the
+/// `AdvisorGroup` class of a Rails `advisor_groups` schema table.
+pub fn data_flow_edges_with(
+    func: &mut NodeData,
+    models: &[Edge],
+    code_name: impl Fn(&str) -> String,
+) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    let mut read_fields = BTreeSet::new();
+    let mut written_fields = BTreeSet::new();
+    for model in models {
+        if model.edge != EdgeType::Contains || model.target.node_type != NodeType::DataModel {
+            continue;
+        }
+        let dm = &model.target.node_data;
+        let access = match func.meta.get("operation").map(String::as_str) {
+            // derived query methods on a data-access interface have no body to inspect
+            Some("delete") => ModelAccess {
+                writes: true,
+                ..Default::default()
+            },
+            Some(_) => ModelAccess {
+                reads: true,
+                ..Default::default()
+            },
+            None => model_access(&func.body, &code_name(&dm.name)),
+        };
+        let target = NodeData {
+            name: dm.name.clone(),
+            file: dm.file.clone(),
+            start: dm.start,
+            ..Default::default()
+        };
+        if access.reads {
+            edges.push(Edge::reads(func, NodeType::DataModel, &target));
+        }
+        if access.writes {
+            edges.push(Edge::writes(func, NodeType::DataModel, &target));
+        }
+        read_fields.extend(
+            access
+                .read_fields
+                .iter()
+                .map(|f| format!("{}.{}", dm.name, f)),
+        );
+        written_fields.extend(
+            access
+                .written_fields
+                .iter()
+                .map(|f| format!("{}.{}", dm.name, f)),
+        );
+    }
+    if !read_fields.is_empty() {
+        func.add_reads(&read_fields.into_iter().collect::<Vec<_>>().join(","));
+    }
+    if !written_fields.is_empty() {
+        func.add_writes(&written_fields.into_iter().collect::<Vec<_>>().join(","));
+    }
+    edges
+}
+
+/// A bare `User(` / `User {` is a construction only in expression position, not in a
+/// declaration (`struct User {`, `impl User {`, `-> User {`, `def User(`).
+fn is_construction_site(body: &str, start: usize) -> bool {
+    let before = body[..start].trim_end();
+    if before.ends_with("->") || before.ends_with(':') || before.ends_with('.') {
+        return false;
+    }
+    let prev_word: String = before
+        .chars()
+        .rev()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    if !prev_word.is_empty() {
+        return prev_word == "return" || prev_word == "yield" || prev_word == "await";
+    }
+    // `{` and `;` cover a literal starting its own line, e.g. a Rust tail expression
+    matches!(
+        before.chars().last(),
+        Some('=' | '(' | ',' | '&' | '[' | '{' | ';' | '?')
+    )
+}
+
+/// `user.email` must not itself be a member of something else (`other.user.email`).
+fn is_member_access(body: &str, start: usize) -> bool {
+    body[..start].ends_with('.')
+}
+
+fn is_assignment(rest: &str) -> bool {
+    let mut chars = rest.chars();
+    match chars.next() {
+        Some('=') => chars.next() != Some('='),
+        Some('+' | '-' | '*' | '/' | '|' | '&') => chars.next() == Some('='),
+        _ => false,
+    }
+}
+
+/// Variables holding an instance of `model`: annotated (`u: User`, `User u`, `u *User`),
+/// assigned from a construction or query (`u = User.find(..)`, `u := &User{}`), and the
+/// conventional lower-cased name (`user` for `User`, `order_item` for `OrderItem`).
+fn bindings(body: &str, model: &str, patterns: &ModelPatterns) -> BTreeSet<String> {
+    let mut vars = BTreeSet::new();
+    for p in &patterns.bindings {
+        for c in p.captures_iter(body) {
+            let var = &c["var"];
+            if !NOT_BINDINGS.contains(&var) && var != model {
+                vars.insert(var.to_string());
+            }
+        }
+    }
+    vars.insert(lower_first(model));
+    vars.insert(snake_case(model));
+    vars
+}
+
+fn lower_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn snake_case(s: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// The text from an opening bracket to its matching close (or the end of `s`).
+fn balanced(s: &str) -> &str {
+    let mut depth = 0i32;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' | '{' | '[' => depth += 1,
+            ')' | '}' | ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &s[..=i];
+                }
+            }
+            _ => {}
+        }
+    }
+    s
+}
+
+/// Keys named at the top level of a bracketed literal: `{ email: x, name }` with `:`
+/// and `(email=x, name=y)` with `=`. Shorthand `{ email }` counts for braces only.
+fn top_level_keys(segment: &str, sep: char) -> Vec<String> {
+    let inner = segment
+        .get(1..segment.len().saturating_sub(1))
+        .unwrap_or_default();
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+    for c in inner.chars() {
+        match c {
+            '(' | '{' | '[' | '<' => depth += 1,
+            ')' | '}' | ']' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current);
+
+    parts
+        .iter()
+        .filter_map(|p| {
+            let p = p.trim();
+            let ident_end = p
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(p.len());
+            let (ident, rest) = p.split_at(ident_end);
+            if ident.is_empty() || ident.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            let rest = rest.trim_start();
+            let keyed = rest.starts_with(sep) && !rest[1..].starts_with(['=', ':']);
+            let shorthand = sep == ':' && rest.is_empty();
+            (keyed || shorthand).then(|| ident.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(set: &BTreeSet<String>) -> Vec<&str> {
+        set.iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_struct_literal_is_a_write_with_fields() {
+        let body = "fn register(email: &str) -> User {\n    User {\n        email: email.to_string(),\n        name,\n        ..Default::default()\n    }\n}";
+        let access = model_access(body, "User");
+        assert!(access.writes);
+        assert!(!access.reads);
+        assert_eq!(fields(&access.written_fields), vec!["email", "name"]);
+    }
+
+    #[test]
+    fn test_mutation_and_persistence() {
+        let body = "async updateEmail(id: string, email: string) {\n  const user = await User.findOne(id);\n  user.email = email;\n  log(user.name);\n  await this.repo.save(user);\n}";
+        let access = model_access(body, "User");
+        assert!(access.writes);
+        assert!(access.reads);
+        assert_eq!(fields(&access.written_fields), vec!["email"]);
+        assert_eq!(fields(&access.read_fields), vec!["name"]);
+    }
+
+    #[test]
+    fn test_keyword_construction_and_plain_reads() {
+        let body = "def create_order(data):\n    order = Order(total=data.total, status=\"new\")\n    session.add(order)\n";
+        let access = model_access(body, "Order");
+        assert!(access.writes);
+        assert_eq!(fields(&access.written_fields), vec!["status", "total"]);
+
+        let body = "func ListOrders(db *gorm.DB) []Order {\n\tvar orders []Order\n\tdb.Find(&orders)\n\treturn orders\n}";
+        let access = model_access(body, "Order");
+        assert!(access.reads);
+        assert!(!access.writes);
+    }
+
+    #[test]
+    fn test_rails_controller_actions() {
+        let body = "def create\n    @advisor_group = AdvisorGroup.new(advisor_group_params)\n    if @advisor_group.save\n      render json: @advisor_group\n    end\n  end";
+        let access = model_access(body, "AdvisorGroup");
+        assert!(access.writes);
+
+        // the record is loaded by a `before_action`, so the class is never named
+        let access = model_access(
+            "def destroy\n    @advisor_group.destroy!\n  end",
+            "AdvisorGroup",
+        );
+        assert!(access.writes);
+
+        let access = model_access(
+            "def show\n    render json: @advisor_group\n  end",
+            "AdvisorGroup",
+        );
+        assert!(access.reads);
+        assert!(!access.writes);
+    }
+
+    #[test]
+    fn test_declarations_are_not_constructions() {
+        let body =
+            "fn load(id: u64) -> Option<User> {\n    let u: User = repo.get(id)?;\n    Some(u)\n}";
+        let access = model_access(body, "User");
+        assert!(!access.writes);
+        assert!(access.reads);
+    }
+}
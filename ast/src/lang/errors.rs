@@ -0,0 +1,868 @@
+use crate::lang::graphs::{Edge, EdgeType, Graph, NodeType};
+use crate::lang::scan::{code_matches, Syntax};
+use crate::lang::NodeData;
+use crate::utils::line_of;
+use lsp::Language;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::LazyLock;
+use tree_sitter::{Node, Parser};
+
+const LANGS: &[Language] = &[
+    Language::Java,
+    Language::Kotlin,
+    Language::Python,
+    Language::Rust,
+    Language::Go,
+    Language::Typescript,
+    Language::Angular,
+];
+
+// handlers naming these catch everything a callee can raise
+const PY_CATCH_ALL: &[&str] = &["Exception", "BaseException"];
+const JVM_CATCH_ALL: &[&str] = &["Exception", "Throwable"];
+
+static PY_RAISE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*raise[ \t]+(?P<ty>[A-Za-z_][\w.]*)").unwrap());
+static PY_EXCEPT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^(?P<indent>[ \t]*)except\b[ \t]*(?P<types>[^:\n]*?)[ \t]*(?:as[ \t]+(?P<var>\w+)[ \t]*)?:",
+    )
+    .unwrap()
+});
+// `throw new NotFoundException(..)` in Java and TypeScript
+static THROW_NEW_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bthrow\s+new\s+(?:\w+\.)*(?P<ty>[A-Z]\w*)").unwrap());
+static KT_THROW_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bthrow\s+(?:\w+\.)*(?P<ty>[A-Z]\w*)\s*\(").unwrap());
+static JAVA_THROWS_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\)\s*throws\s+(?P<types>[\w.,\s]+?)\s*[{;]").unwrap());
+static KT_THROWS_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@Throws\((?P<types>[^)]*)\)").unwrap());
+static JAVA_CATCH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bcatch\s*\(\s*(?:final\s+)?(?P<types>[\w.|\s]+?)\s+(?P<var>\w+)\s*\)\s*\{")
+        .unwrap()
+});
+static KT_CATCH_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bcatch\s*\(\s*(?P<var>\w+)\s*:\s*(?P<types>[\w.]+)\s*\)\s*\{").unwrap()
+});
+static TS_CATCH_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bcatch\s*(?:\(\s*(?P<var>\w+)?[^)]*\))?\s*\{").unwrap());
+static THROW_VAR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bthrow\s+(?P<var>\w+)\b").unwrap());
+static THROW_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bthrow\b").unwrap());
+static INSTANCEOF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\binstanceof\s+(?:\w+\.)*(?P<ty>[A-Z]\w*)").unwrap());
+static RS_ERR_ARM_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bErr\(\s*(?:\w+::)*(?P<ty>[A-Z]\w*)::\w+[^\n]*?=>").unwrap());
+static RS_ERR_ANY_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bErr\(\s*(?:_|[a-z_]\w*)\s*\)\s*=>(?P<rest>[^\n]*)").unwrap());
+static GO_RETURN_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*return\b(?P<rest>[^\n]*)").unwrap());
+// `&NotFoundError{..}` and sentinel values such as `ErrNotFound` in a return statement
+static GO_RAISED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"&?(?:\w+\.)?(?P<ty>[A-Z]\w*Error)\s*\{|\b(?:\w+\.)?(?P<sentinel>Err[A-Z]\w*)\b")
+        .unwrap()
+});
+static GO_CHECK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"errors\.Is\(\s*\w+\s*,\s*(?:\w+\.)?(?P<is>\w+)\s*\)|\berr\.\(\s*\*?(?:\w+\.)?(?P<assert>[A-Z]\w*)\s*\)|(?m:^[ \t]*case\s+\*?(?:\w+\.)?(?P<case>[A-Z]\w*Error)\s*:)",
+    )
+    .unwrap()
+});
+static GO_AS_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"errors\.As\(\s*\w+\s*,\s*&(?P<var>\w+)\s*\)").unwrap());
+static GO_VAR_DECL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bvar\s+(?P<var>\w+)\s+\*?(?:\w+\.)?(?P<ty>[A-Z]\w*)").unwrap());
+static GO_IF_ERR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bif\s+(?:[^{\n]*;\s*)?err\s*!=\s*nil\s*\{").unwrap());
+static GO_PROPAGATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\breturn\b[^\n]*\berr\b|\bpanic\(|\blog\.Fatal").unwrap());
+
+/// What one statement does with an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorSite {
+    Throws(String),
+    Catches(String),
+    /// a handler that swallows whatever reaches it: a bare `except:`, `catch (e)` without a
+    /// rethrow, `if err != nil` without returning `err`
+    CatchesAll,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorUse {
+    pub line: usize,
+    pub site: ErrorSite,
+}
+
+/// Finds raised and handled error types in one source file, ignoring comments and strings.
+/// Rust raises through the function signature and is handled by `rust_result_error`.
+pub fn find_error_uses(lang: &Language, code: &str) -> Vec<ErrorUse> {
+    let syntax = Syntax::of(lang);
+    let mask = syntax.mask(code);
+    let mut uses = Vec::new();
+    let mut push = |offset: usize, site: ErrorSite| {
+        uses.push(ErrorUse {
+            line: line_of(code, offset),
+            site,
+        })
+    };
+    match lang {
+        Language::Python => {
+            for caps in code_matches(&PY_RAISE_RE, code, &mask) {
+                let ty = last_segment(&caps["ty"]);
+                if starts_upper(ty) {
+                    push(
+                        caps.get(0).unwrap().start(),
+                        ErrorSite::Throws(ty.to_string()),
+                    );
+                }
+            }
+            for caps in code_matches(&PY_EXCEPT_RE, code, &mask) {
+                let whole = caps.get(0).unwrap();
+                let block = indented_block(code, whole.end(), caps["indent"].len());
+                let var = caps.name("var").map(|v| v.as_str());
+                if block.lines().any(|l| is_py_reraise(l.trim(), var)) {
+                    continue;
+                }
+                let types = split_types(&caps["types"]);
+                for site in handler_sites(&types, PY_CATCH_ALL) {
+                    push(whole.start(), site);
+                }
+            }
+        }
+        Language::Java | Language::Kotlin => {
+            let (throw_re, catch_re) = if *lang == Language::Java {
+                (&*THROW_NEW_RE, &*JAVA_CATCH_RE)
+            } else {
+                (&*KT_THROW_RE, &*KT_CATCH_RE)
+            };
+            for caps in code_matches(throw_re, code, &mask) {
+                push(
+                    caps.get(0).unwrap().start(),
+                    ErrorSite::Throws(caps["ty"].to_string()),
+                );
+            }
+            for caps in code_matches(&JAVA_THROWS_RE, code, &mask).chain(code_matches(
+                &KT_THROWS_RE,
+                code,
+                &mask,
+            )) {
+                for ty in split_types(&caps["types"]) {
+                    push(caps.get(0).unwrap().start(), ErrorSite::Throws(ty));
+                }
+            }
+            for caps in code_matches(catch_re, code, &mask) {
+                let whole = caps.get(0).unwrap();
+                let block = syntax.block_body(code, whole.end() - 1);
+                if rethrows(syntax, block, &caps["var"]) {
+                    continue;
+                }
+                let types = split_types(&caps["types"]);
+                for site in handler_sites(&types, JVM_CATCH_ALL) {
+                    push(whole.start(), site);
+                }
+            }
+        }
+        Language::Typescript | Language::Angular => {
+            for caps in code_matches(&THROW_NEW_RE, code, &mask) {
+                push(
+                    caps.get(0).unwrap().start(),
+                    ErrorSite::Throws(caps["ty"].to_string()),
+                );
+            }
+            // the caught value is untyped: `instanceof` checks narrow it, and a handler
+            // that never throws again handles everything
+            for caps in code_matches(&TS_CATCH_RE, code, &mask) {
+                let whole = caps.get(0).unwrap();
+                let block = syntax.block_body(code, whole.end() - 1);
+                let block_mask = syntax.mask(block);
+                if code_matches(&THROW_RE, block, &block_mask).next().is_none() {
+                    push(whole.start(), ErrorSite::CatchesAll);
+                    continue;
+                }
+                let Some(var) = caps.name("var") else {
+                    continue;
+                };
+                for ty in code_matches(&INSTANCEOF_RE, block, &block_mask) {
+                    let branch = instanceof_branch(syntax, block, ty.get(0).unwrap().end());
+                    if !branch.is_some_and(|b| rethrows(syntax, b, var.as_str())) {
+                        push(whole.start(), ErrorSite::Catches(ty["ty"].to_string()));
+                    }
+                }
+            }
+        }
+        Language::Rust => {
+            for caps in code_matches(&RS_ERR_ARM_RE, code, &mask) {
+                push(
+                    caps.get(0).unwrap().start(),
+                    ErrorSite::Catches(caps["ty"].to_string()),
+                );
+            }
+            for caps in code_matches(&RS_ERR_ANY_RE, code, &mask) {
+                let rest = &caps["rest"];
+                let propagates = ["Err(", "return", "?", "panic!", "bail!"]
+                    .iter()
+                    .any(|p| rest.contains(p));
+                if !propagates {
+                    push(caps.get(0).unwrap().start(), ErrorSite::CatchesAll);
+                }
+            }
+        }
+        Language::Go => {
+            for ret in code_matches(&GO_RETURN_RE, code, &mask) {
+                let rest = ret.name("rest").unwrap();
+                for caps in GO_RAISED_RE.captures_iter(rest.as_str()) {
+                    let ty = caps.name("ty").or_else(|| caps.name("sentinel")).unwrap();
+                    push(rest.start(), ErrorSite::Throws(ty.as_str().to_string()));
+                }
+            }
+            for caps in code_matches(&GO_CHECK_RE, code, &mask) {
+                let ty = ["is", "assert", "case"]
+                    .iter()
+                    .find_map(|k| caps.name(k))
+                    .unwrap();
+                push(
+                    caps.get(0).unwrap().start(),
+                    ErrorSite::Catches(ty.as_str().to_string()),
+                );
+            }
+            let mut var_types: HashMap<&str, &str> = HashMap::new();
+            for caps in code_matches(&GO_VAR_DECL_RE, code, &mask) {
+                let (var, ty) = (caps.name("var").unwrap(), caps.name("ty").unwrap());
+                var_types.entry(var.as_str()).or_insert(ty.as_str());
+            }
+            for caps in code_matches(&GO_AS_RE, code, &mask) {
+                if let Some(ty) = var_types.get(&caps["var"]) {
+                    push(
+                        caps.get(0).unwrap().start(),
+                        ErrorSite::Catches(ty.to_string()),
+                    );
+                }
+            }
+            for m in GO_IF_ERR_RE.find_iter(code) {
+                if !mask.is_code(m.start()) {
+                    continue;
+                }
+                if !GO_PROPAGATE_RE.is_match(syntax.block_body(code, m.end() - 1)) {
+                    push(m.start(), ErrorSite::CatchesAll);
+                }
+            }
+        }
+        _ => {}
+    }
+    uses.sort_by_key(|u| u.line);
+    uses
+}
+
+/// The error type of a `Result<T, E>` return type, if it is a named type.
+pub fn rust_result_error(return_type: &str) -> Option<String> {
+    let ty = return_type.trim();
+    let inner = ty
+        .strip_suffix('>')?
+        .split_once('<')
+        .filter(|(outer, _)| last_segment_by(outer, "::") == "Result")?
+        .1;
+    let mut depth = 0;
+    let mut last = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => last = i + 1,
+            _ => {}
+        }
+    }
+    if last == 0 {
+        return None;
+    }
+    let error = last_segment_by(inner[last..].trim(), "::");
+    (starts_upper(error) && error.chars().all(|c| c.is_alphanumeric() || c == '_'))
+        .then(|| error.to_string())
+}
+
+#[derive(Debug, Default)]
+struct Handlers {
+    throws: BTreeSet<String>,
+    catches: BTreeSet<String>,
+    catches_all: bool,
+}
+
+type FunctionKey = (String, String, usize);
+
+fn function_key(nd: &NodeData) -> FunctionKey {
+    (nd.name.clone(), nd.file.clone(), nd.start)
+}
+
+/// Adds THROWS edges from functions to the error types they raise and CATCHES edges to
+/// the error types they handle. Only types declared in the repo (a Class, DataModel or a
+/// Go sentinel Var) get an edge. A catch-all handler, or one naming a base class, gets a
+/// CATCHES edge for each concrete type its callees can raise.
+pub fn add_error_edges<G: Graph>(
+    graph: &mut G,
+    lang: &Language,
+    filez: &[(String, String)],
+) -> usize {
+    if !LANGS.contains(lang) {
+        return 0;
+    }
+    let functions = graph.find_nodes_by_type(NodeType::Function);
+    let mut by_file: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, f) in functions.iter().enumerate() {
+        by_file.entry(f.file.as_str()).or_default().push(i);
+    }
+
+    let mut handlers: Vec<Handlers> = functions.iter().map(|_| Handlers::default()).collect();
+    for (file, code) in filez {
+        let Some(funcs) = by_file.get(file.as_str()) else {
+            continue;
+        };
+        for u in find_error_uses(lang, code) {
+            // innermost function around the statement
+            let Some(&i) = funcs
+                .iter()
+                .filter(|&&i| functions[i].start <= u.line && u.line <= functions[i].end)
+                .max_by_key(|&&i| functions[i].start)
+            else {
+                continue;
+            };
+            match u.site {
+                ErrorSite::Throws(ty) => {
+                    handlers[i].throws.insert(ty);
+                }
+                ErrorSite::Catches(ty) => {
+                    handlers[i].catches.insert(ty);
+                }
+                ErrorSite::CatchesAll => handlers[i].catches_all = true,
+            }
+        }
+    }
+    if *lang == Language::Rust {
+        for (i, f) in functions.iter().enumerate() {
+            let Some(error) = f
+                .signature()
+                .and_then(|s| s.return_type)
+                .and_then(|r| rust_result_error(&r))
+            else {
+                continue;
+            };
+            if rust_returns_err(&f.body) {
+                handlers[i].throws.insert(error);
+            }
+        }
+    }
+    if handlers
+        .iter()
+        .all(|h| h.throws.is_empty() && h.catches.is_empty() && !h.catches_all)
+    {
+        return 0;
+    }
+
+    let types = ErrorTypes::new(graph, lang);
+    let throws: Vec<BTreeSet<String>> = handlers
+        .iter()
+        .zip(&functions)
+        .map(|(h, f)| {
+            h.throws
+                .iter()
+                .filter(|t| types.resolve(t, &f.file).is_some())
+                .cloned()
+                .collect()
+        })
+        .collect();
+    let callees = callees(graph, &functions);
+    let handles = |i: usize, ty: &str| {
+        let h = &handlers[i];
+        h.catches_all || h.catches.iter().any(|c| types.is_a(ty, c))
+    };
+    let escaping = escaping_errors(&throws, &callees, &handles);
+
+    let mut edges = Vec::new();
+    for (i, f) in functions.iter().enumerate() {
+        for ty in &throws[i] {
+            if let Some((nt, error)) = types.resolve(ty, &f.file) {
+                edges.push(Edge::throws(f, nt, error));
+            }
+        }
+        let mut caught: BTreeSet<&String> = callees[i]
+            .iter()
+            .flat_map(|&c| escaping[c].iter())
+            .filter(|ty| handles(i, ty.as_str()))
+            .collect();
+        caught.extend(handlers[i].catches.iter());
+        for ty in caught {
+            if let Some((nt, error)) = types.resolve(ty, &f.file) {
+                edges.push(Edge::catches(f, nt, error));
+            }
+        }
+    }
+    let count = edges.len();
+    for edge in edges {
+        graph.add_edge(&edge);
+    }
+    count
+}
+
+/// Names of the error types that can escape `handler`: those it raises plus those
+/// reaching it through CALLS without a CATCHES edge on the way. Run on an Endpoint's
+/// handler this lists the errors that surface as a server error.
+pub fn unhandled_errors<G: Graph>(graph: &G, handler: &NodeData) -> Vec<String> {
+    EscapingErrors::new(graph).of(handler)
+}
+
+/// The error types escaping every function of a graph, computed once so many handlers
+/// can be looked up without re-walking the call graph.
+pub struct EscapingErrors {
+    index: HashMap<FunctionKey, usize>,
+    escaping: Vec<BTreeSet<String>>,
+}
+
+impl EscapingErrors {
+    pub fn new<G: Graph>(graph: &G) -> Self {
+        let functions = graph.find_nodes_by_type(NodeType::Function);
+        let index: HashMap<FunctionKey, usize> = functions
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (function_key(f), i))
+            .collect();
+        let mut throws = vec![BTreeSet::new(); functions.len()];
+        let mut catches = vec![BTreeSet::new(); functions.len()];
+        for nt in [NodeType::Class, NodeType::DataModel, NodeType::Var] {
+            for (edge, sets) in [
+                (EdgeType::Throws, &mut throws),
+                (EdgeType::Catches, &mut catches),
+            ] {
+                for (f, error) in
+                    graph.find_nodes_with_edge_type(NodeType::Function, nt.clone(), edge)
+                {
+                    if let Some(&i) = index.get(&function_key(&f)) {
+                        sets[i].insert(error.name);
+                    }
+                }
+            }
+        }
+        let callees = callees(graph, &functions);
+        let escaping = escaping_errors(&throws, &callees, &|i: usize, ty: &str| {
+            catches[i].contains(ty)
+        });
+        Self { index, escaping }
+    }
+
+    /// See [`unhandled_errors`].
+    pub fn of(&self, handler: &NodeData) -> Vec<String> {
+        self.index
+            .get(&function_key(handler))
+            .map(|&i| self.escaping[i].iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+fn callees<G: Graph>(graph: &G, functions: &[NodeData]) -> Vec<Vec<usize>> {
+    let index: HashMap<FunctionKey, usize> = functions
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (function_key(f), i))
+        .collect();
+    let mut callees = vec![Vec::new(); functions.len()];
+    for (caller, callee) in
+        graph.find_nodes_with_edge_type(NodeType::Function, NodeType::Function, EdgeType::Calls)
+    {
+        if let (Some(&a), Some(&b)) = (
+            index.get(&function_key(&caller)),
+            index.get(&function_key(&callee)),
+        ) {
+            callees[a].push(b);
+        }
+    }
+    callees
+}
+
+/// Per function, the error types raised by it or its callees that it does not handle.
+/// Iterates to a fixpoint so recursive call chains converge.
+fn escaping_errors(
+    throws: &[BTreeSet<String>],
+    callees: &[Vec<usize>],
+    handles: &dyn Fn(usize, &str) -> bool,
+) -> Vec<BTreeSet<String>> {
+    let mut escaping: Vec<BTreeSet<String>> = throws.to_vec();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in 0..escaping.len() {
+            let incoming: Vec<String> = callees[i]
+                .iter()
+                .flat_map(|&c| escaping[c].iter())
+                .filter(|ty| !escaping[i].contains(*ty) && !handles(i, ty.as_str()))
+                .cloned()
+                .collect();
+            if !incoming.is_empty() {
+                escaping[i].extend(incoming);
+                changed = true;
+            }
+        }
+    }
+    escaping
+}
+
+/// Error types declared in the repo, with their ancestors by name.
+struct ErrorTypes {
+    nodes: HashMap<String, Vec<(NodeType, NodeData)>>,
+    parents: HashMap<String, Vec<String>>,
+}
+
+impl ErrorTypes {
+    fn new<G: Graph>(graph: &G, lang: &Language) -> Self {
+        let mut nodes: HashMap<String, Vec<(NodeType, NodeData)>> = HashMap::new();
+        // a type declared as both keeps one node: Go and Rust later drop Classes without
+        // methods, Python drops the DataModel of a Class with methods
+        let order = if matches!(lang, Language::Go | Language::Rust) {
+            [NodeType::DataModel, NodeType::Class, NodeType::Var]
+        } else {
+            [NodeType::Class, NodeType::DataModel, NodeType::Var]
+        };
+        for nt in order {
+            for nd in graph.find_nodes_by_type(nt.clone()) {
+                let entry = nodes.entry(nd.name.clone()).or_default();
+                if entry.iter().all(|(t, _)| *t == nt) {
+                    entry.push((nt.clone(), nd));
+                }
+            }
+        }
+        let mut parents: HashMap<String, Vec<String>> = HashMap::new();
+        for (parent, child) in
+            graph.find_nodes_with_edge_type(NodeType::Class, NodeType::Class, EdgeType::ParentOf)
+        {
+            parents.entry(child.name).or_default().push(parent.name);
+        }
+        for (_, class) in nodes
+            .values()
+            .flatten()
+            .filter(|(nt, _)| *nt == NodeType::Class)
+        {
+            if let Some(parent) = class.meta.get("parent") {
+                let entry = parents.entry(class.name.clone()).or_default();
+                for p in parent.split(',').map(|p| last_segment(p.trim())) {
+                    if !p.is_empty() && !entry.iter().any(|e| e == p) {
+                        entry.push(p.to_string());
+                    }
+                }
+            }
+        }
+        Self { nodes, parents }
+    }
+
+    fn resolve(&self, name: &str, file: &str) -> Option<(NodeType, &NodeData)> {
+        let candidates = self.nodes.get(name)?;
+        candidates
+            .iter()
+            .find(|(_, nd)| nd.file == file)
+            .or_else(|| candidates.first())
+            .map(|(nt, nd)| (nt.clone(), nd))
+    }
+
+    /// `ty` is `base` or inherits from it.
+    fn is_a(&self, ty: &str, base: &str) -> bool {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([ty]);
+        while let Some(t) = queue.pop_front() {
+            if t == base {
+                return true;
+            }
+            if seen.insert(t) {
+                if let Some(ps) = self.parents.get(t) {
+                    queue.extend(ps.iter().map(String::as_str));
+                }
+            }
+        }
+        false
+    }
+}
+
+/// Whether a Rust function can return its error: a `?` or an `Err(..)` call in the
+/// function itself. Closures, async blocks and nested functions return their own errors,
+/// and a `?` or `Err(` in a string, comment or `Err(e) =>` pattern returns nothing.
+fn rust_returns_err(body: &str) -> bool {
+    let mut parser = Parser::new();
+    let lang: tree_sitter::Language = tree_sitter_rust::LANGUAGE.into();
+    if parser.set_language(&lang).is_err() {
+        return false;
+    }
+    let Some(tree) = parser.parse(body, None) else {
+        return false;
+    };
+    let root = tree.root_node();
+    let mut stack: Vec<Node> = vec![root];
+    while let Some(node) = stack.pop() {
+        match node.kind() {
+            "try_expression" => return true,
+            "call_expression" => {
+                let callee = node
+                    .child_by_field_name("function")
+                    .and_then(|f| f.utf8_text(body.as_bytes()).ok());
+                if callee.is_some_and(|c| c == "Err" || c.ends_with("::Err")) {
+                    return true;
+                }
+            }
+            "closure_expression" | "async_block" => continue,
+            "function_item" if node.parent().is_some_and(|p| p.id() != root.id()) => continue,
+            _ => {}
+        }
+        for i in 0..node.named_child_count() {
+            if let Some(child) = node.named_child(i) {
+                stack.push(child);
+            }
+        }
+    }
+    false
+}
+
+fn handler_sites(types: &[String], catch_all: &[&str]) -> Vec<ErrorSite> {
+    if types.is_empty() || types.iter().any(|t| catch_all.contains(&t.as_str())) {
+        return vec![ErrorSite::CatchesAll];
+    }
+    types
+        .iter()
+        .map(|t| ErrorSite::Catches(t.clone()))
+        .collect()
+}
+
+/// `(ValueError, KeyError)`, `IOException | SQLException`, `A::class, B::class`
+fn split_types(s: &str) -> Vec<String> {
+    s.split([',', '|'])
+        .map(|t| t.trim().trim_matches(['(', ')']).trim())
+        .map(|t| t.strip_suffix("::class").unwrap_or(t))
+        .map(last_segment)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_py_reraise(line: &str, var: Option<&str>) -> bool {
+    let Some(rest) = line.strip_prefix("raise") else {
+        return false;
+    };
+    let rest = rest.trim();
+    rest.is_empty() || var.is_some_and(|v| rest == v || rest.starts_with(&format!("{v} ")))
+}
+
+fn rethrows(syntax: Syntax, block: &str, var: &str) -> bool {
+    code_matches(&THROW_VAR_RE, block, &syntax.mask(block)).any(|caps| &caps["var"] == var)
+}
+
+/// The statement an `if (e instanceof X ...)` condition guards, from just after the
+/// `instanceof` match; `None` when the check is not an `if` condition.
+fn instanceof_branch(syntax: Syntax, block: &str, from: usize) -> Option<&str> {
+    let mut depth = 1;
+    let mut close = None;
+    for (i, c) in block[from..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(from + i + 1);
+                    break;
+                }
+            }
+            ';' | '{' | '}' => return None,
+            _ => {}
+        }
+    }
+    let rest = &block[close?..];
+    let body = rest.trim_start();
+    let at = block.len() - body.len();
+    if body.starts_with('{') {
+        return Some(syntax.block_body(block, at));
+    }
+    let end = body.find([';', '\n']).unwrap_or(body.len());
+    Some(&body[..end])
+}
+
+/// The lines after `from` indented deeper than `indent`.
+fn indented_block(code: &str, from: usize, indent: usize) -> &str {
+    let rest = &code[from..];
+    let mut end = 0;
+    for line in rest.split_inclusive('\n').skip(1) {
+        let depth = line.len() - line.trim_start().len();
+        if !line.trim().is_empty() && depth <= indent {
+            break;
+        }
+        end += line.len();
+    }
+    let start = rest.find('\n').map(|i| i + 1).unwrap_or(rest.len());
+    &rest[start..(start + end).min(rest.len())]
+}
+
+fn last_segment(s: &str) -> &str {
+    last_segment_by(s, ".")
+}
+
+fn last_segment_by<'a>(s: &'a str, sep: &str) -> &'a str {
+    s.rsplit(sep).next().unwrap_or(s)
+}
+
+fn starts_upper(s: &str) -> bool {
+    s.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sites(lang: Language, code: &str) -> Vec<(usize, ErrorSite)> {
+        find_error_uses(&lang, code)
+            .into_iter()
+            .map(|u| (u.line, u.site))
+            .collect()
+    }
+
+    fn throws(ty: &str) -> ErrorSite {
+        ErrorSite::Throws(ty.to_string())
+    }
+
+    fn catches(ty: &str) -> ErrorSite {
+        ErrorSite::Catches(ty.to_string())
+    }
+
+    #[test]
+    fn test_python_raise_and_except() {
+        let code = "def create(data):\n    try:\n        validate(data)\n    except (ValidationError, errors.Conflict) as e:\n        return 400\n    except KeyError:\n        raise\n    if not data:\n        raise errors.NotFound(\"missing\")\n    try:\n        save(data)\n    except Exception:\n        log()\n    \"\"\"\n    raise Documented()\n    \"\"\"\n";
+        assert_eq!(
+            sites(Language::Python, code),
+            vec![
+                (3, catches("ValidationError")),
+                (3, catches("Conflict")),
+                (8, throws("NotFound")),
+                (11, ErrorSite::CatchesAll),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_java_and_typescript_throw_and_catch() {
+        let code = "public Order place(Cart cart) throws PaymentException, StockException {\n    try {\n        charge(cart);\n    } catch (CardDeclinedException | FraudException e) {\n        throw new PaymentException(e);\n    } catch (IOException e) {\n        throw e;\n    }\n}\n";
+        assert_eq!(
+            sites(Language::Java, code),
+            vec![
+                (0, throws("PaymentException")),
+                (0, throws("StockException")),
+                (3, catches("CardDeclinedException")),
+                (3, catches("FraudException")),
+                (4, throws("PaymentException")),
+            ]
+        );
+
+        let code = "async function handler(req) {\n  try {\n    await load(req.id);\n  } catch (err) {\n    if (err instanceof NotFoundError) return null;\n    throw err;\n  }\n  try { audit(); } catch { }\n  throw new HttpError(500);\n}\n";
+        assert_eq!(
+            sites(Language::Typescript, code),
+            vec![
+                (3, catches("NotFoundError")),
+                (7, ErrorSite::CatchesAll),
+                (8, throws("HttpError")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_typescript_instanceof_rethrow() {
+        let code = "try {\n  await load();\n} catch (e) {\n  if (e instanceof Conflict) throw e;\n  if (e instanceof Gone) {\n    return null;\n  }\n  throw e;\n}\ntry { audit(); } catch (e) { const throwaway = e; }\n";
+        assert_eq!(
+            sites(Language::Typescript, code),
+            vec![(2, catches("Gone")), (9, ErrorSite::CatchesAll)]
+        );
+    }
+
+    #[test]
+    fn test_go_and_rust_errors() {
+        let code = "func Get(id string) (*User, error) {\n\tu, err := repo.Find(id)\n\tif errors.Is(err, ErrNotFound) {\n\t\treturn nil, &ValidationError{Field: \"id\"}\n\t}\n\tif err != nil {\n\t\treturn nil, fmt.Errorf(\"get: %w\", err)\n\t}\n\tif err := audit(u); err != nil {\n\t\tlog.Println(err)\n\t}\n\treturn u, nil\n}\n";
+        assert_eq!(
+            sites(Language::Go, code),
+            vec![
+                (2, catches("ErrNotFound")),
+                (3, throws("ValidationError")),
+                (8, ErrorSite::CatchesAll),
+            ]
+        );
+        let code = "var nf *NotFoundError\nif errors.As(err, &nf) {\n\treturn nil\n}\n";
+        assert_eq!(
+            sites(Language::Go, code),
+            vec![(1, catches("NotFoundError"))]
+        );
+
+        let code = "match service.load(id) {\n    Ok(u) => Ok(u),\n    Err(AppError::NotFound(_)) => Ok(User::default()),\n    Err(e) => Err(e),\n}\nif let Err(_) = x {}\nmatch y { Err(_) => 0, Ok(v) => v }\n";
+        assert_eq!(
+            sites(Language::Rust, code),
+            vec![(2, catches("AppError")), (6, ErrorSite::CatchesAll)]
+        );
+        assert_eq!(
+            rust_result_error("Result<Vec<(i32, String)>, crate::errors::AppError>").as_deref(),
+            Some("AppError")
+        );
+        assert_eq!(rust_result_error("Result<()>"), None);
+        assert_eq!(rust_result_error("Option<User>"), None);
+    }
+
+    #[test]
+    fn test_rust_returns_err() {
+        assert!(rust_returns_err(
+            "fn load(id: u64) -> Result<User, AppError> {\n    let u = repo.get(id)?;\n    Ok(u)\n}"
+        ));
+        assert!(rust_returns_err(
+            "fn check(n: i32) -> Result<(), AppError> {\n    if n < 0 {\n        return Err(AppError::Negative);\n    }\n    Ok(())\n}"
+        ));
+        // handled, quoted or returned by a closure: nothing escapes the function
+        assert!(!rust_returns_err(
+            "fn load(id: u64) -> Result<User, AppError> {\n    let label = \"why? Err(\";\n    // retry? Err(..) is handled below\n    let parse = |s: &str| -> Result<u64, ParseIntError> { Ok(s.parse()?) };\n    match repo.get(id) {\n        Ok(u) => Ok(u),\n        Err(_) => Ok(User::default()),\n    }\n}"
+        ));
+    }
+
+    #[test]
+    fn test_unhandled_errors_reach_handler() {
+        use crate::lang::graphs::ArrayGraph;
+        let mut graph = ArrayGraph::new(String::new(), Language::Python);
+        let file = "app/users.py";
+        let code = "def create_user(data):\n    try:\n        validate(data)\n    except ValidationError:\n        return 400\n    save(data)\n\ndef validate(data):\n    raise InvalidEmail()\n\ndef save(data):\n    raise DatabaseError()\n";
+
+        let mut functions = Vec::new();
+        for (name, start, end) in [("create_user", 0, 5), ("validate", 7, 8), ("save", 10, 11)] {
+            let mut f = NodeData::name_file(name, file);
+            f.start = start;
+            f.end = end;
+            graph.add_node(&NodeType::Function, &f);
+            functions.push(f);
+        }
+        let mut classes = Vec::new();
+        for name in ["ValidationError", "InvalidEmail", "DatabaseError"] {
+            let class = NodeData::name_file(name, "app/errors.py");
+            graph.add_node(&NodeType::Class, &class);
+            classes.push(class);
+        }
+        graph.add_edge(&Edge::parent_of(&classes[0], &classes[1]));
+        for callee in &functions[1..] {
+            graph.add_edge(&Edge::calls(
+                NodeType::Function,
+                &functions[0],
+                NodeType::Function,
+                callee,
+            ));
+        }
+
+        let count = add_error_edges(
+            &mut graph,
+            &Language::Python,
+            &[(file.to_string(), code.to_string())],
+        );
+        // two THROWS, plus CATCHES for InvalidEmail (a ValidationError) and ValidationError
+        assert_eq!(count, 4);
+        let caught: Vec<String> = graph
+            .find_nodes_with_edge_type(NodeType::Function, NodeType::Class, EdgeType::Catches)
+            .into_iter()
+            .map(|(_, c)| c.name)
+            .collect();
+        assert_eq!(caught, vec!["InvalidEmail", "ValidationError"]);
+        assert_eq!(
+            unhandled_errors(&graph, &functions[0]),
+            vec!["DatabaseError"]
+        );
+    }
+}
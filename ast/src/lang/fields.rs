@@ -0,0 +1,533 @@
+use crate::lang::signature::{clean_default, clean_type, is_false, text};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+use tree_sitter::Node as TreeNode;
+
+/// One member of a DataModel, stored as JSON in the `fields` meta of DataModel nodes so
+/// schema changes can be compared field by field.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub optional: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<String>,
+}
+
+/// Containers holding the members of a struct, class, interface or record.
+const BODY_KINDS: &[&str] = &[
+    "field_declaration_list",
+    "class_body",
+    "interface_body",
+    "object_type",
+    "declaration_list",
+    "block",
+    "primary_constructor",
+    "formal_parameters",
+    "record_declaration_body",
+];
+
+const FIELD_KINDS: &[&str] = &[
+    "field_declaration",
+    "property_signature",
+    "public_field_definition",
+    "property_declaration",
+    "class_parameter",
+    "formal_parameter",
+    "expression_statement",
+];
+
+const ANNOTATION_KINDS: &[&str] = &[
+    "attribute_item",
+    "decorator",
+    "attribute_list",
+    "marker_annotation",
+    "annotation",
+];
+
+/// Grammar-agnostic extraction from a DataModel definition node: Rust and Go struct
+/// fields, TypeScript interface and class properties, Python class attributes (with
+/// Pydantic validators attached to the fields they check), Java fields and records,
+/// Kotlin constructor and body properties, and C# fields and properties.
+pub fn extract_fields(def: TreeNode, code: &str) -> Vec<Field> {
+    let mut fields: Vec<Field> = Vec::new();
+    let mut validators: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for body in bodies(def) {
+        let mut pending = Vec::new();
+        let mut cursor = body.walk();
+        for member in body.named_children(&mut cursor) {
+            let kind = member.kind();
+            if kind.contains("comment") {
+                continue;
+            }
+            // Rust attributes are siblings preceding the field they annotate
+            if kind == "attribute_item" {
+                pending.push(text(member, code));
+                continue;
+            }
+            if kind == "decorated_definition" {
+                collect_validators(member, code, &mut validators);
+                continue;
+            }
+            if !FIELD_KINDS.contains(&kind) {
+                pending.clear();
+                continue;
+            }
+            let mut annotations = std::mem::take(&mut pending);
+            annotations.extend(annotations_of(member, code));
+            for mut field in fields_of(member, code) {
+                field.annotations.extend(annotations.iter().cloned());
+                if !fields.iter().any(|f| f.name == field.name) {
+                    fields.push(field);
+                }
+            }
+        }
+    }
+    for field in fields.iter_mut() {
+        if let Some(v) = validators.get(&field.name) {
+            field.annotations.extend(v.iter().cloned());
+        }
+    }
+    fields
+}
+
+/// Member containers of `def`: its body plus, for Kotlin classes, the primary
+/// constructor, without descending into nested method bodies.
+fn bodies(def: TreeNode) -> Vec<TreeNode> {
+    let mut out = Vec::new();
+    let mut queue = vec![(def, 0)];
+    let mut i = 0;
+    while i < queue.len() {
+        let (node, depth) = queue[i];
+        i += 1;
+        if node.id() != def.id() && BODY_KINDS.contains(&node.kind()) {
+            out.push(node);
+            continue;
+        }
+        if depth >= 3 {
+            continue;
+        }
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if child.kind().contains("function") || child.kind().contains("method") {
+                continue;
+            }
+            queue.push((child, depth + 1));
+        }
+    }
+    out
+}
+
+fn fields_of(member: TreeNode, code: &str) -> Vec<Field> {
+    // Python class attributes: `email: str = Field(..)`
+    if member.kind() == "expression_statement" {
+        let Some(assign) = member.named_child(0).filter(|n| n.kind() == "assignment") else {
+            return Vec::new();
+        };
+        let Some(name) = assign
+            .child_by_field_name("left")
+            .filter(|n| n.kind() == "identifier")
+        else {
+            return Vec::new();
+        };
+        let type_name = assign
+            .child_by_field_name("type")
+            .and_then(|n| clean_type(text(n, code)));
+        let default = assign
+            .child_by_field_name("right")
+            .and_then(|n| clean_default(text(n, code)));
+        return vec![field(text(name, code), type_name, default, false)];
+    }
+
+    let mut type_name = member
+        .child_by_field_name("type")
+        .and_then(|n| clean_type(text(n, code)));
+    let mut default = ["value", "default_value", "right"]
+        .iter()
+        .find_map(|f| member.child_by_field_name(f))
+        .and_then(|n| clean_default(text(n, code)));
+    let mut marked_optional = false;
+    let mut names = Vec::new();
+
+    let mut cursor = member.walk();
+    for n in member.children_by_field_name("name", &mut cursor) {
+        names.push(text(n, code));
+    }
+    let mut cursor = member.walk();
+    for child in member.children(&mut cursor) {
+        match child.kind() {
+            "?" => marked_optional = true,
+            // Java `declarator:` and C# `variable_declaration` hold name and initializer
+            "variable_declarator" if names.is_empty() || default.is_none() => {
+                if let Some(n) = child
+                    .child_by_field_name("name")
+                    .or_else(|| child.named_child(0))
+                {
+                    names.push(text(n, code));
+                }
+                default = default.or_else(|| initializer(child, code));
+            }
+            "variable_declaration" => {
+                type_name = type_name.or_else(|| {
+                    child
+                        .child_by_field_name("type")
+                        .or_else(|| type_child(child))
+                        .and_then(|n| clean_type(text(n, code)))
+                });
+                let mut c = child.walk();
+                for d in child.named_children(&mut c) {
+                    match d.kind() {
+                        "variable_declarator" => {
+                            if let Some(n) =
+                                d.child_by_field_name("name").or_else(|| d.named_child(0))
+                            {
+                                names.push(text(n, code));
+                            }
+                            default = default.or_else(|| initializer(d, code));
+                        }
+                        "simple_identifier" | "identifier" => names.push(text(d, code)),
+                        _ => {}
+                    }
+                }
+            }
+            "equals_value_clause" => default = default.or_else(|| clean_default(text(child, code))),
+            _ => {}
+        }
+    }
+    // Kotlin `class_parameter` has no field names: identifier, type, then the default
+    if names.is_empty() {
+        let mut cursor = member.walk();
+        let mut seen_type = false;
+        for child in member.named_children(&mut cursor) {
+            match child.kind() {
+                "simple_identifier" | "identifier" | "property_identifier" | "field_identifier"
+                    if names.is_empty() =>
+                {
+                    names.push(text(child, code))
+                }
+                k if is_type_kind(k) && type_name.is_none() => {
+                    type_name = clean_type(text(child, code));
+                    seen_type = true;
+                }
+                k if seen_type && !ANNOTATION_KINDS.contains(&k) && k != "modifiers" => {
+                    default = default.or_else(|| clean_default(text(child, code)));
+                }
+                _ => {}
+            }
+        }
+    }
+    // Go struct tags (`json:"email,omitempty"`) behave like annotations
+    let tag = member
+        .child_by_field_name("tag")
+        .map(|n| text(n, code).trim_matches('`').to_string());
+
+    names
+        .into_iter()
+        .filter(|n| !n.is_empty())
+        .map(|name| {
+            let mut f = field(name, type_name.clone(), default.clone(), marked_optional);
+            if let Some(tag) = &tag {
+                f.annotations.push(tag.clone());
+            }
+            f
+        })
+        .collect()
+}
+
+fn field(name: String, type_name: Option<String>, default: Option<String>, marked: bool) -> Field {
+    let optional = marked || type_name.as_deref().is_some_and(is_optional_type);
+    Field {
+        name,
+        type_name,
+        optional,
+        default,
+        annotations: Vec::new(),
+    }
+}
+
+fn is_type_kind(kind: &str) -> bool {
+    matches!(
+        kind,
+        "user_type" | "nullable_type" | "type_identifier" | "function_type" | "type"
+    )
+}
+
+fn type_child(node: TreeNode) -> Option<TreeNode> {
+    let mut cursor = node.walk();
+    let found = node
+        .named_children(&mut cursor)
+        .find(|c| is_type_kind(c.kind()) || c.kind().ends_with("_type"));
+    found
+}
+
+fn initializer(declarator: TreeNode, code: &str) -> Option<String> {
+    declarator
+        .child_by_field_name("value")
+        .or_else(|| {
+            let mut cursor = declarator.walk();
+            let found = declarator
+                .named_children(&mut cursor)
+                .find(|c| c.kind() == "equals_value_clause");
+            found
+        })
+        .and_then(|n| clean_default(text(n, code)))
+}
+
+/// `Option<T>`, `Optional[T]`, `T | None`, `T?` and Go pointers can be absent.
+fn is_optional_type(t: &str) -> bool {
+    let t = t.trim();
+    t.ends_with('?')
+        || t.starts_with('*')
+        || ["Option<", "Optional[", "Optional<", "typing.Optional["]
+            .iter()
+            .any(|p| t.starts_with(p))
+        || (t.contains('|')
+            && t.split('|')
+                .any(|p| matches!(p.trim(), "None" | "null" | "undefined" | "nil")))
+}
+
+fn annotations_of(member: TreeNode, code: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cursor = member.walk();
+    for child in member.named_children(&mut cursor) {
+        if ANNOTATION_KINDS.contains(&child.kind()) {
+            out.push(text(child, code));
+        } else if child.kind() == "modifiers" {
+            let mut c = child.walk();
+            for m in child.named_children(&mut c) {
+                if ANNOTATION_KINDS.contains(&m.kind()) {
+                    out.push(text(m, code));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Pydantic `@validator("email")` / `@field_validator("email", "name")` methods name the
+/// fields they check; record the decorator on each of them.
+fn collect_validators(def: TreeNode, code: &str, out: &mut BTreeMap<String, Vec<String>>) {
+    let mut cursor = def.walk();
+    for d in def.named_children(&mut cursor) {
+        if d.kind() != "decorator" {
+            continue;
+        }
+        let decorator = text(d, code);
+        let Some((head, args)) = decorator.split_once('(') else {
+            continue;
+        };
+        if !head.ends_with("validator") {
+            continue;
+        }
+        for arg in args.trim_end_matches(')').split(',') {
+            let arg = arg.trim();
+            if arg.len() > 2 && (arg.starts_with('"') || arg.starts_with('\'')) {
+                out.entry(arg[1..arg.len() - 1].to_string())
+                    .or_default()
+                    .push(decorator.clone());
+            }
+        }
+    }
+}
+
+static SCHEMA_COLUMN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^\s*t\.(?P<type>\w+)\s+["'](?P<name>[^"']+)["'](?P<opts>.*)$"#).unwrap()
+});
+static SCHEMA_DEFAULT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bdefault:\s*(?P<value>[^,]+)").unwrap());
+
+/// Columns of a Rails `create_table` block: `t.string "email", null: false, default: ""`.
+/// Columns are nullable unless declared `null: false`.
+pub fn schema_columns(block: &str) -> Vec<Field> {
+    SCHEMA_COLUMN_RE
+        .captures_iter(block)
+        .filter(|c| &c["type"] != "index")
+        .map(|c| {
+            let opts = &c["opts"];
+            Field {
+                name: c["name"].to_string(),
+                type_name: Some(c["type"].to_string()),
+                optional: !opts.contains("null: false"),
+                default: SCHEMA_DEFAULT_RE
+                    .captures(opts)
+                    .map(|d| d["value"].trim().to_string()),
+                annotations: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// Identifiers in a field type that may name another DataModel:
+/// `Vec<Address>` -> ["Vec", "Address"], `list[Item] | None` -> ["list", "Item", "None"].
+pub fn type_references(type_name: &str) -> Vec<String> {
+    type_name
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|s| s.chars().next().is_some_and(|c| c.is_alphabetic()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Field-level differences between two versions of a DataModel.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum FieldChange {
+    Added {
+        name: String,
+        #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+        type_name: Option<String>,
+    },
+    Removed {
+        name: String,
+        #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+        type_name: Option<String>,
+    },
+    Retyped {
+        name: String,
+        before: Option<String>,
+        after: Option<String>,
+    },
+    Changed {
+        name: String,
+    },
+}
+
+pub fn diff_fields(before: &[Field], after: &[Field]) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    for b in before {
+        match after.iter().find(|a| a.name == b.name) {
+            None => changes.push(FieldChange::Removed {
+                name: b.name.clone(),
+                type_name: b.type_name.clone(),
+            }),
+            Some(a) if a.type_name != b.type_name => changes.push(FieldChange::Retyped {
+                name: b.name.clone(),
+                before: b.type_name.clone(),
+                after: a.type_name.clone(),
+            }),
+            Some(a) if a != b => changes.push(FieldChange::Changed {
+                name: b.name.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for a in after {
+        if !before.iter().any(|b| b.name == a.name) {
+            changes.push(FieldChange::Added {
+                name: a.name.clone(),
+                type_name: a.type_name.clone(),
+            });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_sitter::Parser;
+
+    fn first_def<'a>(tree: &'a tree_sitter::Tree, kinds: &[&str]) -> TreeNode<'a> {
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if kinds.contains(&node.kind()) {
+                return node;
+            }
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.named_children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+        panic!("no {:?} in tree", kinds);
+    }
+
+    fn parse(lang: tree_sitter::Language, code: &str) -> tree_sitter::Tree {
+        let mut parser = Parser::new();
+        parser.set_language(&lang).unwrap();
+        parser.parse(code, None).unwrap()
+    }
+
+    #[test]
+    fn test_rust_struct_fields() {
+        let code = "pub struct User {\n    #[serde(rename = \"mail\")]\n    pub email: String,\n    pub address: Option<Address>,\n}\n";
+        let tree = parse(tree_sitter_rust::LANGUAGE.into(), code);
+        let fields = extract_fields(first_def(&tree, &["struct_item"]), code);
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].name, "email");
+        assert_eq!(fields[0].type_name.as_deref(), Some("String"));
+        assert_eq!(fields[0].annotations, vec!["#[serde(rename = \"mail\")]"]);
+        assert!(fields[1].optional);
+        assert!(fields[1].annotations.is_empty());
+    }
+
+    #[test]
+    fn test_typescript_interface_fields() {
+        let code = "interface User { id: number; nickname?: string; address: Address | null }";
+        let tree = parse(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(), code);
+        let fields = extract_fields(first_def(&tree, &["interface_declaration"]), code);
+        let names: Vec<_> = fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "nickname", "address"]);
+        assert!(!fields[0].optional);
+        assert!(fields[1].optional);
+        assert!(fields[2].optional);
+    }
+
+    #[test]
+    fn test_python_pydantic_fields() {
+        let code = "class User(BaseModel):\n    email: str\n    age: int = 0\n\n    @validator(\"email\")\n    def check_email(cls, v):\n        return v\n";
+        let tree = parse(tree_sitter_python::LANGUAGE.into(), code);
+        let fields = extract_fields(first_def(&tree, &["class_definition"]), code);
+        assert_eq!(fields.len(), 2);
+        assert_eq!(fields[0].annotations, vec!["@validator(\"email\")"]);
+        assert_eq!(fields[1].default.as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn test_go_and_java_fields() {
+        let code =
+            "package m\ntype User struct {\n\tEmail string `json:\"email\"`\n\tOrg *Org\n}\n";
+        let tree = parse(tree_sitter_go::LANGUAGE.into(), code);
+        let fields = extract_fields(first_def(&tree, &["type_declaration"]), code);
+        assert_eq!(fields[0].annotations, vec!["json:\"email\""]);
+        assert!(fields[1].optional);
+
+        let code = "@Entity class User { @Column(nullable = false) private String email = \"\"; }";
+        let tree = parse(tree_sitter_java::LANGUAGE.into(), code);
+        let fields = extract_fields(first_def(&tree, &["class_declaration"]), code);
+        assert_eq!(fields[0].name, "email");
+        assert_eq!(fields[0].type_name.as_deref(), Some("String"));
+        assert_eq!(fields[0].annotations, vec!["@Column(nullable = false)"]);
+        assert_eq!(fields[0].default.as_deref(), Some("\"\""));
+    }
+
+    #[test]
+    fn test_rails_schema_columns() {
+        let block = "create_table \"people\", force: :cascade do |t|\n    t.string \"name\"\n    t.integer \"age\", null: false, default: 0\n    t.index [\"name\"], name: \"index_people_on_name\"\n  end";
+        let fields = schema_columns(block);
+        assert_eq!(fields.len(), 2);
+        assert!(fields[0].optional);
+        assert!(!fields[1].optional);
+        assert_eq!(fields[1].default.as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn test_diff_fields() {
+        let before = vec![
+            field("email".into(), Some("String".into()), None, false),
+            field("age".into(), Some("u32".into()), None, false),
+        ];
+        let after = vec![
+            field("age".into(), Some("u64".into()), None, false),
+            field("name".into(), Some("String".into()), None, false),
+        ];
+        let changes = diff_fields(&before, &after);
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(&changes[0], FieldChange::Removed { name, .. } if name == "email"));
+        assert!(matches!(&changes[1], FieldChange::Retyped { name, .. } if name == "age"));
+        assert!(matches!(&changes[2], FieldChange::Added { name, .. } if name == "name"));
+    }
+}
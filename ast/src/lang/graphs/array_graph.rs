@@ -22,6 +22,10 @@ pub struct ArrayGraph {
     node_keys: HashSet<String>,
     #[serde(skip)]
     edge_keys: HashSet<String>,
+    /// Position of each edge in `edges`, to fold repeat calls into it. Removals shift
+    /// positions, so an entry is checked before use and refreshed when stale.
+    #[serde(skip)]
+    edge_index: HashMap<String, usize>,
 }
 
 impl Graph for ArrayGraph {
@@ -68,7 +72,8 @@ impl Graph for ArrayGraph {
                 || final_filter.contains(&edge.target.node_data.file)
             {
                 let key = self.create_edge_key(edge);
-                new_graph.edge_keys.insert(key);
+                new_graph.edge_keys.insert(key.clone());
+                new_graph.edge_index.insert(key, new_graph.edges.len());
                 new_graph.edges.push(edge.clone());
             }
         }
@@ -85,10 +90,7 @@ impl Graph for ArrayGraph {
             }
         }
         for edge in &other.edges {
-            let key = self.create_edge_key(edge);
-            if self.edge_keys.insert(key) {
-                self.edges.push(edge.clone());
-            }
+            self.add_edge(edge);
         }
         self.errors.extend(other.errors);
     }
@@ -99,8 +101,22 @@ impl Graph for ArrayGraph {
     fn add_edge(&mut self, edge: &Edge) {
         let key = self.create_edge_key(edge);
 
-        if self.edge_keys.insert(key) {
+        if self.edge_keys.insert(key.clone()) {
+            self.edge_index.insert(key, self.edges.len());
             self.edges.push(edge.clone());
+        } else if !edge.call_sites.is_empty() || edge.resolution.is_some() {
+            let same = |e: &Edge| {
+                e.edge == edge.edge && e.source == edge.source && e.target == edge.target
+            };
+            let index = match self.edge_index.get(&key) {
+                Some(&i) if self.edges.get(i).is_some_and(same) => Some(i),
+                _ => self.edges.iter().rposition(same),
+            };
+            if let Some(i) = index {
+                merge_call_sites(&mut self.edges[i].call_sites, &edge.call_sites);
+                merge_resolution(&mut self.edges[i].resolution, &edge.resolution);
+                self.edge_index.insert(key, i);
+            }
         }
     }
 
@@ -451,20 +467,9 @@ impl Graph for ArrayGraph {
                     }
                 }
             } else {
-                let edge_key = (
-                    fc.source.name.clone(),
-                    fc.source.file.clone(),
-                    fc.source.start,
-                    fc.target.name.clone(),
-                    fc.target.file.clone(),
-                    fc.target.start,
-                );
-
-                if !unique_edges.contains(&edge_key) {
-                    unique_edges.insert(edge_key);
-                    let edge = fc.clone().into();
-                    self.add_edge(&edge);
-                }
+                // Repeat calls to the same target fold their call sites into one edge
+                let edge = fc.clone().into();
+                self.add_edge(&edge);
             }
         }
 
@@ -526,20 +531,9 @@ impl Graph for ArrayGraph {
             } else if tc.target.is_empty() {
                 continue;
             } else {
-                let edge_key = (
-                    tc.source.name.clone(),
-                    tc.source.file.clone(),
-                    tc.source.start,
-                    tc.target.name.clone(),
-                    tc.target.file.clone(),
-                    tc.target.start,
-                );
-
-                if !unique_edges.contains(&edge_key) {
-                    unique_edges.insert(edge_key);
-                    let edge = Edge::from_test_call(&tc, lang, self);
-                    self.add_edge(&edge);
-                }
+                // Repeat calls to the same target fold their call sites into one edge
+                let edge = Edge::from_test_call(&tc, lang, self);
+                self.add_edge(&edge);
             }
         }
         for edge in int_tests {
@@ -1010,16 +1004,16 @@ impl Graph for ArrayGraph {
                     && edge.target.node_type == target_type
             })
             .filter_map(|edge| {
-                let source_nodes = self
-                    .find_nodes_by_name(edge.source.node_type.clone(), &edge.source.node_data.name);
-                let target_nodes = self
-                    .find_nodes_by_name(edge.target.node_type.clone(), &edge.target.node_data.name);
-
-                if let (Some(source), Some(target)) = (source_nodes.first(), target_nodes.first()) {
-                    Some((source.clone(), target.clone()))
-                } else {
-                    None
-                }
+                // same-named nodes (trait declaration vs impl method) are told apart by file and line
+                let resolve = |r: &NodeRef| {
+                    let nodes = self.find_nodes_by_name(r.node_type.clone(), &r.node_data.name);
+                    nodes
+                        .iter()
+                        .find(|n| n.file == r.node_data.file && n.start == r.node_data.start)
+                        .or(nodes.first())
+                        .cloned()
+                };
+                Some((resolve(&edge.source)?, resolve(&edge.target)?))
             })
             .collect::<Vec<(NodeData, NodeData)>>()
     }
@@ -1098,12 +1092,33 @@ impl ArrayGraph {
         format!("{}-{}-{}", source_key, target_key, edge_type,)
     }
 
+    /// Drop resolved CALLS edges whose confidence is below `min_confidence`.
+    /// Edges that never went through call resolution are kept.
+    pub fn retain_calls_with_min_confidence(&mut self, min_confidence: f32) {
+        let dropped: Vec<String> = self
+            .edges
+            .iter()
+            .filter(|e| e.edge == EdgeType::Calls)
+            .filter(|e| matches!(e.confidence(), Some(c) if c < min_confidence))
+            .map(|e| self.create_edge_key(e))
+            .collect();
+        if dropped.is_empty() {
+            return;
+        }
+        self.edges.retain(|e| {
+            e.edge != EdgeType::Calls || !matches!(e.confidence(), Some(c) if c < min_confidence)
+        });
+        for key in &dropped {
+            self.edge_keys.remove(key);
+        }
+    }
+
     pub fn find_dependents(
         &self,
         target_name: &str,
         target_file: &str,
         edge_types: &[EdgeType],
-    ) -> Vec<(NodeRef, EdgeType)> {
+    ) -> Vec<(NodeRef, EdgeType, Option<CallResolution>)> {
         self.edges
             .iter()
             .filter(|e| {
@@ -1111,7 +1126,7 @@ impl ArrayGraph {
                     && e.target.node_data.name == target_name
                     && e.target.node_data.file == target_file
             })
-            .map(|e| (e.source.clone(), e.edge.clone()))
+            .map(|e| (e.source.clone(), e.edge.clone(), e.resolution.clone()))
             .collect()
     }
 }
@@ -12,6 +12,10 @@ pub struct BTreeMapGraph {
     pub nodes: BTreeMap<String, Node>,
     pub edges: BTreeSet<(String, String, EdgeType)>,
     #[serde(skip)]
+    call_resolutions: BTreeMap<(String, String, EdgeType), CallResolution>,
+    #[serde(skip)]
+    call_sites: BTreeMap<(String, String, EdgeType), Vec<CallSite>>,
+    #[serde(skip)]
     allow_unverified_calls: bool,
 }
 
@@ -20,6 +24,8 @@ impl Graph for BTreeMapGraph {
         BTreeMapGraph {
             nodes: BTreeMap::new(),
             edges: BTreeSet::new(),
+            call_resolutions: BTreeMap::new(),
+            call_sites: BTreeMap::new(),
             allow_unverified_calls: false,
         }
     }
@@ -43,6 +49,18 @@ impl Graph for BTreeMapGraph {
     fn extend_graph(&mut self, other: Self) {
         self.nodes.extend(other.nodes);
         self.edges.extend(other.edges);
+        for (key, resolution) in other.call_resolutions {
+            let best = self
+                .call_resolutions
+                .entry(key)
+                .or_insert(resolution.clone());
+            if *best < resolution {
+                *best = resolution;
+            }
+        }
+        for (key, sites) in other.call_sites {
+            merge_call_sites(self.call_sites.entry(key).or_default(), &sites);
+        }
     }
 
     fn get_graph_size(&self) -> (u32, u32) {
@@ -51,8 +69,23 @@ impl Graph for BTreeMapGraph {
     fn add_edge(&mut self, edge: &Edge) {
         let source_key = create_node_key_from_ref(&edge.source);
         let target_key = create_node_key_from_ref(&edge.target);
-        self.edges
-            .insert((source_key, target_key, edge.edge.clone()));
+        let key = (source_key, target_key, edge.edge.clone());
+        if let Some(resolution) = &edge.resolution {
+            let best = self
+                .call_resolutions
+                .entry(key.clone())
+                .or_insert_with(|| resolution.clone());
+            if *best < *resolution {
+                *best = resolution.clone();
+            }
+        }
+        if !edge.call_sites.is_empty() {
+            merge_call_sites(
+                self.call_sites.entry(key.clone()).or_default(),
+                &edge.call_sites,
+            );
+        }
+        self.edges.insert(key);
     }
     fn add_node(&mut self, node_type: &NodeType, node_data: &NodeData) {
         let node = Node::new(node_type.clone(), node_data.clone());
@@ -153,9 +186,16 @@ impl Graph for BTreeMapGraph {
                 if final_filter.contains(&src_node.node_data.file)
                     || final_filter.contains(&dst_node.node_data.file)
                 {
-                    filtered
-                        .edges
-                        .insert((src.clone(), dst.clone(), edge_type.clone()));
+                    let key = (src.clone(), dst.clone(), edge_type.clone());
+                    if let Some(resolution) = self.call_resolutions.get(&key) {
+                        filtered
+                            .call_resolutions
+                            .insert(key.clone(), resolution.clone());
+                    }
+                    if let Some(sites) = self.call_sites.get(&key) {
+                        filtered.call_sites.insert(key.clone(), sites.clone());
+                    }
+                    filtered.edges.insert(key);
                 }
             }
         }
@@ -431,20 +471,9 @@ impl Graph for BTreeMapGraph {
                     self.add_edge(&edge);
                 }
             } else {
-                let edge_key = (
-                    fc.source.name.clone(),
-                    fc.source.file.clone(),
-                    fc.source.start,
-                    fc.target.name.clone(),
-                    fc.target.file.clone(),
-                    fc.target.start,
-                );
-
-                if !unique_edges.contains(&edge_key) {
-                    unique_edges.insert(edge_key);
-                    let edge = fc.clone().into();
-                    self.add_edge(&edge);
-                }
+                // Repeat calls to the same target fold their call sites into one edge
+                let edge = fc.clone().into();
+                self.add_edge(&edge);
             }
         }
 
@@ -495,20 +524,9 @@ impl Graph for BTreeMapGraph {
             } else if tc.target.is_empty() {
                 continue;
             } else {
-                let edge_key = (
-                    tc.source.name.clone(),
-                    tc.source.file.clone(),
-                    tc.source.start,
-                    tc.target.name.clone(),
-                    tc.target.file.clone(),
-                    tc.target.start,
-                );
-
-                if !unique_edges.contains(&edge_key) {
-                    unique_edges.insert(edge_key);
-                    let edge = Edge::from_test_call(&tc, lang, self);
-                    self.add_edge(&edge);
-                }
+                // Repeat calls to the same target fold their call sites into one edge
+                let edge = Edge::from_test_call(&tc, lang, self);
+                self.add_edge(&edge);
             }
         }
 
@@ -1034,15 +1052,49 @@ impl Graph for BTreeMapGraph {
             .filter_map(|(src_key, dst_key, edge_type)| {
                 let src_node = self.nodes.get(src_key)?;
                 let dst_node = self.nodes.get(dst_key)?;
-                Some(Edge::new(
+                let mut edge = Edge::new(
                     edge_type.clone(),
                     NodeRef::from((&src_node.node_data).into(), src_node.node_type.clone()),
                     NodeRef::from((&dst_node.node_data).into(), dst_node.node_type.clone()),
-                ))
+                );
+                edge.resolution = self.get_call_resolution(src_key, dst_key, edge_type);
+                edge.call_sites = self.get_call_sites(src_key, dst_key, edge_type);
+                Some(edge)
             })
             .collect()
     }
 
+    fn get_call_resolution(
+        &self,
+        source_key: &str,
+        target_key: &str,
+        edge_type: &EdgeType,
+    ) -> Option<CallResolution> {
+        self.call_resolutions
+            .get(&(
+                source_key.to_string(),
+                target_key.to_string(),
+                edge_type.clone(),
+            ))
+            .cloned()
+    }
+
+    fn get_call_sites(
+        &self,
+        source_key: &str,
+        target_key: &str,
+        edge_type: &EdgeType,
+    ) -> Vec<CallSite> {
+        self.call_sites
+            .get(&(
+                source_key.to_string(),
+                target_key.to_string(),
+                edge_type.clone(),
+            ))
+            .cloned()
+            .unwrap_or_default()
+    }
+
     fn set_allow_unverified_calls(&mut self, allow: bool) {
         self.allow_unverified_calls = allow;
     }
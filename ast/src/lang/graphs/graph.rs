@@ -6,7 +6,7 @@ use shared::Result;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::Debug;
 
-use super::{EdgeType, NodeData, NodeKeys};
+use super::{CallResolution, CallSite, EdgeType, NodeData, NodeKeys};
 
 pub trait Graph: Default + Debug {
     fn new(_root: String, _lang_kind: Language) -> Self
@@ -52,6 +52,24 @@ pub trait Graph: Default + Debug {
     fn get_edge_keys(&self) -> BTreeSet<(String, String, EdgeType)> {
         BTreeSet::new()
     }
+    /// Resolver outcome recorded for the edge with these keys, if it is a resolved call.
+    fn get_call_resolution(
+        &self,
+        _source_key: &str,
+        _target_key: &str,
+        _edge_type: &EdgeType,
+    ) -> Option<CallResolution> {
+        None
+    }
+    /// Call sites aggregated on the edge with these keys; empty for non-call edges.
+    fn get_call_sites(
+        &self,
+        _source_key: &str,
+        _target_key: &str,
+        _edge_type: &EdgeType,
+    ) -> Vec<CallSite> {
+        Vec::new()
+    }
 
     fn iter_all_nodes(&self) -> Box<dyn Iterator<Item = (&NodeType, &NodeData)> + '_> {
         Box::new(std::iter::empty())
@@ -107,6 +107,18 @@ impl GraphOps {
     ) -> Result<(u32, u32)> {
         let revs = vec![stored_hash.to_string(), current_hash.to_string()];
         let repo_path = Repo::get_path_from_url(repo_url)?;
+        // Graphs written before stable keys existed need them before any state
+        // can be carried across the rebuild below. Once migrated, only the cheap
+        // existence check runs.
+        match self.graph.has_nodes_missing_stable_key().await {
+            Ok(true) => {
+                if let Err(e) = self.graph.backfill_stable_keys().await {
+                    tracing::warn!("Error backfilling stable keys: {:?}", e);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => tracing::warn!("Error checking for missing stable keys: {:?}", e),
+        }
         if let Some(modified_files) = check_revs_files(&repo_path, revs.clone()) {
             info!(
                 "Processing {} changed files between commits",
@@ -143,6 +155,7 @@ impl GraphOps {
                     .collect();
 
                 let muted_nodes = self.collect_muted_nodes_for_files(&absolute_files).await?;
+                let node_states = self.graph.get_node_state_for_files(&absolute_files).await?;
 
                 info!(
                     "[incremental] removing existing nodes for {} modified file(s)",
@@ -185,6 +198,14 @@ impl GraphOps {
                     info!("[incremental] restoring {} muted nodes", muted_nodes.len());
                     self.restore_muted_nodes(muted_nodes).await?;
                 }
+
+                if !node_states.is_empty() {
+                    let restored = self.graph.restore_node_state(node_states).await?;
+                    info!(
+                        "[incremental] restored descriptions/embeddings on {} unchanged nodes",
+                        restored
+                    );
+                }
             }
             info!("Setting Data_Bank property for nodes missing it...");
             if let Err(e) = self.graph.set_missing_data_bank().await {
@@ -202,6 +223,7 @@ impl GraphOps {
         } else if !current_hash.is_empty() {
             let mut preserved_muted = Vec::new();
             let mut preserved_dynamic = Vec::new();
+            let mut preserved_states = Vec::new();
             if stored_hash.is_empty() {
                 info!("Processing new repository with hash: {}", current_hash);
             } else {
@@ -220,6 +242,7 @@ impl GraphOps {
                     .collect();
                 preserved_muted = self.collect_muted_nodes_for_files(&repo_files).await?;
                 preserved_dynamic = self.graph.get_all_dynamic_edges().await?;
+                preserved_states = self.graph.get_node_state_for_files(&repo_files).await?;
                 self.graph.clear_existing_graph(&graph_root).await?;
             }
             let mut repos = Repo::new_clone_multi_detect(
@@ -257,6 +280,14 @@ impl GraphOps {
                 self.restore_muted_nodes(preserved_muted).await?;
             }
 
+            if !preserved_states.is_empty() {
+                let restored = self.graph.restore_node_state(preserved_states).await?;
+                info!(
+                    "[full re-index] restored descriptions/embeddings on {} unchanged nodes",
+                    restored
+                );
+            }
+
             info!("Setting Data_Bank property for nodes missing it...");
             if let Err(e) = self.graph.set_missing_data_bank().await {
                 tracing::warn!("Error setting Data_Bank property: {:?}", e);
@@ -398,7 +429,16 @@ impl GraphOps {
         let edges_with_ref_ids = btree_graph.edges.iter().map(|(src, tgt, et)| {
             use uuid::Uuid;
             let ref_id = Uuid::new_v4().to_string();
-            (src.clone(), tgt.clone(), et.clone(), ref_id)
+            let resolution = btree_graph.get_call_resolution(src, tgt, et);
+            let call_sites = btree_graph.get_call_sites(src, tgt, et);
+            (
+                src.clone(),
+                tgt.clone(),
+                et.clone(),
+                ref_id,
+                resolution,
+                call_sites,
+            )
         });
         let edge_queries = build_batch_edge_queries(edges_with_ref_ids, 256);
 
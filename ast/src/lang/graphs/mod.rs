@@ -53,6 +53,55 @@ pub enum NodeType {
     #[serde(rename = "E2etest")]
     E2eTest,
     Mock,
+    Resource,
+    Service,
+    Workflow,
+    WorkflowJob,
+    WorkflowStep,
+    Queue,
+    Job,
+    Command,
+    Channel,
+    ConfigKey,
+    Module,
+}
+
+impl NodeType {
+    /// Every node type, in declaration order.
+    pub const ALL: [NodeType; 32] = [
+        NodeType::Repository,
+        NodeType::Package,
+        NodeType::Language,
+        NodeType::Directory,
+        NodeType::File,
+        NodeType::Import,
+        NodeType::Library,
+        NodeType::Class,
+        NodeType::Trait,
+        NodeType::Instance,
+        NodeType::Function,
+        NodeType::Endpoint,
+        NodeType::Request,
+        NodeType::DataModel,
+        NodeType::Concept,
+        NodeType::Page,
+        NodeType::Var,
+        NodeType::UnitTest,
+        NodeType::IntegrationTest,
+        NodeType::E2eTest,
+        NodeType::Mock,
+        NodeType::Resource,
+        NodeType::Service,
+        NodeType::Workflow,
+        NodeType::WorkflowJob,
+        NodeType::WorkflowStep,
+        NodeType::Queue,
+        NodeType::Job,
+        NodeType::Command,
+        NodeType::Channel,
+        NodeType::ConfigKey,
+        NodeType::Module,
+    ];
 }
 
 // pub enum TestType {
@@ -75,6 +124,86 @@ pub struct Edge {
     pub ref_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub operand: Option<String>,
+    #[serde(flatten)]
+    pub resolution: Option<CallResolution>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub call_sites: Vec<CallSite>,
+}
+
+/// How the target of a CALLS edge was resolved: `confidence` in `0.0..=1.0` and the
+/// resolver `strategy` that produced it (`lsp`, `type_resolved`, `import`, `same_dir`, ...).
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CallResolution {
+    pub confidence: f32,
+    pub strategy: String,
+}
+
+impl CallResolution {
+    pub fn new(confidence: f32, strategy: &str) -> Self {
+        Self {
+            confidence,
+            strategy: strategy.to_string(),
+        }
+    }
+}
+
+impl Eq for CallResolution {}
+
+impl PartialOrd for CallResolution {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CallResolution {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.confidence
+            .total_cmp(&other.confidence)
+            .then_with(|| self.strategy.cmp(&other.strategy))
+    }
+}
+
+/// Where a CALLS edge is made in the caller: zero-based `line`/`column` of the callee
+/// name (like `NodeData::start`) and the number of arguments, when the grammar exposes them.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct CallSite {
+    pub line: usize,
+    pub column: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arg_count: Option<usize>,
+}
+
+impl CallSite {
+    pub fn new(line: usize, column: usize, arg_count: Option<usize>) -> Self {
+        Self {
+            line,
+            column,
+            arg_count,
+        }
+    }
+    /// Compact `line:column[:args]` form used for the `call_sites` relationship property.
+    pub fn to_property(&self) -> String {
+        match self.arg_count {
+            Some(n) => format!("{}:{}:{}", self.line, self.column, n),
+            None => format!("{}:{}", self.line, self.column),
+        }
+    }
+}
+
+/// Keep whichever of two resolutions of the same edge is the more confident.
+pub fn merge_resolution(into: &mut Option<CallResolution>, other: &Option<CallResolution>) {
+    if let Some(other) = other {
+        if into.as_ref().is_none_or(|r| r < other) {
+            *into = Some(other.clone());
+        }
+    }
+}
+
+/// Merge `sites` into `into`, keeping the list sorted and free of duplicates.
+pub fn merge_call_sites(into: &mut Vec<CallSite>, sites: &[CallSite]) {
+    into.extend(sites.iter().cloned());
+    into.sort();
+    into.dedup();
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord, Hash)]
@@ -96,6 +225,19 @@ pub enum EdgeType {
     Implements, // Class -> Trait
     #[serde(rename = "NESTED_IN")]
     NestedIn, // Function -> Function (child to parent)
+    #[serde(rename = "DEPENDS_ON")]
+    DependsOn, // Resource -> Resource, Service -> Service, WorkflowJob -> WorkflowJob
+    #[serde(rename = "CONNECTS_TO")]
+    ConnectsTo, // Service -> Service
+    Runs, // WorkflowStep -> File, Directory, WorkflowJob
+    Publishes, // Function -> Queue
+    Consumes, // Function -> Queue
+    Emits, // Function -> Channel
+    Reads, // Function -> DataModel, ConfigKey
+    Writes, // Function -> DataModel
+    Overrides, // Function -> Function (implementation to base or trait method)
+    Throws, // Function -> Class, DataModel, Var (error types raised directly)
+    Catches, // Function -> Class, DataModel, Var (error types handled from callees)
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord)]
@@ -130,12 +272,32 @@ impl Edge {
             target,
             ref_id: Uuid::new_v4().to_string(),
             operand: None,
+            resolution: None,
+            call_sites: Vec::new(),
         }
     }
     pub fn with_operand(mut self, operand: Option<String>) -> Self {
         self.operand = operand;
         self
     }
+    /// Attach the resolver outcome of `call`; calls that never went through a
+    /// resolver (empty strategy) are left without one.
+    pub fn with_resolution_of(mut self, call: &Calls) -> Self {
+        if !call.strategy.is_empty() {
+            self.resolution = Some(CallResolution::new(call.confidence, &call.strategy));
+        }
+        self
+    }
+    /// Record the call site of `call` on this edge, if the parser captured one.
+    pub fn with_call_site_of(mut self, call: &Calls) -> Self {
+        if let Some(site) = &call.call_site {
+            merge_call_sites(&mut self.call_sites, std::slice::from_ref(site));
+        }
+        self
+    }
+    pub fn confidence(&self) -> Option<f32> {
+        self.resolution.as_ref().map(|r| r.confidence)
+    }
     pub fn from_test_call(call: &Calls, lang: &Lang, graph: &impl Graph) -> Edge {
         // Look up test node to get body for language-specific classification
         let test_node = graph.find_test_node(&call.source.name, &call.source.file);
@@ -149,6 +311,8 @@ impl Edge {
         let mut tgt_nd = NodeData::name_file(&call.target.name, &call.target.file);
         tgt_nd.start = call.target.start;
         Edge::test_calls(tt, &src_nd, NodeType::Function, &tgt_nd)
+            .with_resolution_of(call)
+            .with_call_site_of(call)
     }
 
     pub fn from_test_class_call(
@@ -291,6 +455,118 @@ impl Edge {
             NodeRef::from(tr.into(), NodeType::Trait),
         )
     }
+    pub fn depends_on(nt1: NodeType, s: &NodeData, nt2: NodeType, t: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::DependsOn,
+            NodeRef::from(s.into(), nt1),
+            NodeRef::from(t.into(), nt2),
+        )
+    }
+    pub fn publishes(f: &NodeData, q: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Publishes,
+            NodeRef::from(f.into(), NodeType::Function),
+            NodeRef::from(q.into(), NodeType::Queue),
+        )
+    }
+    pub fn consumes(f: &NodeData, q: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Consumes,
+            NodeRef::from(f.into(), NodeType::Function),
+            NodeRef::from(q.into(), NodeType::Queue),
+        )
+    }
+    pub fn emits(f: &NodeData, c: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Emits,
+            NodeRef::from(f.into(), NodeType::Function),
+            NodeRef::from(c.into(), NodeType::Channel),
+        )
+    }
+    pub fn reads(f: &NodeData, nt: NodeType, t: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Reads,
+            NodeRef::from(f.into(), NodeType::Function),
+            NodeRef::from(t.into(), nt),
+        )
+    }
+    pub fn writes(f: &NodeData, nt: NodeType, t: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Writes,
+            NodeRef::from(f.into(), NodeType::Function),
+            NodeRef::from(t.into(), nt),
+        )
+    }
+    pub fn overrides(method: &NodeData, base: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Overrides,
+            NodeRef::from(method.into(), NodeType::Function),
+            NodeRef::from(base.into(), NodeType::Function),
+        )
+    }
+    pub fn throws(f: &NodeData, nt: NodeType, error: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Throws,
+            NodeRef::from(f.into(), NodeType::Function),
+            NodeRef::from(error.into(), nt),
+        )
+    }
+    pub fn catches(f: &NodeData, nt: NodeType, error: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Catches,
+            NodeRef::from(f.into(), NodeType::Function),
+            NodeRef::from(error.into(), nt),
+        )
+    }
+    pub fn resource_handler(r: &NodeData, f: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Handler,
+            NodeRef::from(r.into(), NodeType::Resource),
+            NodeRef::from(f.into(), NodeType::Function),
+        )
+    }
+    pub fn service_handler(s: &NodeData, f: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Handler,
+            NodeRef::from(s.into(), NodeType::Service),
+            NodeRef::from(f.into(), NodeType::Function),
+        )
+    }
+    pub fn job_handler(j: &NodeData, f: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Handler,
+            NodeRef::from(j.into(), NodeType::Job),
+            NodeRef::from(f.into(), NodeType::Function),
+        )
+    }
+    pub fn command_handler(c: &NodeData, f: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Handler,
+            NodeRef::from(c.into(), NodeType::Command),
+            NodeRef::from(f.into(), NodeType::Function),
+        )
+    }
+    pub fn channel_handler(c: &NodeData, f: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Handler,
+            NodeRef::from(c.into(), NodeType::Channel),
+            NodeRef::from(f.into(), NodeType::Function),
+        )
+    }
+    pub fn connects_to(s: &NodeData, t: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::ConnectsTo,
+            NodeRef::from(s.into(), NodeType::Service),
+            NodeRef::from(t.into(), NodeType::Service),
+        )
+    }
+    pub fn runs(step: &NodeData, nt: NodeType, t: &NodeData) -> Edge {
+        Edge::new(
+            EdgeType::Runs,
+            NodeRef::from(step.into(), NodeType::WorkflowStep),
+            NodeRef::from(t.into(), nt),
+        )
+    }
 }
 
 impl From<Operand> for Edge {
@@ -307,9 +583,11 @@ impl From<Calls> for Edge {
     fn from(m: Calls) -> Self {
         Edge::new(
             EdgeType::Calls,
-            NodeRef::from(m.source, NodeType::Function),
-            NodeRef::from(m.target, NodeType::Function),
+            NodeRef::from(m.source.clone(), NodeType::Function),
+            NodeRef::from(m.target.clone(), NodeType::Function),
         )
+        .with_resolution_of(&m)
+        .with_call_site_of(&m)
         .with_operand(m.operand)
     }
 }
@@ -352,6 +630,17 @@ impl std::fmt::Display for EdgeType {
             EdgeType::Calls => "CALLS",
             EdgeType::Implements => "IMPLEMENTS",
             EdgeType::NestedIn => "NESTED_IN",
+            EdgeType::DependsOn => "DEPENDS_ON",
+            EdgeType::ConnectsTo => "CONNECTS_TO",
+            EdgeType::Runs => "RUNS",
+            EdgeType::Publishes => "PUBLISHES",
+            EdgeType::Consumes => "CONSUMES",
+            EdgeType::Emits => "EMITS",
+            EdgeType::Reads => "READS",
+            EdgeType::Writes => "WRITES",
+            EdgeType::Overrides => "OVERRIDES",
+            EdgeType::Throws => "THROWS",
+            EdgeType::Catches => "CATCHES",
         };
         write!(f, "{}", s)
     }
@@ -374,7 +663,31 @@ impl FromStr for EdgeType {
             "PARENT_OF" => Ok(EdgeType::ParentOf),
             "IMPLEMENTS" => Ok(EdgeType::Implements),
             "NESTED_IN" => Ok(EdgeType::NestedIn),
+            "DEPENDS_ON" => Ok(EdgeType::DependsOn),
+            "CONNECTS_TO" => Ok(EdgeType::ConnectsTo),
+            "RUNS" => Ok(EdgeType::Runs),
+            "PUBLISHES" => Ok(EdgeType::Publishes),
+            "CONSUMES" => Ok(EdgeType::Consumes),
+            "EMITS" => Ok(EdgeType::Emits),
+            "READS" => Ok(EdgeType::Reads),
+            "WRITES" => Ok(EdgeType::Writes),
+            "OVERRIDES" => Ok(EdgeType::Overrides),
+            "THROWS" => Ok(EdgeType::Throws),
+            "CATCHES" => Ok(EdgeType::Catches),
             _ => Err(Error::validation(format!("Invalid EdgeType: {}", s))),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_type_labels_round_trip() {
+        for node_type in NodeType::ALL {
+            let label = node_type.to_string();
+            assert_eq!(NodeType::from_str(&label).ok(), Some(node_type), "{label}");
+        }
+    }
+}
@@ -54,6 +54,8 @@ impl Neo4jGraph {
         let queries = vec![
             "CREATE INDEX data_bank_node_key_index IF NOT EXISTS FOR (n:Data_Bank) ON (n.node_key)",
             "CREATE INDEX data_bank_ref_id_index IF NOT EXISTS FOR (n:Data_Bank) ON (n.ref_id)",
+            // Line-independent identity used to restore state after re-ingest.
+            "CREATE INDEX data_bank_stable_key_index IF NOT EXISTS FOR (n:Data_Bank) ON (n.stable_key)",
             // Range index on `file` so incremental sync deletions
             // (`remove_nodes_by_files_query` -> `n.file IN $files`) and other
             // file-scoped lookups can do an index seek instead of a full
@@ -166,11 +166,13 @@ fn extract_muted_identifier(row: &neo4rs::Row) -> Result<MutedNodeIdentifier> {
     let name = row.get::<String>("name")?;
     let node_type_str = row.get::<String>("node_type")?;
     let file = row.get::<String>("file")?;
+    let stable_key = row.get::<String>("stable_key").ok();
     let node_type = NodeType::from_str(&node_type_str)?;
     Ok(MutedNodeIdentifier {
         node_type,
         name,
         file,
+        stable_key,
     })
 }
 
@@ -4,7 +4,7 @@ use crate::lang::graphs::{
 use crate::lang::{
     asg::TestRecord, Calls, Edge, EdgeType, Graph, NodeData, NodeKeys, NodeType, TestFilters,
 };
-use crate::utils::{create_node_key, sync_fn};
+use crate::utils::{create_node_key, create_stable_key, sync_fn};
 use crate::{lang::Function, lang::Node, Lang};
 use lsp::Language;
 use neo4rs::{query, BoltMap, Graph as Neo4jConnection};
@@ -753,7 +753,9 @@ impl Neo4jGraph {
             let full_path = format!("{}{}", prefix, endpoint.name);
             let mut new_node_data = endpoint.clone();
             new_node_data.name = full_path.clone();
-            let new_key = create_node_key(&Node::new(NodeType::Endpoint, new_node_data));
+            let new_node = Node::new(NodeType::Endpoint, new_node_data);
+            let new_key = create_node_key(&new_node);
+            let new_stable_key = create_stable_key(&new_node);
 
             let endpoint_verb = endpoint.meta.get("verb").map(|v| v.as_str());
             let mut update_query = String::from(
@@ -762,7 +764,9 @@ impl Neo4jGraph {
             if endpoint_verb.is_some() {
                 update_query.push_str(" AND e.verb = $verb");
             }
-            update_query.push_str(" SET e.name = $new_name, e.node_key = $new_key RETURN e.name");
+            update_query.push_str(
+                " SET e.name = $new_name, e.node_key = $new_key, e.stable_key = $new_stable_key RETURN e.name",
+            );
 
             let mut params = BoltMap::new();
             boltmap_insert_str(&mut params, "old_name", &endpoint.name);
@@ -770,6 +774,7 @@ impl Neo4jGraph {
             boltmap_insert_int(&mut params, "start", endpoint.start as i64);
             boltmap_insert_str(&mut params, "new_name", &full_path);
             boltmap_insert_str(&mut params, "new_key", &new_key);
+            boltmap_insert_str(&mut params, "new_stable_key", &new_stable_key);
             if let Some(verb) = endpoint_verb {
                 boltmap_insert_str(&mut params, "verb", verb);
             }
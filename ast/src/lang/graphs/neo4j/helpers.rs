@@ -17,6 +17,19 @@ pub struct MutedNodeIdentifier {
     pub node_type: NodeType,
     pub name: String,
     pub file: String,
+    pub stable_key: Option<String>,
+}
+
+/// An edge from a non-code node (Hint, Prompt, ...) into a code node, kept aside
+/// while the target's file is re-ingested.
+#[derive(Debug, Clone)]
+pub struct DynamicEdge {
+    pub source_ref_id: String,
+    pub edge_type: String,
+    pub target_name: String,
+    pub target_file: String,
+    pub target_type: String,
+    pub target_stable_key: Option<String>,
 }
 
 pub fn boltmap_insert_str(map: &mut BoltMap, key: &str, value: &str) {
@@ -1,11 +1,13 @@
 use crate::lang::graphs::{
     executor::{with_transient_retry_reconnect, TransactionManager},
-    helpers::{boltmap_insert_list, boltmap_insert_str},
+    helpers::{boltmap_insert_int, boltmap_insert_list, boltmap_insert_str, DynamicEdge},
     queries::*,
-    Edge, EdgeType, Neo4jGraph, NodeData, NodeKeys, NodeRef, NodeType,
+    Edge, EdgeType, Neo4jGraph, Node, NodeData, NodeKeys, NodeRef, NodeType,
 };
-use neo4rs::{query, BoltMap};
+use crate::utils::create_stable_key;
+use neo4rs::{query, BoltFloat, BoltMap, BoltType};
 use shared::{Error, Result};
+use std::collections::HashMap;
 use tracing::{info, warn};
 use std::str::FromStr;
 
@@ -179,10 +181,7 @@ impl Neo4jGraph {
         Ok(())
     }
 
-    pub async fn get_dynamic_edges_for_file(
-        &self,
-        file: &str,
-    ) -> Result<Vec<(String, String, String, String, String)>> {
+    pub async fn get_dynamic_edges_for_file(&self, file: &str) -> Result<Vec<DynamicEdge>> {
         let connection = self.ensure_connected().await?;
         let (query_str, params) = find_dynamic_edges_for_file_query(file);
         let mut query_obj = query(&query_str);
@@ -192,34 +191,14 @@ impl Neo4jGraph {
         let mut edges = Vec::new();
         let mut result = connection.execute(query_obj).await?;
         while let Some(row) = result.next().await? {
-            if let (
-                Ok(source_ref_id),
-                Ok(edge_type),
-                Ok(target_name),
-                Ok(target_file),
-                Ok(target_type),
-            ) = (
-                row.get::<String>("source_ref_id"),
-                row.get::<String>("edge_type"),
-                row.get::<String>("target_name"),
-                row.get::<String>("target_file"),
-                row.get::<String>("target_type"),
-            ) {
-                edges.push((
-                    source_ref_id,
-                    edge_type,
-                    target_name,
-                    target_file,
-                    target_type,
-                ));
+            if let Some(edge) = dynamic_edge_from_row(&row) {
+                edges.push(edge);
             }
         }
         Ok(edges)
     }
 
-    pub async fn get_all_dynamic_edges(
-        &self,
-    ) -> Result<Vec<(String, String, String, String, String)>> {
+    pub async fn get_all_dynamic_edges(&self) -> Result<Vec<DynamicEdge>> {
         let connection = self.ensure_connected().await?;
         let (query_str, params) = find_all_dynamic_edges_query();
         let mut query_obj = query(&query_str);
@@ -229,35 +208,14 @@ impl Neo4jGraph {
         let mut edges = Vec::new();
         let mut result = connection.execute(query_obj).await?;
         while let Some(row) = result.next().await? {
-            if let (
-                Ok(source_ref_id),
-                Ok(edge_type),
-                Ok(target_name),
-                Ok(target_file),
-                Ok(target_type),
-            ) = (
-                row.get::<String>("source_ref_id"),
-                row.get::<String>("edge_type"),
-                row.get::<String>("target_name"),
-                row.get::<String>("target_file"),
-                row.get::<String>("target_type"),
-            ) {
-                edges.push((
-                    source_ref_id,
-                    edge_type,
-                    target_name,
-                    target_file,
-                    target_type,
-                ));
+            if let Some(edge) = dynamic_edge_from_row(&row) {
+                edges.push(edge);
             }
         }
         Ok(edges)
     }
 
-    pub async fn restore_dynamic_edges(
-        &self,
-        edges: Vec<(String, String, String, String, String)>,
-    ) -> Result<usize> {
+    pub async fn restore_dynamic_edges(&self, edges: Vec<DynamicEdge>) -> Result<usize> {
         if edges.is_empty() {
             return Ok(0);
         }
@@ -265,14 +223,8 @@ impl Neo4jGraph {
         let connection = self.ensure_connected().await?;
         let mut restored_count = 0;
 
-        for (source_ref_id, edge_type, target_name, target_file, target_type) in edges {
-            let (query_str, params) = restore_dynamic_edge_query(
-                &source_ref_id,
-                &edge_type,
-                &target_name,
-                &target_file,
-                &target_type,
-            );
+        for edge in edges {
+            let (query_str, params) = restore_dynamic_edge_query(&edge);
             let mut query_obj = query(&query_str);
             for (k, v) in params.value.iter() {
                 query_obj = query_obj.param(k.value.as_str(), v.clone());
@@ -287,7 +239,7 @@ impl Neo4jGraph {
                 Err(e) => {
                     warn!(
                         "Failed to restore edge {} -> {} -> {}:{}: {}",
-                        source_ref_id, edge_type, target_type, target_name, e
+                        edge.source_ref_id, edge.edge_type, edge.target_type, edge.target_name, e
                     );
                 }
             }
@@ -295,6 +247,177 @@ impl Neo4jGraph {
 
         Ok(restored_count)
     }
+
+    /// Snapshot the descriptions and embeddings of nodes in `files` so they can be
+    /// put back after the files are deleted and re-ingested.
+    pub async fn get_node_state_for_files(&self, files: &[String]) -> Result<Vec<NodeState>> {
+        let connection = self.ensure_connected().await?;
+        let (query_str, params) = get_node_state_for_files_query(files);
+        let mut query_obj = query(&query_str);
+        for (k, v) in params.value.iter() {
+            query_obj = query_obj.param(k.value.as_str(), v.clone());
+        }
+        let mut states = Vec::new();
+        let mut result = connection.execute(query_obj).await?;
+        while let Some(row) = result.next().await? {
+            let Ok(stable_key) = row.get::<String>("stable_key") else {
+                continue;
+            };
+            states.push(NodeState {
+                stable_key,
+                body_hash: sha256::digest(row.get::<String>("body").unwrap_or_default()),
+                description: row.get::<String>("description").ok(),
+                embeddings: row.get::<Vec<f64>>("embeddings").ok(),
+            });
+        }
+        Ok(states)
+    }
+
+    /// Put snapshotted state back on the re-ingested nodes with the same stable key.
+    /// State is only restored where the body is unchanged, since a description or
+    /// embedding of an edited body is stale.
+    pub async fn restore_node_state(&self, states: Vec<NodeState>) -> Result<usize> {
+        if states.is_empty() {
+            return Ok(0);
+        }
+
+        let connection = self.ensure_connected().await?;
+        let keys: Vec<String> = states.iter().map(|s| s.stable_key.clone()).collect();
+        let (query_str, params) = get_bodies_by_stable_keys_query(&keys);
+        let mut query_obj = query(&query_str);
+        for (k, v) in params.value.iter() {
+            query_obj = query_obj.param(k.value.as_str(), v.clone());
+        }
+        let mut current_hashes = HashMap::new();
+        let mut result = connection.execute(query_obj).await?;
+        while let Some(row) = result.next().await? {
+            if let Ok(stable_key) = row.get::<String>("stable_key") {
+                let body = row.get::<String>("body").unwrap_or_default();
+                current_hashes.insert(stable_key, sha256::digest(body));
+            }
+        }
+
+        let unchanged: Vec<NodeState> = states
+            .into_iter()
+            .filter(|s| current_hashes.get(&s.stable_key) == Some(&s.body_hash))
+            .collect();
+        if unchanged.is_empty() {
+            return Ok(0);
+        }
+
+        let (query_str, params) = restore_node_state_query(&unchanged);
+        let mut query_obj = query(&query_str);
+        for (k, v) in params.value.iter() {
+            query_obj = query_obj.param(k.value.as_str(), v.clone());
+        }
+        let mut result = connection.execute(query_obj).await?;
+        let restored = match result.next().await? {
+            Some(row) => row.get::<i64>("restored_count").unwrap_or(0) as usize,
+            None => 0,
+        };
+        Ok(restored)
+    }
+
+    /// Whether any code node still lacks a `stable_key`, i.e. the graph predates
+    /// stable keys and `backfill_stable_keys` has not run on it yet.
+    pub async fn has_nodes_missing_stable_key(&self) -> Result<bool> {
+        let connection = self.ensure_connected().await?;
+        let (query_str, params) = any_node_missing_stable_key_query();
+        let mut query_obj = query(&query_str);
+        for (k, v) in params.value.iter() {
+            query_obj = query_obj.param(k.value.as_str(), v.clone());
+        }
+        let mut result = connection.execute(query_obj).await?;
+        Ok(result.next().await?.is_some())
+    }
+
+    /// Migration for graphs written before nodes carried a `stable_key`: computes
+    /// the key for every code node that lacks one, and drops the `return_type` /
+    /// `param_types` meta that `signature` replaced. Safe to run repeatedly. Pages by
+    /// `node_key` so nodes that can't be converted are skipped instead of refetched.
+    pub async fn backfill_stable_keys(&self) -> Result<usize> {
+        let connection = self.ensure_connected().await?;
+        let mut total = 0;
+        let mut after = String::new();
+        loop {
+            let (query_str, params) =
+                find_nodes_missing_stable_key_query(&after, STABLE_KEY_BACKFILL_BATCH);
+            let mut query_obj = query(&query_str);
+            for (k, v) in params.value.iter() {
+                query_obj = query_obj.param(k.value.as_str(), v.clone());
+            }
+            let mut rows = 0;
+            let mut updates = Vec::new();
+            let mut result = connection.execute(query_obj).await?;
+            while let Some(row) = result.next().await? {
+                let Ok(node_key) = row.get::<String>("node_key") else {
+                    continue;
+                };
+                rows += 1;
+                after = node_key.clone();
+                let (Ok(node), Ok(label)) =
+                    (row.get::<neo4rs::Node>("n"), row.get::<String>("node_type"))
+                else {
+                    continue;
+                };
+                let Ok(node_type) = NodeType::from_str(&label) else {
+                    continue;
+                };
+                let node_data = match NodeData::try_from(&node) {
+                    Ok(node_data) => node_data,
+                    Err(e) => {
+                        warn!("Skipping stable_key backfill for {}: {:?}", node_key, e);
+                        continue;
+                    }
+                };
+                let stable_key = create_stable_key(&Node::new(node_type, node_data));
+                updates.push((node_key, stable_key));
+            }
+            if rows == 0 {
+                break;
+            }
+            if updates.is_empty() {
+                continue;
+            }
+
+            let (query_str, params) = set_stable_keys_query(&updates);
+            let mut query_obj = query(&query_str);
+            for (k, v) in params.value.iter() {
+                query_obj = query_obj.param(k.value.as_str(), v.clone());
+            }
+            let mut result = connection.execute(query_obj).await?;
+            total += match result.next().await? {
+                Some(row) => row.get::<i64>("updated_count").unwrap_or(0) as usize,
+                None => 0,
+            };
+        }
+        if total > 0 {
+            info!("Backfilled stable_key for {} nodes", total);
+        }
+        Ok(total)
+    }
+}
+
+const STABLE_KEY_BACKFILL_BATCH: usize = 1000;
+
+/// Description and embeddings of a node, keyed by its stable identity.
+#[derive(Debug, Clone)]
+pub struct NodeState {
+    pub stable_key: String,
+    pub body_hash: String,
+    pub description: Option<String>,
+    pub embeddings: Option<Vec<f64>>,
+}
+
+fn dynamic_edge_from_row(row: &neo4rs::Row) -> Option<DynamicEdge> {
+    Some(DynamicEdge {
+        source_ref_id: row.get::<String>("source_ref_id").ok()?,
+        edge_type: row.get::<String>("edge_type").ok()?,
+        target_name: row.get::<String>("target_name").ok()?,
+        target_file: row.get::<String>("target_file").ok()?,
+        target_type: row.get::<String>("target_type").ok()?,
+        target_stable_key: row.get::<String>("target_stable_key").ok(),
+    })
 }
 
 pub fn get_repository_hash_query(repo_url: &str) -> (String, BoltMap) {
@@ -416,12 +539,14 @@ pub fn update_endpoint_name_query(
     verb: Option<&str>,
     new_name: &str,
     new_key: &str,
+    new_stable_key: &str,
 ) -> (String, BoltMap) {
     let mut params = BoltMap::new();
     boltmap_insert_str(&mut params, "old_name", old_name);
     boltmap_insert_str(&mut params, "file", file);
     boltmap_insert_str(&mut params, "new_name", new_name);
     boltmap_insert_str(&mut params, "new_key", new_key);
+    boltmap_insert_str(&mut params, "new_stable_key", new_stable_key);
     let verb_clause = if let Some(v) = verb {
         boltmap_insert_str(&mut params, "verb", v);
         " AND n.verb = $verb"
@@ -430,7 +555,7 @@ pub fn update_endpoint_name_query(
     };
 
     let query = format!(
-        "MATCH (n:Endpoint {{name: $old_name, file: $file}}){}\n                 SET n.name = $new_name, n.node_key = $new_key\n                 RETURN n",
+        "MATCH (n:Endpoint {{name: $old_name, file: $file}}){}\n                 SET n.name = $new_name, n.node_key = $new_key, n.stable_key = $new_stable_key\n                 RETURN n",
         verb_clause
     );
 
@@ -492,3 +617,128 @@ pub fn set_default_namespace_query() -> String {
     "#
     .to_string()
 }
+
+pub fn get_node_state_for_files_query(files: &[String]) -> (String, BoltMap) {
+    let mut params = BoltMap::new();
+    let files_list = files
+        .iter()
+        .map(|f| BoltType::String(f.clone().into()))
+        .collect::<Vec<_>>();
+    boltmap_insert_list(&mut params, "files", files_list);
+
+    let query = "MATCH (n:Data_Bank)
+                 WHERE n.file IN $files
+                 AND n.stable_key IS NOT NULL
+                 AND (n.description IS NOT NULL OR n.embeddings IS NOT NULL)
+                 RETURN n.stable_key as stable_key, n.body as body,
+                        n.description as description, n.embeddings as embeddings";
+
+    (query.to_string(), params)
+}
+
+pub fn get_bodies_by_stable_keys_query(stable_keys: &[String]) -> (String, BoltMap) {
+    let mut params = BoltMap::new();
+    let keys = stable_keys
+        .iter()
+        .map(|k| BoltType::String(k.clone().into()))
+        .collect::<Vec<_>>();
+    boltmap_insert_list(&mut params, "stable_keys", keys);
+
+    let query = "MATCH (n:Data_Bank)
+                 WHERE n.stable_key IN $stable_keys
+                 RETURN n.stable_key as stable_key, n.body as body";
+
+    (query.to_string(), params)
+}
+
+pub fn restore_node_state_query(states: &[NodeState]) -> (String, BoltMap) {
+    let mut params = BoltMap::new();
+    let state_maps: Vec<BoltType> = states
+        .iter()
+        .map(|state| {
+            let mut map = BoltMap::new();
+            boltmap_insert_str(&mut map, "stable_key", &state.stable_key);
+            if let Some(description) = &state.description {
+                boltmap_insert_str(&mut map, "description", description);
+            }
+            if let Some(embeddings) = &state.embeddings {
+                let values = embeddings
+                    .iter()
+                    .map(|&value| BoltType::Float(BoltFloat { value }))
+                    .collect();
+                boltmap_insert_list(&mut map, "embeddings", values);
+            }
+            BoltType::Map(map)
+        })
+        .collect();
+    boltmap_insert_list(&mut params, "states", state_maps);
+
+    let query = "UNWIND $states as state
+                 MATCH (n:Data_Bank {stable_key: state.stable_key})
+                 SET n.description = coalesce(n.description, state.description),
+                     n.embeddings = coalesce(n.embeddings, state.embeddings)
+                 RETURN count(n) as restored_count";
+
+    (query.to_string(), params)
+}
+
+pub fn find_nodes_missing_stable_key_query(after: &str, limit: usize) -> (String, BoltMap) {
+    let mut params = BoltMap::new();
+    boltmap_insert_str(&mut params, "after", after);
+    boltmap_insert_int(&mut params, "limit", limit as i64);
+
+    let query = "MATCH (n:Data_Bank)
+                 WHERE n.stable_key IS NULL AND n.node_key > $after AND NOT n:Schema
+                 WITH n, [label IN labels(n) WHERE label IN $labels][0] as node_type
+                 WHERE node_type IS NOT NULL
+                 RETURN n, node_type, n.node_key as node_key
+                 ORDER BY n.node_key
+                 LIMIT $limit";
+    boltmap_insert_list(&mut params, "labels", stable_key_labels());
+
+    (query.to_string(), params)
+}
+
+pub fn any_node_missing_stable_key_query() -> (String, BoltMap) {
+    let mut params = BoltMap::new();
+    boltmap_insert_list(&mut params, "labels", stable_key_labels());
+
+    let query = "MATCH (n:Data_Bank)
+                 WHERE n.stable_key IS NULL AND NOT n:Schema
+                   AND any(label IN labels(n) WHERE label IN $labels)
+                 RETURN n.node_key as node_key
+                 LIMIT 1";
+
+    (query.to_string(), params)
+}
+
+/// Labels of every node type, whose stable key can be derived.
+fn stable_key_labels() -> Vec<BoltType> {
+    NodeType::ALL
+        .iter()
+        .map(|t| BoltType::String(t.to_string().into()))
+        .collect()
+}
+
+pub fn set_stable_keys_query(updates: &[(String, String)]) -> (String, BoltMap) {
+    let mut params = BoltMap::new();
+    let rows: Vec<BoltType> = updates
+        .iter()
+        .map(|(node_key, stable_key)| {
+            let mut map = BoltMap::new();
+            boltmap_insert_str(&mut map, "node_key", node_key);
+            boltmap_insert_str(&mut map, "stable_key", stable_key);
+            BoltType::Map(map)
+        })
+        .collect();
+    boltmap_insert_list(&mut params, "rows", rows);
+
+    let query = "UNWIND $rows as row
+                 MATCH (n:Data_Bank {node_key: row.node_key})
+                 WHERE n.stable_key IS NULL
+                 SET n.stable_key = row.stable_key
+                 REMOVE n.return_type, n.param_types
+                 RETURN count(n) as updated_count";
+
+    (query.to_string(), params)
+}
@@ -1,9 +1,13 @@
 use crate::lang::graphs::{
-    queries::add_node_query, Calls, Edge, EdgeType, Graph, Node, NodeData, NodeRef, NodeType,
+    queries::add_node_query, CallResolution, CallSite, Calls, Edge, EdgeType, Graph, Node,
+    NodeData, NodeRef, NodeType,
+};
+use crate::lang::helpers::{
+    boltmap_insert_float, boltmap_insert_list, boltmap_insert_list_of_maps, boltmap_insert_str,
+    DynamicEdge,
 };
-use crate::lang::helpers::{boltmap_insert_float, boltmap_insert_list_of_maps, boltmap_insert_str};
 use crate::utils::create_node_key_from_ref;
-use neo4rs::BoltMap;
+use neo4rs::{BoltMap, BoltType};
 
 pub struct EdgeQueryBuilder {
     edge: Edge,
@@ -27,19 +31,22 @@ impl EdgeQueryBuilder {
         let target_key = create_node_key_from_ref(&self.edge.target);
         boltmap_insert_str(&mut params, "target_key", &target_key);
         boltmap_insert_str(&mut params, "ref_id", &self.edge.ref_id);
+        let resolution_set = self.resolution_set(&mut params);
+        let call_sites_set = self.call_sites_set(&mut params);
 
         // println!(
         //     "[EdgeQueryBuilder] source_key: {}, target_key: {}",
         //     source_key, target_key
         // );
 
+        // edges are rebuilt on every ingest, so they match on node_key rather than stable_key
         let query = format!(
             "MATCH (source:{} {{node_key: $source_key}}),
                  (target:{} {{node_key: $target_key}})
             MERGE (source)-[r:{}]->(target)
-            SET r.ref_id = $ref_id
+            SET r.ref_id = $ref_id{}{}
             RETURN r",
-            source_type, target_type, rel_type
+            source_type, target_type, rel_type, resolution_set, call_sites_set
         );
         (query, params)
     }
@@ -57,16 +64,48 @@ impl EdgeQueryBuilder {
         let target_key = create_node_key_from_ref(&self.edge.target);
         boltmap_insert_str(&mut params, "target_key", &target_key);
         boltmap_insert_str(&mut params, "ref_id", &self.edge.ref_id);
+        let resolution_set = self.resolution_set(&mut params);
+        let call_sites_set = self.call_sites_set(&mut params);
 
         let query = format!(
             "MATCH (source:{} {{node_key: $source_key}}),
                  (target:{} {{node_key: $target_key}})
             MERGE (source)-[r:{}]->(target)
-            SET r.ref_id = $ref_id",
-            source_type, target_type, rel_type
+            SET r.ref_id = $ref_id{}{}",
+            source_type, target_type, rel_type, resolution_set, call_sites_set
         );
         (query, params)
     }
+
+    fn resolution_set(&self, params: &mut BoltMap) -> &'static str {
+        match &self.edge.resolution {
+            Some(resolution) => {
+                boltmap_insert_float(params, "confidence", resolution.confidence as f64);
+                boltmap_insert_str(params, "strategy", &resolution.strategy);
+                // an edge merged again (AST pass, then LSP) keeps its best resolution
+                ", r.strategy = CASE WHEN r.confidence >= $confidence THEN r.strategy ELSE $strategy END,
+                   r.confidence = CASE WHEN r.confidence >= $confidence THEN r.confidence ELSE $confidence END"
+            }
+            None => "",
+        }
+    }
+
+    /// Appends this edge's call sites to those already on the relationship, so a
+    /// caller re-merged once per call keeps every site.
+    fn call_sites_set(&self, params: &mut BoltMap) -> &'static str {
+        if self.edge.call_sites.is_empty() {
+            return "";
+        }
+        boltmap_insert_list(params, "call_sites", call_sites_list(&self.edge.call_sites));
+        ", r.call_sites = coalesce(r.call_sites, []) + [s IN $call_sites WHERE NOT s IN coalesce(r.call_sites, [])]"
+    }
+}
+
+fn call_sites_list(sites: &[CallSite]) -> Vec<BoltType> {
+    sites
+        .iter()
+        .map(|site| BoltType::String(site.to_property().into()))
+        .collect()
 }
 
 pub fn add_edge_query(edge: &Edge) -> (String, BoltMap) {
@@ -77,32 +116,6 @@ pub fn add_edge_query_stream(edge: &Edge) -> (String, BoltMap) {
     EdgeQueryBuilder::new(edge).build_stream()
 }
 
-pub fn add_calls_edge_query(edge: &Edge, confidence: f32, strategy: &str) -> (String, BoltMap) {
-    let mut params = BoltMap::new();
-
-    let rel_type = edge.edge.to_string();
-    let source_type = edge.source.node_type.to_string();
-    let source_key = create_node_key_from_ref(&edge.source);
-    boltmap_insert_str(&mut params, "source_key", &source_key);
-
-    let target_type = edge.target.node_type.to_string();
-    let target_key = create_node_key_from_ref(&edge.target);
-    boltmap_insert_str(&mut params, "target_key", &target_key);
-    boltmap_insert_str(&mut params, "ref_id", &edge.ref_id);
-    boltmap_insert_float(&mut params, "confidence", confidence as f64);
-    boltmap_insert_str(&mut params, "strategy", strategy);
-
-    let query = format!(
-        "MATCH (source:{} {{node_key: $source_key}}),
-             (target:{} {{node_key: $target_key}})
-        MERGE (source)-[r:{}]->(target)
-        SET r.ref_id = $ref_id, r.confidence = $confidence, r.strategy = $strategy
-        RETURN r",
-        source_type, target_type, rel_type
-    );
-    (query, params)
-}
-
 pub fn add_calls_query(
     funcs: &[(Calls, Option<NodeData>, Option<NodeData>)],
     tests: &[(Calls, Option<NodeData>, Option<NodeData>)],
@@ -164,16 +177,37 @@ pub fn add_calls_query(
     queries
 }
 
+type BatchEdge = (
+    String,
+    String,
+    String,
+    Option<CallResolution>,
+    Vec<CallSite>,
+);
+
 pub fn build_batch_edge_queries<I>(edges: I, batch_size: usize) -> Vec<(String, BoltMap)>
 where
-    I: Iterator<Item = (String, String, EdgeType, String)>,
+    I: Iterator<
+        Item = (
+            String,
+            String,
+            EdgeType,
+            String,
+            Option<CallResolution>,
+            Vec<CallSite>,
+        ),
+    >,
 {
     use itertools::Itertools;
     use std::collections::HashMap;
 
     // Group edges by type
-    let edges_by_type: HashMap<EdgeType, Vec<(String, String, String)>> = edges
-        .map(|(source, target, edge_type, ref_id)| (edge_type, (source, target, ref_id)))
+    let edges_by_type: HashMap<EdgeType, Vec<BatchEdge>> = edges
+        .map(
+            |(source, target, edge_type, ref_id, resolution, call_sites)| {
+                (edge_type, (source, target, ref_id, resolution, call_sites))
+            },
+        )
         .into_group_map();
 
     // Create batched queries for each edge type
@@ -181,7 +215,7 @@ where
         .into_iter()
         .flat_map(|(edge_type, type_edges)| {
             // Batch the edges for this type
-            let chunks: Vec<Vec<(String, String, String)>> = type_edges
+            let chunks: Vec<Vec<BatchEdge>> = type_edges
                 .into_iter()
                 .chunks(batch_size)
                 .into_iter()
@@ -193,11 +227,26 @@ where
                 .map(|chunk| {
                     let edges_data: Vec<BoltMap> = chunk
                         .into_iter()
-                        .map(|(source, target, ref_id)| {
+                        .map(|(source, target, ref_id, resolution, call_sites)| {
                             let mut edge_map = BoltMap::new();
                             boltmap_insert_str(&mut edge_map, "source", &source);
                             boltmap_insert_str(&mut edge_map, "target", &target);
                             boltmap_insert_str(&mut edge_map, "ref_id", &ref_id);
+                            if let Some(resolution) = resolution {
+                                boltmap_insert_float(
+                                    &mut edge_map,
+                                    "confidence",
+                                    resolution.confidence as f64,
+                                );
+                                boltmap_insert_str(&mut edge_map, "strategy", &resolution.strategy);
+                            }
+                            if !call_sites.is_empty() {
+                                boltmap_insert_list(
+                                    &mut edge_map,
+                                    "call_sites",
+                                    call_sites_list(&call_sites),
+                                );
+                            }
                             edge_map
                         })
                         .collect();
@@ -209,7 +258,11 @@ where
                         "UNWIND $edges AS edge
                          MATCH (source:Data_Bank {{node_key: edge.source}}), (target:Data_Bank {{node_key: edge.target}})
                          MERGE (source)-[r:{}]->(target)
-                         SET r.ref_id = edge.ref_id
+                         SET r.ref_id = edge.ref_id,
+                             r.strategy = CASE WHEN edge.confidence IS NULL OR r.confidence >= edge.confidence THEN r.strategy ELSE edge.strategy END,
+                             r.confidence = CASE WHEN edge.confidence IS NULL OR r.confidence >= edge.confidence THEN r.confidence ELSE edge.confidence END,
+                             r.call_sites = CASE WHEN edge.call_sites IS NULL THEN r.call_sites
+                                 ELSE coalesce(r.call_sites, []) + [s IN edge.call_sites WHERE NOT s IN coalesce(r.call_sites, [])] END
                          RETURN count(r)",
                         edge_type.to_string()
                     );
@@ -223,19 +276,32 @@ where
 
 pub fn build_batch_edge_queries_stream<I>(edges: I, batch_size: usize) -> Vec<(String, BoltMap)>
 where
-    I: Iterator<Item = (String, String, EdgeType, String)>,
+    I: Iterator<
+        Item = (
+            String,
+            String,
+            EdgeType,
+            String,
+            Option<CallResolution>,
+            Vec<CallSite>,
+        ),
+    >,
 {
     use itertools::Itertools;
     use std::collections::HashMap;
 
-    let edges_by_type: HashMap<EdgeType, Vec<(String, String, String)>> = edges
-        .map(|(source, target, edge_type, ref_id)| (edge_type, (source, target, ref_id)))
+    let edges_by_type: HashMap<EdgeType, Vec<BatchEdge>> = edges
+        .map(
+            |(source, target, edge_type, ref_id, resolution, call_sites)| {
+                (edge_type, (source, target, ref_id, resolution, call_sites))
+            },
+        )
         .into_group_map();
 
     edges_by_type
         .into_iter()
         .flat_map(|(edge_type, type_edges)| {
-            let chunks: Vec<Vec<(String, String, String)>> = type_edges
+            let chunks: Vec<Vec<BatchEdge>> = type_edges
                 .into_iter()
                 .chunks(batch_size)
                 .into_iter()
@@ -247,11 +313,26 @@ where
                 .map(|chunk| {
                     let edges_data: Vec<BoltMap> = chunk
                         .into_iter()
-                        .map(|(source, target, ref_id)| {
+                        .map(|(source, target, ref_id, resolution, call_sites)| {
                             let mut edge_map = BoltMap::new();
                             boltmap_insert_str(&mut edge_map, "source", &source);
                             boltmap_insert_str(&mut edge_map, "target", &target);
                             boltmap_insert_str(&mut edge_map, "ref_id", &ref_id);
+                            if let Some(resolution) = resolution {
+                                boltmap_insert_float(
+                                    &mut edge_map,
+                                    "confidence",
+                                    resolution.confidence as f64,
+                                );
+                                boltmap_insert_str(&mut edge_map, "strategy", &resolution.strategy);
+                            }
+                            if !call_sites.is_empty() {
+                                boltmap_insert_list(
+                                    &mut edge_map,
+                                    "call_sites",
+                                    call_sites_list(&call_sites),
+                                );
+                            }
                             edge_map
                         })
                         .collect();
@@ -263,7 +344,11 @@ where
                         "UNWIND $edges AS edge
                          MATCH (source:Data_Bank {{node_key: edge.source}}), (target:Data_Bank {{node_key: edge.target}})
                          MERGE (source)-[r:{}]->(target)
-                         SET r.ref_id = edge.ref_id",
+                         SET r.ref_id = edge.ref_id,
+                             r.strategy = CASE WHEN edge.confidence IS NULL OR r.confidence >= edge.confidence THEN r.strategy ELSE edge.strategy END,
+                             r.confidence = CASE WHEN edge.confidence IS NULL OR r.confidence >= edge.confidence THEN r.confidence ELSE edge.confidence END,
+                             r.call_sites = CASE WHEN edge.call_sites IS NULL THEN r.call_sites
+                                 ELSE coalesce(r.call_sites, []) + [s IN edge.call_sites WHERE NOT s IN coalesce(r.call_sites, [])] END",
                         edge_type.to_string()
                     );
 
@@ -339,6 +424,17 @@ pub fn find_dynamic_edges_for_file_query(file: &str) -> (String, BoltMap) {
         "UnitTest",
         "IntegrationTest",
         "E2etest",
+        "Resource",
+        "Service",
+        "Workflow",
+        "WorkflowJob",
+        "WorkflowStep",
+        "Queue",
+        "Job",
+        "Command",
+        "Channel",
+        "ConfigKey",
+        "Module",
     ];
 
     let static_labels = static_types
@@ -352,7 +448,8 @@ pub fn find_dynamic_edges_for_file_query(file: &str) -> (String, BoltMap) {
          WHERE target.file ENDS WITH $file 
          AND NOT ({})
          RETURN source.ref_id as source_ref_id, type(r) as edge_type, 
-                target.name as target_name, target.file as target_file, labels(target)[0] as target_type",
+                target.name as target_name, target.file as target_file, labels(target)[0] as target_type,
+                target.stable_key as target_stable_key",
         static_labels
     );
 
@@ -383,6 +480,17 @@ pub fn find_all_dynamic_edges_query() -> (String, BoltMap) {
         "UnitTest",
         "IntegrationTest",
         "E2etest",
+        "Resource",
+        "Service",
+        "Workflow",
+        "WorkflowJob",
+        "WorkflowStep",
+        "Queue",
+        "Job",
+        "Command",
+        "Channel",
+        "ConfigKey",
+        "Module",
     ];
 
     let static_labels = static_types
@@ -395,32 +503,37 @@ pub fn find_all_dynamic_edges_query() -> (String, BoltMap) {
         "MATCH (source)-[r]->(target)
          WHERE NOT ({})
          RETURN source.ref_id as source_ref_id, type(r) as edge_type, 
-                target.name as target_name, target.file as target_file, labels(target)[0] as target_type",
+                target.name as target_name, target.file as target_file, labels(target)[0] as target_type,
+                target.stable_key as target_stable_key",
         static_labels
     );
 
     (query, params)
 }
 
-pub fn restore_dynamic_edge_query(
-    source_ref_id: &str,
-    edge_type: &str,
-    target_name: &str,
-    target_file: &str,
-    target_type: &str,
-) -> (String, BoltMap) {
+pub fn restore_dynamic_edge_query(edge: &DynamicEdge) -> (String, BoltMap) {
     let mut params = BoltMap::new();
-    boltmap_insert_str(&mut params, "source_ref_id", source_ref_id);
-    boltmap_insert_str(&mut params, "edge_type", edge_type);
-    boltmap_insert_str(&mut params, "target_name", target_name);
-    boltmap_insert_str(&mut params, "target_file", target_file);
+    boltmap_insert_str(&mut params, "source_ref_id", &edge.source_ref_id);
+    boltmap_insert_str(&mut params, "edge_type", &edge.edge_type);
+
+    let target_match = match &edge.target_stable_key {
+        Some(stable_key) => {
+            boltmap_insert_str(&mut params, "target_stable_key", stable_key);
+            "{stable_key: $target_stable_key}"
+        }
+        None => {
+            boltmap_insert_str(&mut params, "target_name", &edge.target_name);
+            boltmap_insert_str(&mut params, "target_file", &edge.target_file);
+            "{name: $target_name, file: $target_file}"
+        }
+    };
 
     let query = format!(
         "MATCH (source:Data_Bank {{ref_id: $source_ref_id}})
-         MATCH (target:{} {{name: $target_name, file: $target_file}})
+         MATCH (target:{} {})
          MERGE (source)-[r:{}]->(target)
          RETURN r",
-        target_type, edge_type
+        edge.target_type, target_match, edge.edge_type
     );
 
     (query, params)
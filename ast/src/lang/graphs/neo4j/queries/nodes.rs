@@ -4,7 +4,7 @@ use crate::lang::{
     migration::{update_endpoint_name_query, update_endpoint_relationships_query},
     Edge, Node, NodeData, NodeType, Operand, TestFilters,
 };
-use crate::utils::create_node_key;
+use crate::utils::{create_node_key, create_stable_key};
 use neo4rs::{BoltMap, BoltType};
 pub struct NodeQueryBuilder {
     node_type: NodeType,
@@ -32,6 +32,10 @@ impl NodeQueryBuilder {
         let node_key = create_node_key(&Node::new(self.node_type.clone(), self.node_data.clone()));
         boltmap_insert_str(&mut properties, "node_key", &node_key);
 
+        let stable_key =
+            create_stable_key(&Node::new(self.node_type.clone(), self.node_data.clone()));
+        boltmap_insert_str(&mut properties, "stable_key", &stable_key);
+
         let token_count = calculate_token_count(&self.node_data.body).unwrap_or(0);
         boltmap_insert_int(&mut properties, "token_count", token_count);
 
@@ -70,6 +74,10 @@ impl NodeQueryBuilder {
         let node_key = create_node_key(&Node::new(self.node_type.clone(), self.node_data.clone()));
         boltmap_insert_str(&mut properties, "node_key", &node_key);
 
+        let stable_key =
+            create_stable_key(&Node::new(self.node_type.clone(), self.node_data.clone()));
+        boltmap_insert_str(&mut properties, "stable_key", &stable_key);
+
         let token_count = calculate_token_count(&self.node_data.body).unwrap_or(0);
         boltmap_insert_int(&mut properties, "token_count", token_count);
 
@@ -469,7 +477,9 @@ pub fn process_endpoint_groups_queries(
             let new_name = format!("{}{}", group.name, endpoint.name);
             let mut new_node_data = endpoint.clone();
             new_node_data.name = new_name.clone();
-            let new_key = create_node_key(&Node::new(NodeType::Endpoint, new_node_data));
+            let new_node = Node::new(NodeType::Endpoint, new_node_data);
+            let new_key = create_node_key(&new_node);
+            let new_stable_key = create_stable_key(&new_node);
             let endpoint_verb = endpoint.meta.get("verb").map(|v| v.as_str());
 
             queries.push(update_endpoint_name_query(
@@ -478,6 +488,7 @@ pub fn process_endpoint_groups_queries(
                 endpoint_verb,
                 &new_name,
                 &new_key,
+                &new_stable_key,
             ));
 
             queries.push(update_endpoint_relationships_query(
@@ -505,7 +516,7 @@ pub fn get_muted_nodes_for_files_query(files: &[String]) -> (String, BoltMap) {
                  AND (n.is_muted = true OR n.is_muted = 'true')
                  WITH n, [label IN labels(n) WHERE label IN ['Function', 'Class', 'DataModel', 'Endpoint', 'Request', 'File', 'Directory', 'Repository', 'Language', 'Library', 'Import', 'Instance', 'Page', 'Var', 'UnitTest', 'IntegrationTest', 'E2eTest', 'Trait']][0] as node_type
                  WHERE node_type IS NOT NULL
-                 RETURN node_type, n.name as name, n.file as file".to_string();
+                 RETURN node_type, n.name as name, n.file as file, n.stable_key as stable_key".to_string();
 
     (query, params)
 }
@@ -519,6 +530,11 @@ pub fn restore_muted_status_query(identifiers: &[MutedNodeIdentifier]) -> (Strin
             boltmap_insert_str(&mut map, "node_type", &ident.node_type.to_string());
             boltmap_insert_str(&mut map, "name", &ident.name);
             boltmap_insert_str(&mut map, "file", &ident.file);
+            boltmap_insert_str(
+                &mut map,
+                "stable_key",
+                ident.stable_key.as_deref().unwrap_or_default(),
+            );
             BoltType::Map(map)
         })
         .collect();
@@ -527,8 +543,10 @@ pub fn restore_muted_status_query(identifiers: &[MutedNodeIdentifier]) -> (Strin
     let query = "UNWIND $identifiers as ident
                  MATCH (n)
                  WHERE ident.node_type IN labels(n) 
-                 AND n.name = ident.name 
-                 AND n.file = ident.file
+                 AND CASE
+                     WHEN ident.stable_key <> '' THEN n.stable_key = ident.stable_key
+                     ELSE n.name = ident.name AND n.file = ident.file
+                 END
                  SET n.is_muted = true
                  RETURN count(n) as restored_count"
         .to_string();
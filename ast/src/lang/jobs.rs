@@ -0,0 +1,472 @@
+use crate::lang::graphs::{Edge, Graph, NodeType};
+use crate::lang::queries::skips::summary::should_skip_dir;
+use crate::lang::scan::{
+    code_matches, enclosing_function, first_line, next_named_function, scan_files, CodeMask, Syntax,
+};
+use crate::lang::NodeData;
+use crate::utils::line_of;
+use lsp::Language;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+use walkdir::WalkDir;
+
+const JS: &[Language] = &[Language::Typescript];
+const PY: &[Language] = &[Language::Python];
+const RB: &[Language] = &[Language::Ruby];
+const GO: &[Language] = &[Language::Go];
+const RS: &[Language] = &[Language::Rust];
+const JVM: &[Language] = &[Language::Java, Language::Kotlin];
+
+/// How a schedule declaration points at the function it runs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobHandler {
+    /// a function by name, preferring the next one in the same file
+    Named(String),
+    /// a method on a class/worker, e.g. Sidekiq `perform` or whenever `runner "Report.build"`
+    Method(String, String),
+    /// the function the declaration sits in (inline closures)
+    Enclosing,
+    /// a rake task or shell command with no function in the graph
+    None,
+}
+
+/// A scheduler API whose matches become Job nodes. Named groups:
+/// `schedule` (required), `name`, `handler`, `class` and `method`.
+struct Detector {
+    scheduler: &'static str,
+    langs: &'static [Language],
+    /// method run on `class` when the pattern does not capture one
+    default_method: Option<&'static str>,
+    re: Regex,
+}
+
+fn detector(scheduler: &'static str, langs: &'static [Language], pattern: &str) -> Detector {
+    Detector {
+        scheduler,
+        langs,
+        default_method: None,
+        re: Regex::new(pattern).unwrap(),
+    }
+}
+
+// decorators/annotations sitting between the schedule and the method it marks
+const DECORATED: &str = r"(?:\s*@\w+(?:\([^)]*\))?)*\s*(?:(?:public|private|protected|static|final|synchronized|suspend|open|override|internal|async)\s+)*";
+
+static DETECTORS: LazyLock<Vec<Detector>> = LazyLock::new(|| {
+    vec![
+        // Spring `@Scheduled(cron = "0 0 * * * *")` / `@Scheduled(fixedRate = 5000)`
+        detector(
+            "spring",
+            JVM,
+            &format!(
+                r"@Scheduled\((?P<schedule>[^)]*)\){}(?:fun\s+|[\w<>\[\],?]+\s+)(?P<handler>\w+)\s*\(",
+                DECORATED
+            ),
+        ),
+        // NestJS `@Cron("0 0 * * *")` / `@Cron(CronExpression.EVERY_HOUR)` / `@Interval(5000)`
+        detector(
+            "nestjs",
+            JS,
+            &format!(
+                r"@(?:Cron|Interval)\((?P<schedule>[^)]*)\){}(?P<handler>[A-Za-z_$][\w$]*)\s*\(",
+                DECORATED
+            ),
+        ),
+        // node-cron `cron.schedule("*/5 * * * *", cleanup)`
+        detector(
+            "node-cron",
+            JS,
+            r#"\bcron\.schedule\(\s*['"`](?P<schedule>[^'"`]+)['"`]\s*,\s*(?:(?P<handler>[A-Za-z_$][\w$]*)\s*[,)])?"#,
+        ),
+        // Celery beat `"nightly": {"task": "app.tasks.cleanup", "schedule": crontab(hour=3)}`
+        detector(
+            "celery-beat",
+            PY,
+            r#"['"](?P<name>[^'"]+)['"]\s*:\s*(?:\{|dict\()\s*['"]?task['"]?\s*[:=]\s*['"](?:[\w.]*\.)?(?P<handler>\w+)['"]\s*,\s*['"]?schedule['"]?\s*[:=]\s*(?P<schedule>[\w.]+\([^)]*\)|[^,\n})]+)"#,
+        ),
+        // APScheduler `@sched.scheduled_job("cron", hour=3)` and `sched.add_job(cleanup, "interval", minutes=5)`
+        detector(
+            "apscheduler",
+            PY,
+            r"@\w+\.scheduled_job\((?P<schedule>[^)]*)\)(?:\s*@[^\n]*)*\s*(?:async\s+)?def\s+(?P<handler>\w+)",
+        ),
+        detector(
+            "apscheduler",
+            PY,
+            r"\.add_job\(\s*(?:\w+\.)*(?P<handler>[A-Za-z_]\w*)\s*,\s*(?P<schedule>[^)]*)\)",
+        ),
+        // sidekiq-cron `Sidekiq::Cron::Job.create(name: "nightly", cron: "0 3 * * *", class: "CleanupWorker")`
+        Detector {
+            default_method: Some("perform"),
+            ..detector(
+                "sidekiq-cron",
+                RB,
+                r#"Sidekiq::Cron::Job\.create\(\s*name:\s*['"](?P<name>[^'"]+)['"]\s*,\s*cron:\s*['"](?P<schedule>[^'"]+)['"]\s*,\s*class:\s*['"](?:\w+::)*(?P<class>\w+)['"]"#,
+            )
+        },
+        // robfig/cron `c.AddFunc("@every 1h", cleanup)` and gocron `s.Every(1).Day().At("03:00").Do(cleanup)`
+        detector(
+            "cron",
+            GO,
+            r#"\.AddFunc\(\s*"(?P<schedule>[^"]+)"\s*,\s*(?:(?:\w+\.)*(?P<handler>\w+)\s*\))?"#,
+        ),
+        detector(
+            "gocron",
+            GO,
+            r"\.(?P<schedule>Every\([^)]*\)(?:\.\w+\([^)]*\))*)\.Do\(\s*(?:\w+\.)*(?P<handler>\w+)",
+        ),
+        // tokio-cron-scheduler `Job::new_async("0 0 3 * * *", |_, _| Box::pin(cleanup()))`
+        detector(
+            "tokio-cron-scheduler",
+            RS,
+            r#"Job::new(?:_async|_repeated|_repeated_async|_one_shot|_one_shot_async)?(?:_tz)?\(\s*"(?P<schedule>[^"]+)"(?:\s*,[^|]*\|[^|]*\|\s*(?:Box::pin\(\s*)?(?:async\s+)?(?:move\s+)?\{?\s*(?:\w+::)*(?P<handler>[a-z_]\w*)\()?"#,
+        ),
+    ]
+});
+
+// whenever's config/schedule.rb: `every 1.day, at: "4:30 am" do ... end`
+static WHENEVER_EVERY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*every\s+(?P<schedule>.+?)\s+do\s*(?:\|[^|]*\|)?\s*$").unwrap()
+});
+static WHENEVER_JOB_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\s*(?P<kind>runner|rake|command)\s+['"](?P<target>[^'"]+)['"]"#).unwrap()
+});
+static RUNNER_TARGET_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:\w+::)*(?P<class>\w+)\.(?P<method>\w+)").unwrap());
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobDef {
+    pub scheduler: &'static str,
+    /// explicit job name; otherwise named after its handler
+    pub name: Option<String>,
+    pub schedule: String,
+    pub handler: JobHandler,
+    pub line: usize,
+    pub snippet: String,
+}
+
+impl JobDef {
+    /// The declared name, or the worker class/method it runs.
+    pub fn declared_name(&self) -> Option<String> {
+        self.name.clone().or_else(|| match &self.handler {
+            JobHandler::Method(class, method) if method == "perform" => Some(class.clone()),
+            JobHandler::Method(class, method) => Some(format!("{}.{}", class, method)),
+            _ => None,
+        })
+    }
+
+    /// A name for listings without a graph to resolve the handler against.
+    pub fn label(&self) -> String {
+        self.declared_name()
+            .or_else(|| match &self.handler {
+                JobHandler::Named(name) => Some(name.clone()),
+                _ => None,
+            })
+            .unwrap_or_else(|| self.schedule.clone())
+    }
+}
+
+/// Finds scheduled jobs declared in one source file, ignoring comments and strings.
+pub fn find_jobs(lang: &Language, file: &str, code: &str) -> Vec<JobDef> {
+    let mask = Syntax::of(lang).mask(code);
+    let mut jobs = Vec::new();
+    for det in DETECTORS.iter().filter(|d| d.langs.contains(lang)) {
+        for caps in code_matches(&det.re, code, &mask) {
+            let whole = caps.get(0).unwrap();
+            jobs.push(JobDef {
+                scheduler: det.scheduler,
+                name: caps.name("name").map(|n| n.as_str().to_string()),
+                schedule: schedule_of(&caps),
+                handler: handler_of(&caps, det.default_method),
+                line: line_of(code, whole.start()),
+                snippet: first_line(whole.as_str()),
+            });
+        }
+    }
+    if RB.contains(lang) && file.ends_with("schedule.rb") {
+        jobs.extend(whenever_jobs(code, &mask));
+    }
+    jobs.sort_by_key(|j| j.line);
+    jobs
+}
+
+fn schedule_of(caps: &Captures) -> String {
+    let raw = caps["schedule"].trim();
+    let mut parts = raw.split(['"', '\'', '`']);
+    let (Some(before), Some(quoted), rest) = (parts.next(), parts.next(), parts.next()) else {
+        return raw.to_string();
+    };
+    // `cron = "0 0 * * *", zone = "UTC"` / `"0 0 * * *", { name: "x" }` -> the cron expression itself
+    let rest = rest.unwrap_or_default().trim_start();
+    if before.trim_start().starts_with("cron")
+        || (before.is_empty()
+            && (rest.is_empty() || rest.trim_start_matches(',').trim_start().starts_with('{')))
+    {
+        return quoted.to_string();
+    }
+    raw.to_string()
+}
+
+fn handler_of(caps: &Captures, default_method: Option<&'static str>) -> JobHandler {
+    if let Some(class) = caps.name("class") {
+        let method = caps
+            .name("method")
+            .map(|m| m.as_str())
+            .or(default_method)
+            .unwrap_or("perform");
+        return JobHandler::Method(class.as_str().to_string(), method.to_string());
+    }
+    match caps.name("handler") {
+        Some(h) => JobHandler::Named(h.as_str().to_string()),
+        None => JobHandler::Enclosing,
+    }
+}
+
+fn whenever_jobs(code: &str, mask: &CodeMask) -> Vec<JobDef> {
+    let mut jobs = Vec::new();
+    let mut schedule = None;
+    let mut at = 0;
+    for (line, raw) in code.split_inclusive('\n').enumerate() {
+        let text = raw.trim_end_matches(['\n', '\r']);
+        let indent = raw.len() - raw.trim_start().len();
+        at += raw.len();
+        // commented-out lines, or lines inside a heredoc
+        if !mask.is_code(at - raw.len() + indent) {
+            continue;
+        }
+        if let Some(caps) = WHENEVER_EVERY_RE.captures(text) {
+            schedule = Some(caps["schedule"].to_string());
+            continue;
+        }
+        let (Some(schedule), Some(caps)) = (&schedule, WHENEVER_JOB_RE.captures(text)) else {
+            continue;
+        };
+        let target = caps["target"].to_string();
+        let handler = match (&caps["kind"], RUNNER_TARGET_RE.captures(&target)) {
+            ("runner", Some(t)) => {
+                JobHandler::Method(t["class"].to_string(), t["method"].to_string())
+            }
+            _ => JobHandler::None,
+        };
+        jobs.push(JobDef {
+            scheduler: "whenever",
+            name: Some(target),
+            schedule: schedule.clone(),
+            handler,
+            line,
+            snippet: text.trim().to_string(),
+        });
+    }
+    jobs
+}
+
+/// Adds a Job node per schedule declaration, with a HANDLER edge to the function it runs.
+pub fn add_job_nodes<G: Graph>(
+    graph: &mut G,
+    lang: &Language,
+    filez: &[(String, String)],
+) -> usize {
+    let found = scan_files(filez, |file, code| find_jobs(lang, file, code));
+    if found.is_empty() {
+        return 0;
+    }
+
+    let all_functions = graph.find_nodes_by_type(NodeType::Function);
+    let mut functions: HashMap<&str, Vec<&NodeData>> = HashMap::new();
+    for func in &all_functions {
+        functions.entry(func.file.as_str()).or_default().push(func);
+    }
+    let classes = graph.find_nodes_by_type(NodeType::Class);
+
+    let mut count = 0;
+    for (file, _, jobs) in found {
+        let in_file = functions.get(file).map(|f| f.as_slice()).unwrap_or(&[]);
+        for job in jobs {
+            let handler = match &job.handler {
+                JobHandler::Named(name) => {
+                    next_named_function(in_file.iter().copied(), name, job.line)
+                        .or_else(|| all_functions.iter().find(|f| &f.name == name))
+                }
+                JobHandler::Method(class, method) => {
+                    classes.iter().filter(|c| &c.name == class).find_map(|c| {
+                        functions
+                            .get(c.file.as_str())?
+                            .iter()
+                            .copied()
+                            .find(|f| &f.name == method && c.start <= f.start && f.end <= c.end)
+                    })
+                }
+                JobHandler::Enclosing => enclosing_function(in_file.iter().copied(), job.line),
+                JobHandler::None => None,
+            };
+            let name = job
+                .declared_name()
+                .or_else(|| handler.map(|h| h.name.clone()))
+                .unwrap_or_else(|| job.schedule.clone());
+
+            let mut nd = NodeData::name_file(&name, file);
+            nd.start = job.line;
+            nd.end = job.line;
+            nd.body = job.snippet.clone();
+            nd.meta.insert("schedule".to_string(), job.schedule.clone());
+            nd.meta
+                .insert("scheduler".to_string(), job.scheduler.to_string());
+            graph.add_node_with_parent(&NodeType::Job, &nd, &NodeType::File, file);
+            if let Some(handler) = handler {
+                graph.add_edge(&Edge::job_handler(&nd, handler));
+            }
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Scheduled jobs under `root` with the file declaring each, without building a graph.
+pub fn collect_jobs(root: &Path) -> Vec<(String, JobDef)> {
+    let mut jobs = Vec::new();
+    let walker = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !should_skip_dir(&e.file_name().to_string_lossy()));
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path().display().to_string();
+        let Some(lang) = Language::from_path(&path) else {
+            continue;
+        };
+        let Ok(code) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        jobs.extend(
+            find_jobs(&lang, &path, &code)
+                .into_iter()
+                .map(|j| (path.clone(), j)),
+        );
+    }
+    jobs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(jobs: &[JobDef]) -> Vec<(&str, &str, JobHandler)> {
+        jobs.iter()
+            .map(|j| (j.scheduler, j.schedule.as_str(), j.handler.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_spring_scheduled() {
+        let code = r#"
+@Component
+public class Reports {
+    @Scheduled(cron = "0 0 3 * * *")
+    @Transactional
+    public void nightly() {}
+
+    @Scheduled(fixedRate = 5000)
+    void heartbeat() {}
+}
+"#;
+        let jobs = find_jobs(&Language::Java, "Reports.java", code);
+        assert_eq!(
+            summary(&jobs),
+            vec![
+                ("spring", "0 0 3 * * *", JobHandler::Named("nightly".into())),
+                (
+                    "spring",
+                    "fixedRate = 5000",
+                    JobHandler::Named("heartbeat".into())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_celery_beat_and_apscheduler() {
+        let code = r#"
+app.conf.beat_schedule = {
+    "nightly-cleanup": {"task": "app.tasks.cleanup", "schedule": crontab(hour=3, minute=0)},
+}
+
+@sched.scheduled_job("interval", minutes=5)
+def poll():
+    pass
+"#;
+        let jobs = find_jobs(&Language::Python, "app/celery.py", code);
+        assert_eq!(
+            summary(&jobs),
+            vec![
+                (
+                    "celery-beat",
+                    "crontab(hour=3, minute=0)",
+                    JobHandler::Named("cleanup".into())
+                ),
+                (
+                    "apscheduler",
+                    "\"interval\", minutes=5",
+                    JobHandler::Named("poll".into())
+                ),
+            ]
+        );
+        assert_eq!(jobs[0].name.as_deref(), Some("nightly-cleanup"));
+    }
+
+    #[test]
+    fn test_whenever_schedule() {
+        let code = r#"
+every 1.day, at: "4:30 am" do
+  runner "Reports::Digest.deliver"
+  rake "cache:clear"
+end
+"#;
+        let jobs = find_jobs(&Language::Ruby, "config/schedule.rb", code);
+        assert_eq!(
+            summary(&jobs),
+            vec![
+                (
+                    "whenever",
+                    "1.day, at: \"4:30 am\"",
+                    JobHandler::Method("Digest".into(), "deliver".into())
+                ),
+                ("whenever", "1.day, at: \"4:30 am\"", JobHandler::None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokio_cron_and_go_cron() {
+        let rust =
+            r#"sched.add(Job::new_async("0 0 3 * * *", |_uuid, _l| Box::pin(cleanup()))?).await?;"#;
+        let jobs = find_jobs(&Language::Rust, "src/main.rs", rust);
+        assert_eq!(
+            summary(&jobs),
+            vec![(
+                "tokio-cron-scheduler",
+                "0 0 3 * * *",
+                JobHandler::Named("cleanup".into())
+            )]
+        );
+
+        let go = r#"
+c.AddFunc("@every 1h", refreshCache)
+c.AddFunc("0 3 * * *", func() { log.Println("tick") })
+"#;
+        let jobs = find_jobs(&Language::Go, "main.go", go);
+        assert_eq!(
+            summary(&jobs),
+            vec![
+                (
+                    "cron",
+                    "@every 1h",
+                    JobHandler::Named("refreshCache".into())
+                ),
+                ("cron", "0 3 * * *", JobHandler::Enclosing),
+            ]
+        );
+    }
+}
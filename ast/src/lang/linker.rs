@@ -1,5 +1,6 @@
+use crate::lang::config_keys::canonical_key;
 use crate::lang::graphs::{EdgeType, Graph, NodeType};
-use crate::lang::{Edge, Language, NodeData};
+use crate::lang::{Edge, Language, NodeData, NodeRef};
 use lsp::language::PROGRAMMING_LANGUAGES;
 use regex::Regex;
 use shared::{Context, Error, Result};
@@ -379,11 +380,16 @@ pub fn link_api_nodes<G: Graph>(graph: &mut G) -> Result<()> {
         }
     }
 
+    let scopes = ServiceScopes::new(graph);
+
     // Create edges between matching paths and verbs
     let mut i = 0;
     for (req, req_path) in frontend_requests {
         for (endpoint, normalized) in &backend_endpoints {
-            if paths_match(&req_path, normalized) && verbs_match(&req, endpoint) {
+            if paths_match(&req_path, normalized)
+                && verbs_match(&req, endpoint)
+                && scopes.allows(&req, endpoint)
+            {
                 let edge = Edge::calls(NodeType::Request, &req, NodeType::Endpoint, endpoint);
                 graph.add_edge(&edge);
                 i += 1;
@@ -395,6 +401,163 @@ pub fn link_api_nodes<G: Graph>(graph: &mut G) -> Result<()> {
     Ok(())
 }
 
+/// Each repo keeps its own Queue node for a topic, so a producer in one repo and a consumer in
+/// another meet at different nodes. Publishers are linked to every same-named queue of the same broker.
+pub fn link_queue_nodes<G: Graph>(graph: &mut G) -> Result<()> {
+    let i = link_peer_nodes(
+        graph,
+        NodeType::Queue,
+        EdgeType::Publishes,
+        |q| (meta_or_empty(q, "broker"), q.name.clone()),
+        |_| true,
+    );
+    info!("linked {} queue publishers across repos", i);
+    Ok(())
+}
+
+/// Frontend and backend usually land in different language passes with their own Channel node,
+/// so emitters are linked to every same-named channel of the same protocol.
+pub fn link_channel_nodes<G: Graph>(graph: &mut G) -> Result<()> {
+    let i = link_peer_nodes(
+        graph,
+        NodeType::Channel,
+        EdgeType::Emits,
+        |c| (meta_or_empty(c, "protocol"), c.name.clone()),
+        |_| true,
+    );
+    info!("linked {} channel emitters across repos", i);
+    Ok(())
+}
+
+/// Code reads a key under one spelling (`app.db-url`, `process.env.APP_DB_URL`) while `.env`
+/// templates, compose files and manifests declare it under another, each as its own ConfigKey
+/// node. Readers are linked to every declaration with the same environment-style name; other
+/// code reads of the key are not declarations and stay unlinked.
+pub fn link_config_keys<G: Graph>(graph: &mut G) -> Result<()> {
+    let i = link_peer_nodes(
+        graph,
+        NodeType::ConfigKey,
+        EdgeType::Reads,
+        |k| canonical_key(&k.name),
+        |k| {
+            matches!(
+                k.meta.get("source").map(|s| s.as_str()),
+                Some("dotenv" | "compose" | "kubernetes")
+            )
+        },
+    );
+    info!("linked {} config key reads to their definitions", i);
+    Ok(())
+}
+
+/// Separate repos and language passes each create their own node for the same queue, channel
+/// or config key. Every `edge_type` edge from a Function to one of them is copied onto the other
+/// `node_type` nodes in other files that share its `key` and pass `is_peer`.
+fn link_peer_nodes<G, K>(
+    graph: &mut G,
+    node_type: NodeType,
+    edge_type: EdgeType,
+    key: impl Fn(&NodeData) -> K,
+    is_peer: impl Fn(&NodeData) -> bool,
+) -> usize
+where
+    G: Graph,
+    K: std::hash::Hash + Eq,
+{
+    let mut by_key: HashMap<K, Vec<NodeData>> = HashMap::new();
+    for node in graph.find_nodes_by_type(node_type.clone()) {
+        by_key.entry(key(&node)).or_default().push(node);
+    }
+    by_key.retain(|_, nodes| nodes.len() > 1);
+    if by_key.is_empty() {
+        return 0;
+    }
+
+    let edges =
+        graph.find_nodes_with_edge_type(NodeType::Function, node_type.clone(), edge_type.clone());
+    let mut i = 0;
+    for (func, target) in edges {
+        let Some(peers) = by_key.get(&key(&target)) else {
+            continue;
+        };
+        for peer in peers.iter().filter(|p| p.file != target.file && is_peer(p)) {
+            graph.add_edge(&Edge::new(
+                edge_type.clone(),
+                NodeRef::from((&func).into(), NodeType::Function),
+                NodeRef::from(peer.into(), node_type.clone()),
+            ));
+            i += 1;
+        }
+    }
+    i
+}
+
+fn meta_or_empty(nd: &NodeData, key: &str) -> String {
+    nd.meta.get(key).cloned().unwrap_or_default()
+}
+
+/// Service topology (from compose, Dockerfiles and k8s manifests) used to scope
+/// Request -> Endpoint links. Files belong to the Service whose build context contains them.
+/// Services are keyed by the file that declares them, so two compose files that both
+/// define `api` stay apart.
+struct ServiceScopes {
+    contexts: Vec<(String, ServiceKey)>,
+    names: HashSet<ServiceKey>,
+}
+
+/// (declaring file, service name)
+type ServiceKey = (String, String);
+
+impl ServiceScopes {
+    fn new<G: Graph>(graph: &G) -> Self {
+        let services = graph.find_nodes_by_type(NodeType::Service);
+        let mut contexts: Vec<(String, ServiceKey)> = services
+            .iter()
+            .filter_map(|s| {
+                s.meta
+                    .get("context")
+                    .map(|c| (c.clone(), (s.file.clone(), s.name.clone())))
+            })
+            .collect();
+        // most specific context first
+        contexts.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        let names = services.into_iter().map(|s| (s.file, s.name)).collect();
+        Self { contexts, names }
+    }
+
+    fn service_of(&self, file: &str) -> Option<&ServiceKey> {
+        self.contexts
+            .iter()
+            .find(|(ctx, _)| {
+                file.starts_with(ctx.as_str())
+                    && (file.len() == ctx.len() || file[ctx.len()..].starts_with('/'))
+            })
+            .map(|(_, key)| key)
+    }
+
+    /// Links are only dropped on positive evidence: a request to an absolute URL whose host
+    /// is another service declared next to the endpoint's service. Missing or partial
+    /// topology keeps every match.
+    fn allows(&self, req: &NodeData, endpoint: &NodeData) -> bool {
+        let (Some(host), Some((file, target))) =
+            (request_host(&req.name), self.service_of(&endpoint.file))
+        else {
+            return true;
+        };
+        host == *target || !self.names.contains(&(file.clone(), host))
+    }
+}
+
+fn request_host(url: &str) -> Option<String> {
+    let rest = &url[url.find("://")? + 3..];
+    let host = rest.split(['/', ':', '?']).next()?;
+    let host = host.split('.').next()?;
+    if host.is_empty() || host.starts_with("${") {
+        return None;
+    }
+    Some(host.to_string())
+}
+
 pub fn normalize_frontend_path(path: &str) -> Option<String> {
     if path.starts_with("${") && path.ends_with("}") && !path[2..].contains("${") {
         return None;
@@ -612,4 +775,153 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_link_peer_nodes() -> Result<()> {
+        use crate::lang::graphs::ArrayGraph;
+
+        type Linker = fn(&mut ArrayGraph) -> Result<()>;
+        struct Case {
+            link: Linker,
+            node_type: NodeType,
+            edge_type: EdgeType,
+            meta_key: &'static str,
+            // (name, file, meta value); the first node is the one the function points at
+            nodes: Vec<(&'static str, &'static str, &'static str)>,
+            linked: Vec<&'static str>,
+        }
+        let cases = [
+            // same topic on another broker is a different queue
+            Case {
+                link: link_queue_nodes,
+                node_type: NodeType::Queue,
+                edge_type: EdgeType::Publishes,
+                meta_key: "broker",
+                nodes: vec![
+                    ("orders", "shop/src/orders.ts", "kafka"),
+                    ("orders", "billing/worker.py", "kafka"),
+                    ("orders", "billing/tasks.py", "rabbitmq"),
+                ],
+                linked: vec!["shop/src/orders.ts", "billing/worker.py"],
+            },
+            Case {
+                link: link_channel_nodes,
+                node_type: NodeType::Channel,
+                edge_type: EdgeType::Emits,
+                meta_key: "protocol",
+                nodes: vec![
+                    ("/events", "web/src/live.ts", "sse"),
+                    ("/events", "api/app.py", "sse"),
+                    ("/events", "api/ws.py", "websocket"),
+                ],
+                linked: vec!["web/src/live.ts", "api/app.py"],
+            },
+            // other code reads of the same key, in any language, are not definitions
+            Case {
+                link: link_config_keys,
+                node_type: NodeType::ConfigKey,
+                edge_type: EdgeType::Reads,
+                meta_key: "source",
+                nodes: vec![
+                    ("app.mail-sender", "src/MailService.java", "spring"),
+                    ("APP_MAIL_SENDER", ".env.example", "dotenv"),
+                    ("APP_MAIL_SENDER", "docker-compose.yml", "compose"),
+                    ("APP_MAIL_SENDER", "worker/mail.py", "env"),
+                    ("APP_MAIL_SENDER", "web/src/mail.ts", "env"),
+                    ("APP_MAIL_HOST", ".env.example", "dotenv"),
+                ],
+                linked: vec!["src/MailService.java", ".env.example", "docker-compose.yml"],
+            },
+        ];
+
+        for case in cases {
+            let mut graph = ArrayGraph::new(String::new(), Language::Typescript);
+            let func = NodeData::name_file("caller", case.nodes[0].1);
+            graph.add_node(&NodeType::Function, &func);
+            let mut targets = Vec::new();
+            for (name, file, value) in &case.nodes {
+                let mut nd = NodeData::name_file(name, file);
+                nd.meta.insert(case.meta_key.to_string(), value.to_string());
+                graph.add_node(&case.node_type, &nd);
+                targets.push(nd);
+            }
+            graph.add_edge(&Edge::new(
+                case.edge_type.clone(),
+                NodeRef::from((&func).into(), NodeType::Function),
+                NodeRef::from((&targets[0]).into(), case.node_type.clone()),
+            ));
+
+            (case.link)(&mut graph)?;
+
+            let linked: Vec<&str> = graph
+                .edges
+                .iter()
+                .filter(|e| e.edge == case.edge_type)
+                .map(|e| e.target.node_data.file.as_str())
+                .collect();
+            assert_eq!(linked, case.linked, "{:?}", case.node_type);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_link_api_nodes_scoped_by_service() -> Result<()> {
+        use crate::lang::graphs::ArrayGraph;
+        let mut graph = ArrayGraph::new(String::new(), Language::Typescript);
+
+        for (name, file, context) in [
+            ("web", "docker-compose.yml", "web"),
+            ("users", "docker-compose.yml", "services/users"),
+            ("orders", "docker-compose.yml", "services/orders"),
+            // only declared by another stack, so it says nothing about these endpoints
+            ("billing", "deploy/docker-compose.yml", "billing"),
+        ] {
+            let mut svc = NodeData::name_file(name, file);
+            svc.meta.insert("context".to_string(), context.to_string());
+            graph.add_node(&NodeType::Service, &svc);
+        }
+
+        for (url, file) in [
+            ("/api/items", "web/src/App.tsx"),
+            ("http://users:8080/api/items", "web/src/users.ts"),
+            ("http://billing/api/items", "web/src/billing.ts"),
+        ] {
+            let mut req = NodeData::name_file(url, file);
+            req.meta.insert("verb".to_string(), "GET".to_string());
+            graph.add_node(&NodeType::Request, &req);
+        }
+        for file in ["services/users/routes.ts", "services/orders/routes.ts"] {
+            let mut endpoint = NodeData::name_file("/api/items", file);
+            endpoint.meta.insert("verb".to_string(), "GET".to_string());
+            graph.add_node(&NodeType::Endpoint, &endpoint);
+        }
+
+        link_api_nodes(&mut graph)?;
+
+        let mut links: Vec<(&str, &str)> = graph
+            .edges
+            .iter()
+            .filter(|e| e.edge == EdgeType::Calls)
+            .map(|e| {
+                (
+                    e.source.node_data.file.as_str(),
+                    e.target.node_data.file.as_str(),
+                )
+            })
+            .collect();
+        links.sort();
+        assert_eq!(
+            links,
+            vec![
+                ("web/src/App.tsx", "services/orders/routes.ts"),
+                ("web/src/App.tsx", "services/users/routes.ts"),
+                ("web/src/billing.ts", "services/orders/routes.ts"),
+                ("web/src/billing.ts", "services/users/routes.ts"),
+                ("web/src/users.ts", "services/users/routes.ts"),
+            ]
+        );
+
+        Ok(())
+    }
 }
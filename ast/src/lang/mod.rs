@@ -1,12 +1,24 @@
 pub mod asg;
 pub mod call_finder;
+pub mod channels;
+pub mod commands;
+pub mod config_keys;
+pub mod data_flow;
+pub mod errors;
+pub mod fields;
 #[cfg(feature = "openssl")]
 pub mod embedding;
 pub mod graphs;
+pub mod jobs;
 pub mod linker;
+pub mod modules;
+pub mod overrides;
 pub mod parse;
 pub mod queries;
+pub mod queues;
 pub mod registry;
+pub mod scan;
+pub mod signature;
 
 use crate::builder::utils::log_stage_timing;
 use crate::lang::parse::utils::trim_quotes;
@@ -293,6 +305,20 @@ impl Lang {
             query_cache: DashMap::new(),
         }
     }
+    pub fn new_lua() -> Self {
+        Self {
+            kind: Language::Lua,
+            lang: Box::new(lua::Lua::new()),
+            query_cache: DashMap::new(),
+        }
+    }
+    pub fn new_zig() -> Self {
+        Self {
+            kind: Language::Zig,
+            lang: Box::new(zig::Zig::new()),
+            query_cache: DashMap::new(),
+        }
+    }
     pub fn new_bash() -> Self {
         Self {
             kind: Language::Bash,
@@ -818,6 +844,17 @@ impl Lang {
                         )?;
                         res.3.extend(extras);
                     }
+                    for dep in self.lang.injected_dependencies(&full_body) {
+                        if let Some(edge) = self.injected_dependency_edge(
+                            &dep,
+                            &caller_name,
+                            file,
+                            caller_start,
+                            graph,
+                        ) {
+                            res.3.push(edge);
+                        }
+                    }
                 }
                 Ok(())
             })?;
@@ -944,6 +981,37 @@ impl Lang {
         }
         Ok(res)
     }
+    // `Depends(get_db)` -> caller DEPENDS_ON get_db, resolved like a call from the caller's file
+    fn injected_dependency_edge<G: Graph>(
+        &self,
+        dependency: &str,
+        caller_name: &str,
+        file: &str,
+        caller_start: usize,
+        graph: &G,
+    ) -> Option<Edge> {
+        let (operand, name) = match dependency.rsplit_once('.') {
+            Some((operand, name)) => (Some(operand.to_string()), name),
+            None => (None, dependency),
+        };
+        let import_names = call_finder::get_imports_for_file(file, self, graph);
+        let (target, _, _) = call_finder::node_data_finder(
+            name,
+            &operand,
+            graph,
+            file,
+            caller_start,
+            NodeType::Function,
+            import_names,
+            self,
+            None,
+        )?;
+        Some(Edge::new(
+            EdgeType::DependsOn,
+            NodeRef::from(NodeKeys::new(caller_name, file, caller_start), NodeType::Function),
+            NodeRef::from((&target).into(), NodeType::Function),
+        ))
+    }
     fn add_calls_inside(
         &self,
         res: &mut (Vec<FunctionCall>, Vec<FunctionCall>, Vec<Edge>, Vec<Edge>),
@@ -979,6 +1047,8 @@ impl Lang {
             Language::C => Lang::new_c(),
             Language::Php => Lang::new_php(),
             Language::CSharp => Lang::new_csharp(),
+            Language::Lua => Lang::new_lua(),
+            Language::Zig => Lang::new_zig(),
         }
     }
 }
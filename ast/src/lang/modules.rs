@@ -0,0 +1,578 @@
+use crate::lang::asg::NodeKeys;
+use crate::lang::graphs::{Edge, EdgeType, Graph, NodeType};
+use crate::lang::scan::{code_matches, Syntax};
+use crate::lang::NodeData;
+use crate::utils::line_of;
+use lsp::Language;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// Languages whose source declares the module it belongs to, either by file layout (Rust,
+/// Python) or by a `package` / `namespace` statement. Elixir has no parser here yet.
+const MODULE_LANGS: &[Language] = &[
+    Language::Rust,
+    Language::Python,
+    Language::Go,
+    Language::Java,
+    Language::Kotlin,
+    Language::CSharp,
+];
+
+/// Node types a module CONTAINS besides its submodules.
+const MEMBER_TYPES: [NodeType; 4] = [
+    NodeType::Function,
+    NodeType::Class,
+    NodeType::Trait,
+    NodeType::DataModel,
+];
+
+static RUST_MOD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*(?:pub(?:\([^)]*\))?[ \t]+)?mod[ \t]+(?P<name>\w+)[ \t]*\{").unwrap()
+});
+static GO_PACKAGE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^package[ \t]+(?P<name>\w+)").unwrap());
+static JVM_PACKAGE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*package[ \t]+(?P<name>[\w.]+)").unwrap());
+static CS_NAMESPACE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*namespace[ \t]+(?P<name>[\w.]+)[ \t]*(?P<scoped>;)?").unwrap()
+});
+
+/// `(root, name)` of a module.
+type ModuleKey = (String, String);
+
+/// A module declared by one source file over lines `start..=end`. `root` scopes the name:
+/// two crates both have a `crate::utils`, two Python source roots both have a `main`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleDecl {
+    pub root: String,
+    pub name: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Separator between the segments of a fully-qualified name in `file`.
+pub fn separator(file: &str) -> &'static str {
+    if file.ends_with(".rs") {
+        "::"
+    } else {
+        "."
+    }
+}
+
+/// `crate::lang::graphs` for `<root>/src/lang/graphs/mod.rs`. Binaries, tests, benches and
+/// examples are crates of their own, so their entry files are `crate` too.
+pub fn rust_module_path(file: &str, files: &HashSet<&str>) -> (String, String) {
+    let parts: Vec<&str> = file.split('/').collect();
+    // `tests/` only starts crates beside a `src/` holding the crate's own root
+    let is_crate = |dir: &[&str]| {
+        let dir = dir.join("/");
+        ["lib.rs", "main.rs"]
+            .iter()
+            .any(|f| files.contains(format!("{}/src/{}", dir, f).trim_start_matches('/')))
+    };
+    let side = parts.iter().enumerate().rposition(|(i, p)| {
+        matches!(*p, "tests" | "benches" | "examples") && is_crate(&parts[..i])
+    });
+    let src = parts.iter().rposition(|p| *p == "src");
+    let Some(root_idx) = side.max(src) else {
+        let stem = file_stem(file);
+        let name = match stem.as_str() {
+            "lib" | "main" | "mod" => "crate".to_string(),
+            _ => format!("crate::{}", stem),
+        };
+        return (parent(file), name);
+    };
+    let mut root = parts[..=root_idx].join("/");
+    let mut rel = &parts[root_idx + 1..];
+    if parts[root_idx] == "src" && rel.len() >= 2 && rel[0] == "bin" {
+        // src/bin/tool.rs or src/bin/tool/main.rs
+        root = parts[..root_idx + 3].join("/");
+        rel = &rel[2..];
+    } else if parts[root_idx] != "src" && rel.len() == 1 {
+        root = file.to_string();
+        rel = &[];
+    } else if parts[root_idx] == "src"
+        && rel == ["main.rs"]
+        && files.contains(format!("{}/lib.rs", root).as_str())
+    {
+        // a binary next to a library is its own crate
+        root = file.to_string();
+        rel = &[];
+    }
+    let mut segments = vec!["crate".to_string()];
+    if let Some((last, dirs)) = rel.split_last() {
+        segments.extend(dirs.iter().map(|d| d.to_string()));
+        let stem = last.trim_end_matches(".rs");
+        let is_entry = matches!(stem, "mod" | "lib") || (dirs.is_empty() && stem == "main");
+        if !is_entry {
+            segments.push(stem.to_string());
+        }
+    }
+    (root, segments.join("::"))
+}
+
+/// Dotted module name of a Python file, walking up through directories holding an
+/// `__init__.py`. The returned root is the first directory that is not a package.
+pub fn python_module_path(file: &str, files: &HashSet<&str>) -> (String, String) {
+    let stem = file_stem(file);
+    let mut segments = Vec::new();
+    if stem != "__init__" {
+        segments.push(stem);
+    }
+    let mut dir = Path::new(file).parent();
+    while let Some(d) = dir.filter(|d| !d.as_os_str().is_empty()) {
+        let init = d.join("__init__.py");
+        if !files.contains(init.to_string_lossy().as_ref()) {
+            break;
+        }
+        segments.push(
+            d.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned(),
+        );
+        dir = d.parent();
+    }
+    segments.reverse();
+    let root = dir
+        .map(|d| d.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    (root, segments.join("."))
+}
+
+/// Import path of the Go package in `dir`: the `go.mod` module path plus the directory
+/// below it. Without a `go.mod` the package clause is the best name there is.
+pub fn go_import_path(dir: &str, go_mod: Option<(&str, &str)>, package: &str) -> String {
+    let Some((mod_dir, mod_path)) = go_mod else {
+        return package.to_string();
+    };
+    match Path::new(dir).strip_prefix(mod_dir) {
+        Ok(rel) if !rel.as_os_str().is_empty() => {
+            format!("{}/{}", mod_path, rel.to_string_lossy())
+        }
+        _ => mod_path.to_string(),
+    }
+}
+
+/// The nearest `go.mod` above `dir` with the module path it declares.
+fn find_go_mod(
+    dir: &str,
+    cache: &mut HashMap<String, Option<(String, String)>>,
+) -> Option<(String, String)> {
+    if let Some(found) = cache.get(dir) {
+        return found.clone();
+    }
+    let found = std::fs::read_to_string(Path::new(dir).join("go.mod"))
+        .ok()
+        .and_then(|code| {
+            code.lines()
+                .find_map(|l| l.trim().strip_prefix("module "))
+                .map(|m| (dir.to_string(), m.trim().trim_matches('"').to_string()))
+        })
+        .or_else(|| {
+            let up = parent(dir);
+            if up.is_empty() || up == dir {
+                None
+            } else {
+                find_go_mod(&up, cache)
+            }
+        });
+    cache.insert(dir.to_string(), found.clone());
+    found
+}
+
+/// Modules one file declares, outermost first. Go, Java, Kotlin and C# files only name
+/// their package or namespace; Rust and Python files are modules themselves. Declarations
+/// in comments and strings are ignored.
+pub fn file_modules(
+    lang: &Language,
+    file: &str,
+    code: &str,
+    files: &HashSet<&str>,
+    go_mod: Option<(&str, &str)>,
+) -> Vec<ModuleDecl> {
+    let end = code.lines().count();
+    let syntax = Syntax::of(lang);
+    let mask = syntax.mask(code);
+    match lang {
+        Language::Rust => {
+            let (root, name) = rust_module_path(file, files);
+            let mut decls = vec![ModuleDecl {
+                root: root.clone(),
+                name: name.clone(),
+                start: 0,
+                end,
+            }];
+            // inline `mod tests { .. }`, nested by range
+            let mut inline: Vec<(usize, usize, String)> = Vec::new();
+            for caps in code_matches(&RUST_MOD_RE, code, &mask) {
+                let whole = caps.get(0).unwrap();
+                let close = syntax
+                    .block_end(code, whole.end() - 1)
+                    .unwrap_or(code.len());
+                inline.push((
+                    line_of(code, whole.start()),
+                    line_of(code, close),
+                    caps["name"].to_string(),
+                ));
+            }
+            for (start, stop, m) in &inline {
+                let mut path = name.clone();
+                for (s, e, outer) in &inline {
+                    if s < start && stop <= e {
+                        path = format!("{}::{}", path, outer);
+                    }
+                }
+                decls.push(ModuleDecl {
+                    root: root.clone(),
+                    name: format!("{}::{}", path, m),
+                    start: *start,
+                    end: *stop,
+                });
+            }
+            decls
+        }
+        Language::Python => {
+            let (root, name) = python_module_path(file, files);
+            vec![ModuleDecl {
+                root,
+                name,
+                start: 0,
+                end,
+            }]
+        }
+        Language::Go => code_matches(&GO_PACKAGE_RE, code, &mask)
+            .next()
+            .map(|caps| ModuleDecl {
+                root: String::new(),
+                name: go_import_path(&parent(file), go_mod, &caps["name"]),
+                start: 0,
+                end,
+            })
+            .into_iter()
+            .collect(),
+        Language::Java | Language::Kotlin => code_matches(&JVM_PACKAGE_RE, code, &mask)
+            .next()
+            .map(|caps| ModuleDecl {
+                root: String::new(),
+                name: caps["name"].to_string(),
+                start: 0,
+                end,
+            })
+            .into_iter()
+            .collect(),
+        Language::CSharp => {
+            let mut decls: Vec<ModuleDecl> = Vec::new();
+            for caps in code_matches(&CS_NAMESPACE_RE, code, &mask) {
+                let whole = caps.get(0).unwrap();
+                let start = line_of(code, whole.start());
+                let stop = if caps.name("scoped").is_some() {
+                    end
+                } else {
+                    match code[whole.end()..].find('{') {
+                        Some(i) => line_of(
+                            code,
+                            syntax
+                                .block_end(code, whole.end() + i)
+                                .unwrap_or(code.len()),
+                        ),
+                        None => continue,
+                    }
+                };
+                let outer = decls
+                    .iter()
+                    .filter(|d| d.start < start && stop <= d.end)
+                    .max_by_key(|d| d.start);
+                let name = match outer {
+                    Some(o) => format!("{}.{}", o.name, &caps["name"]),
+                    None => caps["name"].to_string(),
+                };
+                decls.push(ModuleDecl {
+                    root: String::new(),
+                    name,
+                    start,
+                    end: stop,
+                });
+            }
+            decls
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The enclosing module name, one segment up: `crate::lang` for `crate::lang::graphs`.
+fn parent_module<'a>(name: &'a str, sep: &str) -> Option<&'a str> {
+    name.rsplit_once(sep).map(|(p, _)| p)
+}
+
+/// Adds a Module node per Rust module, Python module or package, Go, Java or Kotlin
+/// package and C# namespace, named by its fully-qualified name. Modules CONTAIN the
+/// functions, classes, traits and data models declared in them and their submodules.
+pub fn add_module_nodes<G: Graph>(
+    graph: &mut G,
+    lang: &Language,
+    filez: &[(String, String)],
+) -> usize {
+    if !MODULE_LANGS.contains(lang) {
+        return 0;
+    }
+    let files: HashSet<&str> = filez.iter().map(|(f, _)| f.as_str()).collect();
+    let mut sorted: Vec<&(String, String)> = filez.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut go_mods = HashMap::new();
+    let mut modules: BTreeMap<ModuleKey, NodeData> = BTreeMap::new();
+    let mut ranges: HashMap<&str, Vec<(usize, usize, ModuleKey)>> = HashMap::new();
+    for (file, code) in sorted {
+        let go_mod = match lang {
+            Language::Go => find_go_mod(&parent(file), &mut go_mods),
+            _ => None,
+        };
+        let go_mod = go_mod.as_ref().map(|(d, m)| (d.as_str(), m.as_str()));
+        for decl in file_modules(lang, file, code, &files, go_mod) {
+            let key = (decl.root.clone(), decl.name.clone());
+            modules.entry(key.clone()).or_insert_with(|| {
+                // a package spans files, so it lives at the directory of its first one
+                let spans_files = !matches!(lang, Language::Rust | Language::Python);
+                let home = if spans_files {
+                    parent(file)
+                } else {
+                    file.clone()
+                };
+                let mut nd = NodeData::name_file(&decl.name, &home);
+                if !spans_files {
+                    nd.start = decl.start;
+                    nd.end = decl.end;
+                }
+                nd
+            });
+            ranges
+                .entry(file.as_str())
+                .or_default()
+                .push((decl.start, decl.end, key));
+        }
+    }
+    if modules.is_empty() {
+        return 0;
+    }
+
+    for module in modules.values() {
+        graph.add_node(&NodeType::Module, module);
+    }
+    let sep = match lang {
+        Language::Rust => "::",
+        Language::Go => "/",
+        _ => ".",
+    };
+    for ((root, name), module) in &modules {
+        let mut up = parent_module(name, sep);
+        while let Some(p) = up {
+            if let Some(outer) = modules.get(&(root.clone(), p.to_string())) {
+                graph.add_edge(&Edge::contains(
+                    NodeType::Module,
+                    outer,
+                    NodeType::Module,
+                    module,
+                ));
+                break;
+            }
+            up = parent_module(p, sep);
+        }
+    }
+    for node_type in MEMBER_TYPES {
+        for item in graph.find_nodes_by_type(node_type.clone()) {
+            let Some(decls) = ranges.get(item.file.as_str()) else {
+                continue;
+            };
+            // innermost module around the declaration
+            let Some((_, _, key)) = decls
+                .iter()
+                .filter(|(s, e, _)| *s <= item.start && item.start <= *e)
+                .max_by_key(|(s, _, _)| *s)
+            else {
+                continue;
+            };
+            graph.add_edge(&Edge::contains(
+                NodeType::Module,
+                &modules[key],
+                node_type.clone(),
+                &item,
+            ));
+        }
+    }
+    modules.len()
+}
+
+/// Module nodes grouped by the file they were declared in. Only Rust and Python modules
+/// are files; package and namespace modules are keyed by directory.
+pub fn modules_by_file<G: Graph>(graph: &G) -> HashMap<String, Vec<NodeData>> {
+    let mut by_file: HashMap<String, Vec<NodeData>> = HashMap::new();
+    for module in graph.find_nodes_by_type(NodeType::Module) {
+        by_file.entry(module.file.clone()).or_default().push(module);
+    }
+    by_file
+}
+
+/// Fully-qualified names of everything a Module CONTAINS: `crate::lang::graphs::Graph`,
+/// `com.example.web.UserController.list`. Methods are qualified by their operand.
+pub fn qualified_names<G: Graph>(graph: &G) -> HashMap<NodeKeys, String> {
+    let mut names = HashMap::new();
+    for node_type in MEMBER_TYPES {
+        for (module, item) in
+            graph.find_nodes_with_edge_type(NodeType::Module, node_type, EdgeType::Contains)
+        {
+            let sep = separator(&item.file);
+            let fqn = match item.meta.get("operand") {
+                Some(operand) => format!("{}{sep}{}{sep}{}", module.name, operand, item.name),
+                None => format!("{}{sep}{}", module.name, item.name),
+            };
+            names.insert(NodeKeys::from(&item), fqn);
+        }
+    }
+    names
+}
+
+/// The candidate sharing the longest path prefix with `file`, to pick between same-named
+/// modules of different crates or source roots.
+pub fn closest<'a, T>(candidates: &'a [T], file: &str, path: impl Fn(&T) -> &str) -> Option<&'a T> {
+    candidates.iter().max_by_key(|c| {
+        path(c)
+            .bytes()
+            .zip(file.bytes())
+            .take_while(|(a, b)| a == b)
+            .count()
+    })
+}
+
+fn parent(file: &str) -> String {
+    Path::new(file)
+        .parent()
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn file_stem(file: &str) -> String {
+    Path::new(file)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_module_path() {
+        let files: HashSet<&str> = ["ast/src/lib.rs", "src/fixtures/app/src/main.rs"]
+            .into_iter()
+            .collect();
+        let name = |f: &str| rust_module_path(f, &files).1;
+        assert_eq!(name("ast/src/lib.rs"), "crate");
+        assert_eq!(name("ast/src/lang/graphs/mod.rs"), "crate::lang::graphs");
+        assert_eq!(
+            name("ast/src/lang/graphs/graph.rs"),
+            "crate::lang::graphs::graph"
+        );
+        assert_eq!(name("cli/src/bin/tool/args.rs"), "crate::args");
+        assert_eq!(name("ast/src/tests/api.rs"), "crate::tests::api");
+        assert_eq!(name("ast/tests/api.rs"), "crate");
+        assert_eq!(name("src/fixtures/app/benches/load.rs"), "crate");
+        assert_eq!(rust_module_path("ast/src/lib.rs", &files).0, "ast/src");
+        assert_eq!(
+            rust_module_path("ast/src/main.rs", &files).0,
+            "ast/src/main.rs"
+        );
+        assert_eq!(rust_module_path("cli/src/main.rs", &files).0, "cli/src");
+    }
+
+    #[test]
+    fn test_python_module_path() {
+        let files: HashSet<&str> = ["app/pkg/__init__.py", "app/pkg/sub/__init__.py"]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            python_module_path("app/pkg/sub/views.py", &files),
+            ("app".to_string(), "pkg.sub.views".to_string())
+        );
+        assert_eq!(python_module_path("app/pkg/__init__.py", &files).1, "pkg");
+        assert_eq!(python_module_path("app/main.py", &files).1, "main");
+    }
+
+    #[test]
+    fn test_package_declarations() {
+        let files = HashSet::new();
+        let go = file_modules(
+            &Language::Go,
+            "svc/internal/db/db.go",
+            "package db\n\nfunc Open() {}\n",
+            &files,
+            Some(("svc", "example.com/svc")),
+        );
+        assert_eq!(go[0].name, "example.com/svc/internal/db");
+
+        let kt = file_modules(
+            &Language::Kotlin,
+            "A.kt",
+            "package com.app.ui\n",
+            &files,
+            None,
+        );
+        assert_eq!(kt[0].name, "com.app.ui");
+
+        let cs =
+            "namespace Shop\n{\n    namespace Orders\n    {\n        class Order {}\n    }\n}\n";
+        let cs = file_modules(&Language::CSharp, "Order.cs", cs, &files, None);
+        let names: Vec<_> = cs
+            .iter()
+            .map(|d| (d.name.as_str(), d.start, d.end))
+            .collect();
+        assert_eq!(names, vec![("Shop", 0, 6), ("Shop.Orders", 2, 5)]);
+    }
+
+    #[test]
+    fn test_rust_inline_modules() {
+        let code = "pub fn run() {}\n\n#[cfg(test)]\nmod tests {\n    mod helpers {\n        fn make() {}\n    }\n}\n";
+        let decls = file_modules(
+            &Language::Rust,
+            "x/src/jobs.rs",
+            code,
+            &HashSet::new(),
+            None,
+        );
+        let names: Vec<_> = decls
+            .iter()
+            .map(|d| (d.name.as_str(), d.start, d.end))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("crate::jobs", 0, 8),
+                ("crate::jobs::tests", 3, 7),
+                ("crate::jobs::tests::helpers", 4, 6)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rust_inline_module_ignores_braces_in_literals() {
+        let code = "#[cfg(test)]\nmod tests {\n    const OPEN: &str = \"{\";\n    fn close<'a>(s: &'a str) -> char {\n        '}'\n    }\n}\n\nfn after() {}\n";
+        let decls = file_modules(
+            &Language::Rust,
+            "x/src/jobs.rs",
+            code,
+            &HashSet::new(),
+            None,
+        );
+        let names: Vec<_> = decls
+            .iter()
+            .map(|d| (d.name.as_str(), d.start, d.end))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("crate::jobs", 0, 9), ("crate::jobs::tests", 1, 6)]
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use crate::lang::graphs::{Edge, EdgeType, Graph, NodeType};
+use crate::lang::NodeData;
+use lsp::Language;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+const LANGS: &[Language] = &[
+    Language::Rust,
+    Language::Java,
+    Language::Kotlin,
+    Language::CSharp,
+    Language::Swift,
+    Language::Typescript,
+    Language::Angular,
+    Language::Python,
+];
+
+type TypeKey = (String, String);
+type MethodKey = (String, String, usize);
+
+fn type_key(nd: &NodeData) -> TypeKey {
+    (nd.name.clone(), nd.file.clone())
+}
+
+fn method_key(nd: &NodeData) -> MethodKey {
+    (nd.name.clone(), nd.file.clone(), nd.start)
+}
+
+/// Links each method to the method it overrides in a parent class or the declaration it
+/// implements in a trait/interface, walking `PARENT_OF` and `IMPLEMENTS` upwards and
+/// stopping at the nearest supertype that declares the same name.
+pub fn add_override_edges<G: Graph>(graph: &mut G, lang: &Language) -> usize {
+    if !LANGS.contains(lang) {
+        return 0;
+    }
+    let classes = graph.find_nodes_by_type(NodeType::Class);
+    let traits = graph.find_nodes_by_type(NodeType::Trait);
+    if classes.is_empty() {
+        return 0;
+    }
+
+    let mut methods: HashMap<TypeKey, Vec<NodeData>> = HashMap::new();
+    let mut seen: HashSet<(TypeKey, MethodKey)> = HashSet::new();
+    let mut add_method = |owner: &NodeData, f: NodeData| {
+        if seen.insert((type_key(owner), method_key(&f))) {
+            methods.entry(type_key(owner)).or_default().push(f);
+        }
+    };
+    for (owner, f) in
+        graph.find_nodes_with_edge_type(NodeType::Class, NodeType::Function, EdgeType::Operand)
+    {
+        add_method(&owner, f);
+    }
+    for (owner, f) in
+        graph.find_nodes_with_edge_type(NodeType::Trait, NodeType::Function, EdgeType::Operand)
+    {
+        add_method(&owner, f);
+    }
+    // trait and interface declarations often have no operand edge: fall back to the
+    // functions declared inside the owner's range, leaving those of a nested type to it
+    let mut functions_by_file: HashMap<String, Vec<NodeData>> = HashMap::new();
+    for f in graph.find_nodes_by_type(NodeType::Function) {
+        functions_by_file.entry(f.file.clone()).or_default().push(f);
+    }
+    let owners: Vec<&NodeData> = classes.iter().chain(traits.iter()).collect();
+    for owner in &owners {
+        let Some(functions) = functions_by_file.get(&owner.file) else {
+            continue;
+        };
+        for f in functions {
+            let nested = owners
+                .iter()
+                .any(|o| o.file == owner.file && encloses(owner, o) && encloses(o, f));
+            if encloses(owner, f) && !nested {
+                add_method(owner, f.clone());
+            }
+        }
+    }
+
+    let mut supertypes: HashMap<TypeKey, Vec<TypeKey>> = HashMap::new();
+    for (parent, child) in
+        graph.find_nodes_with_edge_type(NodeType::Class, NodeType::Class, EdgeType::ParentOf)
+    {
+        supertypes
+            .entry(type_key(&child))
+            .or_default()
+            .push(type_key(&parent));
+    }
+    for (class, trait_) in
+        graph.find_nodes_with_edge_type(NodeType::Class, NodeType::Trait, EdgeType::Implements)
+    {
+        supertypes
+            .entry(type_key(&class))
+            .or_default()
+            .push(type_key(&trait_));
+    }
+    // `class Impl : Repo` in Kotlin/Swift/C# records the interface as the parent
+    for class in &classes {
+        let names = ["parent", "implements"]
+            .iter()
+            .filter_map(|k| class.meta.get(*k))
+            .flat_map(|v| v.split(',').map(str::trim).collect::<Vec<_>>());
+        for name in names {
+            let candidates: Vec<&NodeData> = traits.iter().filter(|t| t.name == name).collect();
+            let Some(t) = candidates
+                .iter()
+                .find(|t| t.file == class.file)
+                .or_else(|| candidates.first())
+            else {
+                continue;
+            };
+            let entry = supertypes.entry(type_key(class)).or_default();
+            if !entry.contains(&type_key(t)) {
+                entry.push(type_key(t));
+            }
+        }
+    }
+
+    let mut edges = Vec::new();
+    for (owner, parents) in &supertypes {
+        let Some(own_methods) = methods.get(owner) else {
+            continue;
+        };
+        for method in own_methods {
+            if method.name == owner.0 || method.name == "constructor" {
+                continue;
+            }
+            for base in find_base_methods(method, parents, &supertypes, &methods) {
+                if method_key(&base) != method_key(method) {
+                    edges.push(Edge::overrides(method, &base));
+                }
+            }
+        }
+    }
+    let count = edges.len();
+    for edge in edges {
+        graph.add_edge(&edge);
+    }
+    count
+}
+
+fn encloses(outer: &NodeData, inner: &NodeData) -> bool {
+    inner.start > outer.start && inner.end <= outer.end
+}
+
+fn find_base_methods(
+    method: &NodeData,
+    parents: &[TypeKey],
+    supertypes: &HashMap<TypeKey, Vec<TypeKey>>,
+    methods: &HashMap<TypeKey, Vec<NodeData>>,
+) -> Vec<NodeData> {
+    let mut found = Vec::new();
+    let mut visited: HashSet<&TypeKey> = HashSet::new();
+    let mut queue: VecDeque<&TypeKey> = parents.iter().collect();
+    while let Some(ty) = queue.pop_front() {
+        if !visited.insert(ty) {
+            continue;
+        }
+        let matches: Vec<&NodeData> = methods
+            .get(ty)
+            .map(|m| m.iter().filter(|f| f.name == method.name).collect())
+            .unwrap_or_default();
+        if matches.is_empty() {
+            if let Some(next) = supertypes.get(ty) {
+                queue.extend(next.iter());
+            }
+            continue;
+        }
+        for m in matches {
+            if !found
+                .iter()
+                .any(|f: &NodeData| method_key(f) == method_key(m))
+            {
+                found.push(m.clone());
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str, start: usize) -> NodeData {
+        NodeData {
+            name: name.to_string(),
+            file: "shapes.py".to_string(),
+            start,
+            end: start + 1,
+            ..Default::default()
+        }
+    }
+
+    fn key(name: &str) -> TypeKey {
+        (name.to_string(), "shapes.py".to_string())
+    }
+
+    #[test]
+    fn test_find_base_methods_stops_at_nearest_declaration() {
+        // Shape <- Polygon <- Square, with Square also implementing Drawable
+        let mut supertypes = HashMap::new();
+        supertypes.insert(key("Polygon"), vec![key("Shape")]);
+        supertypes.insert(key("Square"), vec![key("Polygon"), key("Drawable")]);
+        let mut methods = HashMap::new();
+        methods.insert(key("Shape"), vec![node("area", 1), node("name", 3)]);
+        methods.insert(key("Polygon"), vec![node("area", 10)]);
+        methods.insert(key("Drawable"), vec![node("draw", 20)]);
+
+        let parents = &supertypes[&key("Square")];
+        let base = |name: &str| -> Vec<usize> {
+            find_base_methods(&node(name, 30), parents, &supertypes, &methods)
+                .iter()
+                .map(|f| f.start)
+                .collect()
+        };
+        // Polygon.area shadows Shape.area
+        assert_eq!(base("area"), vec![10]);
+        // two levels up when the middle class does not redeclare it
+        assert_eq!(base("name"), vec![3]);
+        assert_eq!(base("draw"), vec![20]);
+        assert!(base("perimeter").is_empty());
+    }
+}
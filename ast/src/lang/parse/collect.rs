@@ -134,6 +134,27 @@ impl Lang {
         Ok(results)
     }
 
+    pub fn collect_class_dependencies(
+        &self,
+        q: &Query,
+        code: &str,
+        file: &str,
+    ) -> Result<Vec<(String, String, String)>> {
+        let tree = self.parse(code, &NodeType::Class)?;
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(q, tree.root_node(), code.as_bytes());
+        let mut results = Vec::new();
+
+        while let Some(m) = matches.next() {
+            let (class_name, dependency) = self.format_class_dependency(m, code, q)?;
+            if !class_name.is_empty() && !dependency.is_empty() {
+                results.push((class_name, dependency, file.to_string()));
+            }
+        }
+
+        Ok(results)
+    }
+
     pub fn collect_implements_edges<G: Graph>(
         &self,
         q: &Query,
@@ -487,6 +508,7 @@ impl Lang {
             let mut import_names = Vec::new();
             let mut import_source = None;
             let mut is_reexport = false;
+            let mut is_module = false;
 
             Self::loop_captures_multi(q, m, code, |body, _node, o| {
                 if o == IMPORTS_NAME {
@@ -495,6 +517,8 @@ impl Lang {
                     import_source = Some(trim_quotes(&body).to_string());
                 } else if o == IMPORTS_REEXPORT {
                     is_reexport = true;
+                } else if o == IMPORTS_MODULE {
+                    is_module = true;
                 }
                 Ok(())
             })?;
@@ -502,7 +526,8 @@ impl Lang {
             if let Some(source_path) = import_source {
                 let resolved_path = self.lang.resolve_import_path(&source_path, file);
 
-                if is_reexport && import_names.is_empty() {
+                // whole-module imports (reexports, lua require) link file to file
+                if (is_reexport || is_module) && import_names.is_empty() {
                     let file_nodes = graph.find_nodes_by_file_ends_with(NodeType::File, file);
                     let file_node = file_nodes
                         .first()
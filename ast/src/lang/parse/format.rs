@@ -6,11 +6,12 @@ use crate::lang::{graphs::Graph, *};
 use lsp::{Cmd as LspCmd, Position, Res as LspRes};
 use shared::Result;
 use streaming_iterator::StreamingIterator;
-use tree_sitter::QueryMatch;
-use serde_json;
+use tree_sitter::{Node as TreeNode, QueryMatch};
 
 use super::super::queries::consts::*;
-use super::utils::{clean_class_name, find_def, is_capitalized, log_cmd, trim_quotes};
+use super::utils::{
+    clean_class_name, count_call_arguments, find_def, is_capitalized, log_cmd, trim_quotes,
+};
 
 impl Lang {
     pub fn format_class_with_associations<G: Graph>(
@@ -323,6 +324,25 @@ impl Lang {
         Ok((class_name, trait_name))
     }
 
+    pub fn format_class_dependency(
+        &self,
+        m: &QueryMatch,
+        code: &str,
+        q: &Query,
+    ) -> Result<(String, String)> {
+        let mut class_name = String::new();
+        let mut dependency = String::new();
+        Self::loop_captures(q, m, code, |body, _node, o| {
+            if o == CLASS_NAME {
+                class_name = body;
+            } else if o == CLASS_DEPENDENCY {
+                dependency = body;
+            }
+            Ok(())
+        })?;
+        Ok((class_name, dependency))
+    }
+
     pub fn format_endpoint<G: Graph>(
         &self,
         m: &QueryMatch,
@@ -354,6 +374,18 @@ impl Lang {
                 endp.body = body;
                 endp.start = node.start_position().row;
                 endp.end = node.end_position().row;
+                if let Some(name) = self.lang.decorated_handler(node) {
+                    let method = name.parent().unwrap_or(name);
+                    endp.end = method.end_position().row;
+                    endp.body = code[node.start_byte()..method.end_byte()].to_string();
+                    endp.add_handler(name.utf8_text(code.as_bytes()).unwrap_or_default());
+                    let p = name.start_position();
+                    handler_position = Some(Position::new(file, p.row as u32, p.column as u32)?);
+                    if let Some(graph) = graph {
+                        params.parents =
+                            self.endpoint_parents(name, code, file, graph, &mut endp)?;
+                    }
+                }
             } else if o == ENDPOINT_COMMENT {
                 endp.docs = Some(self.clean_and_combine_comments(&[body]));
             } else if o == HANDLER {
@@ -362,28 +394,14 @@ impl Lang {
                 let p = node.start_position();
                 handler_position = Some(Position::new(file, p.row as u32, p.column as u32)?);
                 if let Some(graph) = graph {
-                    // collect parents
-                    params.parents =
-                        self.lang.find_endpoint_parents(node, code, file, &|name| {
-                            graph
-                                .find_nodes_by_name(NodeType::Function, name)
-                                .first()
-                                .cloned()
-                        })?;
-                    // Extract middleware from parents and store in endpoint metadata
-                    let middlewares: Vec<String> = params
-                        .parents
-                        .iter()
-                        .filter(|p| matches!(p.item_type, HandlerItemType::Middleware))
-                        .map(|p| p.name.clone())
-                        .collect();
-                    if !middlewares.is_empty() {
-                        endp.add_middleware(&middlewares.join(","));
-                    }
+                    params.parents = self.endpoint_parents(node, code, file, graph, &mut endp)?;
                 }
             } else if o == ANONYMOUS_FUNCTION {
                 let p = node.start_position();
                 handler_position = Some(Position::new(file, p.row as u32, p.column as u32)?);
+                if let Some(graph) = graph.filter(|_| self.lang.anonymous_handler_parents()) {
+                    params.parents = self.endpoint_parents(node, code, file, graph, &mut endp)?;
+                }
 
                 let method = endp
                     .meta
@@ -577,6 +595,31 @@ impl Lang {
 
         Ok(vec![(endp, handler)])
     }
+    // collect the enclosing route scopes of a handler, storing their middleware on the endpoint
+    fn endpoint_parents<G: Graph>(
+        &self,
+        node: TreeNode,
+        code: &str,
+        file: &str,
+        graph: &G,
+        endp: &mut NodeData,
+    ) -> Result<Vec<HandlerItem>> {
+        let parents = self.lang.find_endpoint_parents(node, code, file, &|name| {
+            graph
+                .find_nodes_by_name(NodeType::Function, name)
+                .first()
+                .cloned()
+        })?;
+        let middlewares: Vec<String> = parents
+            .iter()
+            .filter(|p| matches!(p.item_type, HandlerItemType::Middleware))
+            .map(|p| p.name.clone())
+            .collect();
+        if !middlewares.is_empty() {
+            endp.add_middleware(&middlewares.join(","));
+        }
+        Ok(parents)
+    }
     pub fn format_data_model(
         &self,
         m: &QueryMatch,
@@ -587,6 +630,7 @@ impl Lang {
         let mut inst = NodeData::in_file(file);
         let mut attributes = Vec::new();
         let mut comments = Vec::new();
+        let mut fields = Vec::new();
         Self::loop_captures(q, m, code, |body, node, o| {
             if o == STRUCT_NAME {
                 inst.name = trim_quotes(&body).to_string();
@@ -594,6 +638,7 @@ impl Lang {
                 inst.body = body;
                 inst.start = node.start_position().row;
                 inst.end = node.end_position().row;
+                fields = self.lang.data_model_fields(node, code);
             } else if o == ATTRIBUTES {
                 if let Some(filtered) = self.lang.filter_attribute(&body, &o) {
                     attributes.push(filtered);
@@ -609,6 +654,7 @@ impl Lang {
         if !comments.is_empty() {
             inst.docs = Some(self.clean_and_combine_comments(&comments));
         }
+        inst.add_fields(&fields);
         Ok(inst)
     }
 
@@ -686,8 +732,7 @@ impl Lang {
         let mut return_type_data_models = Vec::new();
         let mut comments = Vec::new();
         let mut attributes = Vec::new();
-        let mut raw_args: Option<String> = None;
-        let mut raw_return: Option<String> = None;
+        let mut signature = None;
         let mut def_start_byte: Option<usize> = None;
         let mut def_end_byte: Option<usize> = None;
         let mut args_end_byte: Option<usize> = None;
@@ -714,6 +759,7 @@ impl Lang {
                 if attributes_start_byte.is_none() {
                     attributes_start_byte = Some(node.start_byte());
                 }
+                signature = self.lang.function_signature(node, code);
                 // parent
                 parent = self.lang.find_function_parent(
                     node,
@@ -791,6 +837,24 @@ impl Lang {
                         }
                     }
                 }
+                // derived queries on a data-access interface operate on its model
+                if let Some((model, operation)) = self.lang.repository_operation(node, code) {
+                    func.meta.insert("operation".to_string(), operation);
+                    if !models.iter().any(|e| e.target.node_data.name == model) {
+                        if let Some(dmr) = graph
+                            .find_nodes_by_name(NodeType::DataModel, &model)
+                            .first()
+                            .cloned()
+                        {
+                            models.push(Edge::contains(
+                                NodeType::Function,
+                                &func,
+                                NodeType::DataModel,
+                                &dmr,
+                            ));
+                        }
+                    }
+                }
                 // find variables in functions
                 let vuq = self.lang.identifier_query();
                 if !vuq.is_empty() {
@@ -849,10 +913,8 @@ impl Lang {
                     }
                 }
             } else if o == ARGUMENTS {
-                raw_args = Some(body);
                 args_end_byte = Some(node.end_byte());
             } else if o == RETURN_TYPES {
-                raw_return = Some(body);
                 return_end_byte = Some(node.end_byte());
                 if let Some(lsp) = lsp_tx {
                     for (name, pos) in self.find_type_identifiers(node, code, file)? {
@@ -937,23 +999,13 @@ impl Lang {
             }
         }
 
-        if matches!(self.kind, Language::Typescript) {
-            if let Some(ret) = &raw_return {
-                let clean = ret.trim_start_matches(':').trim().to_string();
-                if !clean.is_empty() {
-                    func.meta.insert("return_type".to_string(), clean);
-                }
-            }
-            if let Some(args) = &raw_args {
-                let pts = parse_param_types(args);
-                if !pts.is_empty() {
-                    if let Ok(json) = serde_json::to_string(&pts) {
-                        func.meta.insert("param_types".to_string(), json);
-                    }
-                }
-            }
+        if let Some(signature) = &signature {
+            func.add_signature(signature);
         }
 
+        let flows = data_flow::data_flow_edges(&mut func, &models);
+        models.extend(flows);
+
         let mut return_types = Vec::new();
         for t in return_type_data_models {
             return_types.push(Edge::contains(
@@ -1032,6 +1084,7 @@ impl Lang {
         let mut class_call = None;
         let mut call_name_and_point = None;
         let mut is_variable_call = false;
+        let mut arg_count = None;
 
         Self::loop_captures(q, m, code, |body, node, o| {
             if o == FUNCTION_NAME {
@@ -1045,6 +1098,7 @@ impl Lang {
                 }
             } else if o == FUNCTION_CALL {
                 fc.source = NodeKeys::new(caller_name, file, caller_start);
+                arg_count = count_call_arguments(&node);
             } else if o == OPERAND {
                 fc.operand = Some(body.clone());
                 if self.lang.direct_class_calls() {
@@ -1075,6 +1129,7 @@ impl Lang {
         if called.is_empty() {
             return Ok(None);
         }
+        fc.call_site = Some(CallSite::new(call_point.row, call_point.column, arg_count));
 
         // AST-level type resolver: fires before skip list so type-resolved calls (e.g. api.users.create)
         // are not suppressed by heuristic skip rules for common method names.
@@ -1113,6 +1168,8 @@ impl Lang {
                         called, &t.file
                     ));
                     fc.target = NodeKeys::new(&called, &t.file, t.start);
+                    fc.confidence = 1.0;
+                    fc.strategy = "lsp".to_string();
                     // set extenal func so this is marked as USES edge rather than CALLS
                     if t.body.is_empty() && t.docs.is_some() {
                         log_cmd(format!("==> ! found target is external {:?}!!!", called));
@@ -1176,6 +1233,8 @@ impl Lang {
                                         called, &t_file
                                     ));
                                     fc.target = NodeKeys::new(&called, &t_file.file, t_file.start);
+                                    fc.confidence = 0.95;
+                                    fc.strategy = "lsp_impl".to_string();
                                 }
                             }
                         }
@@ -1235,6 +1294,8 @@ impl Lang {
                     return Ok(None);
                 }
                 fc.target = NodeKeys::new(&called, "unverified", call_point.row);
+                fc.confidence = 0.0;
+                fc.strategy = "unverified".to_string();
             }
         }
 
@@ -1508,74 +1569,3 @@ impl Lang {
         lines.join("\n")
     }
 }
-
-fn parse_param_types(raw: &str) -> Vec<std::collections::HashMap<String, String>> {
-    let inner = raw
-        .trim()
-        .strip_prefix('(')
-        .and_then(|s| s.strip_suffix(')'))
-        .unwrap_or(raw);
-    split_top_level_params(inner)
-        .into_iter()
-        .filter_map(|p| {
-            let p = p.trim().strip_prefix("...").unwrap_or(p.trim());
-            if p.is_empty() {
-                return None;
-            }
-            let colon = find_top_level_colon(p)?;
-            let name = p[..colon].trim().trim_end_matches('?').to_string();
-            let type_str = p[colon + 1..].trim().to_string();
-            if name.is_empty() || type_str.is_empty() {
-                return None;
-            }
-            let mut m = std::collections::HashMap::new();
-            m.insert("name".to_string(), name);
-            m.insert("type".to_string(), type_str);
-            Some(m)
-        })
-        .collect()
-}
-
-fn split_top_level_params(s: &str) -> Vec<String> {
-    let mut parts = Vec::new();
-    let mut depth: i32 = 0;
-    let mut current = String::new();
-    for ch in s.chars() {
-        match ch {
-            '<' | '(' | '[' | '{' => {
-                depth += 1;
-                current.push(ch);
-            }
-            '>' | ')' | ']' | '}' => {
-                depth -= 1;
-                current.push(ch);
-            }
-            ',' if depth == 0 => {
-                let trimmed = current.trim().to_string();
-                if !trimmed.is_empty() {
-                    parts.push(trimmed);
-                }
-                current = String::new();
-            }
-            _ => current.push(ch),
-        }
-    }
-    let trimmed = current.trim().to_string();
-    if !trimmed.is_empty() {
-        parts.push(trimmed);
-    }
-    parts
-}
-
-fn find_top_level_colon(s: &str) -> Option<usize> {
-    let mut depth: i32 = 0;
-    for (i, ch) in s.char_indices() {
-        match ch {
-            '<' | '(' | '[' | '{' => depth += 1,
-            '>' | ')' | ']' | '}' => depth -= 1,
-            ':' if depth == 0 => return Some(i),
-            _ => {}
-        }
-    }
-    None
-}
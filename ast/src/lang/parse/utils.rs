@@ -29,6 +29,24 @@ pub fn log_cmd(cmd: String) {
     debug!("{}", cmd);
 }
 
+/// Number of arguments passed at a call expression, read from its argument list.
+/// Returns `None` when the grammar exposes no recognizable argument node.
+pub fn count_call_arguments(call: &TreeNode) -> Option<usize> {
+    let args = call.child_by_field_name("arguments").or_else(|| {
+        let mut cursor = call.walk();
+        let found = call
+            .named_children(&mut cursor)
+            .find(|c| c.kind().contains("argument"));
+        found
+    })?;
+    let mut cursor = args.walk();
+    let count = args
+        .named_children(&mut cursor)
+        .filter(|c| !c.kind().contains("comment"))
+        .count();
+    Some(count)
+}
+
 pub fn is_capitalized(name: &str) -> bool {
     if name.is_empty() {
         return false;
@@ -7,6 +7,7 @@ pub const IMPORTS_FROM: &str = "imports-from";
 pub const IMPORTS_NAME: &str = "imports-name";
 pub const IMPORTS_ALIAS: &str = "imports-alias";
 pub const IMPORTS_REEXPORT: &str = "imports-reexport";
+pub const IMPORTS_MODULE: &str = "imports-module";
 pub const TRAIT: &str = "trait";
 pub const TRAIT_NAME: &str = "trait-name";
 pub const TRAIT_METHOD_NAME: &str = "trait-method-name";
@@ -14,6 +15,7 @@ pub const MODULE_NAME: &str = "module-name";
 pub const CLASS_NAME: &str = "class-name";
 pub const CLASS_PARENT: &str = "class-parent";
 pub const INCLUDED_MODULES: &str = "included-modules";
+pub const CLASS_DEPENDENCY: &str = "class-dependency";
 pub const INSTANCE: &str = "instance";
 pub const INSTANCE_NAME: &str = "instance-name";
 pub const CLASS_DEFINITION: &str = "class-definition";
@@ -2,9 +2,17 @@ use super::super::*;
 use super::consts::*;
 use super::HandlerParams;
 use crate::lang::parse::trim_quotes;
+use regex::Regex;
 use shared::error::{Context, Result};
+use std::sync::LazyLock;
 use tree_sitter::{Language, Parser, Query, Tree};
 
+static ROUTE_PATTERN_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bpattern\s*:\s*@?"([^"]*)""#).unwrap());
+static STRING_ARG_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"@?"([^"]*)""#).unwrap());
+
+const DEFAULT_CONTROLLER_ROUTE: &str = "{controller=Home}/{action=Index}/{id?}";
+
 pub struct CSharp(Language);
 
 impl Default for CSharp {
@@ -383,9 +391,87 @@ impl Stack for CSharp {
                     )
                 ) @{ROUTE}"#
             ),
+            // Conventional routing: public actions of a controller without attributes
+            format!(
+                r#"(class_declaration
+                    name: (identifier) @{CONTROLLER_CONTEXT} (#match? @{CONTROLLER_CONTEXT} "Controller$")
+                    (declaration_list
+                        (method_declaration
+                            (modifier) @visibility (#eq? @visibility "public")
+                            name: (identifier) @{HANDLER}
+                        ) @{ROUTE} (#not-match? @{ROUTE} "^\\[")
+                    )
+                )"#
+            ),
         ]
     }
 
+    // app.MapControllerRoute(name: "default", pattern: "{controller}/{action}")
+    fn endpoint_group_find(&self) -> Option<String> {
+        Some(format!(
+            r#"(invocation_expression
+                function: (member_access_expression
+                    name: (identifier) @{ENDPOINT_VERB} (#match? @{ENDPOINT_VERB} "^Map(Default)?ControllerRoute$")
+                )
+                arguments: (argument_list) @{ENDPOINT_GROUP}
+            ) @{ROUTE}"#
+        ))
+    }
+
+    fn find_endpoint_parents(
+        &self,
+        node: TreeNode,
+        code: &str,
+        _file: &str,
+        _callback: &dyn Fn(&str) -> Option<NodeData>,
+    ) -> Result<Vec<HandlerItem>> {
+        // minimal API: the handler is an argument of `<receiver>.MapGet(...)`
+        if let Some(map_call) = enclosing_invocation(node) {
+            let receiver = map_call
+                .child_by_field_name("function")
+                .and_then(|f| f.child_by_field_name("expression"));
+            let prefixes = receiver
+                .map(|r| map_group_prefixes(r, code, 0))
+                .unwrap_or_default();
+            return Ok(prefixes
+                .into_iter()
+                .map(|name| HandlerItem {
+                    name,
+                    item_type: HandlerItemType::Namespace,
+                })
+                .collect());
+        }
+
+        // conventional action: report the controller's own [Route], if any
+        let Some(method) = node.parent().filter(|p| p.kind() == "method_declaration") else {
+            return Ok(Vec::new());
+        };
+        let mut cursor = method.walk();
+        if method
+            .named_children(&mut cursor)
+            .any(|c| c.kind() == "attribute_list")
+        {
+            return Ok(Vec::new());
+        }
+        let class = method
+            .parent()
+            .and_then(|body| body.parent())
+            .filter(|c| c.kind() == "class_declaration");
+        Ok(class
+            .map(|c| class_route_templates(c, code))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|name| HandlerItem {
+                name,
+                item_type: HandlerItemType::Namespace,
+            })
+            .collect())
+    }
+
+    fn anonymous_handler_parents(&self) -> bool {
+        true
+    }
+
     fn find_function_parent(
         &self,
         node: TreeNode,
@@ -422,16 +508,48 @@ impl Stack for CSharp {
 
     fn handler_finder(
         &self,
-        endpoint: NodeData,
+        mut endpoint: NodeData,
         find_fn: &dyn Fn(&str, &str) -> Option<NodeData>,
-        _find_fns_in: &dyn Fn(&str) -> Vec<NodeData>,
-        _handler_params: HandlerParams,
+        find_fns_in: &dyn Fn(&str) -> Vec<NodeData>,
+        handler_params: HandlerParams,
     ) -> Vec<(NodeData, Option<Edge>)> {
         let mut edge = None;
 
+        let mut segments: Vec<&str> = handler_params
+            .parents
+            .iter()
+            .filter(|p| matches!(p.item_type, HandlerItemType::Namespace))
+            .map(|p| p.name.as_str())
+            .collect();
+        if endpoint.meta.contains_key("controller_context") {
+            // a controller with its own [Route] is attribute routed, not conventional
+            if !segments.is_empty() {
+                return Vec::new();
+            }
+        } else if !segments.is_empty() {
+            segments.push(&endpoint.name);
+            endpoint.name = join_route(&segments);
+        }
+
         if let Some(handler) = endpoint.meta.get("handler") {
             let handler_name = trim_quotes(handler);
-            if let Some(nd) = find_fn(&handler_name, &endpoint.file) {
+            // method groups may be qualified: `UserHandlers.GetUser`
+            let (owner, method) = match handler_name.rsplit_once('.') {
+                Some((owner, method)) => (Some(owner), method),
+                None => (None, handler_name),
+            };
+            let target = find_fn(handler_name, &endpoint.file)
+                .or_else(|| find_fn(method, &endpoint.file))
+                .or_else(|| {
+                    let candidates: Vec<NodeData> = find_fns_in(".cs")
+                        .into_iter()
+                        .filter(|f| f.name == method)
+                        .collect();
+                    owner
+                        .and_then(|o| candidates.iter().find(|f| f.file.contains(o)).cloned())
+                        .or_else(|| candidates.into_iter().next())
+                });
+            if let Some(nd) = target {
                 edge = Some(Edge::handler(&endpoint, &nd));
             }
         }
@@ -439,6 +557,69 @@ impl Stack for CSharp {
         vec![(endpoint, edge)]
     }
 
+    fn update_endpoint(&self, nd: &mut NodeData, _call: &Option<String>) {
+        // HttpGet / MapGet -> GET
+        if let Some(verb) = nd.meta.get("verb").cloned() {
+            let plain = verb.trim_start_matches("HTTP").trim_start_matches("MAP");
+            if matches!(
+                plain,
+                "GET" | "POST" | "PUT" | "DELETE" | "PATCH" | "OPTIONS" | "HEAD"
+            ) && plain != verb
+            {
+                nd.add_verb(plain);
+            }
+        }
+        // conventional actions take the default `{controller}/{action}` shape
+        if let (Some(controller), Some(action)) = (
+            nd.meta.get("controller_context").cloned(),
+            nd.meta.get("handler").cloned(),
+        ) {
+            nd.name = format!("/{}/{}", controller_short_name(&controller), action);
+            if !nd.meta.contains_key("verb") {
+                nd.add_verb("ANY");
+            }
+        }
+    }
+
+    fn match_endpoint_groups(
+        &self,
+        groups: &[NodeData],
+        endpoints: &[NodeData],
+        _find_import_node: &dyn Fn(&str) -> Option<NodeData>,
+    ) -> Vec<(NodeData, String)> {
+        let templates: Vec<String> = groups
+            .iter()
+            .filter_map(|g| match g.meta.get("verb").map(|v| v.as_str()) {
+                Some("MAPDEFAULTCONTROLLERROUTE") => Some(DEFAULT_CONTROLLER_ROUTE.to_string()),
+                Some("MAPCONTROLLERROUTE") => {
+                    conventional_template(g.meta.get("group").map(|a| a.as_str()).unwrap_or(""))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut matches = Vec::new();
+        for endpoint in endpoints {
+            let (Some(controller), Some(action)) = (
+                endpoint.meta.get("controller_context"),
+                endpoint.meta.get("handler"),
+            ) else {
+                continue;
+            };
+            for template in &templates {
+                let rendered =
+                    render_conventional_route(template, controller_short_name(controller), action);
+                // only templates that end in `{controller}/{action}` can be expressed as a prefix
+                if let Some(prefix) = rendered.strip_suffix(endpoint.name.as_str()) {
+                    if !prefix.is_empty() {
+                        matches.push((endpoint.clone(), prefix.to_string()));
+                    }
+                }
+            }
+        }
+        matches
+    }
+
     fn add_endpoint_verb(&self, nd: &mut NodeData, call: &Option<String>) -> Option<String> {
         if let Some(verb_attr) = call {
             let verb = verb_attr
@@ -492,3 +673,171 @@ fn query_to_ident(query: Query, node: TreeNode, code: &str) -> Result<Option<Str
     }
     Ok(None)
 }
+
+// the `app.MapGet(...)` call a handler argument belongs to
+fn enclosing_invocation(node: TreeNode) -> Option<TreeNode> {
+    let call = node.parent()?.parent()?.parent()?;
+    (call.kind() == "invocation_expression").then_some(call)
+}
+
+// `app.MapGroup("/api").MapGroup("/users")`, following `var users = api.MapGroup(...)` declarations
+fn map_group_prefixes(expr: TreeNode, code: &str, depth: usize) -> Vec<String> {
+    if depth > 8 {
+        return Vec::new();
+    }
+    match expr.kind() {
+        "invocation_expression" => {
+            let Some(function) = expr
+                .child_by_field_name("function")
+                .filter(|f| f.kind() == "member_access_expression")
+            else {
+                return Vec::new();
+            };
+            let mut prefixes = function
+                .child_by_field_name("expression")
+                .map(|e| map_group_prefixes(e, code, depth + 1))
+                .unwrap_or_default();
+            let name = function
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(code.as_bytes()).ok())
+                .unwrap_or("");
+            if name == "MapGroup" {
+                if let Some(prefix) = first_string_argument(expr, code) {
+                    prefixes.push(prefix);
+                }
+            }
+            prefixes
+        }
+        "identifier" => {
+            let name = expr.utf8_text(code.as_bytes()).unwrap_or("");
+            let mut root = expr;
+            while let Some(parent) = root.parent() {
+                root = parent;
+            }
+            let mut declared = None;
+            find_declared_value(root, name, expr.start_byte(), code, &mut declared);
+            declared
+                .map(|value| map_group_prefixes(value, code, depth + 1))
+                .unwrap_or_default()
+        }
+        _ => Vec::new(),
+    }
+}
+
+// the initializer of the last `var <name> = ...` declared before `before`
+fn find_declared_value<'a>(
+    node: TreeNode<'a>,
+    name: &str,
+    before: usize,
+    code: &str,
+    found: &mut Option<TreeNode<'a>>,
+) {
+    if node.start_byte() >= before {
+        return;
+    }
+    if node.kind() == "variable_declarator" {
+        let mut cursor = node.walk();
+        let children: Vec<TreeNode> = node.named_children(&mut cursor).collect();
+        let declares = children
+            .first()
+            .is_some_and(|c| c.kind() == "identifier" && c.utf8_text(code.as_bytes()) == Ok(name));
+        if declares {
+            if let Some(value) = children
+                .iter()
+                .skip(1)
+                .find(|c| c.kind() != "bracketed_argument_list")
+            {
+                // older grammars wrap the initializer in an equals_value_clause
+                *found = if value.kind() == "equals_value_clause" {
+                    value.named_child(0)
+                } else {
+                    Some(*value)
+                };
+            }
+        }
+        return;
+    }
+    let mut cursor = node.walk();
+    let children: Vec<TreeNode> = node.named_children(&mut cursor).collect();
+    for child in children {
+        find_declared_value(child, name, before, code, found);
+    }
+}
+
+fn first_string_argument(call: TreeNode, code: &str) -> Option<String> {
+    let arg = call.child_by_field_name("arguments")?.named_child(0)?;
+    let literal = arg
+        .named_child(0)
+        .filter(|l| l.kind() == "string_literal")?;
+    Some(trim_quotes(literal.utf8_text(code.as_bytes()).ok()?).to_string())
+}
+
+// [Route("...")] templates on a controller class
+fn class_route_templates(class: TreeNode, code: &str) -> Vec<String> {
+    let mut templates = Vec::new();
+    let mut cursor = class.walk();
+    for list in class.named_children(&mut cursor) {
+        if list.kind() != "attribute_list" {
+            continue;
+        }
+        let mut list_cursor = list.walk();
+        for attribute in list.named_children(&mut list_cursor) {
+            let is_route = attribute
+                .child_by_field_name("name")
+                .and_then(|n| n.utf8_text(code.as_bytes()).ok())
+                == Some("Route");
+            if is_route {
+                let text = attribute.utf8_text(code.as_bytes()).unwrap_or("");
+                if let Some(c) = STRING_ARG_RE.captures(text) {
+                    templates.push(c[1].to_string());
+                }
+            }
+        }
+    }
+    templates
+}
+
+fn join_route(segments: &[&str]) -> String {
+    let parts: Vec<&str> = segments
+        .iter()
+        .flat_map(|s| s.split('/'))
+        .filter(|s| !s.is_empty())
+        .collect();
+    format!("/{}", parts.join("/"))
+}
+
+fn controller_short_name(controller: &str) -> &str {
+    controller.strip_suffix("Controller").unwrap_or(controller)
+}
+
+// `MapControllerRoute("default", "{controller}/{action}")` or `pattern: "..."`
+fn conventional_template(args: &str) -> Option<String> {
+    if let Some(c) = ROUTE_PATTERN_RE.captures(args) {
+        return Some(c[1].to_string());
+    }
+    STRING_ARG_RE
+        .captures_iter(args)
+        .nth(1)
+        .map(|c| c[1].to_string())
+}
+
+// fill in {controller} and {action}; optional and defaulted parameters are dropped
+fn render_conventional_route(template: &str, controller: &str, action: &str) -> String {
+    let segments: Vec<&str> = template
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .filter_map(|segment| {
+            if segment.starts_with("{controller") {
+                Some(controller)
+            } else if segment.starts_with("{action") {
+                Some(action)
+            } else if segment.starts_with('{') && (segment.ends_with("?}") || segment.contains('='))
+            {
+                None
+            } else {
+                Some(segment)
+            }
+        })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
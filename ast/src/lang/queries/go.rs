@@ -1,5 +1,6 @@
 use super::super::*;
 use super::consts::*;
+use crate::lang::parse::trim_quotes;
 use lsp::{Cmd as LspCmd, CmdSender, Position, Res as LspRes};
 use shared::error::{Context, Result};
 use tree_sitter::{Language, Parser, Query, Tree};
@@ -238,6 +239,32 @@ impl Stack for Go {
                 )
             ) @{ROUTE}"#
             ),
+            // gorilla/mux: r.HandleFunc("/path", handler).Methods("GET")
+            format!(
+                r#"(call_expression
+                function: (selector_expression
+                    operand: (call_expression
+                        function: (selector_expression
+                            operand: (identifier)
+                            field: (field_identifier) @handle (#match? @handle "^(HandleFunc|Handle)$")
+                        )
+                        arguments: (argument_list
+                            . (interpreted_string_literal) @{ENDPOINT}
+                            [
+                                (selector_expression
+                                    field: (field_identifier) @{HANDLER}
+                                )
+                                (identifier) @{HANDLER}
+                            ]
+                        )
+                    )
+                    field: (field_identifier) @methods (#eq? @methods "Methods")
+                )
+                arguments: (argument_list
+                    . (interpreted_string_literal) @{ENDPOINT_VERB}
+                )
+            ) @{ROUTE}"#
+            ),
             format!(
                 r#"(call_expression
                 function: (selector_expression
@@ -310,22 +337,74 @@ impl Stack for Go {
 
         Some(format!("{}_{}_func_L{}", clean_method, clean_path, line))
     }
+    // router groups: gin/echo `Group("/v1")`, chi `Route("/users", fn)` / `Mount`, mux `PathPrefix`
     fn endpoint_group_find(&self) -> Option<String> {
         Some(format!(
             r#"(call_expression
                 function: (selector_expression
-                    operand: (identifier)
-                    field: (field_identifier) @{ENDPOINT_VERB} (#match? @{ENDPOINT_VERB} "^(Mount|Group)$")
+                    field: (field_identifier) @{ENDPOINT_VERB} (#match? @{ENDPOINT_VERB} "^(Mount|Group|Route|PathPrefix)$")
                 )
                 arguments: (argument_list
-                    (interpreted_string_literal) @{ENDPOINT}
-                    (call_expression
-                        function: (identifier) @{ENDPOINT_GROUP}
-                    )
+                    . (interpreted_string_literal) @{ENDPOINT}
                 )
             ) @{ROUTE}"#
         ))
     }
+    fn match_endpoint_groups(
+        &self,
+        groups: &[NodeData],
+        endpoints: &[NodeData],
+        _find_import_node: &dyn Fn(&str) -> Option<NodeData>,
+    ) -> Vec<(NodeData, String)> {
+        let mut files: Vec<&str> = groups
+            .iter()
+            .chain(endpoints.iter())
+            .map(|n| n.file.as_str())
+            .collect();
+        files.sort();
+        files.dedup();
+
+        // routers cross files through registration calls and mounts, so keep every tree around
+        let sources: Vec<GoSource> = files
+            .into_iter()
+            .filter_map(|file| {
+                let code = std::fs::read_to_string(file).ok()?;
+                let tree = self.parse(&code, &NodeType::Endpoint).ok()?;
+                Some(GoSource {
+                    file: file.to_string(),
+                    code,
+                    tree,
+                })
+            })
+            .collect();
+        let index = RouterIndex { sources: &sources };
+
+        let mut matches = Vec::new();
+        for endpoint in endpoints {
+            let Some(src) = sources.iter().find(|s| s.file == endpoint.file) else {
+                continue;
+            };
+            let Some(call) = find_route_call(src.tree.root_node(), endpoint, &src.code) else {
+                continue;
+            };
+            let Some(receiver) = route_receiver(call, &src.code) else {
+                continue;
+            };
+            let prefix = index.prefix_of(src, receiver, 0);
+            if !prefix.is_empty() {
+                matches.push((endpoint.clone(), prefix));
+            }
+        }
+        matches
+    }
+    fn update_endpoint(&self, nd: &mut NodeData, _call: &Option<String>) {
+        // mux `.Methods("GET")` captures the quoted verb
+        if let Some(verb) = nd.meta.get("verb").cloned() {
+            if verb.starts_with('"') {
+                nd.add_verb(trim_quotes(&verb));
+            }
+        }
+    }
     fn request_finder(&self) -> Option<String> {
         Some(format!(
             r#"
@@ -381,6 +460,16 @@ impl Stack for Go {
         }
         inst.meta.get("verb").cloned()
     }
+    fn function_signature(&self, node: TreeNode, code: &str) -> Option<signature::Signature> {
+        let mut sig = signature::extract_signature(node, code)?;
+        // exported identifiers start with an upper-case letter
+        if let Some(name) = node.child_by_field_name("name") {
+            let name = signature::text(name, code);
+            let exported = name.chars().next().is_some_and(|c| c.is_uppercase());
+            sig.visibility = Some(if exported { "public" } else { "private" }.to_string());
+        }
+        Some(sig)
+    }
     fn find_function_parent(
         &self,
         node: TreeNode,
@@ -567,3 +656,367 @@ impl Stack for Go {
         callback(NodeType::Class, NodeType::Function, "operand");
     }
 }
+
+// Router prefix resolution
+
+const MAX_ROUTER_DEPTH: usize = 12;
+
+struct GoSource {
+    file: String,
+    code: String,
+    tree: Tree,
+}
+
+struct RouterIndex<'a> {
+    sources: &'a [GoSource],
+}
+
+impl<'a> RouterIndex<'a> {
+    // path prefix carried by a router expression such as `v1`, `r.Group("/api")` or `s.PathPrefix("/x").Subrouter()`
+    fn prefix_of(&self, src: &'a GoSource, expr: TreeNode<'a>, depth: usize) -> String {
+        if depth > MAX_ROUTER_DEPTH {
+            return String::new();
+        }
+        match expr.kind() {
+            "identifier" => self.identifier_prefix(src, expr, depth),
+            "unary_expression" | "parenthesized_expression" => expr
+                .named_child(expr.named_child_count().saturating_sub(1))
+                .map(|inner| self.prefix_of(src, inner, depth + 1))
+                .unwrap_or_default(),
+            "call_expression" => {
+                let Some(function) = expr
+                    .child_by_field_name("function")
+                    .filter(|f| f.kind() == "selector_expression")
+                else {
+                    return self.mounted_prefix(expr, depth);
+                };
+                let base = || {
+                    function
+                        .child_by_field_name("operand")
+                        .map(|o| self.prefix_of(src, o, depth + 1))
+                        .unwrap_or_default()
+                };
+                match field_name(function, &src.code) {
+                    "Group" | "Route" | "PathPrefix" => join_route(
+                        &base(),
+                        &first_string_arg(expr, &src.code).unwrap_or_default(),
+                    ),
+                    "Subrouter" | "With" => base(),
+                    // a fresh router: its prefix comes from wherever the building function is mounted
+                    _ => self.mounted_prefix(expr, depth),
+                }
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn identifier_prefix(&self, src: &'a GoSource, ident: TreeNode<'a>, depth: usize) -> String {
+        let name = node_text(ident, &src.code);
+        let mut scope = enclosing_function(ident);
+        while let Some(func) = scope {
+            if let Some(value) = declared_value(func, name, ident.start_byte(), &src.code) {
+                return self.prefix_of(src, value, depth + 1);
+            }
+            if let Some(index) = parameter_index(func, name, &src.code) {
+                if func.kind() == "func_literal" {
+                    return self.callback_prefix(src, func, depth);
+                }
+                let Some(func_name) = func.child_by_field_name("name") else {
+                    return String::new();
+                };
+                return self.call_site_prefix(node_text(func_name, &src.code), index, depth);
+            }
+            scope = enclosing_function(func);
+        }
+        String::new()
+    }
+
+    // chi: `r.Route("/users", func(r chi.Router) { ... })` and `r.Group(func(r chi.Router) { ... })`
+    fn callback_prefix(
+        &self,
+        src: &'a GoSource,
+        func_literal: TreeNode<'a>,
+        depth: usize,
+    ) -> String {
+        let Some(call) = func_literal
+            .parent()
+            .and_then(|args| args.parent())
+            .filter(|c| c.kind() == "call_expression")
+        else {
+            return String::new();
+        };
+        let Some(function) = call
+            .child_by_field_name("function")
+            .filter(|f| f.kind() == "selector_expression")
+        else {
+            return String::new();
+        };
+        if !matches!(field_name(function, &src.code), "Route" | "Group") {
+            return String::new();
+        }
+        let base = function
+            .child_by_field_name("operand")
+            .map(|o| self.prefix_of(src, o, depth + 1))
+            .unwrap_or_default();
+        join_route(
+            &base,
+            &first_string_arg(call, &src.code).unwrap_or_default(),
+        )
+    }
+
+    // a router handed to `RegisterRoutes(api)` picks up the prefix of the argument at the call site
+    fn call_site_prefix(&self, func_name: &str, index: usize, depth: usize) -> String {
+        for src in self.sources {
+            for call in calls_to(src.tree.root_node(), func_name, &src.code) {
+                let Some(args) = call.child_by_field_name("arguments") else {
+                    continue;
+                };
+                let mut cursor = args.walk();
+                let arg = args.named_children(&mut cursor).nth(index);
+                if let Some(arg) = arg {
+                    let prefix = self.prefix_of(src, arg, depth + 1);
+                    if !prefix.is_empty() {
+                        return prefix;
+                    }
+                }
+            }
+        }
+        String::new()
+    }
+
+    // chi: `r.Mount("/users", usersRouter())` for a router built inside `usersRouter`
+    fn mounted_prefix(&self, node: TreeNode<'a>, depth: usize) -> String {
+        let mut current = node.parent();
+        let builder = loop {
+            match current {
+                Some(n) if matches!(n.kind(), "function_declaration" | "method_declaration") => {
+                    break n;
+                }
+                Some(n) => current = n.parent(),
+                None => return String::new(),
+            }
+        };
+        let Some(src) = self
+            .sources
+            .iter()
+            .find(|s| s.tree.root_node().id() == root_of(builder).id())
+        else {
+            return String::new();
+        };
+        let Some(builder_name) = builder.child_by_field_name("name") else {
+            return String::new();
+        };
+        let builder_name = node_text(builder_name, &src.code);
+        for src in self.sources {
+            for mount in calls_to(src.tree.root_node(), "Mount", &src.code) {
+                let Some(args) = mount.child_by_field_name("arguments") else {
+                    continue;
+                };
+                let mounts_builder = args
+                    .named_child(1)
+                    .filter(|a| a.kind() == "call_expression")
+                    .and_then(|a| a.child_by_field_name("function"))
+                    .is_some_and(|f| callee_name(f, &src.code) == builder_name);
+                if !mounts_builder {
+                    continue;
+                }
+                let base = mount
+                    .child_by_field_name("function")
+                    .and_then(|f| f.child_by_field_name("operand"))
+                    .map(|o| self.prefix_of(src, o, depth + 1))
+                    .unwrap_or_default();
+                return join_route(
+                    &base,
+                    &first_string_arg(mount, &src.code).unwrap_or_default(),
+                );
+            }
+        }
+        String::new()
+    }
+}
+
+fn node_text<'a>(node: TreeNode, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+fn field_name<'a>(selector: TreeNode, code: &'a str) -> &'a str {
+    selector
+        .child_by_field_name("field")
+        .map(|f| node_text(f, code))
+        .unwrap_or("")
+}
+
+// `register` for `register(...)`, `Register` for `users.Register(...)`
+fn callee_name<'a>(function: TreeNode, code: &'a str) -> &'a str {
+    match function.kind() {
+        "identifier" => node_text(function, code),
+        "selector_expression" => field_name(function, code),
+        _ => "",
+    }
+}
+
+fn root_of(node: TreeNode) -> TreeNode {
+    let mut root = node;
+    while let Some(parent) = root.parent() {
+        root = parent;
+    }
+    root
+}
+
+fn first_string_arg(call: TreeNode, code: &str) -> Option<String> {
+    let arg = call.child_by_field_name("arguments")?.named_child(0)?;
+    matches!(
+        arg.kind(),
+        "interpreted_string_literal" | "raw_string_literal"
+    )
+    .then(|| {
+        trim_quotes(node_text(arg, code))
+            .trim_matches('`')
+            .to_string()
+    })
+}
+
+fn join_route(base: &str, segment: &str) -> String {
+    let segment = segment.trim_matches('/');
+    if segment.is_empty() {
+        return base.to_string();
+    }
+    format!("{}/{}", base.trim_end_matches('/'), segment)
+}
+
+fn enclosing_function(node: TreeNode) -> Option<TreeNode> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if matches!(
+            n.kind(),
+            "function_declaration" | "method_declaration" | "func_literal"
+        ) {
+            return Some(n);
+        }
+        current = n.parent();
+    }
+    None
+}
+
+// the value last assigned to `name` inside `scope` before byte offset `before`
+fn declared_value<'a>(
+    scope: TreeNode<'a>,
+    name: &str,
+    before: usize,
+    code: &str,
+) -> Option<TreeNode<'a>> {
+    let mut found = None;
+    let mut stack = vec![scope.child_by_field_name("body")?];
+    while let Some(node) = stack.pop() {
+        if node.start_byte() >= before {
+            continue;
+        }
+        let (left, right) = match node.kind() {
+            "short_var_declaration" | "assignment_statement" => (
+                node.child_by_field_name("left"),
+                node.child_by_field_name("right"),
+            ),
+            _ => (None, None),
+        };
+        if let (Some(left), Some(right)) = (left, right) {
+            let mut cursor = left.walk();
+            let position = left
+                .named_children(&mut cursor)
+                .position(|l| node_text(l, code) == name);
+            if let Some(position) = position {
+                let mut cursor = right.walk();
+                let values: Vec<TreeNode> = right.named_children(&mut cursor).collect();
+                let value = values.get(position).or(values.first()).copied();
+                // keep the latest declaration in source order
+                let later = match found {
+                    Some((start, _)) => start < node.start_byte(),
+                    None => true,
+                };
+                if let (Some(value), true) = (value, later) {
+                    found = Some((node.start_byte(), value));
+                }
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    found.map(|(_, value)| value)
+}
+
+fn parameter_index(func: TreeNode, name: &str, code: &str) -> Option<usize> {
+    let params = func.child_by_field_name("parameters")?;
+    let mut index = 0;
+    let mut cursor = params.walk();
+    for param in params.named_children(&mut cursor) {
+        let mut name_cursor = param.walk();
+        let names: Vec<TreeNode> = param
+            .children_by_field_name("name", &mut name_cursor)
+            .collect();
+        if names.is_empty() {
+            index += 1;
+            continue;
+        }
+        for n in names {
+            if node_text(n, code) == name {
+                return Some(index);
+            }
+            index += 1;
+        }
+    }
+    None
+}
+
+fn calls_to<'a>(root: TreeNode<'a>, func_name: &str, code: &str) -> Vec<TreeNode<'a>> {
+    let mut calls = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.kind() == "call_expression" {
+            let name = node
+                .child_by_field_name("function")
+                .map(|f| callee_name(f, code))
+                .unwrap_or("");
+            if name == func_name {
+                calls.push(node);
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    calls.sort_by_key(|c| c.start_byte());
+    calls
+}
+
+// the call an endpoint was parsed from: same row, same text
+fn find_route_call<'a>(
+    root: TreeNode<'a>,
+    endpoint: &NodeData,
+    code: &str,
+) -> Option<TreeNode<'a>> {
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.start_position().row > endpoint.start || node.end_position().row < endpoint.start {
+            continue;
+        }
+        if node.kind() == "call_expression"
+            && node.start_position().row == endpoint.start
+            && node_text(node, code) == endpoint.body
+        {
+            return Some(node);
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    None
+}
+
+// `v1` in `v1.GET(...)`, `s` in `s.HandleFunc(...).Methods("GET")`
+fn route_receiver<'a>(call: TreeNode<'a>, code: &str) -> Option<TreeNode<'a>> {
+    let function = call
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "selector_expression")?;
+    let operand = function.child_by_field_name("operand")?;
+    if field_name(function, code) == "Methods" && operand.kind() == "call_expression" {
+        return route_receiver(operand, code);
+    }
+    Some(operand)
+}
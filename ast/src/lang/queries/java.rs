@@ -1,8 +1,21 @@
 use super::super::*;
 use super::consts::*;
+use regex::Regex;
 use shared::error::{Context, Result};
+use std::sync::LazyLock;
 use tree_sitter::{Language, Parser, Query, Tree};
 
+// `interface UserRepository extends JpaRepository<User, Long>` -> User
+static REPOSITORY_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bextends\b[^{]*?\b\w*Repository\s*<\s*([A-Z]\w*)\s*,").unwrap());
+// Spring Data derived query subjects: findByEmail, countDistinctByStatus, deleteAllByOwner
+static DERIVED_QUERY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(find|read|get|query|search|stream|count|exists|delete|remove)(?:[A-Z]\w*)?By[A-Z]",
+    )
+    .unwrap()
+});
+
 pub struct Java(Language);
 
 impl Default for Java {
@@ -286,7 +299,35 @@ impl Stack for Java {
                                 body: (_) @lambda.body
                             ) @{ANONYMOUS_FUNCTION}
                             (method_reference
-                                (identifier) @{HANDLER}
+                                (identifier) @{HANDLER} .
+                            )
+                        ]
+                    )
+                ) @{ROUTE}
+                "#
+            ),
+            // RouterFunctions.route(GET("/users"), handler::list).andRoute(POST("/users"), handler::create)
+            format!(
+                r#"
+                (method_invocation
+                    name: (identifier) @route (#match? @route "^(route|andRoute)$")
+                    arguments: (argument_list
+                        .
+                        (method_invocation
+                            name: (identifier) @{ENDPOINT_VERB} (#match? @{ENDPOINT_VERB} "^GET$|^POST$|^PUT$|^DELETE$|^PATCH$")
+                            arguments: (argument_list
+                                .
+                                (string_literal) @{ENDPOINT}
+                            )
+                        )
+                        .
+                        [
+                            (lambda_expression
+                                parameters: (_) @{ARGUMENTS}
+                                body: (_) @lambda.body
+                            ) @{ANONYMOUS_FUNCTION}
+                            (method_reference
+                                (identifier) @{HANDLER} .
                             )
                         ]
                     )
@@ -333,9 +374,73 @@ impl Stack for Java {
                 )
                 name: (identifier) @{ENDPOINT_GROUP}
             )@{ROUTE}
+
+            (method_invocation
+                name: (identifier) @{ENDPOINT_GROUP} (#eq? @{ENDPOINT_GROUP} "path")
+                arguments: (argument_list
+                    .
+                    (string_literal) @{ENDPOINT}
+                    .
+                    (lambda_expression) @{ROUTE}
+                )
+            )
+
+            (method_invocation
+                name: (identifier) @{ENDPOINT_GROUP} (#eq? @{ENDPOINT_GROUP} "nest")
+                arguments: (argument_list
+                    .
+                    (method_invocation
+                        name: (identifier) @path (#eq? @path "path")
+                        arguments: (argument_list
+                            .
+                            (string_literal) @{ENDPOINT}
+                        )
+                    )
+                    .
+                    (_) @{ROUTE}
+                )
+            )
             "#
         ))
     }
+    // WebFlux `path("/api", builder -> ...)` / `nest(path("/api"), ...)` prefix every route declared inside them
+    fn match_endpoint_groups(
+        &self,
+        groups: &[NodeData],
+        endpoints: &[NodeData],
+        _find_import_node: &dyn Fn(&str) -> Option<NodeData>,
+    ) -> Vec<(NodeData, String)> {
+        let mut scopes: Vec<&NodeData> = groups
+            .iter()
+            .filter(|g| {
+                matches!(
+                    g.meta.get("group").map(|s| s.as_str()),
+                    Some("path" | "nest")
+                )
+            })
+            .collect();
+        // outermost scope first
+        scopes.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+        let mut matches = Vec::new();
+        for endpoint in endpoints {
+            let prefix: String = scopes
+                .iter()
+                .filter(|g| {
+                    g.file == endpoint.file
+                        && g.start <= endpoint.start
+                        && endpoint.end <= g.end
+                        // a route chained after the scope spans it rather than sitting inside it
+                        && !(endpoint.body.len() > g.body.len() && endpoint.body.contains(&g.body))
+                })
+                .map(|g| g.name.trim_end_matches('/'))
+                .collect();
+            if !prefix.is_empty() {
+                matches.push((endpoint.clone(), prefix));
+            }
+        }
+        matches
+    }
 
     fn update_endpoint(&self, nd: &mut NodeData, _call: &Option<String>) {
         if let Some(verb_annotation) = nd.meta.get("verb").cloned() {
@@ -438,6 +543,37 @@ impl Stack for Java {
             "#
         ))
     }
+    fn repository_model(&self, trait_body: &str) -> Option<String> {
+        let header = trait_body.split('{').next().unwrap_or(trait_body);
+        REPOSITORY_RE
+            .captures(header)
+            .map(|caps| caps[1].to_string())
+    }
+    fn repository_operation(&self, node: TreeNode, code: &str) -> Option<(String, String)> {
+        let mut parent = node.parent();
+        while let Some(p) = parent {
+            if p.kind() == "interface_declaration" {
+                break;
+            }
+            parent = p.parent();
+        }
+        let interface = parent?;
+        let header =
+            &code[interface.start_byte()..interface.child_by_field_name("body")?.start_byte()];
+        let model = self.repository_model(header)?;
+        let name = node
+            .child_by_field_name("name")?
+            .utf8_text(code.as_bytes())
+            .ok()?;
+        let subject = DERIVED_QUERY_RE.captures(name)?;
+        let operation = match &subject[1] {
+            "count" => "count",
+            "exists" => "exists",
+            "delete" | "remove" => "delete",
+            _ => "find",
+        };
+        Some((model, operation.to_string()))
+    }
     fn resolve_import_name(&self, import_name: &str) -> String {
         let import_name = import_name.to_string();
         let name = import_name
@@ -1,5 +1,6 @@
 use super::super::*;
 use super::consts::*;
+use crate::lang::parse::trim_quotes;
 use shared::error::{Context, Result};
 use tree_sitter::{Language, Node as TreeNode, Parser, Query, Tree};
 
@@ -87,9 +88,12 @@ impl Stack for Kotlin {
                 (modifiers (annotation)*)? @{ATTRIBUTES}
                 (type_identifier)@{CLASS_NAME}
                 (delegation_specifier
-                    (constructor_invocation
+                    [
+                        (constructor_invocation
+                            (user_type)@{CLASS_PARENT}
+                        )
                         (user_type)@{CLASS_PARENT}
-                    )
+                    ]
                 )?
 
             )@{CLASS_DEFINITION}
@@ -97,9 +101,12 @@ impl Stack for Kotlin {
                 (modifiers (annotation)*)? @{ATTRIBUTES}
                 (type_identifier)@{CLASS_NAME}
                 (delegation_specifier
-                    (constructor_invocation
+                    [
+                        (constructor_invocation
+                            (user_type)@{CLASS_PARENT}
+                        )
                         (user_type)@{CLASS_PARENT}
-                    )
+                    ]
                 )?
 
             )@{CLASS_DEFINITION}
@@ -175,6 +182,69 @@ impl Stack for Kotlin {
         ))
     }
 
+    fn function_signature(&self, node: TreeNode, code: &str) -> Option<signature::Signature> {
+        // the Kotlin grammar exposes no field names on declarations, so walk child kinds
+        const SKIP_KINDS: &[&str] = &[
+            "modifiers",
+            "simple_identifier",
+            "function_value_parameters",
+            "function_body",
+            "type_constraints",
+            "type_parameters",
+            "type_modifiers",
+            "receiver_type",
+        ];
+        let mut sig = signature::Signature::default();
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            match child.kind() {
+                "function_value_parameters" => {
+                    let mut pc = child.walk();
+                    for p in child.named_children(&mut pc) {
+                        match p.kind() {
+                            "parameter" => {
+                                let mut c = p.walk();
+                                let name = p
+                                    .named_children(&mut c)
+                                    .find(|n| n.kind() == "simple_identifier")
+                                    .map(|n| signature::text(n, code));
+                                let mut c = p.walk();
+                                let type_name = p
+                                    .named_children(&mut c)
+                                    .find(|n| n.kind() != "simple_identifier")
+                                    .and_then(|n| signature::clean_type(signature::text(n, code)));
+                                if let Some(name) = name {
+                                    sig.params.push(signature::Param {
+                                        name,
+                                        type_name,
+                                        default: None,
+                                    });
+                                }
+                            }
+                            "parameter_modifiers" | "line_comment" | "multiline_comment" => {}
+                            // `= expr` follows the parameter it belongs to
+                            _ => {
+                                if let Some(last) = sig.params.last_mut() {
+                                    last.default =
+                                        signature::clean_default(signature::text(p, code));
+                                }
+                            }
+                        }
+                    }
+                }
+                "type_parameters" => {
+                    sig.generics = signature::generics_from_node(child, code);
+                }
+                kind if !SKIP_KINDS.contains(&kind) && sig.return_type.is_none() => {
+                    sig.return_type = signature::clean_type(signature::text(child, code));
+                }
+                _ => {}
+            }
+        }
+        signature::apply_modifiers(&mut sig, node, code);
+        Some(sig)
+    }
+
     fn find_function_parent(
         &self,
         node: TreeNode,
@@ -198,6 +268,106 @@ impl Stack for Kotlin {
         })
     }
 
+    // Ktor routing DSL: `get("/x") { ... }` inside `routing { route("/api") { ... } }`
+    fn endpoint_finders(&self) -> Vec<String> {
+        vec![format!(
+            r#"(call_expression
+                (simple_identifier) @{ENDPOINT_VERB} (#match? @{ENDPOINT_VERB} "{KTOR_VERBS}")
+                (call_suffix
+                    (value_arguments
+                        . (value_argument
+                            (string_literal) @{ENDPOINT}
+                        )
+                    )?
+                    (annotated_lambda
+                        (lambda_literal) @{ANONYMOUS_FUNCTION}
+                    )
+                )
+            ) @{ROUTE}"#
+        )]
+    }
+
+    fn find_endpoint_parents(
+        &self,
+        node: TreeNode,
+        code: &str,
+        _file: &str,
+        _callback: &dyn Fn(&str) -> Option<NodeData>,
+    ) -> Result<Vec<HandlerItem>> {
+        let mut parents = Vec::new();
+        let mut current = node.parent();
+        while let Some(parent) = current {
+            if parent.kind() == "call_expression" {
+                // walking outwards, so outer scopes go in front
+                let mut items = ktor_scope_items(parent, code);
+                items.append(&mut parents);
+                parents = items;
+            }
+            current = parent.parent();
+        }
+        Ok(parents)
+    }
+
+    fn anonymous_handler_parents(&self) -> bool {
+        true
+    }
+
+    fn use_handler_finder(&self) -> bool {
+        true
+    }
+
+    fn handler_finder(
+        &self,
+        mut endpoint: NodeData,
+        find_fn: &dyn Fn(&str, &str) -> Option<NodeData>,
+        _find_fns_in: &dyn Fn(&str) -> Vec<NodeData>,
+        handler_params: HandlerParams,
+    ) -> Vec<(NodeData, Option<Edge>)> {
+        let Some(handler) = endpoint.meta.get("handler").cloned() else {
+            return vec![(endpoint, None)];
+        };
+        let mut segments: Vec<&str> = handler_params
+            .parents
+            .iter()
+            .filter(|p| matches!(p.item_type, HandlerItemType::Namespace))
+            .map(|p| p.name.as_str())
+            .collect();
+        segments.push(&endpoint.name);
+        endpoint.name = ktor_join_path(&segments);
+        let edge = find_fn(&handler, &endpoint.file).map(|nd| Edge::handler(&endpoint, &nd));
+        vec![(endpoint, edge)]
+    }
+
+    fn update_endpoint(&self, nd: &mut NodeData, call: &Option<String>) {
+        // bare `get { }` serves the enclosing route's path
+        if call.is_none() && nd.name.is_empty() && nd.meta.contains_key("verb") {
+            nd.name = "/".to_string();
+        }
+    }
+
+    fn generate_anonymous_handler_name(
+        &self,
+        method: &str,
+        path: &str,
+        line: usize,
+    ) -> Option<String> {
+        let clean_path = path
+            .replace("/", "_")
+            .replace(['{', '}'], "")
+            .trim_matches('_')
+            .to_string();
+        if clean_path.is_empty() {
+            Some(format!("{}_lambda_L{}", method.to_uppercase(), line))
+        } else {
+            Some(format!(
+                "{}_{}_lambda_L{}",
+                method.to_uppercase(),
+                clean_path,
+                line
+            ))
+        }
+    }
+
     fn request_finder(&self) -> Option<String> {
         Some(format!(
             r#"
@@ -404,3 +574,73 @@ impl Stack for Kotlin {
         }
     }
 }
+
+// Ktor routing helpers
+
+const KTOR_VERBS: &str = "^(get|post|put|patch|delete|head|options)$";
+
+// `route("/users") { }` is a path scope, `authenticate("jwt") { }` guards everything inside it
+fn ktor_scope_items(call: TreeNode, code: &str) -> Vec<HandlerItem> {
+    let Some(callee) = call
+        .named_child(0)
+        .filter(|c| c.kind() == "simple_identifier")
+    else {
+        return Vec::new();
+    };
+    let args = ktor_string_args(call, code);
+    match callee.utf8_text(code.as_bytes()).unwrap_or("") {
+        "route" => args
+            .into_iter()
+            .take(1)
+            .map(|path| HandlerItem {
+                name: path,
+                item_type: HandlerItemType::Namespace,
+            })
+            .collect(),
+        "authenticate" if args.is_empty() => vec![HandlerItem {
+            name: "authenticate".to_string(),
+            item_type: HandlerItemType::Middleware,
+        }],
+        "authenticate" => args
+            .into_iter()
+            .map(|provider| HandlerItem {
+                name: format!("authenticate:{}", provider),
+                item_type: HandlerItemType::Middleware,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn ktor_string_args(call: TreeNode, code: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut cursor = call.walk();
+    for suffix in call.named_children(&mut cursor) {
+        if suffix.kind() != "call_suffix" {
+            continue;
+        }
+        let mut suffix_cursor = suffix.walk();
+        for value_args in suffix.named_children(&mut suffix_cursor) {
+            if value_args.kind() != "value_arguments" {
+                continue;
+            }
+            let mut args_cursor = value_args.walk();
+            for arg in value_args.named_children(&mut args_cursor) {
+                if let Some(s) = arg.named_child(0).filter(|a| a.kind() == "string_literal") {
+                    let text = s.utf8_text(code.as_bytes()).unwrap_or("");
+                    args.push(trim_quotes(text).to_string());
+                }
+            }
+        }
+    }
+    args
+}
+
+fn ktor_join_path(segments: &[&str]) -> String {
+    let parts: Vec<&str> = segments
+        .iter()
+        .flat_map(|s| s.split('/'))
+        .filter(|s| !s.is_empty())
+        .collect();
+    format!("/{}", parts.join("/"))
+}
@@ -0,0 +1,195 @@
+use super::super::*;
+use super::consts::*;
+use shared::error::{Context, Result};
+use tree_sitter::{Language, Parser, Query, Tree};
+
+pub struct Lua(Language);
+
+impl Default for Lua {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Lua {
+    pub fn new() -> Self {
+        Lua(tree_sitter_lua::LANGUAGE.into())
+    }
+}
+
+impl Stack for Lua {
+    fn should_skip_function_call(&self, called: &str, operand: &Option<String>) -> bool {
+        super::skips::lua::should_skip(called, operand)
+    }
+    fn q(&self, q: &str, _nt: &NodeType) -> Query {
+        match Query::new(&self.0, q) {
+            Ok(query) => query,
+            Err(err) => panic!("Failed to compile Lua query '{}': {}", q, err),
+        }
+    }
+    fn parse(&self, code: &str, _nt: &NodeType) -> Result<Tree> {
+        let mut parser = Parser::new();
+        parser.set_language(&self.0)?;
+        parser.parse(code, None).context("failed to parse Lua")
+    }
+    fn program_node_name(&self) -> String {
+        "chunk".to_string()
+    }
+    fn imports_query(&self) -> Option<String> {
+        let require = format!(
+            r#"(function_call
+                name: (identifier) @require (#eq? @require "require")
+                arguments: (arguments
+                    (string content: (string_content) @{IMPORTS_FROM})
+                )
+            )"#
+        );
+        Some(format!(
+            r#"[
+                (variable_declaration
+                    (assignment_statement
+                        (expression_list value: {require})
+                    )
+                )
+                (chunk {require})
+            ] @{IMPORTS} @{IMPORTS_MODULE}"#
+        ))
+    }
+    fn resolve_import_path(&self, import_path: &str, _current_file: &str) -> String {
+        format!("{}.lua", import_path.replace('.', "/"))
+    }
+    // module tables: `local M = {}`, `local Account = setmetatable({}, Base)`, `Account = {}`
+    fn class_definition_query(&self) -> String {
+        let table = r#"[
+            (table_constructor)
+            (function_call name: (identifier) @setmetatable (#eq? @setmetatable "setmetatable"))
+        ]"#;
+        let name = format!(
+            r#"(variable_list
+                name: (identifier) @{CLASS_NAME} (#match? @{CLASS_NAME} "^(_?M|_?[A-Z][A-Za-z0-9_]*)$")
+            )"#
+        );
+        format!(
+            r#"(chunk
+                [
+                    (variable_declaration
+                        (assignment_statement
+                            {name}
+                            (expression_list value: {table})
+                        )
+                    )
+                    (assignment_statement
+                        {name}
+                        (expression_list value: {table})
+                    )
+                ] @{CLASS_DEFINITION}
+            )"#
+        )
+    }
+    fn function_definition_query(&self) -> String {
+        let function_value = format!(
+            r#"(function_definition
+                parameters: (parameters) @{ARGUMENTS}
+            )"#
+        );
+        format!(
+            r#"[
+                (function_declaration
+                    name: [
+                        (identifier) @{FUNCTION_NAME}
+                        (dot_index_expression
+                            table: (_) @{PARENT_TYPE}
+                            field: (identifier) @{FUNCTION_NAME}
+                        )
+                        (method_index_expression
+                            table: (_) @{PARENT_TYPE}
+                            method: (identifier) @{FUNCTION_NAME}
+                        )
+                    ]
+                    parameters: (parameters) @{ARGUMENTS}
+                )
+                (variable_declaration
+                    (assignment_statement
+                        (variable_list name: (identifier) @{FUNCTION_NAME})
+                        (expression_list value: {function_value})
+                    )
+                )
+                (assignment_statement
+                    (variable_list
+                        name: (dot_index_expression
+                            table: (_) @{PARENT_TYPE}
+                            field: (identifier) @{FUNCTION_NAME}
+                        )
+                    )
+                    (expression_list value: {function_value})
+                )
+            ] @{FUNCTION_DEFINITION}"#
+        )
+    }
+    // busted: top-level `describe("...", function() ... end)` and `it(...)`
+    fn test_query(&self) -> Option<String> {
+        Some(format!(
+            r#"(chunk
+                (function_call
+                    name: (identifier) @describe (#match? @describe "^(describe|insulate|expose|it|test)$")
+                    arguments: (arguments
+                        (string content: (string_content) @{FUNCTION_NAME})
+                        (function_definition)
+                    )
+                ) @{FUNCTION_DEFINITION}
+            )"#
+        ))
+    }
+    fn is_test(&self, _func_name: &str, func_file: &str, _func_body: &str) -> bool {
+        self.is_test_file(func_file)
+    }
+    fn is_test_file(&self, filename: &str) -> bool {
+        filename.ends_with("_spec.lua")
+            || filename.ends_with("_test.lua")
+            || filename.contains("/spec/")
+    }
+    fn find_function_parent(
+        &self,
+        node: TreeNode,
+        _code: &str,
+        file: &str,
+        func_name: &str,
+        find_class: &dyn Fn(&str) -> Option<(NodeData, NodeType)>,
+        parent_type: Option<&str>,
+    ) -> Result<Option<Operand>> {
+        let Some(parent_str) = parent_type else {
+            return Ok(None);
+        };
+        // `function a.b.c()` belongs to the innermost table
+        let table = parent_str.rsplit(['.', ':']).next().unwrap_or(parent_str);
+        Ok(find_class(table).map(|(class, source_type)| Operand {
+            source: NodeKeys::new(&class.name, &class.file, class.start),
+            target: NodeKeys::new(func_name, file, node.start_position().row),
+            source_type,
+        }))
+    }
+    fn comment_query(&self) -> Option<String> {
+        Some(format!(r#"(comment) @{FUNCTION_COMMENT}"#))
+    }
+    fn class_comment_query(&self) -> Option<String> {
+        Some(format!(r#"(comment) @{CLASS_COMMENT}"#))
+    }
+    fn function_call_query(&self) -> String {
+        format!(
+            r#"(function_call
+                name: [
+                    (identifier) @{FUNCTION_NAME}
+                    (dot_index_expression
+                        table: (_) @{OPERAND}
+                        field: (identifier) @{FUNCTION_NAME}
+                    )
+                    (method_index_expression
+                        table: (_) @{OPERAND}
+                        method: (identifier) @{FUNCTION_NAME}
+                    )
+                ]
+                arguments: (arguments) @{ARGUMENTS}
+            ) @{FUNCTION_CALL}"#
+        )
+    }
+}
@@ -9,6 +9,7 @@ pub mod go;
 pub mod haml;
 pub mod java;
 pub mod kotlin;
+pub mod lua;
 pub mod php;
 pub mod python;
 mod rails_routes;
@@ -19,9 +20,12 @@ pub mod skips;
 pub mod svelte;
 pub mod swift;
 pub mod toml;
+pub mod zig;
 
 use crate::lang::asg::Operand;
 use crate::lang::graphs::Edge;
+use crate::lang::fields::{self, Field};
+use crate::lang::signature::{self, Signature};
 use crate::lang::{Function, NodeData, NodeType};
 use lsp::Language as LspLanguage;
 use lsp::{CmdSender, Position};
@@ -93,6 +97,10 @@ pub trait Stack {
     fn implements_query(&self) -> Option<String> {
         None
     }
+    // CLASS_NAME depends on CLASS_DEPENDENCY (e.g. DI module registrations)
+    fn class_dependency_query(&self) -> Option<String> {
+        None
+    }
     fn function_definition_query(&self) -> String;
     fn test_query(&self) -> Option<String> {
         None
@@ -158,6 +166,22 @@ pub trait Stack {
     fn data_model_name(&self, dm_name: &str) -> String {
         dm_name.to_string()
     }
+    // model managed by a data-access interface, e.g. Spring Data `JpaRepository<User, Long>` -> "User"
+    fn repository_model(&self, _trait_body: &str) -> Option<String> {
+        None
+    }
+    // (model, operation) for a derived query method on a data-access interface, e.g. `findByEmail` -> find
+    fn repository_operation(&self, _node: TreeNode, _code: &str) -> Option<(String, String)> {
+        None
+    }
+    /// Parameters, return type, generics and modifiers of a function definition node.
+    fn function_signature(&self, node: TreeNode, code: &str) -> Option<Signature> {
+        signature::extract_signature(node, code)
+    }
+    /// Members of a DataModel definition node, with their types, defaults and annotations.
+    fn data_model_fields(&self, node: TreeNode, code: &str) -> Vec<Field> {
+        fields::extract_fields(node, code)
+    }
     fn find_function_parent(
         &self,
         _node: TreeNode,
@@ -194,6 +218,11 @@ pub trait Stack {
     ) -> Result<Vec<HandlerItem>> {
         Ok(Vec::new())
     }
+    // the name node of the method a ROUTE decorator annotates, found by walking past any
+    // other decorators in between (queries can't repeat an anchored sibling)
+    fn decorated_handler<'a>(&self, _route: TreeNode<'a>) -> Option<TreeNode<'a>> {
+        None
+    }
     fn endpoint_group_find(&self) -> Option<String> {
         None
     }
@@ -260,6 +289,10 @@ pub trait Stack {
     fn use_handler_finder(&self) -> bool {
         false
     }
+    // also walk enclosing route scopes for inline (anonymous) handlers
+    fn anonymous_handler_parents(&self) -> bool {
+        false
+    }
     fn handler_finder(
         &self,
         endpoint: NodeData,
@@ -384,6 +417,10 @@ pub trait Stack {
     fn extra_calls_queries(&self) -> Vec<String> {
         vec![]
     }
+    // names handed to a DI marker in a function's signature or decorators, e.g. `Depends(get_db)`
+    fn injected_dependencies(&self, _body: &str) -> Vec<String> {
+        Vec::new()
+    }
     fn class_contains_datamodel(
         &self,
         _datamodel: &NodeData,
@@ -415,6 +452,8 @@ pub fn treesitter_from_lsp_language(ll: LspLanguage) -> tree_sitter::Language {
         LspLanguage::C => tree_sitter_c::LANGUAGE.into(),
         LspLanguage::Php => tree_sitter_php::LANGUAGE_PHP.into(),
         LspLanguage::CSharp => tree_sitter_c_sharp::LANGUAGE.into(),
+        LspLanguage::Lua => tree_sitter_lua::LANGUAGE.into(),
+        LspLanguage::Zig => tree_sitter_zig::LANGUAGE.into(),
     }
 }
 
@@ -1,8 +1,21 @@
 use super::super::*;
 use super::consts::*;
+use regex::Regex;
 use shared::error::{Context, Result};
+use std::collections::HashMap;
+use std::sync::LazyLock;
 use tree_sitter::{Language, Node as TreeNode, Parser, Query, Tree};
 
+static DEPENDS_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:Depends|Security)\(\s*([A-Za-z_][\w.]*)").unwrap());
+static PREFIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bprefix\s*=\s*["']([^"']*)["']"#).unwrap());
+static FROM_IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\s*from\s+(\.*[\w.]*)\s+import\s+(\([^)]*\)|[^\n]+)").unwrap()
+});
+static IMPORT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^\s*import\s+([\w.]+)(?:\s+as\s+(\w+))?").unwrap());
+
 pub struct Python(Language);
 
 impl Default for Python {
@@ -226,6 +239,29 @@ impl Stack for Python {
         NodeType::UnitTest
     }
 
+    fn function_signature(&self, node: TreeNode, code: &str) -> Option<signature::Signature> {
+        let mut sig = signature::extract_signature(node, code)?;
+        if let Some(decorated) = node.parent().filter(|p| p.kind() == "decorated_definition") {
+            let mut cursor = decorated.walk();
+            for d in decorated.named_children(&mut cursor) {
+                if d.kind() != "decorator" {
+                    continue;
+                }
+                let text = signature::text(d, code);
+                if matches!(text.trim_start_matches('@'), "staticmethod" | "classmethod") {
+                    sig.is_static = true;
+                }
+            }
+        }
+        // leading underscore marks a private helper, dunder methods stay public
+        if let Some(name) = node.child_by_field_name("name") {
+            let name = signature::text(name, code);
+            let private = name.starts_with('_') && !name.ends_with("__");
+            sig.visibility = Some(if private { "private" } else { "public" }.to_string());
+        }
+        Some(sig)
+    }
+
     fn find_function_parent(
         &self,
         node: TreeNode,
@@ -280,6 +316,16 @@ impl Stack for Python {
         )
     }
 
+    fn injected_dependencies(&self, body: &str) -> Vec<String> {
+        let mut deps: Vec<String> = Vec::new();
+        for c in DEPENDS_RE.captures_iter(body) {
+            if !deps.iter().any(|d| d == &c[1]) {
+                deps.push(c[1].to_string());
+            }
+        }
+        deps
+    }
+
     fn endpoint_finders(&self) -> Vec<String> {
         vec![
             format!(
@@ -287,7 +333,8 @@ impl Stack for Python {
                     (decorator
                         (call
                             function: (attribute
-                                attribute: (identifier) @{ENDPOINT_VERB} (#match? @{ENDPOINT_VERB} "^get$|^post$|^put$|^delete$")
+                                object: (_) @{ENDPOINT_OBJECT}
+                                attribute: (identifier) @{ENDPOINT_VERB} (#match? @{ENDPOINT_VERB} "^get$|^post$|^put$|^patch$|^delete$")
                             )
                             arguments: (argument_list
                                 (string) @{ENDPOINT}
@@ -383,6 +430,30 @@ impl Stack for Python {
         ]
     }
 
+    // FastAPI routers: `router = APIRouter(prefix=...)` and `app.include_router(router, prefix=...)`
+    fn endpoint_group_find(&self) -> Option<String> {
+        Some(format!(
+            r#"
+            (assignment
+                left: (identifier) @{ENDPOINT_GROUP}
+                right: (call
+                    function: [(identifier) (attribute)] @{ENDPOINT_VERB} (#match? @{ENDPOINT_VERB} "(^|\\.)APIRouter$")
+                )
+            ) @{ROUTE}
+
+            (call
+                function: (attribute
+                    object: (_) @{ENDPOINT_OBJECT}
+                    attribute: (identifier) @{ENDPOINT_VERB} (#eq? @{ENDPOINT_VERB} "include_router")
+                )
+                arguments: (argument_list
+                    . [(identifier) (attribute)] @{ENDPOINT_GROUP}
+                )
+            ) @{ROUTE}
+            "#
+        ))
+    }
+
     fn request_finder(&self) -> Option<String> {
         Some(format!(
             r#"(call
@@ -569,6 +640,72 @@ impl Stack for Python {
         callback(NodeType::DataModel, NodeType::Class, "deduplicate");
     }
 
+    fn match_endpoint_groups(
+        &self,
+        groups: &[NodeData],
+        endpoints: &[NodeData],
+        find_import_node: &dyn Fn(&str) -> Option<NodeData>,
+    ) -> Vec<(NodeData, String)> {
+        let mut matches = Vec::new();
+
+        // (file, router var) -> the router's own `APIRouter(prefix=...)`
+        let mut own_prefixes: HashMap<(String, String), String> = HashMap::new();
+        for group in groups {
+            let verb = group.meta.get("verb").map(|v| v.as_str()).unwrap_or("");
+            if !verb.ends_with("APIROUTER") {
+                continue;
+            }
+            if let Some(var) = group.meta.get("group") {
+                own_prefixes.insert(
+                    (group.file.clone(), var.clone()),
+                    router_prefix(&group.body),
+                );
+            }
+        }
+
+        // child (file, router var) -> parent (file, router var) and the `include_router` prefix
+        let mut mounts: HashMap<(String, String), ((String, String), String)> = HashMap::new();
+        for group in groups {
+            if group.meta.get("verb").map(|v| v.as_str()) != Some("INCLUDE_ROUTER") {
+                continue;
+            }
+            let (Some(child_ref), Some(parent)) =
+                (group.meta.get("group"), group.meta.get("object"))
+            else {
+                continue;
+            };
+            let import_body = find_import_node(&group.file)
+                .map(|n| n.body)
+                .unwrap_or_default();
+            let (module, var) = resolve_router_ref(child_ref, &import_body, &group.file);
+            let child_file = match module {
+                Some(module) => own_prefixes
+                    .keys()
+                    .find(|(f, v)| v == &var && module_matches_file(&module, f))
+                    .map(|(f, _)| f.clone()),
+                None => Some(group.file.clone()),
+            };
+            if let Some(child_file) = child_file {
+                mounts.entry((child_file, var)).or_insert((
+                    (group.file.clone(), parent.clone()),
+                    router_prefix(&group.body),
+                ));
+            }
+        }
+
+        for endpoint in endpoints {
+            let Some(object) = endpoint.meta.get("object") else {
+                continue;
+            };
+            let key = (endpoint.file.clone(), object.clone());
+            let prefix = full_router_prefix(&key, &own_prefixes, &mounts, 0);
+            if !prefix.is_empty() {
+                matches.push((endpoint.clone(), prefix));
+            }
+        }
+        matches
+    }
+
     fn generate_anonymous_handler_name(
         &self,
         method: &str,
@@ -587,3 +724,94 @@ impl Stack for Python {
         Some(format!("{}_{}_lambda_L{}", clean_method, clean_path, line))
     }
 }
+
+// FastAPI router helpers
+
+fn router_prefix(call: &str) -> String {
+    PREFIX_RE
+        .captures(call)
+        .map(|c| c[1].to_string())
+        .unwrap_or_default()
+}
+
+// `users.router` or `router` -> (module path the router lives in, router var); None means this file
+fn resolve_router_ref(router_ref: &str, import_body: &str, file: &str) -> (Option<String>, String) {
+    let (alias, var) = match router_ref.rsplit_once('.') {
+        Some((alias, var)) => (alias, var),
+        None => (router_ref, router_ref),
+    };
+    let qualified = router_ref.contains('.');
+    for c in FROM_IMPORT_RE.captures_iter(import_body) {
+        let from = resolve_module(&c[1], file);
+        for name in c[2].trim_matches(|ch| ch == '(' || ch == ')').split(',') {
+            let mut parts = name.split_whitespace();
+            let original = parts.next().unwrap_or_default();
+            let local = parts.nth(1).unwrap_or(original);
+            if local != alias {
+                continue;
+            }
+            return if qualified {
+                // `from app.routers import users` + `users.router`
+                (Some(format!("{}/{}", from, original)), var.to_string())
+            } else {
+                (Some(from), original.to_string())
+            };
+        }
+    }
+    if qualified {
+        for c in IMPORT_RE.captures_iter(import_body) {
+            let local = c.get(2).map(|m| m.as_str()).unwrap_or(&c[1]);
+            if local == alias {
+                return (Some(resolve_module(&c[1], file)), var.to_string());
+            }
+        }
+    }
+    (None, var.to_string())
+}
+
+// `.routers.users` relative to `app/main.py` -> `app/routers/users`
+fn resolve_module(module: &str, file: &str) -> String {
+    let dots = module.chars().take_while(|c| *c == '.').count();
+    let path = module[dots..].replace('.', "/");
+    if dots == 0 {
+        return path;
+    }
+    let mut dir = std::path::Path::new(file).parent();
+    for _ in 1..dots {
+        dir = dir.and_then(|d| d.parent());
+    }
+    let dir = dir.map(|d| d.display().to_string()).unwrap_or_default();
+    match (dir.is_empty(), path.is_empty()) {
+        (true, _) => path,
+        (false, true) => dir,
+        (false, false) => format!("{}/{}", dir, path),
+    }
+}
+
+fn module_matches_file(module: &str, file: &str) -> bool {
+    [format!("{}.py", module), format!("{}/__init__.py", module)]
+        .iter()
+        .any(|candidate| file == candidate || file.ends_with(&format!("/{}", candidate)))
+}
+
+fn full_router_prefix(
+    key: &(String, String),
+    own_prefixes: &HashMap<(String, String), String>,
+    mounts: &HashMap<(String, String), ((String, String), String)>,
+    depth: usize,
+) -> String {
+    let own = own_prefixes.get(key).cloned().unwrap_or_default();
+    // include_router cycles are a runtime error in FastAPI; bail out instead of recursing forever
+    if depth > 8 {
+        return own;
+    }
+    match mounts.get(key) {
+        Some((parent, mount_prefix)) => format!(
+            "{}{}{}",
+            full_router_prefix(parent, own_prefixes, mounts, depth + 1),
+            mount_prefix,
+            own
+        ),
+        None => own,
+    }
+}
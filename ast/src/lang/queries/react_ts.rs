@@ -2,6 +2,7 @@ use std::fs;
 
 use super::super::*;
 use super::consts::*;
+use crate::lang::parse::trim_quotes;
 use lsp::strip_tmp;
 use shared::error::{Context, Result};
 use tree_sitter::{Language, Parser, Query, QueryCursor, Tree};
@@ -708,6 +709,8 @@ impl Stack for TypeScriptReact {
                     ) @{ROUTE}
                 "#
             ),
+            // NestJS: @Get(':id') on a controller method, possibly followed by @UseGuards(...) etc.
+            nest_method_finder(),
         ]
     }
 
@@ -774,6 +777,13 @@ impl Stack for TypeScriptReact {
                     (string) @{ENDPOINT}
                     (identifier) @{ENDPOINT_GROUP}
                 )
+            ) @{ROUTE}
+            ;; NestJS: app.setGlobalPrefix('api') applies to every controller route
+            (call_expression
+                function: (member_expression
+                    property: (property_identifier) @{ENDPOINT_VERB} (#eq? @{ENDPOINT_VERB} "setGlobalPrefix")
+                )
+                arguments: (arguments . (string) @{ENDPOINT})
             ) @{ROUTE}"#
         ))
     }
@@ -926,6 +936,13 @@ impl Stack for TypeScriptReact {
 
     // FROM REACT: update_endpoint (for Next.js)
     fn update_endpoint(&self, nd: &mut NodeData, _call: &Option<String>) {
+        // NestJS: `@Get()` has no path of its own, the controller prefix is added in handler_finder
+        if nd.meta.contains_key("controller_context") {
+            nd.name = nest_join_path(&[&nd.name]);
+            if nd.meta.get("verb").map(|v| v.as_str()) == Some("ALL") {
+                nd.add_verb("ANY");
+            }
+        }
         // for next.js - update endpoint name from file path
         if matches!(
             nd.name.as_str(),
@@ -956,8 +973,12 @@ impl Stack for TypeScriptReact {
         endpoint: NodeData,
         find_fn: &dyn Fn(&str, &str) -> Option<NodeData>,
         find_fns_in: &dyn Fn(&str) -> Vec<NodeData>,
-        _handler_params: HandlerParams,
+        handler_params: HandlerParams,
     ) -> Vec<(NodeData, Option<Edge>)> {
+        if endpoint.meta.contains_key("controller_context") {
+            return nest_controller_handler(endpoint, find_fns_in, handler_params);
+        }
+
         let http_verbs = ["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS"];
 
         // Helper to find HTTP verb handler directly in the same file
@@ -1115,6 +1136,95 @@ impl Stack for TypeScriptReact {
         ))
     }
 
+    // NestJS: decorators are siblings of the method inside the class body
+    fn decorated_handler<'a>(&self, route: TreeNode<'a>) -> Option<TreeNode<'a>> {
+        let mut next = route.next_named_sibling();
+        while let Some(sibling) = next {
+            match sibling.kind() {
+                "decorator" | "comment" => next = sibling.next_named_sibling(),
+                "method_definition" => return sibling.child_by_field_name("name"),
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    // NestJS: `@Controller` prefix plus guards/interceptors from the class and the method
+    fn find_endpoint_parents(
+        &self,
+        node: TreeNode,
+        code: &str,
+        _file: &str,
+        _callback: &dyn Fn(&str) -> Option<NodeData>,
+    ) -> Result<Vec<HandlerItem>> {
+        let Some(method) = node.parent().filter(|p| p.kind() == "method_definition") else {
+            return Ok(Vec::new());
+        };
+        let mut method_decorators = Vec::new();
+        let mut prev = method.prev_named_sibling();
+        while let Some(sibling) = prev.filter(|s| matches!(s.kind(), "decorator" | "comment")) {
+            if sibling.kind() == "decorator" {
+                method_decorators.insert(0, sibling);
+            }
+            prev = sibling.prev_named_sibling();
+        }
+
+        let mut class_decorators = Vec::new();
+        let class = method.parent().and_then(|body| body.parent());
+        if let Some(class) = class.filter(|c| c.kind() == "class_declaration") {
+            // decorators written before `export` belong to the export statement
+            let holders = [
+                class.parent().filter(|p| p.kind() == "export_statement"),
+                Some(class),
+            ];
+            for holder in holders.into_iter().flatten() {
+                let mut cursor = holder.walk();
+                class_decorators.extend(
+                    holder
+                        .named_children(&mut cursor)
+                        .filter(|c| c.kind() == "decorator"),
+                );
+            }
+        }
+
+        let mut parents = nest_decorator_items(&class_decorators, code);
+        parents.extend(nest_decorator_items(&method_decorators, code));
+        Ok(parents)
+    }
+
+    // NestJS: @Module({ imports, controllers, providers }) wires classes together
+    fn class_dependency_query(&self) -> Option<String> {
+        let module = format!(
+            r#"(decorator
+                (call_expression
+                    function: (identifier) @module (#eq? @module "Module")
+                    arguments: (arguments
+                        (object
+                            (pair
+                                key: (property_identifier) @key (#match? @key "^(imports|controllers|providers|exports)$")
+                                value: (array (identifier) @{CLASS_DEPENDENCY})
+                            )
+                        )
+                    )
+                )
+            )"#
+        );
+        Some(format!(
+            r#"
+            (export_statement
+                {module}
+                declaration: (class_declaration
+                    name: (type_identifier) @{CLASS_NAME}
+                )
+            )
+            (class_declaration
+                {module}
+                name: (type_identifier) @{CLASS_NAME}
+            )
+            "#
+        ))
+    }
+
     // FROM REACT: find_function_parent
     fn find_function_parent(
         &self,
@@ -1463,6 +1573,24 @@ impl Stack for TypeScriptReact {
     ) -> Vec<(NodeData, String)> {
         let mut matches = Vec::new();
 
+        // NestJS global prefix applies to every controller route
+        if let Some(global) = groups
+            .iter()
+            .find(|g| g.meta.get("verb").map(|v| v.as_str()) == Some("SETGLOBALPREFIX"))
+        {
+            let prefix = nest_join_path(&[&global.name]);
+            if prefix != "/" {
+                for endpoint in endpoints {
+                    if endpoint.meta.contains_key("controller_context")
+                        && !endpoint.name.starts_with(&format!("{}/", prefix))
+                        && endpoint.name != prefix
+                    {
+                        matches.push((endpoint.clone(), prefix.clone()));
+                    }
+                }
+            }
+        }
+
         for group in groups {
             if let Some(router_var_name) = group.meta.get("group") {
                 let prefix = &group.name;
@@ -1472,7 +1600,9 @@ impl Stack for TypeScriptReact {
                     let endpoint_name = &endpoint.name;
                     let endpoint_file = &endpoint.file;
 
-                    if endpoint_name.starts_with(prefix) {
+                    if endpoint_name.starts_with(prefix)
+                        || endpoint.meta.contains_key("controller_context")
+                    {
                         continue;
                     }
 
@@ -1516,6 +1646,144 @@ impl Stack for TypeScriptReact {
     }
 }
 
+// NestJS helpers
+
+const NEST_VERBS: &str = "^(Get|Post|Put|Patch|Delete|Options|Head|All)$";
+
+fn nest_method_finder() -> String {
+    // only the verb decorator is captured: `decorated_handler` walks to the method it
+    // annotates, past however many `@UseGuards`/`@HttpCode`/... sit around it
+    format!(
+        r#"(class_declaration
+            name: (type_identifier) @{CONTROLLER_CONTEXT}
+            (class_body
+                (decorator
+                    (call_expression
+                        function: (identifier) @{ENDPOINT_VERB} (#match? @{ENDPOINT_VERB} "{NEST_VERBS}")
+                        arguments: (arguments [(string) (template_string)]? @{ENDPOINT})
+                    )
+                ) @{ROUTE}
+            )
+        )"#
+    )
+}
+
+// `["api", "/users/", ":id"]` -> `/api/users/:id`
+fn nest_join_path(segments: &[&str]) -> String {
+    let parts: Vec<&str> = segments
+        .iter()
+        .map(|s| s.trim_matches('/'))
+        .filter(|s| !s.is_empty())
+        .collect();
+    format!("/{}", parts.join("/"))
+}
+
+fn nest_decorator_call<'a>(decorator: &TreeNode<'a>, code: &str) -> Option<(String, TreeNode<'a>)> {
+    let call = decorator
+        .named_child(0)
+        .filter(|c| c.kind() == "call_expression")?;
+    let name = call
+        .child_by_field_name("function")?
+        .utf8_text(code.as_bytes())
+        .ok()?
+        .to_string();
+    let args = call.child_by_field_name("arguments")?;
+    Some((name, args))
+}
+
+// `@Controller('users')` or `@Controller({ path: 'users' })`
+fn nest_controller_path(args: &TreeNode, code: &str) -> Option<String> {
+    let first = args.named_child(0)?;
+    match first.kind() {
+        "string" | "template_string" => {
+            Some(trim_quotes(first.utf8_text(code.as_bytes()).ok()?).to_string())
+        }
+        "object" => {
+            let mut cursor = first.walk();
+            let pairs: Vec<TreeNode> = first.named_children(&mut cursor).collect();
+            pairs.into_iter().find_map(|pair| {
+                let key = pair.child_by_field_name("key")?;
+                if key.utf8_text(code.as_bytes()).ok()? != "path" {
+                    return None;
+                }
+                let value = pair.child_by_field_name("value")?;
+                Some(trim_quotes(value.utf8_text(code.as_bytes()).ok()?).to_string())
+            })
+        }
+        _ => None,
+    }
+}
+
+// `@UseGuards(AuthGuard, RolesGuard)`, `@UseGuards(AuthGuard('jwt'))`, `@UseInterceptors(new CacheInterceptor())`
+fn nest_middleware_names(args: &TreeNode, code: &str) -> Vec<String> {
+    let mut cursor = args.walk();
+    let named: Vec<TreeNode> = args.named_children(&mut cursor).collect();
+    named
+        .into_iter()
+        .filter_map(|arg| {
+            let named = match arg.kind() {
+                "call_expression" => arg.child_by_field_name("function"),
+                "new_expression" => arg.child_by_field_name("constructor"),
+                _ => Some(arg),
+            }?;
+            named.utf8_text(code.as_bytes()).ok().map(|s| s.to_string())
+        })
+        .collect()
+}
+
+fn nest_decorator_items(decorators: &[TreeNode], code: &str) -> Vec<HandlerItem> {
+    let mut items = Vec::new();
+    for decorator in decorators {
+        let Some((name, args)) = nest_decorator_call(decorator, code) else {
+            continue;
+        };
+        match name.as_str() {
+            "Controller" => {
+                if let Some(path) = nest_controller_path(&args, code) {
+                    items.push(HandlerItem {
+                        name: path,
+                        item_type: HandlerItemType::Namespace,
+                    });
+                }
+            }
+            "UseGuards" | "UseInterceptors" => {
+                for mw in nest_middleware_names(&args, code) {
+                    items.push(HandlerItem {
+                        name: mw,
+                        item_type: HandlerItemType::Middleware,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    items
+}
+
+// the handler is the decorated method itself, so look it up by position rather than by name
+fn nest_controller_handler(
+    mut endpoint: NodeData,
+    find_fns_in: &dyn Fn(&str) -> Vec<NodeData>,
+    handler_params: HandlerParams,
+) -> Vec<(NodeData, Option<Edge>)> {
+    let mut segments: Vec<String> = handler_params
+        .parents
+        .iter()
+        .filter(|p| matches!(p.item_type, HandlerItemType::Namespace))
+        .map(|p| p.name.clone())
+        .collect();
+    segments.push(endpoint.name.clone());
+    let segments: Vec<&str> = segments.iter().map(|s| s.as_str()).collect();
+    endpoint.name = nest_join_path(&segments);
+
+    let handler = endpoint.meta.get("handler").cloned().unwrap_or_default();
+    let edge = find_fns_in(&endpoint.file)
+        .into_iter()
+        .find(|f| f.name == handler && f.start >= endpoint.start && f.end <= endpoint.end)
+        .map(|f| Edge::handler(&endpoint, &f));
+    vec![(endpoint, edge)]
+}
+
 // Helper functions from react.rs
 
 pub fn endpoint_name_from_file(file: &str) -> String {
@@ -253,6 +253,11 @@ impl Stack for Ruby {
             )"#
         ))
     }
+    fn data_model_fields(&self, node: TreeNode, code: &str) -> Vec<fields::Field> {
+        node.utf8_text(code.as_bytes())
+            .map(fields::schema_columns)
+            .unwrap_or_default()
+    }
     fn data_model_path_filter(&self) -> Option<String> {
         Some("db/schema.rb".to_string())
     }
@@ -271,13 +276,17 @@ impl Stack for Ruby {
 
         let funcs = find_fns_in(format!("{}{}", &data_model.name, CONTROLLER_FILE_SUFFIX).as_str());
 
-        for func in funcs {
-            models.push(Edge::contains(
-                NodeType::Function,
-                &func,
-                NodeType::DataModel,
-                data_model,
-            ));
+        for mut func in funcs {
+            let contains =
+                Edge::contains(NodeType::Function, &func, NodeType::DataModel, data_model);
+            // schema tables are referred to by their model class: advisor_groups -> AdvisorGroup
+            let flows = data_flow::data_flow_edges_with(
+                &mut func,
+                std::slice::from_ref(&contains),
+                |table| inflection::singularize(table).to_case(Case::Pascal),
+            );
+            models.push(contains);
+            models.extend(flows);
         }
 
         // without: Returning Graph with 12726 nodes and 13283 edges
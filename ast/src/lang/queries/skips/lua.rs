@@ -0,0 +1,46 @@
+const STDLIB_TABLES: [&str; 10] = [
+    "string",
+    "table",
+    "math",
+    "os",
+    "io",
+    "coroutine",
+    "debug",
+    "utf8",
+    "ngx",
+    "cjson",
+];
+
+const BUILTIN_FUNCTIONS: [&str; 16] = [
+    "print",
+    "pairs",
+    "ipairs",
+    "next",
+    "require",
+    "tostring",
+    "tonumber",
+    "type",
+    "error",
+    "assert",
+    "pcall",
+    "xpcall",
+    "select",
+    "unpack",
+    "setmetatable",
+    "getmetatable",
+];
+
+pub fn should_skip(called: &str, operand: &Option<String>) -> bool {
+    if BUILTIN_FUNCTIONS.contains(&called) {
+        return true;
+    }
+
+    if let Some(op) = operand {
+        let root = op.split(['.', ':']).next().unwrap_or(op);
+        if STDLIB_TABLES.contains(&root) {
+            return true;
+        }
+    }
+
+    false
+}
@@ -4,6 +4,7 @@ pub mod cpp;
 pub mod csharp;
 pub mod go;
 pub mod java;
+pub mod lua;
 pub mod php;
 pub mod python;
 pub mod react_ts;
@@ -12,3 +13,4 @@ pub mod rust;
 pub mod summary;
 pub mod svelte;
 pub mod swift;
+pub mod zig;
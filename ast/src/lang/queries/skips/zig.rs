@@ -0,0 +1,25 @@
+const STDLIB_ROOTS: [&str; 4] = ["std", "builtin", "testing", "mem"];
+
+const BUILTIN_FUNCTIONS: [&str; 6] = [
+    "print",
+    "expect",
+    "expectEqual",
+    "expectEqualStrings",
+    "expectError",
+    "deinit",
+];
+
+pub fn should_skip(called: &str, operand: &Option<String>) -> bool {
+    if BUILTIN_FUNCTIONS.contains(&called) {
+        return true;
+    }
+
+    if let Some(op) = operand {
+        let root = op.split('.').next().unwrap_or(op);
+        if STDLIB_ROOTS.contains(&root) {
+            return true;
+        }
+    }
+
+    false
+}
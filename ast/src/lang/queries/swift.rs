@@ -156,6 +156,28 @@ impl Stack for Swift {
         )
     }
 
+    fn function_signature(&self, node: TreeNode, code: &str) -> Option<signature::Signature> {
+        // parameters and their `default_value`s are direct children of the declaration
+        let mut sig = signature::Signature::default();
+        let mut cursor = node.walk();
+        for (i, child) in node.children(&mut cursor).enumerate() {
+            if child.kind() == "parameter" {
+                sig.params.extend(signature::params_from_node(child, code));
+            } else if child.kind() == "type_parameters" {
+                sig.generics = signature::generics_from_node(child, code);
+            } else if node.field_name_for_child(i as u32) == Some("default_value") {
+                if let Some(last) = sig.params.last_mut() {
+                    last.default = signature::clean_default(signature::text(child, code));
+                }
+            }
+        }
+        sig.return_type = node
+            .child_by_field_name("return_type")
+            .and_then(|n| signature::clean_type(signature::text(n, code)));
+        signature::apply_modifiers(&mut sig, node, code);
+        Some(sig)
+    }
+
     fn find_function_parent(
         &self,
         node: TreeNode,
@@ -0,0 +1,189 @@
+use super::super::*;
+use super::consts::*;
+use shared::error::{Context, Result};
+use std::path::{Component, Path, PathBuf};
+use tree_sitter::{Language, Parser, Query, Tree};
+
+const CONTAINERS: [&str; 4] = [
+    "struct_declaration",
+    "enum_declaration",
+    "union_declaration",
+    "opaque_declaration",
+];
+
+pub struct Zig(Language);
+
+impl Default for Zig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Zig {
+    pub fn new() -> Self {
+        Zig(tree_sitter_zig::LANGUAGE.into())
+    }
+}
+
+impl Stack for Zig {
+    fn should_skip_function_call(&self, called: &str, operand: &Option<String>) -> bool {
+        super::skips::zig::should_skip(called, operand)
+    }
+    fn q(&self, q: &str, _nt: &NodeType) -> Query {
+        match Query::new(&self.0, q) {
+            Ok(query) => query,
+            Err(err) => panic!("Failed to compile Zig query '{}': {}", q, err),
+        }
+    }
+    fn parse(&self, code: &str, _nt: &NodeType) -> Result<Tree> {
+        let mut parser = Parser::new();
+        parser.set_language(&self.0)?;
+        parser.parse(code, None).context("failed to parse Zig")
+    }
+    fn program_node_name(&self) -> String {
+        "source_file".to_string()
+    }
+    fn imports_query(&self) -> Option<String> {
+        let import = format!(
+            r#"(builtin_function
+                (builtin_identifier) @import (#eq? @import "@import")
+                (_ (string) @{IMPORTS_FROM})
+            )"#
+        );
+        Some(format!(
+            r#"[
+                (variable_declaration
+                    (identifier)
+                    {import}
+                ) @{IMPORTS} @{IMPORTS_MODULE}
+                (variable_declaration
+                    (identifier)
+                    (field_expression
+                        . {import}
+                        member: (identifier) @{IMPORTS_NAME}
+                    )
+                ) @{IMPORTS}
+            ]"#
+        ))
+    }
+    // `@import("util.zig")` is relative to the importing file; `@import("std")` names a module
+    fn resolve_import_path(&self, import_path: &str, current_file: &str) -> String {
+        if !import_path.ends_with(".zig") {
+            return import_path.to_string();
+        }
+        let dir = Path::new(current_file).parent().unwrap_or(Path::new(""));
+        let mut out = PathBuf::new();
+        for comp in dir.join(import_path).components() {
+            match comp {
+                Component::ParentDir => {
+                    out.pop();
+                }
+                Component::CurDir => {}
+                other => out.push(other),
+            }
+        }
+        out.display().to_string()
+    }
+    fn class_definition_query(&self) -> String {
+        format!(
+            r#"(variable_declaration
+                (identifier) @{CLASS_NAME}
+                [
+                    (struct_declaration)
+                    (enum_declaration)
+                    (union_declaration)
+                    (opaque_declaration)
+                ]
+            ) @{CLASS_DEFINITION}"#
+        )
+    }
+    fn data_model_query(&self) -> Option<String> {
+        Some(format!(
+            r#"(variable_declaration
+                (identifier) @{STRUCT_NAME}
+                [
+                    (struct_declaration)
+                    (enum_declaration)
+                    (union_declaration)
+                ]
+            ) @{STRUCT}"#
+        ))
+    }
+    fn function_definition_query(&self) -> String {
+        format!(
+            r#"(function_declaration
+                name: (identifier) @{FUNCTION_NAME}
+                (parameters) @{ARGUMENTS}
+            ) @{FUNCTION_DEFINITION}"#
+        )
+    }
+    fn test_query(&self) -> Option<String> {
+        Some(format!(
+            r#"(test_declaration
+                [(string) (identifier)] @{FUNCTION_NAME}
+            ) @{FUNCTION_DEFINITION}"#
+        ))
+    }
+    fn is_test(&self, _func_name: &str, _func_file: &str, func_body: &str) -> bool {
+        func_body.trim_start().starts_with("test ")
+    }
+    fn find_function_parent(
+        &self,
+        node: TreeNode,
+        code: &str,
+        file: &str,
+        func_name: &str,
+        find_class: &dyn Fn(&str) -> Option<(NodeData, NodeType)>,
+        _parent_type: Option<&str>,
+    ) -> Result<Option<Operand>> {
+        // methods live inside `const Name = struct { ... };`
+        let mut parent = node.parent();
+        while let Some(current) = parent {
+            if CONTAINERS.contains(&current.kind()) {
+                break;
+            }
+            parent = current.parent();
+        }
+        let Some(declaration) = parent
+            .and_then(|c| c.parent())
+            .filter(|p| p.kind() == "variable_declaration")
+        else {
+            return Ok(None);
+        };
+        let mut cursor = declaration.walk();
+        let Some(name_node) = declaration
+            .named_children(&mut cursor)
+            .find(|c| c.kind() == "identifier")
+        else {
+            return Ok(None);
+        };
+        let name = name_node.utf8_text(code.as_bytes())?;
+        Ok(find_class(name).map(|(class, source_type)| Operand {
+            source: NodeKeys::new(&class.name, &class.file, class.start),
+            target: NodeKeys::new(func_name, file, node.start_position().row),
+            source_type,
+        }))
+    }
+    fn comment_query(&self) -> Option<String> {
+        Some(format!(r#"(comment) @{FUNCTION_COMMENT}"#))
+    }
+    fn class_comment_query(&self) -> Option<String> {
+        Some(format!(r#"(comment) @{CLASS_COMMENT}"#))
+    }
+    fn data_model_comment_query(&self) -> Option<String> {
+        Some(format!(r#"(comment) @{STRUCT_COMMENT}"#))
+    }
+    fn function_call_query(&self) -> String {
+        format!(
+            r#"(call_expression
+                function: [
+                    (identifier) @{FUNCTION_NAME}
+                    (field_expression
+                        . (_) @{OPERAND}
+                        member: (identifier) @{FUNCTION_NAME}
+                    )
+                ]
+            ) @{FUNCTION_CALL}"#
+        )
+    }
+}
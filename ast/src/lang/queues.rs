@@ -0,0 +1,622 @@
+use crate::lang::graphs::{Edge, Graph, NodeType};
+use crate::lang::scan::{
+    code_matches, enclosing_function, first_line, functions_by_file, next_named_function,
+    scan_files, CodeMask, Syntax,
+};
+use crate::lang::NodeData;
+use crate::utils::line_of;
+use lsp::Language;
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::LazyLock;
+
+const JS: &[Language] = &[Language::Typescript];
+const PY: &[Language] = &[Language::Python];
+const RB: &[Language] = &[Language::Ruby];
+const GO: &[Language] = &[Language::Go];
+const JVM: &[Language] = &[Language::Java, Language::Kotlin];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueueRole {
+    Publish,
+    Consume,
+}
+
+/// A client-library call that sends to or reads from a named queue/topic.
+/// `queue` names the destination; `handler` (or a fixed `handler_name`) points at the
+/// consuming function when it is not the function enclosing the match.
+struct Detector {
+    broker: &'static str,
+    role: QueueRole,
+    langs: &'static [Language],
+    handler_name: Option<&'static str>,
+    re: Regex,
+}
+
+fn detector(
+    broker: &'static str,
+    role: QueueRole,
+    langs: &'static [Language],
+    pattern: &str,
+) -> Detector {
+    Detector {
+        broker,
+        role,
+        langs,
+        handler_name: None,
+        re: Regex::new(pattern).unwrap(),
+    }
+}
+
+static DETECTORS: LazyLock<Vec<Detector>> = LazyLock::new(|| {
+    use QueueRole::*;
+    vec![
+        // Kafka: kafkajs, kafka-python / confluent-kafka, spring-kafka, kafka-go / sarama
+        detector(
+            "kafka",
+            Publish,
+            JS,
+            r#"\.send\(\s*\{\s*topic\s*:\s*['"`](?P<queue>[\w.\-]+)"#,
+        ),
+        detector(
+            "kafka",
+            Consume,
+            JS,
+            r#"\.subscribe\(\s*\{\s*topics?\s*:\s*\[?\s*['"`](?P<queue>[\w.\-]+)"#,
+        ),
+        detector(
+            "kafka",
+            Publish,
+            PY,
+            r#"(?i:\w*producer\w*)\.(?:send|produce)\(\s*(?:topic\s*=\s*)?['"](?P<queue>[\w.\-]+)['"]"#,
+        ),
+        detector(
+            "kafka",
+            Consume,
+            PY,
+            r#"KafkaConsumer\(\s*['"](?P<queue>[\w.\-]+)['"]"#,
+        ),
+        detector(
+            "kafka",
+            Consume,
+            PY,
+            r#"(?i:\w*consumer\w*)\.subscribe\(\s*(?:topics\s*=\s*)?\[\s*['"](?P<queue>[\w.\-]+)['"]"#,
+        ),
+        detector(
+            "kafka",
+            Publish,
+            JVM,
+            r#"(?i:\w*kafka\w*)\.send\(\s*"(?P<queue>[\w.\-]+)""#,
+        ),
+        detector(
+            "kafka",
+            Consume,
+            JVM,
+            r#"@KafkaListener\([^)]*?topics\s*=\s*[\{\[]?\s*"(?P<queue>[\w.\-]+)""#,
+        ),
+        detector(
+            "kafka",
+            Publish,
+            GO,
+            r#"(?:kafka\.Writer|kafka\.Message|sarama\.ProducerMessage)\s*\{[^}]*?Topic\s*:\s*"(?P<queue>[\w.\-]+)""#,
+        ),
+        detector(
+            "kafka",
+            Consume,
+            GO,
+            r#"kafka\.ReaderConfig\s*\{[^}]*?Topic\s*:\s*"(?P<queue>[\w.\-]+)""#,
+        ),
+        detector(
+            "kafka",
+            Consume,
+            GO,
+            r#"\.ConsumePartition\(\s*"(?P<queue>[\w.\-]+)""#,
+        ),
+        // RabbitMQ: pika, amqplib, spring-amqp, amqp091-go
+        detector(
+            "rabbitmq",
+            Publish,
+            PY,
+            r#"\.basic_publish\([^)]*?routing_key\s*=\s*['"](?P<queue>[\w.\-]+)['"]"#,
+        ),
+        detector(
+            "rabbitmq",
+            Consume,
+            PY,
+            r#"\.basic_consume\([^)]*?queue\s*=\s*['"](?P<queue>[\w.\-]+)['"]"#,
+        ),
+        detector(
+            "rabbitmq",
+            Publish,
+            JS,
+            r#"\.sendToQueue\(\s*['"`](?P<queue>[\w.\-]+)['"`]"#,
+        ),
+        detector(
+            "rabbitmq",
+            Consume,
+            JS,
+            r#"\.consume\(\s*['"`](?P<queue>[\w.\-]+)['"`]"#,
+        ),
+        detector(
+            "rabbitmq",
+            Publish,
+            JVM,
+            r#"(?i:\w*rabbit\w*)\.convertAndSend\(\s*"(?P<queue>[\w.\-]+)""#,
+        ),
+        detector(
+            "rabbitmq",
+            Consume,
+            JVM,
+            r#"@RabbitListener\([^)]*?queues\s*=\s*[\{\[]?\s*"(?P<queue>[\w.\-]+)""#,
+        ),
+        detector(
+            "rabbitmq",
+            Publish,
+            GO,
+            r#"\.Publish(?:WithContext)?\(\s*(?:\w+\s*,\s*)?"[\w.\-]*"\s*,\s*"(?P<queue>[\w.\-]+)""#,
+        ),
+        detector(
+            "rabbitmq",
+            Consume,
+            GO,
+            r#"\.Consume(?:WithContext)?\(\s*(?:\w+\s*,\s*)?"(?P<queue>[\w.\-]+)""#,
+        ),
+        // SQS: boto3, @aws-sdk/client-sqs / sqs-consumer, spring-cloud-aws, aws-sdk-go
+        detector(
+            "sqs",
+            Publish,
+            PY,
+            r#"\.send_message(?:_batch)?\([^)]*?QueueUrl\s*=\s*['"](?:[^'"]*/)?(?P<queue>[\w.\-]+)['"]"#,
+        ),
+        detector(
+            "sqs",
+            Consume,
+            PY,
+            r#"\.receive_message\([^)]*?QueueUrl\s*=\s*['"](?:[^'"]*/)?(?P<queue>[\w.\-]+)['"]"#,
+        ),
+        detector(
+            "sqs",
+            Publish,
+            JS,
+            r#"SendMessage(?:Batch)?Command\(\s*\{[^}]*?QueueUrl\s*:\s*['"`](?:[^'"`]*/)?(?P<queue>[\w.\-]+)['"`]"#,
+        ),
+        detector(
+            "sqs",
+            Consume,
+            JS,
+            r#"(?:ReceiveMessageCommand|Consumer\.create)\(\s*\{[^}]*?[qQ]ueueUrl\s*:\s*['"`](?:[^'"`]*/)?(?P<queue>[\w.\-]+)['"`]"#,
+        ),
+        detector(
+            "sqs",
+            Publish,
+            JVM,
+            r#"(?i:\w*sqs\w*)\.send\(\s*"(?P<queue>[\w.\-]+)""#,
+        ),
+        detector(
+            "sqs",
+            Consume,
+            JVM,
+            r#"@SqsListener\(\s*(?:(?:value|queueNames)\s*=\s*)?[\{\[]?\s*"(?P<queue>[\w.\-]+)""#,
+        ),
+        detector(
+            "sqs",
+            Publish,
+            GO,
+            r#"SendMessageInput\s*\{[^}]*?QueueUrl\s*:\s*aws\.String\(\s*"(?:[^"]*/)?(?P<queue>[\w.\-]+)""#,
+        ),
+        detector(
+            "sqs",
+            Consume,
+            GO,
+            r#"ReceiveMessageInput\s*\{[^}]*?QueueUrl\s*:\s*aws\.String\(\s*"(?:[^"]*/)?(?P<queue>[\w.\-]+)""#,
+        ),
+        // Celery: the task function is its own queue; `task.delay(..)` is matched against
+        // the known tasks (see CELERY_CALL_RE)
+        detector(
+            "celery",
+            Consume,
+            PY,
+            r"(?m)^[ \t]*@(?:\w+\.)*(?:task|shared_task)\b[^\n]*\n(?:[ \t]*@[^\n]*\n)*[ \t]*(?:async[ \t]+)?def[ \t]+(?P<handler>\w+)",
+        ),
+        detector(
+            "celery",
+            Publish,
+            PY,
+            r#"\.send_task\(\s*['"](?:[\w.]*\.)?(?P<queue>\w+)['"]"#,
+        ),
+        // Sidekiq: workers are consumed through their `perform` method
+        Detector {
+            handler_name: Some("perform"),
+            ..detector(
+                "sidekiq",
+                Consume,
+                RB,
+                r"(?m)^[ \t]*class[ \t]+(?:\w+::)*(?P<queue>[A-Z]\w*)\b[^\n]*\n(?:[^\n]*\n){0,5}?[ \t]*include[ \t]+Sidekiq::(?:Worker|Job)\b",
+            )
+        },
+        detector(
+            "sidekiq",
+            Publish,
+            RB,
+            r"\b(?:[A-Z]\w*::)*(?P<queue>[A-Z]\w*)\.perform_(?:async|in|at|bulk)\b",
+        ),
+        // BullMQ: `new Worker("emails", processEmail)`; producers go through a bound Queue (see QUEUE_BINDING_RES)
+        detector(
+            "bullmq",
+            Consume,
+            JS,
+            r#"new\s+Worker\(\s*['"`](?P<queue>[\w.\-:]+)['"`]\s*,\s*(?P<handler>[A-Za-z_$][\w$]*)\s*[,)]"#,
+        ),
+        detector(
+            "bullmq",
+            Consume,
+            JS,
+            r#"new\s+Worker\(\s*['"`](?P<queue>[\w.\-:]+)['"`]\s*,\s*(?:async\b|function\b|\()"#,
+        ),
+    ]
+});
+
+// BullMQ `const emails = new Queue("emails")` / NestJS `@InjectQueue("emails") private emails: Queue`
+static QUEUE_BINDING_RES: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    vec![
+        Regex::new(
+            r#"(?:(?:const|let|var)\s+|this\.)(?P<var>[A-Za-z_$][\w$]*)\s*=\s*new\s+Queue\(\s*['"`](?P<queue>[\w.\-:]+)['"`]"#,
+        )
+        .unwrap(),
+        Regex::new(
+            r#"@InjectQueue\(\s*['"`](?P<queue>[\w.\-:]+)['"`]\s*\)\s*(?:(?:private|public|protected|readonly)\s+)*(?P<var>[A-Za-z_$][\w$]*)"#,
+        )
+        .unwrap(),
+    ]
+});
+
+static QUEUE_ADD_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?P<var>[A-Za-z_$][\w$]*)\.add(?:Bulk)?\(").unwrap());
+static CELERY_CALL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?P<task>[A-Za-z_]\w*)\.(?:delay|apply_async)\(").unwrap());
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueueUse {
+    pub broker: &'static str,
+    pub role: QueueRole,
+    pub name: String,
+    /// function that publishes/consumes, when it is not the one enclosing `line`
+    pub handler: Option<String>,
+    pub line: usize,
+    pub snippet: String,
+}
+
+/// Finds queue producers and consumers in one source file, ignoring comments and strings.
+/// Celery `.delay` calls count only for tasks declared in the same file; see
+/// [`add_queue_nodes`] for the repo-wide set.
+pub fn find_queue_uses(lang: &Language, code: &str) -> Vec<QueueUse> {
+    let mask = Syntax::of(lang).mask(code);
+    let tasks = celery_tasks(lang, code, &mask).into_iter().collect();
+    queue_uses(lang, code, &mask, &tasks)
+}
+
+fn queue_uses(
+    lang: &Language,
+    code: &str,
+    mask: &CodeMask,
+    celery_tasks: &HashSet<String>,
+) -> Vec<QueueUse> {
+    let mut uses = Vec::new();
+    for det in DETECTORS.iter().filter(|d| d.langs.contains(lang)) {
+        for caps in code_matches(&det.re, code, mask) {
+            let whole = caps.get(0).unwrap();
+            let handler = caps
+                .name("handler")
+                .map(|h| h.as_str().to_string())
+                .or_else(|| det.handler_name.map(str::to_string));
+            let Some(name) = caps
+                .name("queue")
+                .map(|q| q.as_str().to_string())
+                .or_else(|| handler.clone())
+            else {
+                continue;
+            };
+            uses.push(QueueUse {
+                broker: det.broker,
+                role: det.role,
+                name,
+                handler,
+                line: line_of(code, whole.start()),
+                snippet: first_line(whole.as_str()),
+            });
+        }
+    }
+    if JS.contains(lang) {
+        uses.extend(bound_queue_publishes(code, mask));
+    }
+    if PY.contains(lang) {
+        for caps in code_matches(&CELERY_CALL_RE, code, mask) {
+            let whole = caps.get(0).unwrap();
+            if celery_tasks.contains(&caps["task"]) {
+                uses.push(QueueUse {
+                    broker: "celery",
+                    role: QueueRole::Publish,
+                    name: caps["task"].to_string(),
+                    handler: None,
+                    line: line_of(code, whole.start()),
+                    snippet: first_line(whole.as_str()),
+                });
+            }
+        }
+    }
+    uses.sort_by_key(|u| u.line);
+    uses
+}
+
+/// Functions declared as Celery tasks (`@app.task`, `@shared_task`).
+fn celery_tasks(lang: &Language, code: &str, mask: &CodeMask) -> Vec<String> {
+    if !PY.contains(lang) {
+        return Vec::new();
+    }
+    DETECTORS
+        .iter()
+        .filter(|d| d.broker == "celery" && d.role == QueueRole::Consume)
+        .flat_map(|d| {
+            code_matches(&d.re, code, mask)
+                .map(|caps| caps["handler"].to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// `(var, queue, offset)` for each variable bound to a BullMQ queue.
+fn queue_bindings(code: &str, mask: &CodeMask) -> Vec<(String, String, usize)> {
+    let mut bindings = Vec::new();
+    for re in QUEUE_BINDING_RES.iter() {
+        for caps in code_matches(re, code, mask) {
+            let offset = caps.get(0).unwrap().start();
+            bindings.push((caps["var"].to_string(), caps["queue"].to_string(), offset));
+        }
+    }
+    bindings
+}
+
+fn bound_queue_publishes(code: &str, mask: &CodeMask) -> Vec<QueueUse> {
+    let bindings: BTreeMap<String, String> = queue_bindings(code, mask)
+        .into_iter()
+        .map(|(var, queue, _)| (var, queue))
+        .collect();
+    if bindings.is_empty() {
+        return Vec::new();
+    }
+    code_matches(&QUEUE_ADD_RE, code, mask)
+        .filter_map(|caps| {
+            let queue = bindings.get(&caps["var"])?;
+            let whole = caps.get(0).unwrap();
+            Some(QueueUse {
+                broker: "bullmq",
+                role: QueueRole::Publish,
+                name: queue.clone(),
+                handler: None,
+                line: line_of(code, whole.start()),
+                snippet: first_line(whole.as_str()),
+            })
+        })
+        .collect()
+}
+
+/// Adds a Queue node per (broker, name) and PUBLISHES / CONSUMES edges from the functions using it.
+///
+/// A queue lives where it is declared: the first file, by path, that binds it to a
+/// queue object or consumes it. Queues only ever published to live in a synthetic
+/// `queues/<broker>` file, so their location does not depend on which files were scanned.
+pub fn add_queue_nodes<G: Graph>(
+    graph: &mut G,
+    lang: &Language,
+    filez: &[(String, String)],
+) -> usize {
+    let syntax = Syntax::of(lang);
+    let tasks: HashSet<String> = filez
+        .iter()
+        .flat_map(|(_, code)| celery_tasks(lang, code, &syntax.mask(code)))
+        .collect();
+    let found = scan_files(filez, |_, code| {
+        queue_uses(lang, code, &syntax.mask(code), &tasks)
+    });
+    if found.is_empty() {
+        return 0;
+    }
+
+    let mut queues: BTreeMap<(&'static str, String), NodeData> = BTreeMap::new();
+    for (file, code, uses) in &found {
+        let bindings = if JS.contains(lang) {
+            queue_bindings(code, &syntax.mask(code))
+        } else {
+            Vec::new()
+        };
+        let declared = bindings
+            .iter()
+            .map(|(_, queue, offset)| {
+                let line = line_of(code, *offset);
+                ("bullmq", queue.clone(), line, first_line(&code[*offset..]))
+            })
+            .chain(
+                uses.iter()
+                    .filter(|u| u.role == QueueRole::Consume)
+                    .map(|u| (u.broker, u.name.clone(), u.line, u.snippet.clone())),
+            );
+        for (broker, name, line, snippet) in declared {
+            queues
+                .entry((broker, name))
+                .or_insert_with_key(|(broker, name)| {
+                    let mut nd = NodeData::name_file(name, file);
+                    nd.start = line;
+                    nd.end = line;
+                    nd.body = snippet;
+                    nd.meta.insert("broker".to_string(), broker.to_string());
+                    nd
+                });
+        }
+    }
+
+    let functions = functions_by_file(graph);
+
+    let mut edges = Vec::new();
+    let mut seen = HashSet::new();
+    for (file, _, uses) in &found {
+        let Some(funcs) = functions.get(*file) else {
+            continue;
+        };
+        for u in uses {
+            let Some(func) = using_function(funcs, u) else {
+                continue;
+            };
+            let queue = queues.entry((u.broker, u.name.clone())).or_insert_with(|| {
+                let mut nd = NodeData::name_file(&u.name, &format!("queues/{}", u.broker));
+                nd.meta.insert("broker".to_string(), u.broker.to_string());
+                nd
+            });
+            if !seen.insert((
+                func.name.clone(),
+                func.file.clone(),
+                func.start,
+                u.role,
+                u.broker,
+                u.name.clone(),
+            )) {
+                continue;
+            }
+            edges.push(match u.role {
+                QueueRole::Publish => Edge::publishes(func, queue),
+                QueueRole::Consume => Edge::consumes(func, queue),
+            });
+        }
+    }
+
+    for queue in queues.values() {
+        graph.add_node_with_parent(&NodeType::Queue, queue, &NodeType::File, &queue.file);
+    }
+    for edge in &edges {
+        graph.add_edge(edge);
+    }
+    queues.len()
+}
+
+fn using_function<'a>(funcs: &'a [NodeData], u: &QueueUse) -> Option<&'a NodeData> {
+    match &u.handler {
+        Some(handler) => next_named_function(funcs, handler, u.line),
+        None => enclosing_function(funcs, u.line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(uses: &[QueueUse], role: QueueRole) -> Vec<(&str, &str)> {
+        uses.iter()
+            .filter(|u| u.role == role)
+            .map(|u| (u.broker, u.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_python_queue_uses() {
+        let code = r#"
+@app.task
+def send_receipt(order_id):
+    pass
+
+def place_order(order):
+    producer.send("orders.created", order)
+    channel.basic_publish(exchange="", routing_key="billing", body=order)
+    send_receipt.delay(order.id)
+    time.delay(5)
+"#;
+        let uses = find_queue_uses(&Language::Python, code);
+        assert_eq!(
+            names(&uses, QueueRole::Publish),
+            vec![
+                ("kafka", "orders.created"),
+                ("rabbitmq", "billing"),
+                ("celery", "send_receipt"),
+            ]
+        );
+        assert_eq!(
+            names(&uses, QueueRole::Consume),
+            vec![("celery", "send_receipt")]
+        );
+        assert_eq!(uses[0].handler.as_deref(), Some("send_receipt"));
+    }
+
+    #[test]
+    fn test_bullmq_bound_queue() {
+        let code = r#"
+const emails = new Queue("emails");
+export async function signup(user) {
+  await emails.add("welcome", { id: user.id });
+}
+new Worker("emails", sendEmail);
+"#;
+        let uses = find_queue_uses(&Language::Typescript, code);
+        assert_eq!(names(&uses, QueueRole::Publish), vec![("bullmq", "emails")]);
+        assert_eq!(uses[0].line, 3);
+        let consumer = uses.iter().find(|u| u.role == QueueRole::Consume).unwrap();
+        assert_eq!(consumer.handler.as_deref(), Some("sendEmail"));
+    }
+
+    #[test]
+    fn test_sidekiq_worker() {
+        let code = "class HardWorker\n  include Sidekiq::Worker\n\n  def perform(id)\n  end\nend\n\nHardWorker.perform_async(1)\n";
+        let uses = find_queue_uses(&Language::Ruby, code);
+        assert_eq!(
+            names(&uses, QueueRole::Consume),
+            vec![("sidekiq", "HardWorker")]
+        );
+        assert_eq!(
+            names(&uses, QueueRole::Publish),
+            vec![("sidekiq", "HardWorker")]
+        );
+    }
+
+    #[test]
+    fn test_queue_nodes_do_not_depend_on_file_order() {
+        use crate::lang::graphs::{ArrayGraph, EdgeType};
+        let tasks = (
+            "app/tasks.py".to_string(),
+            "@shared_task\ndef send_receipt(order_id):\n    pass\n".to_string(),
+        );
+        let views = (
+            "app/views.py".to_string(),
+            "def checkout(order):\n    send_receipt.delay(order.id)\n    animation.delay(3)\n    producer.send(\"orders.created\", order)\n".to_string(),
+        );
+        for filez in [
+            vec![tasks.clone(), views.clone()],
+            vec![views.clone(), tasks.clone()],
+        ] {
+            let mut graph = ArrayGraph::new(String::new(), Language::Python);
+            let mut send_receipt = NodeData::name_file_start("send_receipt", "app/tasks.py", 1);
+            send_receipt.end = 2;
+            let mut checkout = NodeData::name_file_start("checkout", "app/views.py", 0);
+            checkout.end = 3;
+            graph.add_node(&NodeType::Function, &send_receipt);
+            graph.add_node(&NodeType::Function, &checkout);
+
+            assert_eq!(add_queue_nodes(&mut graph, &Language::Python, &filez), 2);
+            let mut queues: Vec<_> = graph
+                .find_nodes_by_type(NodeType::Queue)
+                .into_iter()
+                .map(|q| (q.name, q.file))
+                .collect();
+            queues.sort();
+            assert_eq!(
+                queues,
+                vec![
+                    ("orders.created".to_string(), "queues/kafka".to_string()),
+                    ("send_receipt".to_string(), "app/tasks.py".to_string()),
+                ]
+            );
+            let published = graph.find_nodes_with_edge_type(
+                NodeType::Function,
+                NodeType::Queue,
+                EdgeType::Publishes,
+            );
+            assert_eq!(published.len(), 2);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::{go_resolver, Registry};
+use super::{go_resolver, signature_returns, Registry};
 use crate::lang::asg::NodeKeys;
 use crate::lang::graphs::{Graph, NodeType};
 use std::collections::HashMap;
@@ -85,6 +85,10 @@ impl GoRegistry {
             .flat_map(|(_, source)| go_resolver::extract_fn_returns(source))
             .collect();
         reg.fn_returns = fn_returns;
+        // Signature return types fill in functions the source pass left untyped
+        for (name, (ret, _)) in signature_returns(graph, &[".go"]).functions {
+            reg.fn_returns.entry(name).or_insert(ret);
+        }
 
         // Pass 2: pre-resolve call sites per file
         let all_resolved: Vec<((String, usize, usize), NodeKeys)> = filez
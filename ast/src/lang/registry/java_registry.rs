@@ -1,6 +1,6 @@
-use super::{java_resolver, Registry};
+use super::{java_resolver, signature_returns, Registry};
 use crate::lang::asg::NodeKeys;
-use crate::lang::graphs::{Graph, NodeType};
+use crate::lang::graphs::{EdgeType, Graph, NodeType};
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -47,6 +47,38 @@ impl JavaRegistry {
                 .or_insert_with(|| NodeKeys::from(node_data));
         }
 
+        // A package split across source roots (`src/main/java`, `src/test/java`) is one
+        // Module; let each of its directories see the others' functions.
+        let mut package_dirs: HashMap<String, Vec<String>> = HashMap::new();
+        for (module, func) in graph.find_nodes_with_edge_type(
+            NodeType::Module,
+            NodeType::Function,
+            EdgeType::Contains,
+        ) {
+            if !func.file.ends_with(".java") {
+                continue;
+            }
+            let dirs = package_dirs.entry(module.name).or_default();
+            let dir = parent_dir(&func.file);
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+        for dirs in package_dirs.values().filter(|d| d.len() > 1) {
+            let mut merged: HashMap<String, NodeKeys> = HashMap::new();
+            for dir in dirs {
+                for (name, nk) in reg.pkg_fns.get(dir).into_iter().flatten() {
+                    merged.entry(name.clone()).or_insert_with(|| nk.clone());
+                }
+            }
+            for dir in dirs {
+                let fns = reg.pkg_fns.entry(dir.clone()).or_default();
+                for (name, nk) in &merged {
+                    fns.entry(name.clone()).or_insert_with(|| nk.clone());
+                }
+            }
+        }
+
         // Pass 1.5: extract class field types and method return types from source AST.
         for (file, source) in filez {
             if !file.ends_with(".java") {
@@ -65,6 +97,11 @@ impl JavaRegistry {
             }
         }
 
+        // Signature return types fill in methods the source pass left untyped.
+        for (key, ret) in signature_returns(graph, &[".java"]).methods {
+            reg.method_returns.entry(key).or_insert(ret);
+        }
+
         // Pass 2: pre-resolve all call sites per file.
         let all_resolved: Vec<((String, usize, usize), NodeKeys)> = filez
             .iter()
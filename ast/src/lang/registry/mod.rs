@@ -19,9 +19,10 @@ pub mod ts_resolver;
 pub mod typescript;
 
 use crate::lang::asg::NodeKeys;
-use crate::lang::graphs::Graph;
+use crate::lang::graphs::{Graph, NodeType};
 use crate::lang::Lang;
 use lsp::Language;
+use std::collections::HashMap;
 
 pub trait Registry: Send + Sync {
     fn resolve_type(&self, file: &str, var_name: &str) -> Option<&str>;
@@ -52,3 +53,39 @@ pub fn build(
         _ => None,
     }
 }
+
+/// Return types recorded in Function node signatures. The per-language source
+/// extractors only see explicit annotations on declarations they recognise; these
+/// fill the gaps, so registries insert them with `or_insert` after their own pass.
+#[derive(Default)]
+pub(crate) struct SignatureReturns {
+    /// free function name → (base return type, defining file)
+    pub functions: HashMap<String, (String, String)>,
+    /// (owning type, method name) → base return type
+    pub methods: HashMap<(String, String), String>,
+}
+
+pub(crate) fn signature_returns(graph: &impl Graph, exts: &[&str]) -> SignatureReturns {
+    let mut out = SignatureReturns::default();
+    for (node_type, node_data) in graph.iter_all_nodes() {
+        if *node_type != NodeType::Function || !exts.iter().any(|e| node_data.file.ends_with(e)) {
+            continue;
+        }
+        let Some(ret) = node_data.signature().and_then(|s| s.return_base_type()) else {
+            continue;
+        };
+        match node_data.meta.get("operand") {
+            Some(operand) => {
+                out.methods
+                    .entry((operand.clone(), node_data.name.clone()))
+                    .or_insert(ret);
+            }
+            None => {
+                out.functions
+                    .entry(node_data.name.clone())
+                    .or_insert((ret, node_data.file.clone()));
+            }
+        }
+    }
+    out
+}
@@ -1,4 +1,4 @@
-use super::{php_resolver, Registry};
+use super::{php_resolver, signature_returns, Registry};
 use crate::lang::asg::NodeKeys;
 use crate::lang::graphs::{Graph, NodeType};
 use std::collections::HashMap;
@@ -61,6 +61,11 @@ impl PhpRegistry {
             }
         }
 
+        // Signature return types fill in methods the source pass left untyped.
+        for (key, ret) in signature_returns(graph, &[".php"]).methods {
+            reg.method_returns.entry(key).or_insert(ret);
+        }
+
         // Pass 2: pre-resolve all call sites per file.
         let all_resolved: Vec<((String, usize, usize), NodeKeys)> = filez
             .iter()
@@ -1,7 +1,8 @@
-use super::{py_resolver, Registry};
+use super::{py_resolver, signature_returns, Registry};
 use crate::lang::asg::NodeKeys;
 use crate::lang::call_finder::IMPORT_CACHE;
 use crate::lang::graphs::{Graph, NodeType};
+use crate::lang::modules;
 use std::collections::HashMap;
 
 pub struct PythonRegistry {
@@ -66,6 +67,23 @@ impl PythonRegistry {
             }
         }
 
+        // Absolute imports name a module (`app.services.billing`); point them at its file
+        let mut module_files: HashMap<String, Vec<String>> = HashMap::new();
+        for (file, mods) in modules::modules_by_file(graph) {
+            if let Some(stem) = file.strip_suffix(".py") {
+                for m in mods {
+                    module_files.entry(m.name).or_default().push(stem.to_string());
+                }
+            }
+        }
+        for ((file, _), source) in reg.import_sources.iter_mut() {
+            if let Some(found) = module_files.get(source.as_str()) {
+                if let Some(stem) = modules::closest(found, file, |f| f.as_str()) {
+                    *source = stem.clone();
+                }
+            }
+        }
+
         // Pass 1.5: extract class fields and top-level vars from source
         for (file, source) in filez {
             if !file.ends_with(".py") {
@@ -87,7 +105,7 @@ impl PythonRegistry {
         }
 
         // Build fn_returns: func_name → (return_type, defining_file)
-        let fn_returns: HashMap<String, (String, String)> = filez
+        let mut fn_returns: HashMap<String, (String, String)> = filez
             .iter()
             .filter(|(f, _)| f.ends_with(".py"))
             .flat_map(|(file, source)| {
@@ -97,6 +115,11 @@ impl PythonRegistry {
             })
             .collect();
 
+        // Signature return types fill in functions the source pass left untyped
+        for (name, ret) in signature_returns(graph, &[".py"]).functions {
+            fn_returns.entry(name).or_insert(ret);
+        }
+
         // Pass 2: pre-resolve call sites per file
         let all_resolved: Vec<((String, usize, usize), NodeKeys)> = filez
             .iter()
@@ -1,7 +1,8 @@
 use super::{rust_resolver, Registry};
 use crate::lang::asg::NodeKeys;
 use crate::lang::graphs::{Graph, NodeType};
-use std::collections::HashMap;
+use crate::lang::modules;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 fn parent_dir(file: &str) -> String {
@@ -11,13 +12,24 @@ fn parent_dir(file: &str) -> String {
         .unwrap_or_default()
 }
 
+/// Functions a call site can reach without a receiver.
+#[derive(Default)]
+pub struct FnIndex {
+    /// dir → { fn_name → NodeKeys } for free functions in the same crate directory
+    pub by_dir: HashMap<String, HashMap<String, NodeKeys>>,
+    /// `crate::db::connect` → functions with that path, one per crate defining it
+    pub by_path: HashMap<String, Vec<NodeKeys>>,
+    /// file → (start, end, module path) of the modules declared in it
+    pub modules: HashMap<String, Vec<(usize, usize, String)>>,
+}
+
 pub struct RustRegistry {
     /// (operand_type, method_name) → defining file
     methods_idx: HashMap<(String, String), String>,
     /// struct_name → { field_name → base_type }
     struct_fields: HashMap<String, HashMap<String, String>>,
-    /// dir → { fn_name → NodeKeys } for free functions in the same crate directory
-    pkg_fns: HashMap<String, HashMap<String, NodeKeys>>,
+    /// free functions by directory and by module path
+    pkg_fns: FnIndex,
     /// (file, row, col) → pre-resolved target NodeKeys
     resolved: HashMap<(String, usize, usize), NodeKeys>,
 }
@@ -27,11 +39,12 @@ impl RustRegistry {
         let mut reg = RustRegistry {
             methods_idx: HashMap::new(),
             struct_fields: HashMap::new(),
-            pkg_fns: HashMap::new(),
+            pkg_fns: FnIndex::default(),
             resolved: HashMap::new(),
         };
 
         // Pass 1: scan graph nodes for functions.
+        let mut fn_keys = HashSet::new();
         for (node_type, node_data) in graph.iter_all_nodes() {
             let file = &node_data.file;
             if !file.ends_with(".rs") {
@@ -41,9 +54,11 @@ impl RustRegistry {
                 continue;
             }
             let dir = parent_dir(file);
+            fn_keys.insert(NodeKeys::from(node_data));
 
             // Index free functions and methods by directory (crate module scope).
             reg.pkg_fns
+                .by_dir
                 .entry(dir)
                 .or_default()
                 .entry(node_data.name.clone())
@@ -57,6 +72,19 @@ impl RustRegistry {
             }
         }
 
+        // Pass 1.25: index functions by module path for `crate::`, `self::` and `super::` calls.
+        for (nk, path) in modules::qualified_names(graph) {
+            if fn_keys.contains(&nk) {
+                reg.pkg_fns.by_path.entry(path).or_default().push(nk);
+            }
+        }
+        for (file, mods) in modules::modules_by_file(graph) {
+            if file.ends_with(".rs") {
+                let ranges = mods.into_iter().map(|m| (m.start, m.end, m.name)).collect();
+                reg.pkg_fns.modules.insert(file, ranges);
+            }
+        }
+
         // Pass 1.5: extract struct field types from source.
         for (file, source) in filez {
             if !file.ends_with(".rs") {
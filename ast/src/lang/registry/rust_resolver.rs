@@ -1,9 +1,11 @@
 use crate::lang::asg::NodeKeys;
 use crate::lang::graphs::{Graph, NodeType};
+use crate::lang::modules::closest;
 use std::collections::HashMap;
 use std::path::Path;
 use tree_sitter::{Node, Parser};
 
+use super::rust_registry::FnIndex;
 use super::scope::{scope_bind, scope_lookup, scope_pop, scope_push, Scope};
 
 fn make_parser() -> Option<Parser> {
@@ -273,7 +275,7 @@ fn position_of_callee(func_node: Node) -> (usize, usize) {
 fn resolve_callee<G: Graph>(
     scope: &Scope,
     struct_fields: &HashMap<String, HashMap<String, String>>,
-    pkg_fns: &HashMap<String, HashMap<String, NodeKeys>>,
+    pkg_fns: &FnIndex,
     graph: &G,
     node: Node,
     source: &[u8],
@@ -324,9 +326,15 @@ fn resolve_callee<G: Graph>(
             {
                 return Some(NodeKeys::from(&nd));
             }
+            // Fallback: a module path, `crate::db::connect` or `super::helper`
+            let row = node.start_position().row;
+            let path = path_node.utf8_text(source).ok()?;
+            if let Some(nk) = resolve_module_path(pkg_fns, file, row, path, fn_name) {
+                return Some(nk);
+            }
             // Fallback: might be a free function in a module
             let dir = parent_dir(file);
-            pkg_fns.get(&dir)?.get(fn_name).cloned()
+            pkg_fns.by_dir.get(&dir)?.get(fn_name).cloned()
         }
 
         // bare fn() — same file first, then same package directory
@@ -338,13 +346,54 @@ fn resolve_callee<G: Graph>(
                 return Some(NodeKeys::from(&nd));
             }
             let dir = parent_dir(file);
-            pkg_fns.get(&dir)?.get(fname).cloned()
+            pkg_fns.by_dir.get(&dir)?.get(fname).cloned()
         }
 
         _ => None,
     }
 }
 
+/// Resolves `path::fn_name` against the module paths of the index. `crate::` paths are
+/// absolute, `self::` and `super::` start at the module around `row`, and anything else is
+/// tried as a child of that module and then from the crate root.
+fn resolve_module_path(
+    pkg_fns: &FnIndex,
+    file: &str,
+    row: usize,
+    path: &str,
+    fn_name: &str,
+) -> Option<NodeKeys> {
+    let current = pkg_fns
+        .modules
+        .get(file)?
+        .iter()
+        .filter(|(start, end, _)| *start <= row && row <= *end)
+        .max_by_key(|(start, _, _)| *start)
+        .map(|(_, _, m)| m.as_str())?;
+    let candidates = match path.split("::").next()? {
+        "crate" => vec![path.to_string()],
+        "self" | "super" => {
+            let mut base: Vec<&str> = current.split("::").collect();
+            for segment in path.split("::") {
+                match segment {
+                    "self" => {}
+                    "super" if base.len() > 1 => {
+                        base.pop();
+                    }
+                    "super" => return None,
+                    other => base.push(other),
+                }
+            }
+            vec![base.join("::")]
+        }
+        _ => vec![format!("{}::{}", current, path), format!("crate::{}", path)],
+    };
+    candidates.iter().find_map(|module| {
+        let found = pkg_fns.by_path.get(&format!("{}::{}", module, fn_name))?;
+        closest(found, file, |nk| nk.file.as_str()).cloned()
+    })
+}
+
 // ── Parameter binding ──────────────────────────────────────────────────────────
 
 fn bind_params(params: Node, source: &[u8], scope: &mut Scope, impl_type: Option<&str>) {
@@ -391,7 +440,7 @@ fn walk_node<G: Graph>(
     source: &[u8],
     scope: &mut Scope,
     struct_fields: &HashMap<String, HashMap<String, String>>,
-    pkg_fns: &HashMap<String, HashMap<String, NodeKeys>>,
+    pkg_fns: &FnIndex,
     graph: &G,
     out: &mut HashMap<(usize, usize), NodeKeys>,
     file: &str,
@@ -532,7 +581,7 @@ pub fn resolve_file_calls<G: Graph>(
     source: &str,
     file: &str,
     struct_fields: &HashMap<String, HashMap<String, String>>,
-    pkg_fns: &HashMap<String, HashMap<String, NodeKeys>>,
+    pkg_fns: &FnIndex,
     graph: &G,
 ) -> HashMap<(usize, usize), NodeKeys> {
     let mut out = HashMap::new();
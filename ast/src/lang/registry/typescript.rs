@@ -1,4 +1,4 @@
-use super::{ts_resolver, Registry};
+use super::{signature_returns, ts_resolver, Registry};
 use crate::lang::asg::NodeKeys;
 use crate::lang::call_finder::IMPORT_CACHE;
 use crate::lang::graphs::{Graph, NodeType};
@@ -38,19 +38,10 @@ impl TypeScriptRegistry {
                             file.clone(),
                         );
                     }
-                    if let Some(pt_json) = node_data.meta.get("param_types") {
-                        if let Ok(params) =
-                            serde_json::from_str::<Vec<HashMap<String, String>>>(pt_json)
-                        {
-                            for param in params {
-                                if let (Some(name), Some(type_str)) =
-                                    (param.get("name"), param.get("type"))
-                                {
-                                    reg.var_types.insert(
-                                        (file.clone(), name.clone()),
-                                        type_str.clone(),
-                                    );
-                                }
+                    if let Some(signature) = node_data.signature() {
+                        for param in signature.params {
+                            if let Some(type_name) = param.type_name {
+                                reg.var_types.insert((file.clone(), param.name), type_name);
                             }
                         }
                     }
@@ -110,7 +101,7 @@ impl TypeScriptRegistry {
         // Build fn_returns: func_name → (return_type, defining_file)
         // Scan source files directly so this works for all graph backends (including Neo4j
         // which cannot support the synchronous iter_all_nodes interface).
-        let fn_returns: HashMap<String, (String, String)> = filez
+        let mut fn_returns: HashMap<String, (String, String)> = filez
             .iter()
             .filter(|(f, _)| f.ends_with(".ts") || f.ends_with(".tsx"))
             .flat_map(|(file, source)| {
@@ -120,6 +111,19 @@ impl TypeScriptRegistry {
             })
             .collect();
 
+        // Signature return types fill in functions and methods the source pass left untyped
+        let sig_returns = signature_returns(graph, &[".ts", ".tsx"]);
+        for (name, ret) in sig_returns.functions {
+            fn_returns.entry(name).or_insert(ret);
+        }
+        for ((class_name, method_name), ret) in sig_returns.methods {
+            reg.class_fields
+                .entry(class_name)
+                .or_default()
+                .entry(format!("{}()", method_name))
+                .or_insert(ret);
+        }
+
         // Pass 2: pre-resolve call sites per file
         let all_resolved: Vec<((String, usize, usize), NodeKeys)> = filez
             .iter()
@@ -0,0 +1,413 @@
+//! Plumbing shared by the regex detectors (queues, channels, jobs, commands, config keys,
+//! errors and modules): telling code from comments and string literals, matching braces,
+//! and finding the function a match belongs to.
+
+use crate::lang::graphs::{Graph, NodeType};
+use crate::lang::NodeData;
+use lsp::Language;
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quote {
+    /// `'...'` is a string, as `"..."` is
+    String,
+    /// `'x'` is a char literal; a lone `'` (a Rust lifetime) is code
+    Char,
+    /// `'` means nothing special
+    Plain,
+}
+
+/// How a language spells comments and string or char literals.
+#[derive(Debug, Clone, Copy)]
+pub struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comments: bool,
+    single_quote: Quote,
+    backticks: bool,
+    triple_quotes: bool,
+    raw_strings: bool,
+    /// the quote whose strings interpolate, and what opens an interpolation (`${`, `#{`)
+    interpolation: Option<(u8, &'static str)>,
+}
+
+const C_LIKE: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: true,
+    single_quote: Quote::Char,
+    backticks: false,
+    triple_quotes: false,
+    raw_strings: false,
+    interpolation: None,
+};
+
+const SCRIPT: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: false,
+    single_quote: Quote::String,
+    backticks: false,
+    triple_quotes: false,
+    raw_strings: false,
+    interpolation: None,
+};
+
+impl Syntax {
+    /// Terraform / HCL: `#` and `//` comments, double-quoted strings only.
+    pub const HCL: Syntax = Syntax {
+        line_comments: &["#", "//"],
+        single_quote: Quote::Plain,
+        interpolation: Some((b'"', "${")),
+        ..C_LIKE
+    };
+
+    pub fn of(lang: &Language) -> Syntax {
+        match lang {
+            Language::Rust => Syntax {
+                raw_strings: true,
+                ..C_LIKE
+            },
+            Language::Go => Syntax {
+                backticks: true,
+                ..C_LIKE
+            },
+            Language::Kotlin => Syntax {
+                triple_quotes: true,
+                interpolation: Some((b'"', "${")),
+                ..C_LIKE
+            },
+            Language::Java | Language::Swift | Language::CSharp => Syntax {
+                triple_quotes: true,
+                ..C_LIKE
+            },
+            Language::Typescript | Language::Angular | Language::Svelte => Syntax {
+                single_quote: Quote::String,
+                backticks: true,
+                interpolation: Some((b'`', "${")),
+                ..C_LIKE
+            },
+            Language::Php => Syntax {
+                line_comments: &["//", "#"],
+                single_quote: Quote::String,
+                ..C_LIKE
+            },
+            Language::Python | Language::Toml => Syntax {
+                triple_quotes: true,
+                ..SCRIPT
+            },
+            Language::Lua => Syntax {
+                line_comments: &["--"],
+                ..SCRIPT
+            },
+            Language::Ruby => Syntax {
+                interpolation: Some((b'"', "#{")),
+                ..SCRIPT
+            },
+            Language::Bash => SCRIPT,
+            Language::C | Language::Cpp | Language::Zig => C_LIKE,
+        }
+    }
+
+    fn comment_at(&self, rest: &str) -> bool {
+        (self.block_comments && rest.starts_with("/*"))
+            || self.line_comments.iter().any(|c| rest.starts_with(c))
+    }
+
+    /// Offset just past the comment or literal starting at `i`, if one starts there.
+    /// The interpolations of a literal are pushed to `holes`.
+    fn skip(&self, code: &str, i: usize, holes: &mut Vec<(usize, usize)>) -> Option<usize> {
+        let bytes = code.as_bytes();
+        let b = *bytes.get(i)?;
+        if !b.is_ascii() {
+            return None;
+        }
+        let rest = &code[i..];
+        let to = |found: Option<usize>, len: usize| found.map_or(code.len(), |e| i + e + len);
+        if self.comment_at(rest) {
+            return Some(match rest.starts_with("/*") {
+                true => to(rest[2..].find("*/"), 4),
+                false => to(rest.find('\n'), 0),
+            });
+        }
+        if self.triple_quotes
+            && (rest.starts_with("\"\"\"")
+                || (rest.starts_with("'''") && self.single_quote == Quote::String))
+        {
+            return Some(to(rest[3..].find(&rest[..3]), 6));
+        }
+        match b {
+            b'r' if self.raw_strings => raw_string(code, i),
+            b'"' => Some(self.quoted(code, i, b'"', true, holes)),
+            b'\'' => match self.single_quote {
+                Quote::String => Some(self.quoted(code, i, b'\'', true, holes)),
+                Quote::Char => char_literal(code, i),
+                Quote::Plain => None,
+            },
+            b'`' if self.backticks => Some(self.quoted(code, i, b'`', false, holes)),
+            _ => None,
+        }
+    }
+
+    /// A string closed by `quote`, honouring backslash escapes and stepping over
+    /// interpolations. Single-line strings left open stop at the end of the line rather
+    /// than swallowing the rest of the file.
+    fn quoted(
+        &self,
+        code: &str,
+        i: usize,
+        quote: u8,
+        single_line: bool,
+        holes: &mut Vec<(usize, usize)>,
+    ) -> usize {
+        let bytes = code.as_bytes();
+        let opener = self
+            .interpolation
+            .filter(|(q, _)| *q == quote)
+            .map(|(_, open)| open.as_bytes());
+        let mut j = i + 1;
+        while j < bytes.len() {
+            match bytes[j] {
+                b'\\' => j += 1,
+                b'\n' if single_line => return j,
+                c if c == quote => return j + 1,
+                _ if opener.is_some_and(|o| bytes[j..].starts_with(o)) => {
+                    let open = j + opener.unwrap().len() - 1;
+                    let Some(close) = self.block_end(code, open) else {
+                        return bytes.len();
+                    };
+                    holes.push((open + 1, close));
+                    j = close;
+                }
+                _ => {}
+            }
+            j += 1;
+        }
+        bytes.len()
+    }
+
+    /// The comments and literals of `code`. Interpolations inside literals are code.
+    pub fn mask(&self, code: &str) -> CodeMask {
+        let mut spans = Vec::new();
+        let mut holes = Vec::new();
+        let mut i = 0;
+        while i < code.len() {
+            match self.skip(code, i, &mut holes) {
+                Some(end) => {
+                    let literal = !self.comment_at(&code[i..]);
+                    let mut from = i;
+                    for (start, close) in holes.drain(..) {
+                        spans.push((from, start, literal));
+                        from = close;
+                    }
+                    spans.push((from, end, literal));
+                    i = end;
+                }
+                None => i += 1,
+            }
+        }
+        CodeMask { spans }
+    }
+
+    /// Index of the `}` matching the `{` at `open`, skipping braces in comments and literals.
+    pub fn block_end(&self, code: &str, open: usize) -> Option<usize> {
+        let bytes = code.as_bytes();
+        let mut depth = 0usize;
+        let mut i = open;
+        while i < bytes.len() {
+            if let Some(end) = self.skip(code, i, &mut Vec::new()) {
+                i = end;
+                continue;
+            }
+            match bytes[i] {
+                b'{' => depth += 1,
+                b'}' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// The text between the `{` at `open` and its match, or to the end of `code`.
+    pub fn block_body<'a>(&self, code: &'a str, open: usize) -> &'a str {
+        let start = (open + 1).min(code.len());
+        match self.block_end(code, open) {
+            Some(close) => &code[start..close],
+            None => &code[start..],
+        }
+    }
+}
+
+/// `'{'`, `'\''` or `'\u{7B}'`; `None` for a lifetime or label such as `'a`.
+fn char_literal(code: &str, i: usize) -> Option<usize> {
+    let bytes = code.as_bytes();
+    if bytes.get(i + 1) == Some(&b'\\') {
+        return code.get(i + 3..)?.find('\'').map(|n| i + 3 + n + 1);
+    }
+    let c = code[i + 1..].chars().next()?;
+    let end = i + 1 + c.len_utf8();
+    (bytes.get(end) == Some(&b'\'')).then_some(end + 1)
+}
+
+/// Rust `r"..."` / `r#"..."#` (and `br"..."`); `None` when the `r` ends an identifier.
+fn raw_string(code: &str, i: usize) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    match i.checked_sub(1).map(|p| bytes[p]) {
+        Some(b'b') if i < 2 || !ident(bytes[i - 2]) => {}
+        Some(p) if ident(p) => return None,
+        _ => {}
+    }
+    let hashes = bytes[i + 1..].iter().take_while(|b| **b == b'#').count();
+    if bytes.get(i + 1 + hashes) != Some(&b'"') {
+        return None;
+    }
+    let body = i + 2 + hashes;
+    let close = format!("\"{}", "#".repeat(hashes));
+    Some(
+        code[body..]
+            .find(&close)
+            .map_or(code.len(), |e| body + e + close.len()),
+    )
+}
+
+/// Byte ranges of a file that are comments or literals, the latter flagged `true`.
+pub struct CodeMask {
+    spans: Vec<(usize, usize, bool)>,
+}
+
+impl CodeMask {
+    /// True when `offset` is outside every comment and literal, or opens a literal
+    /// (a pattern may start at the quote of the string it captures).
+    pub fn is_code(&self, offset: usize) -> bool {
+        let after = self.spans.partition_point(|(start, _, _)| *start <= offset);
+        let Some(&(start, end, literal)) = after.checked_sub(1).map(|i| &self.spans[i]) else {
+            return true;
+        };
+        end <= offset || (literal && start == offset)
+    }
+}
+
+/// Matches of `re` that start in code, so commented-out calls and text inside strings
+/// that happens to look like one are skipped.
+pub fn code_matches<'r, 'c>(
+    re: &'r Regex,
+    code: &'c str,
+    mask: &'r CodeMask,
+) -> impl Iterator<Item = Captures<'c>> + 'r
+where
+    'c: 'r,
+{
+    re.captures_iter(code)
+        .filter(|caps| mask.is_code(caps.get(0).unwrap().start()))
+}
+
+/// `(file, code, results)` per file, files with no results dropped, sorted by path so the file a
+/// shared node lands in does not depend on the order files were read in.
+pub fn scan_files<T>(
+    filez: &[(String, String)],
+    find: impl Fn(&str, &str) -> Vec<T>,
+) -> Vec<(&str, &str, Vec<T>)> {
+    let mut found: Vec<(&str, &str, Vec<T>)> = filez
+        .iter()
+        .map(|(file, code)| (file.as_str(), code.as_str(), find(file, code)))
+        .filter(|(_, _, items)| !items.is_empty())
+        .collect();
+    found.sort_by(|a, b| a.0.cmp(b.0));
+    found
+}
+
+pub fn functions_by_file<G: Graph>(graph: &G) -> HashMap<String, Vec<NodeData>> {
+    let mut functions: HashMap<String, Vec<NodeData>> = HashMap::new();
+    for func in graph.find_nodes_by_type(NodeType::Function) {
+        functions.entry(func.file.clone()).or_default().push(func);
+    }
+    functions
+}
+
+/// The innermost function whose lines contain `line`.
+pub fn enclosing_function<'a>(
+    funcs: impl IntoIterator<Item = &'a NodeData>,
+    line: usize,
+) -> Option<&'a NodeData> {
+    funcs
+        .into_iter()
+        .filter(|f| f.start <= line && line <= f.end)
+        .max_by_key(|f| f.start)
+}
+
+/// A function called `name`, preferring the first one defined at or after `line`.
+pub fn next_named_function<'a, I>(funcs: I, name: &str, line: usize) -> Option<&'a NodeData>
+where
+    I: IntoIterator<Item = &'a NodeData>,
+    I::IntoIter: Clone,
+{
+    let named = funcs.into_iter().filter(|f| f.name == name);
+    named
+        .clone()
+        .filter(|f| f.start >= line)
+        .min_by_key(|f| f.start)
+        .or_else(|| named.clone().next())
+}
+
+pub fn first_line(s: &str) -> String {
+    s.lines().next().unwrap_or_default().trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_end_skips_literals_and_comments() {
+        let rust = Syntax::of(&Language::Rust);
+        let code = "mod t {\n    const O: &str = \"{\";\n    fn f<'a>(s: &'a str) -> char { '}' }\n    // }\n    #[cfg(x)] fn g() { let e = '\\''; let r = r#\"}\"#; }\n}\nrest }";
+        assert_eq!(rust.block_end(code, 6), code.find("}\nrest"));
+
+        let hcl = "resource \"x\" \"y\" {\n  # }\n  name = \"}\"\n}";
+        let open = hcl.find('{').unwrap();
+        assert_eq!(Syntax::HCL.block_end(hcl, open), Some(hcl.len() - 1));
+
+        // `#` is not a comment in C#, Go or TypeScript
+        let cs = "namespace A {\n#region x\n class B {}\n#endregion\n}";
+        assert_eq!(
+            Syntax::of(&Language::CSharp).block_end(cs, cs.find('{').unwrap()),
+            Some(cs.len() - 1)
+        );
+        let ts = "function f() { const s = `}`; const t = '}'; }";
+        assert_eq!(
+            Syntax::of(&Language::Typescript).block_body(ts, ts.find('{').unwrap()),
+            " const s = `}`; const t = '}'; "
+        );
+    }
+
+    #[test]
+    fn test_mask_marks_comments_and_strings() {
+        let code = "x = 1 # send.delay(\ny = \"\"\"\nsend.delay(\n\"\"\"\nsend.delay(1)\n";
+        let mask = Syntax::of(&Language::Python).mask(code);
+        let re = Regex::new(r"send\.delay\(").unwrap();
+        let lines: Vec<usize> = code_matches(&re, code, &mask)
+            .map(|c| crate::utils::line_of(code, c.get(0).unwrap().start()))
+            .collect();
+        assert_eq!(lines, vec![4]);
+
+        // a match may open on a quote, but not on one inside a comment
+        let code = "h = {'type': 'text/event-stream'} # 'text/event-stream'\n";
+        let mask = Syntax::of(&Language::Python).mask(code);
+        let re = Regex::new(r#"['"]text/event-stream"#).unwrap();
+        assert_eq!(code_matches(&re, code, &mask).count(), 1);
+
+        // interpolations are code, the text around them is not
+        let code = "const u = `${process.env.DB_HOST}/process.env.X ${`${process.env.PORT}`}`; // process.env.OLD\n";
+        let mask = Syntax::of(&Language::Typescript).mask(code);
+        let re = Regex::new(r"process\.env\.(\w+)").unwrap();
+        let keys: Vec<&str> = code_matches(&re, code, &mask)
+            .map(|c| c.get(1).unwrap().as_str())
+            .collect();
+        assert_eq!(keys, vec!["DB_HOST", "PORT"]);
+    }
+}
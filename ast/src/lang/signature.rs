@@ -0,0 +1,757 @@
+use serde::{Deserialize, Serialize};
+use tree_sitter::Node as TreeNode;
+
+/// Language-independent shape of a function's interface, stored as JSON in the
+/// `signature` meta of Function nodes and read back by the call-resolution registries.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Signature {
+    #[serde(default)]
+    pub params: Vec<Param>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generics: Vec<GenericParam>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub is_async: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub is_static: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Param {
+    pub name: String,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GenericParam {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bounds: Vec<String>,
+}
+
+pub(crate) fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+const VISIBILITIES: &[&str] = &[
+    "public",
+    "private",
+    "protected",
+    "internal",
+    "fileprivate",
+    "open",
+    "package",
+];
+
+const PRIMITIVES: &[&str] = &[
+    "void",
+    "None",
+    "null",
+    "undefined",
+    "any",
+    "unknown",
+    "never",
+    "object",
+    "string",
+    "number",
+    "boolean",
+    "bool",
+    "str",
+    "int",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "usize",
+    "isize",
+    "f32",
+    "f64",
+    "int32",
+    "int64",
+    "uint",
+    "float",
+    "float32",
+    "float64",
+    "double",
+    "long",
+    "short",
+    "byte",
+    "char",
+    "error",
+    "Unit",
+    "Void",
+    "self",
+    "Self",
+];
+
+/// Wrappers whose first type argument is the value a caller actually works with.
+const WRAPPERS: &[&str] = &[
+    "Promise",
+    "Awaitable",
+    "Coroutine",
+    "Optional",
+    "Option",
+    "Task",
+    "ValueTask",
+    "Future",
+    "Result",
+    "Box",
+    "Rc",
+    "Arc",
+];
+
+impl Signature {
+    pub fn is_empty(&self) -> bool {
+        self == &Signature::default()
+    }
+    pub fn param(&self, name: &str) -> Option<&Param> {
+        self.params.iter().find(|p| p.name == name)
+    }
+    /// The named type a call's result can be dispatched on: wrappers such as
+    /// `Promise<T>`, `Optional[T]` or `(T, error)` are unwrapped, pointers and
+    /// nullability stripped, and primitives or the function's own type parameters
+    /// rejected. `Repo<User>` yields `Repo`, `models.User` yields `User`.
+    pub fn return_base_type(&self) -> Option<String> {
+        let mut t = self.return_type.as_deref()?.trim().to_string();
+        loop {
+            let before = t.clone();
+            if let Some(inner) = t.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+                t = split_top_level(inner, &[','])
+                    .into_iter()
+                    .find(|p| type_name_of(p) != "error")
+                    .unwrap_or_default();
+            }
+            if let Some(parts) = union_parts(&t) {
+                let real: Vec<String> = parts
+                    .into_iter()
+                    .filter(|p| !matches!(p.as_str(), "null" | "undefined" | "None" | "nil"))
+                    .collect();
+                if real.len() != 1 {
+                    return None;
+                }
+                t = real[0].clone();
+            }
+            t = t
+                .trim()
+                .trim_start_matches(['*', '&', '?'])
+                .trim_end_matches(['?', '!'])
+                .trim_start_matches("mut ")
+                .trim()
+                .to_string();
+            if let Some((outer, arg)) = first_type_argument(&t) {
+                if WRAPPERS.contains(&outer.as_str()) {
+                    t = arg;
+                }
+            }
+            if t == before {
+                break;
+            }
+        }
+        if t.ends_with("[]") || t.starts_with('[') {
+            return None;
+        }
+        let head = t.split(['<', '[']).next().unwrap_or_default().trim();
+        let base = head.rsplit(['.', ':', '\\']).next().unwrap_or_default();
+        if base.is_empty()
+            || !base.chars().all(|c| c.is_alphanumeric() || c == '_')
+            || PRIMITIVES.contains(&base)
+            || self.generics.iter().any(|g| g.name == base)
+        {
+            return None;
+        }
+        Some(base.to_string())
+    }
+}
+
+fn type_name_of(s: &str) -> String {
+    s.trim().trim_start_matches('*').trim().to_string()
+}
+
+fn union_parts(t: &str) -> Option<Vec<String>> {
+    let parts = split_top_level(t, &['|']);
+    if parts.len() > 1 {
+        return Some(parts);
+    }
+    // Python's Union[A, None]
+    let inner = t.strip_prefix("Union[")?.strip_suffix(']')?;
+    Some(split_top_level(inner, &[',']))
+}
+
+fn first_type_argument(t: &str) -> Option<(String, String)> {
+    let open = t.find(['<', '['])?;
+    let close = if t.ends_with('>') || t.ends_with(']') {
+        t.len() - 1
+    } else {
+        return None;
+    };
+    if close <= open {
+        return None;
+    }
+    let outer = t[..open].trim();
+    let outer = outer.rsplit(['.', ':']).next().unwrap_or(outer).to_string();
+    let arg = split_top_level(&t[open + 1..close], &[','])
+        .into_iter()
+        .next()?;
+    Some((outer, arg))
+}
+
+fn split_top_level(s: &str, seps: &[char]) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth: i32 = 0;
+    let mut current = String::new();
+    for ch in s.chars() {
+        match ch {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth -= 1,
+            c if depth == 0 && seps.contains(&c) => {
+                let part = current.trim().to_string();
+                if !part.is_empty() {
+                    parts.push(part);
+                }
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    let part = current.trim().to_string();
+    if !part.is_empty() {
+        parts.push(part);
+    }
+    parts
+}
+
+/// Grammar-agnostic extraction from a function definition node. Relies on the field
+/// names most tree-sitter grammars share (`parameters`, `return_type`/`result`/`returns`/
+/// `type`, `type_parameters`) and on modifier nodes for async, static and visibility.
+/// Stacks whose grammar diverges override `Stack::function_signature` instead.
+pub fn extract_signature(def: TreeNode, code: &str) -> Option<Signature> {
+    let func = signature_node(def).unwrap_or(def);
+    let mut sig = Signature::default();
+
+    if let Some(params) = func.child_by_field_name("parameters") {
+        let mut cursor = params.walk();
+        for p in params.named_children(&mut cursor) {
+            sig.params.extend(params_from_node(p, code));
+        }
+    } else if let Some(p) = func.child_by_field_name("parameter") {
+        // single unparenthesized arrow-function parameter
+        sig.params.extend(params_from_node(p, code));
+    }
+
+    sig.return_type = ["return_type", "result", "returns"]
+        .iter()
+        .find_map(|f| func.child_by_field_name(f))
+        .or_else(|| func.child_by_field_name("type"))
+        // C-style definitions keep the return type beside the declarator
+        .or_else(|| {
+            (def.kind() == "function_definition")
+                .then(|| def.child_by_field_name("type"))
+                .flatten()
+        })
+        .and_then(|n| clean_type(text(n, code)));
+
+    if let Some(tp) = type_parameters(func).or_else(|| type_parameters(def)) {
+        sig.generics = generics_from_node(tp, code);
+    }
+    apply_where_clauses(&mut sig.generics, func, code);
+
+    apply_modifiers(&mut sig, func, code);
+    if func.id() != def.id() {
+        apply_modifiers(&mut sig, def, code);
+    }
+    if matches!(func.kind(), "singleton_method") {
+        sig.is_static = true;
+    }
+
+    Some(sig)
+}
+
+/// The node carrying the parameter list: the definition itself, or (for arrow
+/// functions bound to variables and C-style declarators) the nearest descendant
+/// that has one, without descending into bodies.
+fn signature_node(def: TreeNode) -> Option<TreeNode> {
+    let mut queue = vec![(def, 0)];
+    let mut i = 0;
+    while i < queue.len() {
+        let (node, depth) = queue[i];
+        i += 1;
+        if node.child_by_field_name("parameters").is_some()
+            || node.child_by_field_name("parameter").is_some()
+        {
+            return Some(node);
+        }
+        if depth >= 3 {
+            continue;
+        }
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            let kind = child.kind();
+            if kind.contains("body") || kind.contains("block") || kind.contains("comment") {
+                continue;
+            }
+            queue.push((child, depth + 1));
+        }
+    }
+    None
+}
+
+fn type_parameters(node: TreeNode) -> Option<TreeNode> {
+    node.child_by_field_name("type_parameters").or_else(|| {
+        let mut cursor = node.walk();
+        let found = node
+            .named_children(&mut cursor)
+            .find(|c| matches!(c.kind(), "type_parameters" | "type_parameter_list"));
+        found
+    })
+}
+
+pub(crate) fn text(node: TreeNode, code: &str) -> String {
+    node.utf8_text(code.as_bytes())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
+pub(crate) fn clean_type(raw: String) -> Option<String> {
+    let t = raw
+        .trim()
+        .trim_start_matches("->")
+        .trim_start_matches(':')
+        .trim();
+    let t = t.split_whitespace().collect::<Vec<_>>().join(" ");
+    if t.is_empty() {
+        None
+    } else {
+        Some(t)
+    }
+}
+
+pub(crate) fn clean_default(raw: String) -> Option<String> {
+    let t = raw.trim().trim_start_matches('=').trim();
+    if t.is_empty() {
+        None
+    } else {
+        Some(t.to_string())
+    }
+}
+
+pub(crate) fn params_from_node(p: TreeNode, code: &str) -> Vec<Param> {
+    let kind = p.kind();
+    if kind.contains("comment") || matches!(kind, "keyword_separator" | "positional_separator") {
+        return Vec::new();
+    }
+    if matches!(
+        kind,
+        "identifier"
+            | "simple_identifier"
+            | "variable_name"
+            | "list_splat_pattern"
+            | "dictionary_splat_pattern"
+            | "splat_parameter"
+            | "hash_splat_parameter"
+            | "block_parameter"
+            | "rest_pattern"
+    ) {
+        return vec![Param {
+            name: text(p, code).trim_start_matches(['*', '&']).to_string(),
+            ..Default::default()
+        }];
+    }
+    if kind == "self_parameter" {
+        return vec![Param {
+            name: "self".to_string(),
+            type_name: Some(text(p, code)).filter(|t| t != "self"),
+            default: None,
+        }];
+    }
+
+    let type_name = p
+        .child_by_field_name("type")
+        .and_then(|n| clean_type(text(n, code)));
+    let default = ["value", "default_value", "right", "default"]
+        .iter()
+        .find_map(|f| p.child_by_field_name(f))
+        .or_else(|| {
+            let mut cursor = p.walk();
+            let found = p
+                .named_children(&mut cursor)
+                .find(|c| matches!(c.kind(), "equals_value_clause" | "default_value"));
+            found
+        })
+        // C# keeps the default as an unnamed child after `=`
+        .or_else(|| {
+            let mut cursor = p.walk();
+            let eq = p.children(&mut cursor).find(|c| c.kind() == "=")?;
+            eq.next_named_sibling()
+        })
+        .and_then(|n| clean_default(text(n, code)));
+
+    let mut cursor = p.walk();
+    let mut names: Vec<String> = p
+        .children_by_field_name("name", &mut cursor)
+        .map(|n| text(n, code))
+        .collect();
+    if names.is_empty() {
+        if let Some(n) = ["pattern", "left", "declarator"]
+            .iter()
+            .find_map(|f| p.child_by_field_name(f))
+            .or_else(|| {
+                let mut cursor = p.walk();
+                let found = p.named_children(&mut cursor).find(|c| {
+                    matches!(
+                        c.kind(),
+                        "identifier"
+                            | "simple_identifier"
+                            | "variable_name"
+                            | "list_splat_pattern"
+                            | "dictionary_splat_pattern"
+                    )
+                });
+                found
+            })
+        {
+            names.push(text(n, code));
+        }
+    }
+    let names: Vec<String> = names
+        .into_iter()
+        .map(|n| n.trim_start_matches(['*', '&']).trim().to_string())
+        .collect();
+
+    if names.is_empty() {
+        // unnamed parameter (e.g. Go `func(int)`); keep it only when typed
+        return match type_name {
+            Some(t) => vec![Param {
+                name: String::new(),
+                type_name: Some(t),
+                default,
+            }],
+            None => Vec::new(),
+        };
+    }
+    names
+        .into_iter()
+        .map(|name| Param {
+            name,
+            type_name: type_name.clone(),
+            default: default.clone(),
+        })
+        .collect()
+}
+
+pub(crate) fn generics_from_node(tp: TreeNode, code: &str) -> Vec<GenericParam> {
+    let mut out = Vec::new();
+    let mut cursor = tp.walk();
+    for g in tp.named_children(&mut cursor) {
+        let kind = g.kind();
+        if kind.contains("comment") {
+            continue;
+        }
+        if matches!(kind, "type_identifier" | "identifier" | "lifetime") {
+            out.push(GenericParam {
+                name: text(g, code),
+                bounds: Vec::new(),
+            });
+            continue;
+        }
+        let bounds_node = ["bounds", "bound", "constraint", "type"]
+            .iter()
+            .find_map(|f| g.child_by_field_name(f))
+            .or_else(|| {
+                let mut c = g.walk();
+                let found = g
+                    .named_children(&mut c)
+                    .find(|n| n.kind().contains("bound") || n.kind().contains("constraint"));
+                found
+            });
+        let bounds = bounds_node
+            .map(|n| {
+                let raw = text(n, code);
+                let raw = raw
+                    .trim_start_matches(':')
+                    .trim()
+                    .trim_start_matches("extends")
+                    .trim_start_matches("implements")
+                    .trim()
+                    .to_string();
+                split_top_level(&raw, &['+', '&'])
+            })
+            .unwrap_or_default();
+
+        let mut c = g.walk();
+        let mut names: Vec<String> = g
+            .children_by_field_name("name", &mut c)
+            .map(|n| text(n, code))
+            .collect();
+        if names.is_empty() {
+            if let Some(n) = g.child_by_field_name("left").or_else(|| {
+                let mut c = g.walk();
+                let found = g
+                    .named_children(&mut c)
+                    .find(|n| matches!(n.kind(), "type_identifier" | "identifier" | "lifetime"));
+                found
+            }) {
+                names.push(text(n, code));
+            }
+        }
+        for name in names {
+            out.push(GenericParam {
+                name,
+                bounds: bounds.clone(),
+            });
+        }
+    }
+    out
+}
+
+/// Bounds declared apart from the type parameter list, as in Rust `where T: Into<Row>`
+/// and C# `where T : class, IEntity`, merged into the matching generic.
+fn apply_where_clauses(generics: &mut [GenericParam], node: TreeNode, code: &str) {
+    let mut cursor = node.walk();
+    for clause in node.named_children(&mut cursor) {
+        let mut c = clause.walk();
+        let constraints: Vec<(String, Vec<String>)> = match clause.kind() {
+            "where_clause" => clause
+                .named_children(&mut c)
+                .filter_map(|pred| {
+                    let left = pred.child_by_field_name("left")?;
+                    let bounds = text(pred.child_by_field_name("bounds")?, code);
+                    let bounds = split_top_level(bounds.trim_start_matches(':'), &['+']);
+                    Some((text(left, code), bounds))
+                })
+                .collect(),
+            "type_parameter_constraints_clause" => {
+                let mut parts = clause.named_children(&mut c);
+                let Some(target) = parts.next() else {
+                    continue;
+                };
+                vec![(text(target, code), parts.map(|p| text(p, code)).collect())]
+            }
+            _ => continue,
+        };
+        for (name, bounds) in constraints {
+            if let Some(g) = generics.iter_mut().find(|g| g.name == name) {
+                g.bounds.extend(bounds);
+            }
+        }
+    }
+}
+
+pub(crate) fn apply_modifiers(sig: &mut Signature, node: TreeNode, code: &str) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "async" => sig.is_async = true,
+            "static" => sig.is_static = true,
+            "visibility_modifier" | "accessibility_modifier" => {
+                sig.visibility.get_or_insert_with(|| text(child, code));
+            }
+            "static_modifier" => sig.is_static = true,
+            "modifiers"
+            | "modifier"
+            | "function_modifiers"
+            | "storage_class_specifier"
+            | "member_modifier"
+            | "visibility_modifier_list" => {
+                let words = text(child, code);
+                for word in words.split(|c: char| !c.is_alphanumeric() && c != '_') {
+                    match word {
+                        "async" | "suspend" => sig.is_async = true,
+                        "static" => sig.is_static = true,
+                        w if VISIBILITIES.contains(&w) => {
+                            sig.visibility.get_or_insert_with(|| w.to_string());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_sitter::Parser;
+
+    fn first_def<'a>(tree: &'a tree_sitter::Tree, kinds: &[&str]) -> TreeNode<'a> {
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if kinds.contains(&node.kind()) {
+                return node;
+            }
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.named_children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+        panic!("no {:?} in tree", kinds);
+    }
+
+    fn parse(lang: tree_sitter::Language, code: &str) -> tree_sitter::Tree {
+        let mut parser = Parser::new();
+        parser.set_language(&lang).unwrap();
+        parser.parse(code, None).unwrap()
+    }
+
+    #[test]
+    fn test_typescript_signature() {
+        let code = "export async function load<T extends Model>(id: string, opts: Options = {}): Promise<UserRepo> { return repo; }";
+        let tree = parse(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(), code);
+        let sig = extract_signature(first_def(&tree, &["function_declaration"]), code).unwrap();
+        assert!(sig.is_async);
+        assert_eq!(sig.params.len(), 2);
+        assert_eq!(sig.params[0].name, "id");
+        assert_eq!(sig.params[0].type_name.as_deref(), Some("string"));
+        assert_eq!(sig.params[1].default.as_deref(), Some("{}"));
+        assert_eq!(sig.return_type.as_deref(), Some("Promise<UserRepo>"));
+        assert_eq!(sig.generics[0].name, "T");
+        assert_eq!(sig.return_base_type().as_deref(), Some("UserRepo"));
+
+        let code = "class S { private async get<K extends keyof T>(key: K, fallback?: T[K]): Promise<Item> { return x; } }\nexport const mk = <T,>(a: T, b = 2): Box<T> => a;";
+        let tree = parse(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(), code);
+        let sig = extract_signature(first_def(&tree, &["method_definition"]), code).unwrap();
+        assert_eq!(sig.visibility.as_deref(), Some("private"));
+        assert_eq!(sig.params[1].type_name.as_deref(), Some("T[K]"));
+        assert_eq!(sig.generics[0].bounds, vec!["keyof T"]);
+        assert_eq!(sig.return_base_type().as_deref(), Some("Item"));
+        let sig = extract_signature(first_def(&tree, &["lexical_declaration"]), code).unwrap();
+        let names: Vec<_> = sig.params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(sig.params[1].default.as_deref(), Some("2"));
+        assert_eq!(sig.return_type.as_deref(), Some("Box<T>"));
+        assert_eq!(sig.generics[0].name, "T");
+    }
+
+    #[test]
+    fn test_python_signature() {
+        let code = "def find(self, user_id: int, limit=10) -> Optional[User]:\n    pass\n";
+        let tree = parse(tree_sitter_python::LANGUAGE.into(), code);
+        let sig = extract_signature(first_def(&tree, &["function_definition"]), code).unwrap();
+        let names: Vec<_> = sig.params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["self", "user_id", "limit"]);
+        assert_eq!(sig.params[1].type_name.as_deref(), Some("int"));
+        assert_eq!(sig.params[2].default.as_deref(), Some("10"));
+        assert_eq!(sig.return_base_type().as_deref(), Some("User"));
+    }
+
+    #[test]
+    fn test_go_signature() {
+        let code = "package main\nfunc NewStore(a, b string) (*Store, error) { return nil, nil }\n";
+        let tree = parse(tree_sitter_go::LANGUAGE.into(), code);
+        let sig = extract_signature(first_def(&tree, &["function_declaration"]), code).unwrap();
+        let names: Vec<_> = sig.params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(sig.params[1].type_name.as_deref(), Some("string"));
+        assert_eq!(sig.return_base_type().as_deref(), Some("Store"));
+    }
+
+    #[test]
+    fn test_rust_signature() {
+        let code = "pub async fn get<T: Clone + Send>(&self, key: &str) -> Result<Cache, Error> { todo!() }";
+        let tree = parse(tree_sitter_rust::LANGUAGE.into(), code);
+        let sig = extract_signature(first_def(&tree, &["function_item"]), code).unwrap();
+        assert!(sig.is_async);
+        assert_eq!(sig.visibility.as_deref(), Some("pub"));
+        assert_eq!(sig.params[0].name, "self");
+        assert_eq!(sig.params[1].name, "key");
+        assert_eq!(sig.generics[0].name, "T");
+        assert_eq!(sig.generics[0].bounds, vec!["Clone", "Send"]);
+        assert_eq!(sig.return_base_type().as_deref(), Some("Cache"));
+
+        let code =
+            "fn build<'a, T>(items: &'a [T]) -> Option<Report> where T: Into<Row> + Send { None }";
+        let tree = parse(tree_sitter_rust::LANGUAGE.into(), code);
+        let sig = extract_signature(first_def(&tree, &["function_item"]), code).unwrap();
+        assert_eq!(sig.params[0].type_name.as_deref(), Some("&'a [T]"));
+        let generics: Vec<_> = sig.generics.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(generics, vec!["'a", "T"]);
+        assert_eq!(sig.generics[1].bounds, vec!["Into<Row>", "Send"]);
+        assert_eq!(sig.return_base_type().as_deref(), Some("Report"));
+    }
+
+    #[test]
+    fn test_java_signature() {
+        let code = "class A { public static List<Order> recent(int days) { return null; } }";
+        let tree = parse(tree_sitter_java::LANGUAGE.into(), code);
+        let sig = extract_signature(first_def(&tree, &["method_declaration"]), code).unwrap();
+        assert!(sig.is_static);
+        assert_eq!(sig.visibility.as_deref(), Some("public"));
+        assert_eq!(sig.params[0].name, "days");
+        assert_eq!(sig.return_type.as_deref(), Some("List<Order>"));
+
+        let code = "class A { <T extends Comparable<T>> List<T> sort(List<T> items, int n) { return null; } }";
+        let tree = parse(tree_sitter_java::LANGUAGE.into(), code);
+        let sig = extract_signature(first_def(&tree, &["method_declaration"]), code).unwrap();
+        assert_eq!(sig.params[0].type_name.as_deref(), Some("List<T>"));
+        assert_eq!(sig.generics[0].name, "T");
+        assert_eq!(sig.generics[0].bounds, vec!["Comparable<T>"]);
+        assert_eq!(sig.return_base_type().as_deref(), Some("List"));
+    }
+
+    #[test]
+    fn test_ruby_signature() {
+        let code =
+            "class A\n  def self.find(id, opts = {}, *rest, key: 1, **kw, &blk)\n  end\nend\n";
+        let tree = parse(tree_sitter_ruby::LANGUAGE.into(), code);
+        let sig = extract_signature(first_def(&tree, &["singleton_method"]), code).unwrap();
+        assert!(sig.is_static);
+        let names: Vec<_> = sig.params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "opts", "rest", "key", "kw", "blk"]);
+        assert_eq!(sig.params[1].default.as_deref(), Some("{}"));
+        assert_eq!(sig.params[3].default.as_deref(), Some("1"));
+        assert_eq!(sig.return_type, None);
+    }
+
+    #[test]
+    fn test_csharp_signature() {
+        let code = "class A { public static async Task<User> FindAsync<T>(int id, string name = \"x\") where T : class, IEntity { return null; } }";
+        let tree = parse(tree_sitter_c_sharp::LANGUAGE.into(), code);
+        let sig = extract_signature(first_def(&tree, &["method_declaration"]), code).unwrap();
+        assert!(sig.is_async && sig.is_static);
+        assert_eq!(sig.visibility.as_deref(), Some("public"));
+        assert_eq!(sig.params[0].type_name.as_deref(), Some("int"));
+        assert_eq!(sig.params[1].default.as_deref(), Some("\"x\""));
+        assert_eq!(sig.generics[0].name, "T");
+        assert_eq!(sig.generics[0].bounds, vec!["class", "IEntity"]);
+        assert_eq!(sig.return_base_type().as_deref(), Some("User"));
+    }
+
+    #[test]
+    fn test_return_base_type_rejects_primitives_and_generics() {
+        let sig = |ret: &str| Signature {
+            return_type: Some(ret.to_string()),
+            generics: vec![GenericParam {
+                name: "T".to_string(),
+                bounds: Vec::new(),
+            }],
+            ..Default::default()
+        };
+        assert_eq!(sig("string").return_base_type(), None);
+        assert_eq!(sig("Promise<T>").return_base_type(), None);
+        assert_eq!(sig("User[]").return_base_type(), None);
+        assert_eq!(
+            sig("User | null").return_base_type().as_deref(),
+            Some("User")
+        );
+        assert_eq!(
+            sig("models.User").return_base_type().as_deref(),
+            Some("User")
+        );
+        assert_eq!(
+            sig("Repo<User>").return_base_type().as_deref(),
+            Some("Repo")
+        );
+    }
+}
@@ -1,6 +1,7 @@
 mod builder;
 #[cfg(feature = "openssl")]
 pub mod gat;
+pub mod infra;
 pub mod lang;
 pub mod repo;
 pub mod utils;
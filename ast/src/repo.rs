@@ -2,6 +2,7 @@ use crate::builder::memory;
 pub use crate::builder::progress::StatusUpdate;
 #[cfg(feature = "neo4j")]
 use crate::builder::streaming::{nodes_to_bolt_format, GraphStreamingUploader};
+use crate::infra;
 #[cfg(feature = "neo4j")]
 use crate::lang::graphs::Neo4jGraph;
 use crate::lang::graphs::{Edge, Graph, NodeType};
@@ -162,6 +163,29 @@ impl Repos {
             }
         }
 
+        // walk each tree once: package repos nested in a monorepo root are covered by it.
+        // builds restricted to a files_filter skip the walk
+        let mut infra_roots: Vec<&Path> = self
+            .0
+            .iter()
+            .filter(|r| r.files_filter.is_empty())
+            .map(|r| r.root.as_path())
+            .collect();
+        infra_roots.sort();
+        infra_roots.dedup();
+        let infra_roots: Vec<&Path> = infra_roots
+            .iter()
+            .filter(|root| {
+                !infra_roots
+                    .iter()
+                    .any(|other| other != *root && root.starts_with(other))
+            })
+            .copied()
+            .collect();
+        for root in infra_roots {
+            infra::add_infra_nodes(&mut graph, root)?;
+        }
+
         if let Some(first_repo) = &self.0.first() {
             first_repo.send_status_update("linking_graphs", 14);
         }
@@ -170,6 +194,12 @@ impl Repos {
         linker::link_e2e_tests(&mut graph)?;
         info!("linking api nodes");
         linker::link_api_nodes(&mut graph)?;
+        info!("linking queue nodes");
+        linker::link_queue_nodes(&mut graph)?;
+        info!("linking channel nodes");
+        linker::link_channel_nodes(&mut graph)?;
+        info!("linking config keys");
+        linker::link_config_keys(&mut graph)?;
         info!(
             "[perf][stage] cross_repo_linking s={:.2}",
             stage_start.elapsed().as_secs_f64()
@@ -189,7 +219,7 @@ impl Repos {
             }
             let edges = graph.get_edge_keys();
             if let Err(e) = uploader
-                .flush_edges_stage(neo, "cross_repo_linking", &edges)
+                .flush_edges_stage(neo, "cross_repo_linking", &edges, &graph)
                 .await
             {
                 warn!("Neo4j edge flush failed at stage 'cross_repo_linking': {}", e);
@@ -47,6 +47,17 @@ fn parse_node_type(s: &str) -> Option<NodeType> {
         "IntegrationTest" => Some(NodeType::IntegrationTest),
         "E2eTest" => Some(NodeType::E2eTest),
         "Mock" => Some(NodeType::Mock),
+        "Resource" => Some(NodeType::Resource),
+        "Service" => Some(NodeType::Service),
+        "Workflow" => Some(NodeType::Workflow),
+        "WorkflowJob" => Some(NodeType::WorkflowJob),
+        "WorkflowStep" => Some(NodeType::WorkflowStep),
+        "Queue" => Some(NodeType::Queue),
+        "Job" => Some(NodeType::Job),
+        "Command" => Some(NodeType::Command),
+        "Channel" => Some(NodeType::Channel),
+        "ConfigKey" => Some(NodeType::ConfigKey),
+        "Module" => Some(NodeType::Module),
         _ => None,
     }
 }
@@ -66,6 +77,17 @@ fn parse_edge_type(s: &str) -> Option<EdgeType> {
         "ParentOf" => Some(EdgeType::ParentOf),
         "Implements" => Some(EdgeType::Implements),
         "NestedIn" => Some(EdgeType::NestedIn),
+        "DependsOn" => Some(EdgeType::DependsOn),
+        "ConnectsTo" => Some(EdgeType::ConnectsTo),
+        "Runs" => Some(EdgeType::Runs),
+        "Publishes" => Some(EdgeType::Publishes),
+        "Consumes" => Some(EdgeType::Consumes),
+        "Emits" => Some(EdgeType::Emits),
+        "Reads" => Some(EdgeType::Reads),
+        "Writes" => Some(EdgeType::Writes),
+        "Overrides" => Some(EdgeType::Overrides),
+        "Throws" => Some(EdgeType::Throws),
+        "Catches" => Some(EdgeType::Catches),
         _ => None,
     }
 }
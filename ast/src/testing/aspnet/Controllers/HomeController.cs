@@ -0,0 +1,29 @@
+// @ast node: Class "HomeController"
+// @ast node: Endpoint "/app/Home/Index" [verb=ANY]
+// @ast edge: Handler -> Function "Index" "Controllers/HomeController.cs"
+// @ast node: Endpoint "/app/Home/About" [verb=ANY]
+// @ast edge: Handler -> Function "About" "Controllers/HomeController.cs"
+// @ast node: Function "Index"
+// @ast node: Function "About"
+// @ast node: Function "Footer"
+using Microsoft.AspNetCore.Mvc;
+
+namespace AspNetMinimal.Controllers;
+
+public class HomeController : Controller
+{
+    public IActionResult Index()
+    {
+        return View();
+    }
+
+    public IActionResult About()
+    {
+        return View();
+    }
+
+    private string Footer()
+    {
+        return "footer";
+    }
+}
@@ -0,0 +1,17 @@
+// @ast node: Class "UserHandlers"
+// @ast node: Function "ListUsers"
+// @ast node: Function "GetUser"
+namespace AspNetMinimal.Handlers;
+
+public static class UserHandlers
+{
+    public static IResult ListUsers()
+    {
+        return Results.Ok(new[] { "ada", "grace" });
+    }
+
+    public static IResult GetUser(int id)
+    {
+        return Results.Ok(id);
+    }
+}
@@ -0,0 +1,34 @@
+// @ast node: Class "CreateUser"
+// @ast node: Endpoint "/api/users" [verb=GET]
+// @ast edge: Handler -> Function "ListUsers" "Handlers/UserHandlers.cs"
+// @ast node: Endpoint "/api/users/{id}" [verb=GET]
+// @ast edge: Handler -> Function "GetUser" "Handlers/UserHandlers.cs"
+// @ast node: Endpoint "/api/users" [verb=POST]
+// @ast edge: Handler -> Function "MAPPOST___closure_L23" "Program.cs"
+// @ast node: Endpoint "/status" [verb=GET]
+// @ast edge: Handler -> Function "MAPGET__status_closure_L25" "Program.cs"
+// @ast node: Function "MAPPOST___closure_L23"
+// @ast node: Function "MAPGET__status_closure_L25"
+using AspNetMinimal.Handlers;
+
+var builder = WebApplication.CreateBuilder(args);
+builder.Services.AddControllersWithViews();
+
+var app = builder.Build();
+
+var api = app.MapGroup("/api");
+var users = api.MapGroup("/users");
+
+users.MapGet("/", UserHandlers.ListUsers);
+users.MapGet("/{id}", UserHandlers.GetUser);
+users.MapPost("/", (CreateUser body) => Results.Created($"/api/users/{body.Name}", body));
+
+app.MapGet("/status", () => "ok");
+
+app.MapControllerRoute(
+    name: "default",
+    pattern: "app/{controller=Home}/{action=Index}/{id?}");
+
+app.Run();
+
+public record CreateUser(string Name);
@@ -1,7 +1,8 @@
 use crate::builder::utils::process_files;
-use crate::lang::graphs::{BTreeMapGraph, NodeType};
-use crate::lang::{Graph, Lang};
+use crate::lang::graphs::{ArrayGraph, BTreeMapGraph, CallResolution, Edge, NodeType};
+use crate::lang::{Graph, Lang, NodeData};
 use crate::repo::Repo;
+use lsp::Language;
 use std::str::FromStr;
 
 #[test]
@@ -57,3 +58,29 @@ async fn bad_files_do_not_abort_build() {
 
     std::fs::remove_dir_all(&dir).ok();
 }
+
+fn best_resolution_kept<G: Graph>(first: f32, second: f32) -> Option<CallResolution> {
+    let mut graph = G::new(String::new(), Language::Rust);
+    let caller = NodeData::name_file_start("run", "src/main.rs", 1);
+    let callee = NodeData::name_file_start("save", "src/db.rs", 5);
+    graph.add_node(&NodeType::Function, &caller);
+    graph.add_node(&NodeType::Function, &callee);
+    for (confidence, strategy) in [(first, "first"), (second, "second")] {
+        let mut edge = Edge::calls(NodeType::Function, &caller, NodeType::Function, &callee);
+        edge.resolution = Some(CallResolution::new(confidence, strategy));
+        graph.add_edge(&edge);
+    }
+    let edges = graph.get_edges_vec();
+    assert_eq!(edges.len(), 1);
+    edges[0].resolution.clone()
+}
+
+#[test]
+fn duplicate_calls_edge_keeps_most_confident_resolution() {
+    for (first, second, strategy) in [(0.0, 1.0, "second"), (1.0, 0.0, "first")] {
+        let best = best_resolution_kept::<ArrayGraph>(first, second).unwrap();
+        assert_eq!((best.confidence, best.strategy.as_str()), (1.0, strategy));
+        let best = best_resolution_kept::<BTreeMapGraph>(first, second).unwrap();
+        assert_eq!((best.confidence, best.strategy.as_str()), (1.0, strategy));
+    }
+}
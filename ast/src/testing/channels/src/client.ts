@@ -0,0 +1,28 @@
+import { io } from "socket.io-client";
+
+const socket = io("http://localhost:3001");
+
+// @ast node: Function "sendMessage"
+// @ast edge: Emits -> Channel "chat:message" "src/client.ts"
+export function sendMessage(text: string) {
+  socket.emit("chat:message", { text });
+}
+
+// @ast node: Function "renderMessage"
+// @ast edge: Handler <- Channel "chat:broadcast" "src/client.ts"
+export function renderMessage(msg: { text: string }) {
+  console.log(msg.text);
+}
+
+// @ast node: Function "watchEvents"
+// @ast edge: Emits -> Channel "/events" "src/client.ts"
+export function watchEvents() {
+  const events = new EventSource("/events");
+  return events;
+}
+
+socket.on("chat:broadcast", renderMessage);
+
+// @ast node: Channel "chat:message" [protocol=socketio]
+// @ast node: Channel "chat:broadcast" [protocol=socketio]
+// @ast node: Channel "/events" [protocol=sse]
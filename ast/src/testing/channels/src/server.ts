@@ -0,0 +1,27 @@
+import express from "express";
+import { Server, Socket } from "socket.io";
+
+const app = express();
+const io = new Server(3001);
+
+// @ast node: Function "onChatMessage"
+// @ast edge: Handler <- Channel "chat:message" "src/client.ts"
+// @ast edge: Emits -> Channel "chat:broadcast" "src/client.ts"
+export function onChatMessage(msg: { text: string }) {
+  io.emit("chat:broadcast", msg);
+}
+
+// @ast node: Function "registerHandlers"
+export function registerHandlers(socket: Socket) {
+  socket.on("chat:message", onChatMessage);
+}
+
+// @ast node: Function "streamEvents"
+// @ast edge: Handler <- Channel "/events" "src/client.ts"
+export function streamEvents(req: express.Request, res: express.Response) {
+  res.setHeader("Content-Type", "text/event-stream");
+  res.write("data: hello\n\n");
+}
+
+io.on("connection", registerHandlers);
+app.get("/events", streamEvents);
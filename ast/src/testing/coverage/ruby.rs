@@ -109,7 +109,7 @@ async fn test_btreemap_test_to_class_edges() -> Result<()> {
     assert_eq!(calls_edges, 98);
 
     let contains_edges = graph.count_edges_of_type(EdgeType::Contains);
-    assert_eq!(contains_edges, 333);
+    assert_eq!(contains_edges, 334);
 
     let handler_edges = graph.count_edges_of_type(EdgeType::Handler);
     assert_eq!(handler_edges, 23);
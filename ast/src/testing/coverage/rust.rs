@@ -78,8 +78,8 @@ async fn test_rust_coverage() -> Result<()> {
     let functions = graph.find_nodes_by_type(NodeType::Function);
     assert_eq!(
         functions.len(),
-        102,
-        "Expected 102 functions after test improvements"
+        103,
+        "Expected 103 functions after test improvements"
     );
 
     let unit_tests = graph.find_nodes_by_type(NodeType::UnitTest);
@@ -11,11 +11,11 @@
 // @ast edge: Handler -> Function "MAPGET__api_v2_status_closure_L87" "Program.cs"
 // @ast node: Endpoint "/api/v2/webhook"
 // @ast edge: Handler -> Function "MAPPOST__api_v2_webhook_closure_L88" "Program.cs"
-// @ast node: Endpoint "/echo"
+// @ast node: Endpoint "/api/v2/quick/echo"
 // @ast edge: Handler -> Function "MAPPOST__echo_closure_L92" "Program.cs"
 // @ast node: Endpoint "/health"
 // @ast edge: Handler -> Function "MAPGET__health_closure_L84" "Program.cs"
-// @ast node: Endpoint "/ping"
+// @ast node: Endpoint "/api/v2/quick/ping"
 // @ast edge: Handler -> Function "MAPGET__ping_closure_L91" "Program.cs"
 // @ast node: Endpoint "/ready"
 // @ast edge: Handler -> Function "MAPGET__ready_closure_L85" "Program.cs"
@@ -0,0 +1,7 @@
+# @ast node: Var "api_router"
+from fastapi import APIRouter
+from app.routers import items, users
+
+api_router = APIRouter(prefix="/v1")
+api_router.include_router(users.router)
+api_router.include_router(items.router, prefix="/inventory")
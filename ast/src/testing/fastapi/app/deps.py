@@ -0,0 +1,12 @@
+# @ast node: Function "get_db"
+# @ast node: Function "get_current_user"
+# @ast edge: DependsOn -> Function "get_db" "app/deps.py"
+from fastapi import Depends
+
+
+def get_db():
+    return {"session": "db"}
+
+
+def get_current_user(db=Depends(get_db)):
+    return {"id": 1, "db": db}
@@ -0,0 +1,14 @@
+# @ast node: Var "app"
+# @ast node: Endpoint "/health" [verb=GET]
+# @ast edge: Handler -> Function "health" "app/main.py"
+# @ast node: Function "health"
+from fastapi import FastAPI
+from app.api import api_router
+
+app = FastAPI()
+app.include_router(api_router, prefix="/api")
+
+
+@app.get("/health")
+def health():
+    return {"status": "ok"}
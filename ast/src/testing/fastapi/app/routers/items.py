@@ -0,0 +1,12 @@
+# @ast node: Var "router"
+# @ast node: Endpoint "/api/v1/inventory/items" [verb=GET]
+# @ast edge: Handler -> Function "list_items" "app/routers/items.py"
+# @ast node: Function "list_items"
+from fastapi import APIRouter
+
+router = APIRouter()
+
+
+@router.get("/items")
+def list_items():
+    return []
@@ -0,0 +1,33 @@
+# @ast node: Var "router"
+# @ast node: Endpoint "/api/v1/users/" [verb=GET]
+# @ast edge: Handler -> Function "list_users" "app/routers/users.py"
+# @ast node: Endpoint "/api/v1/users/{user_id}" [verb=GET]
+# @ast edge: Handler -> Function "read_user" "app/routers/users.py"
+# @ast node: Endpoint "/api/v1/users/{user_id}" [verb=PATCH]
+# @ast edge: Handler -> Function "update_user" "app/routers/users.py"
+# @ast node: Function "list_users"
+# @ast edge: DependsOn -> Function "get_db" "app/deps.py"
+# @ast node: Function "read_user"
+# @ast edge: DependsOn -> Function "get_current_user" "app/deps.py"
+# @ast node: Function "update_user"
+# @ast edge: DependsOn -> Function "get_current_user" "app/deps.py"
+# @ast edge: DependsOn -> Function "get_db" "app/deps.py"
+from fastapi import APIRouter, Depends
+from app.deps import get_current_user, get_db
+
+router = APIRouter(prefix="/users", tags=["users"])
+
+
+@router.get("/")
+def list_users(db=Depends(get_db)):
+    return []
+
+
+@router.get("/{user_id}")
+def read_user(user_id: int, user=Depends(get_current_user)):
+    return user
+
+
+@router.patch("/{user_id}", dependencies=[Depends(get_current_user)])
+def update_user(user_id: int, db=Depends(get_db)):
+    return {"id": user_id}
@@ -31,6 +31,8 @@ package main
 // @ast node: Function "UpdatePersonName"
 // @ast node: Function "GetPersonById"
 // @ast node: Function "InitDB"
+// @ast edge: Reads -> ConfigKey "DATABASE_URL" "db.go"
+// @ast node: ConfigKey "DATABASE_URL"
 
 import (
 	"encoding/json"
@@ -8,11 +8,13 @@ package main
 // @ast edge: Handler -> Function "GetLeaderboard" "db.go"
 // @ast node: Function "NewRouter"
 // @ast edge: Calls -> Function "initChi" "routes.go"
+// @ast edge: Reads -> ConfigKey "PORT" "routes.go"
 // @ast node: Function "GetPerson"
 // @ast edge: Calls -> Function "GetPersonById" "db.go"
 // @ast node: Function "CreatePerson"
 // @ast edge: Calls -> Function "NewPerson" "db.go"
 // @ast node: Function "initChi"
+// @ast node: ConfigKey "PORT"
 
 import (
 	"encoding/json"
@@ -0,0 +1,47 @@
+package main
+
+// @ast node: Function "chiRouter"
+// @ast node: Function "reportsRouter"
+// @ast node: Function "getOrder"
+// @ast node: Function "cancelOrder"
+// @ast node: Function "dailyReport"
+// @ast node: Endpoint "/orders/{id}" [verb=GET]
+// @ast edge: Handler -> Function "getOrder" "chi.go"
+// @ast node: Endpoint "/orders/{id}/cancel" [verb=POST]
+// @ast edge: Handler -> Function "cancelOrder" "chi.go"
+// @ast node: Endpoint "/reports/daily" [verb=GET]
+// @ast edge: Handler -> Function "dailyReport" "chi.go"
+
+import (
+	"net/http"
+
+	"github.com/go-chi/chi/v5"
+)
+
+func chiRouter() chi.Router {
+	r := chi.NewRouter()
+	r.Route("/orders", func(r chi.Router) {
+		r.Get("/{id}", getOrder)
+		r.Post("/{id}/cancel", cancelOrder)
+	})
+	r.Mount("/reports", reportsRouter())
+	return r
+}
+
+func reportsRouter() chi.Router {
+	r := chi.NewRouter()
+	r.Get("/daily", dailyReport)
+	return r
+}
+
+func getOrder(w http.ResponseWriter, r *http.Request) {
+	w.Write([]byte(chi.URLParam(r, "id")))
+}
+
+func cancelOrder(w http.ResponseWriter, r *http.Request) {
+	w.WriteHeader(http.StatusAccepted)
+}
+
+func dailyReport(w http.ResponseWriter, r *http.Request) {
+	w.Write([]byte("daily"))
+}
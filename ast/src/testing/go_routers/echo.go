@@ -0,0 +1,23 @@
+package main
+
+// @ast node: Function "echoServer"
+// @ast node: Function "adminStats"
+// @ast node: Endpoint "/admin/stats" [verb=GET]
+// @ast edge: Handler -> Function "adminStats" "echo.go"
+
+import (
+	"net/http"
+
+	"github.com/labstack/echo/v4"
+)
+
+func echoServer() *echo.Echo {
+	e := echo.New()
+	admin := e.Group("/admin")
+	admin.GET("/stats", adminStats)
+	return e
+}
+
+func adminStats(c echo.Context) error {
+	return c.JSON(http.StatusOK, map[string]int{"users": 0})
+}
@@ -0,0 +1,25 @@
+package main
+
+// @ast node: Function "main"
+// @ast node: Function "health"
+// @ast node: Endpoint "/api/v1/health" [verb=GET]
+// @ast edge: Handler -> Function "health" "main.go"
+
+import (
+	"github.com/gin-gonic/gin"
+
+	"gorouters/users"
+)
+
+func main() {
+	r := gin.Default()
+	api := r.Group("/api")
+	v1 := api.Group("/v1")
+	v1.GET("/health", health)
+	users.Register(v1)
+	r.Run()
+}
+
+func health(c *gin.Context) {
+	c.JSON(200, gin.H{"ok": true})
+}
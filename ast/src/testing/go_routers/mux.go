@@ -0,0 +1,31 @@
+package main
+
+// @ast node: Function "muxRouter"
+// @ast node: Function "listProducts"
+// @ast node: Function "updateProduct"
+// @ast node: Endpoint "/shop/products" [verb=GET]
+// @ast edge: Handler -> Function "listProducts" "mux.go"
+// @ast node: Endpoint "/shop/products/{id}" [verb=PUT]
+// @ast edge: Handler -> Function "updateProduct" "mux.go"
+
+import (
+	"net/http"
+
+	"github.com/gorilla/mux"
+)
+
+func muxRouter() *mux.Router {
+	r := mux.NewRouter()
+	s := r.PathPrefix("/shop").Subrouter()
+	s.HandleFunc("/products", listProducts).Methods("GET")
+	s.HandleFunc("/products/{id}", updateProduct).Methods("PUT")
+	return r
+}
+
+func listProducts(w http.ResponseWriter, r *http.Request) {
+	w.Write([]byte("[]"))
+}
+
+func updateProduct(w http.ResponseWriter, r *http.Request) {
+	w.Write([]byte(mux.Vars(r)["id"]))
+}
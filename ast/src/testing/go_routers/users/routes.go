@@ -0,0 +1,26 @@
+package users
+
+// @ast node: Function "Register"
+// @ast node: Function "getUser"
+// @ast node: Function "deleteUser"
+// @ast node: Endpoint "/api/v1/users/:id" [verb=GET]
+// @ast edge: Handler -> Function "getUser" "users/routes.go"
+// @ast node: Endpoint "/api/v1/users/:id" [verb=DELETE]
+// @ast edge: Handler -> Function "deleteUser" "users/routes.go"
+
+import "github.com/gin-gonic/gin"
+
+// Register is mounted under /api/v1 from main.go
+func Register(rg *gin.RouterGroup) {
+	g := rg.Group("/users")
+	g.GET("/:id", getUser)
+	g.DELETE("/:id", deleteUser)
+}
+
+func getUser(c *gin.Context) {
+	c.JSON(200, gin.H{"id": c.Param("id")})
+}
+
+func deleteUser(c *gin.Context) {
+	c.Status(204)
+}
@@ -2,6 +2,7 @@
 // @ast edge: Implements -> Trait "PaymentGateway" "PaymentGateway.java"
 // @ast node: Function "StripePaymentGateway"
 // @ast node: Function "charge"
+// @ast edge: Overrides -> Function "charge" "PaymentGateway.java"
 // @ast node: Var "accountBalances"
 // @ast node: Var "current"
 package graph.stakgraph.java.nonweb;
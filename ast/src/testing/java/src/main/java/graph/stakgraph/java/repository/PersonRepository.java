@@ -1,4 +1,5 @@
 // @ast node: Trait "PersonRepository"
+// @ast edge: Contains -> DataModel "Person" "Person.java"
 package graph.stakgraph.java.repository;
 
 import graph.stakgraph.java.model.Person;
@@ -0,0 +1,10 @@
+import cron from "node-cron";
+
+// @ast node: Function "purgeSessions"
+export function purgeSessions() {
+  return 0;
+}
+
+// @ast node: Job "purgeSessions" [scheduler=node-cron]
+// @ast edge: Handler -> Function "purgeSessions" "src/cron.ts"
+cron.schedule("*/15 * * * *", purgeSessions);
@@ -0,0 +1,13 @@
+import { Injectable } from "@nestjs/common";
+import { Cron, CronExpression } from "@nestjs/schedule";
+
+@Injectable()
+export class ReportsService {
+  // @ast node: Job "generateDaily" [scheduler=nestjs schedule=CronExpression.EVERY_DAY_AT_MIDNIGHT]
+  // @ast edge: Handler -> Function "generateDaily" "src/reports.service.ts"
+  // @ast node: Function "generateDaily"
+  @Cron(CronExpression.EVERY_DAY_AT_MIDNIGHT)
+  async generateDaily() {
+    return "report";
+  }
+}
@@ -6,6 +6,17 @@ package com.kotlintestapp.data.repository
 // @ast edge: Calls -> Function "createUser" "ApiService.kt"
 // @ast node: Function "getUsers"
 // @ast edge: Calls -> Function "getUsers" "ApiService.kt"
+// @ast node: Class "UserCache"
+// @ast edge: ParentOf -> Class "InMemoryUserCache" "UserRepository.kt"
+// @ast edge: Operand -> Function "lookup" "UserRepository.kt" [operand=UserCache]
+// @ast edge: Operand -> Function "store" "UserRepository.kt" [operand=UserCache]
+// @ast node: Function "lookup" [operand=UserCache]
+// @ast node: Function "store" [operand=UserCache]
+// @ast node: Class "InMemoryUserCache"
+// @ast node: Function "lookup" [operand=InMemoryUserCache]
+// @ast edge: Overrides -> Function "lookup" "UserRepository.kt" [operand=UserCache]
+// @ast node: Function "store" [operand=InMemoryUserCache]
+// @ast edge: Overrides -> Function "store" "UserRepository.kt" [operand=UserCache]
 // @ast node: Import "import-imports-srctestingkotlinappsrcmainjavacomkotlintestappdatarepositoryuserrepositorykt-0"
 
 import com.kotlintestapp.data.api.ApiService
@@ -37,3 +48,20 @@ class UserRepository @Inject constructor(
         }
     }
 }
+
+interface UserCache {
+    fun lookup(id: String): User?
+    fun store(user: User)
+}
+
+class InMemoryUserCache : UserCache {
+    private val users = mutableMapOf<String, User>()
+
+    override fun lookup(id: String): User? {
+        return users[id]
+    }
+
+    override fun store(user: User) {
+        users[user.id] = user
+    }
+}
@@ -6,8 +6,9 @@ package com.kotlintestapp.models
 // @ast node: DataModel "Animal"
 // @ast node: DataModel "Dog"
 // @ast node: DataModel "Person"
-// @ast node: Function "speak"
-// @ast node: Function "speak"
+// @ast node: Function "speak" [operand=Animal]
+// @ast node: Function "speak" [operand=Dog]
+// @ast edge: Overrides -> Function "speak" "Person.kt" [operand=Animal]
 // @ast node: Import "import-imports-srctestingkotlinappsrcmainjavacomkotlintestappmodelspersonkt-0"
 
 data class Person(
@@ -0,0 +1,48 @@
+// @ast node: Function "configureRouting"
+// @ast node: Endpoint "/health" [verb=GET]
+// @ast edge: Handler -> Function "GET_health_lambda_L25" "Routing.kt"
+// @ast node: Function "GET_health_lambda_L25"
+// @ast node: Endpoint "/api/users" [verb=GET]
+// @ast edge: Handler -> Function "GET_lambda_L30" "Routing.kt"
+// @ast node: Function "GET_lambda_L30"
+// @ast node: Endpoint "/api/users/{id}" [verb=GET]
+// @ast edge: Handler -> Function "GET_id_lambda_L33" "Routing.kt"
+// @ast node: Function "GET_id_lambda_L33"
+// @ast node: Endpoint "/api/users" [verb=POST middleware=authenticate:auth-jwt]
+// @ast edge: Handler -> Function "POST_lambda_L37" "Routing.kt"
+// @ast node: Function "POST_lambda_L37"
+// @ast node: Endpoint "/api/users/{id}" [verb=DELETE middleware=authenticate:auth-jwt]
+// @ast edge: Handler -> Function "DELETE_id_lambda_L40" "Routing.kt"
+// @ast node: Function "DELETE_id_lambda_L40"
+package com.example
+
+import io.ktor.server.application.*
+import io.ktor.server.auth.*
+import io.ktor.server.response.*
+import io.ktor.server.routing.*
+
+fun Application.configureRouting() {
+    routing {
+        get("/health") {
+            call.respondText("ok")
+        }
+        route("/api") {
+            route("/users") {
+                get {
+                    call.respond(UserStore.all())
+                }
+                get("/{id}") {
+                    call.respond(UserStore.find(call.parameters["id"]))
+                }
+                authenticate("auth-jwt") {
+                    post {
+                        call.respondText("created")
+                    }
+                    delete("/{id}") {
+                        call.respondText("deleted")
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+-- @ast node: Class "M"
+-- @ast edge: Operand -> Function "query" "app/db.lua"
+-- @ast node: Function "query"
+local M = {}
+
+-- runs a statement against the connection pool
+function M.query(sql, params)
+  return { sql = sql, params = params }
+end
+
+return M
@@ -0,0 +1,22 @@
+-- @ast node: Class "M"
+-- @ast edge: Operand -> Function "list" "app/users.lua"
+-- @ast edge: Operand -> Function "find" "app/users.lua"
+-- @ast node: Function "list"
+-- @ast edge: Calls -> Function "find" "app/users.lua"
+-- @ast edge: Handler <- Endpoint "/api/users" "conf/nginx.conf"
+-- @ast node: Function "find"
+-- @ast edge: Calls -> Function "query" "app/db.lua"
+local db = require("app.db")
+
+local M = {}
+
+function M.list(ngx)
+  local rows = M:find({ active = true })
+  ngx.say(#rows)
+end
+
+function M:find(filter)
+  return db.query("select * from users", filter)
+end
+
+return M
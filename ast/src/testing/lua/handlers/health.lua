@@ -0,0 +1,6 @@
+-- @ast node: Function "status"
+local function status()
+  return { ok = true }
+end
+
+ngx.say(status().ok and "ok" or "down")
@@ -0,0 +1,8 @@
+-- @ast node: UnitTest "users"
+local users = require("app.users")
+
+describe("users", function()
+  it("finds active users", function()
+    assert.is_table(users:find({ active = true }))
+  end)
+end)
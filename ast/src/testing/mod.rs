@@ -97,6 +97,22 @@ async fn test_typescript() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_nestjs() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/nestjs", "ts", Language::Typescript).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/nestjs", "ts", Language::Typescript).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/nestjs", "ts", Language::Typescript).await.unwrap();
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_nextjs() {
     pre_test();
@@ -114,6 +130,54 @@ async fn test_nextjs() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_queues() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/queues", "ts", Language::Typescript).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/queues", "ts", Language::Typescript).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/queues", "ts", Language::Typescript).await.unwrap();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_channels() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/channels", "ts", Language::Typescript).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/channels", "ts", Language::Typescript).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/channels", "ts", Language::Typescript).await.unwrap();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_jobs() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/jobs", "ts", Language::Typescript).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/jobs", "ts", Language::Typescript).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/jobs", "ts", Language::Typescript).await.unwrap();
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_go() {
     #[cfg(not(feature = "neo4j"))]
@@ -130,6 +194,22 @@ async fn test_go() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_go_routers() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/go_routers", "go", Language::Go).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/go_routers", "go", Language::Go).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/go_routers", "go", Language::Go).await.unwrap();
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_go_non_web() {
     #[cfg(not(feature = "neo4j"))]
@@ -178,6 +258,22 @@ async fn test_python_web() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fastapi() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/fastapi", "python", Language::Python).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/fastapi", "python", Language::Python).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/fastapi", "python", Language::Python).await.unwrap();
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_python_data_science() {
     #[cfg(not(feature = "neo4j"))]
@@ -242,6 +338,54 @@ async fn test_python_module_calls() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_python_queues() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/python/queues", "python", Language::Python).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/python/queues", "python", Language::Python).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/python/queues", "python", Language::Python).await.unwrap();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_python_jobs() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/python/jobs", "python", Language::Python).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/python/jobs", "python", Language::Python).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/python/jobs", "python", Language::Python).await.unwrap();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_python_cli_commands() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/python/cli_commands", "python", Language::Python).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/python/cli_commands", "python", Language::Python).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/python/cli_commands", "python", Language::Python).await.unwrap();
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_ruby() {
     #[cfg(not(feature = "neo4j"))]
@@ -322,6 +466,22 @@ async fn test_java() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_spring_webflux() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/spring_webflux", "java", Language::Java).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/spring_webflux", "java", Language::Java).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/spring_webflux", "java", Language::Java).await.unwrap();
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_csharp() {
     #[cfg(not(feature = "neo4j"))]
@@ -338,6 +498,22 @@ async fn test_csharp() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_aspnet_minimal() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/aspnet", "csharp", Language::CSharp).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/aspnet", "csharp", Language::CSharp).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/aspnet", "csharp", Language::CSharp).await.unwrap();
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_kotlin() {
     #[cfg(not(feature = "neo4j"))]
@@ -354,6 +530,22 @@ async fn test_kotlin() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_ktor() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/ktor", "kotlin", Language::Kotlin).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/ktor", "kotlin", Language::Kotlin).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/ktor", "kotlin", Language::Kotlin).await.unwrap();
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_swift() {
     #[cfg(not(feature = "neo4j"))]
@@ -421,3 +613,35 @@ async fn test_svelte() {
         run_fixture_test::<Neo4jGraph>("src/testing/svelte", "svelte", Language::Svelte).await.unwrap();
     }
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_lua() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/lua", "lua", Language::Lua).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/lua", "lua", Language::Lua).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/lua", "lua", Language::Lua).await.unwrap();
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_zig() {
+    #[cfg(not(feature = "neo4j"))]
+    {
+        run_fixture_test::<ArrayGraph>("src/testing/zig", "zig", Language::Zig).await.unwrap();
+        run_fixture_test::<BTreeMapGraph>("src/testing/zig", "zig", Language::Zig).await.unwrap();
+    }
+    #[cfg(feature = "neo4j")]
+    {
+        use crate::{lang::graphs::Neo4jGraph, testing::annotations::run_fixture_test};
+        let graph = Neo4jGraph::default();
+        graph.clear().await.unwrap();
+        run_fixture_test::<Neo4jGraph>("src/testing/zig", "zig", Language::Zig).await.unwrap();
+    }
+}
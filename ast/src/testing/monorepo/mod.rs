@@ -325,6 +325,132 @@ async fn test_monorepo_root_files_in_graph() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_monorepo_service_topology() -> Result<()> {
+    let root = Path::new(MONOREPO_TEST_DIR).join("monorepo_npm_go");
+
+    let repos = Repo::new_multi_detect(
+        root.to_str().unwrap(),
+        None,
+        Vec::new(),
+        Vec::new(),
+        Some(false),
+    )
+    .await?;
+
+    let graph = repos
+        .build_graphs_inner::<crate::lang::BTreeMapGraph>()
+        .await?;
+
+    let services = graph.find_nodes_by_type(NodeType::Service);
+    assert_eq!(services.len(), 2, "Should have api and web services");
+
+    let api = services.iter().find(|s| s.name == "api").unwrap();
+    assert_eq!(
+        api.meta.get("base_image").map(|s| s.as_str()),
+        Some("golang:1.21-alpine")
+    );
+    assert_eq!(
+        api.meta.get("entrypoint").map(|s| s.as_str()),
+        Some("./main")
+    );
+
+    let depends =
+        graph.find_nodes_with_edge_type(NodeType::Service, NodeType::Service, EdgeType::DependsOn);
+    assert_eq!(depends.len(), 0);
+
+    let connects =
+        graph.find_nodes_with_edge_type(NodeType::Service, NodeType::Service, EdgeType::ConnectsTo);
+    assert_eq!(connects.len(), 1);
+    assert_eq!(connects[0].0.name, "web");
+    assert_eq!(connects[0].1.name, "api");
+
+    let handlers =
+        graph.find_nodes_with_edge_type(NodeType::Service, NodeType::Function, EdgeType::Handler);
+    assert_eq!(
+        handlers.len(),
+        1,
+        "api CMD should map to the Go main function"
+    );
+    assert_eq!(handlers[0].1.name, "main");
+    assert!(handlers[0].1.file.ends_with("cmd/api/main.go"));
+
+    let config =
+        graph.find_nodes_with_edge_type(NodeType::Service, NodeType::ConfigKey, EdgeType::Contains);
+    assert_eq!(config.len(), 1);
+    assert_eq!(config[0].0.name, "web");
+    assert_eq!(config[0].1.name, "API_URL");
+    assert_eq!(
+        config[0].1.meta.get("default").map(|s| s.as_str()),
+        Some("http://api:8080")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_monorepo_ci_workflows() -> Result<()> {
+    let root = Path::new(MONOREPO_TEST_DIR).join("monorepo_npm_go");
+
+    let repos = Repo::new_multi_detect(
+        root.to_str().unwrap(),
+        None,
+        Vec::new(),
+        Vec::new(),
+        Some(false),
+    )
+    .await?;
+
+    let graph = repos
+        .build_graphs_inner::<crate::lang::BTreeMapGraph>()
+        .await?;
+
+    let workflows = graph.find_nodes_by_type(NodeType::Workflow);
+    assert_eq!(workflows.len(), 2, "Should have the CI workflow and the Makefile");
+    let ci = workflows.iter().find(|w| w.name == "CI").unwrap();
+    assert_eq!(
+        ci.meta.get("triggers").map(|s| s.as_str()),
+        Some("push,pull_request")
+    );
+
+    let jobs = graph.find_nodes_by_type(NodeType::WorkflowJob);
+    let mut job_names: Vec<&str> = jobs.iter().map(|j| j.name.as_str()).collect();
+    job_names.sort();
+    assert_eq!(job_names, vec!["api", "build", "test", "web"]);
+
+    let needs = graph.find_nodes_with_edge_type(
+        NodeType::WorkflowJob,
+        NodeType::WorkflowJob,
+        EdgeType::DependsOn,
+    );
+    assert_eq!(needs.len(), 2, "web needs api, test needs build");
+
+    let make_calls = graph.find_nodes_with_edge_type(
+        NodeType::WorkflowStep,
+        NodeType::WorkflowJob,
+        EdgeType::Runs,
+    );
+    assert_eq!(make_calls.len(), 1);
+    assert_eq!(make_calls[0].0.name, "Test API");
+    assert_eq!(make_calls[0].1.name, "test");
+
+    let web_tests = graph
+        .find_nodes_by_type(NodeType::WorkflowStep)
+        .into_iter()
+        .find(|s| s.name == "npx jest src")
+        .unwrap();
+    assert_eq!(
+        web_tests.meta.get("test_runner").map(|s| s.as_str()),
+        Some("jest")
+    );
+    assert!(web_tests
+        .meta
+        .get("covers")
+        .is_some_and(|c| c.ends_with("apps/web")));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_remote_monorepo_root_detection() -> Result<()> {
     use crate::repo::Repo;
@@ -0,0 +1,9 @@
+import { Module } from '@nestjs/common';
+import { UsersModule } from './users/users.module';
+
+@Module({
+  imports: [UsersModule],
+})
+export class AppModule {}
+// @ast node: Class "AppModule"
+// @ast edge: DependsOn -> Class "UsersModule" "src/users/users.module.ts"
@@ -0,0 +1,60 @@
+import {
+  Body,
+  ClassSerializerInterceptor,
+  Controller,
+  Delete,
+  Get,
+  Header,
+  HttpCode,
+  Param,
+  Post,
+  UseGuards,
+  UseInterceptors,
+} from '@nestjs/common';
+import { AuthGuard } from '../auth/auth.guard';
+import { UsersService } from './users.service';
+
+@Controller('users')
+@UseGuards(AuthGuard)
+export class UsersController {
+  constructor(private readonly usersService: UsersService) {}
+
+  @Get()
+  findAll() {
+    return this.usersService.findAll();
+  }
+
+  @Get(':id')
+  @UseInterceptors(ClassSerializerInterceptor)
+  findOne(@Param('id') id: string) {
+    return this.usersService.findOne(id);
+  }
+
+  @Post()
+  create(@Body() body: { name: string }) {
+    return this.usersService.create(body.name);
+  }
+
+  @HttpCode(204)
+  @Delete(':id')
+  @Header('Cache-Control', 'no-store')
+  @UseInterceptors(ClassSerializerInterceptor)
+  remove(@Param('id') id: string) {
+    return this.usersService.remove(id);
+  }
+}
+// @ast node: Class "UsersController"
+// @ast edge: Operand -> Function "findOne" "src/users/users.controller.ts"
+// @ast node: Function "constructor"
+// @ast node: Function "findAll"
+// @ast edge: Handler <- Endpoint "/api/users" "src/users/users.controller.ts" [verb=GET]
+// @ast node: Function "findOne"
+// @ast edge: Handler <- Endpoint "/api/users/:id" "src/users/users.controller.ts" [verb=GET]
+// @ast node: Function "create"
+// @ast edge: Handler <- Endpoint "/api/users" "src/users/users.controller.ts" [verb=POST]
+// @ast node: Function "remove"
+// @ast edge: Handler <- Endpoint "/api/users/:id" "src/users/users.controller.ts" [verb=DELETE]
+// @ast node: Endpoint "/api/users" [verb=GET middleware=AuthGuard]
+// @ast node: Endpoint "/api/users/:id" [verb=GET middleware=AuthGuard,ClassSerializerInterceptor]
+// @ast node: Endpoint "/api/users" [verb=POST middleware=AuthGuard]
+// @ast node: Endpoint "/api/users/:id" [verb=DELETE middleware=AuthGuard,ClassSerializerInterceptor]
@@ -0,0 +1,13 @@
+import { Module } from '@nestjs/common';
+import { UsersController } from './users.controller';
+import { UsersService } from './users.service';
+
+@Module({
+  controllers: [UsersController],
+  providers: [UsersService],
+  exports: [UsersService],
+})
+export class UsersModule {}
+// @ast node: Class "UsersModule"
+// @ast edge: DependsOn -> Class "UsersController" "src/users/users.controller.ts"
+// @ast edge: DependsOn -> Class "UsersService" "src/users/users.service.ts"
@@ -0,0 +1,30 @@
+import { Injectable } from '@nestjs/common';
+
+@Injectable()
+export class UsersService {
+  private readonly users = new Map<string, string>();
+
+  findAll() {
+    return [...this.users.values()];
+  }
+
+  findOne(id: string) {
+    return this.users.get(id);
+  }
+
+  create(name: string) {
+    const id = String(this.users.size + 1);
+    this.users.set(id, name);
+    return id;
+  }
+
+  remove(id: string) {
+    return this.users.delete(id);
+  }
+}
+// @ast node: Class "UsersService"
+// @ast edge: Operand -> Function "findAll" "src/users/users.service.ts"
+// @ast node: Function "findAll"
+// @ast node: Function "findOne"
+// @ast node: Function "create"
+// @ast node: Function "remove"
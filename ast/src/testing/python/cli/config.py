@@ -8,6 +8,9 @@
 # @ast node: Var "port"
 # @ast node: Var "debug"
 # @ast node: Var "api_key"
+# @ast node: Module "config"
+# @ast edge: Contains -> Class "AppConfig" "config.py"
+# @ast edge: Contains -> Function "from_yaml" "config.py"
 from pydantic import BaseModel, Field, validator
 from typing import Optional
 import yaml
@@ -5,6 +5,8 @@
 # @ast edge: Calls -> Function "from_yaml" "config.py"
 # @ast node: Function "logs"
 # @ast edge: Calls -> Function "fetch_logs" "utils.py"
+# @ast node: Module "main"
+# @ast edge: Contains -> Function "deploy" "main.py"
 import click
 import asyncio
 from .config import AppConfig
@@ -2,6 +2,11 @@
 # @ast node: DataModel "DeploymentError"
 # @ast node: Function "fetch_logs"
 # @ast node: Function "deploy_service"
+# @ast edge: Throws -> DataModel "DeploymentError" "utils.py"
+# @ast edge: Catches -> DataModel "DeploymentError" "utils.py"
+# @ast node: Module "utils"
+# @ast edge: Contains -> DataModel "DeploymentError" "utils.py"
+# @ast edge: Contains -> Function "deploy_service" "utils.py"
 import asyncio
 import random
 
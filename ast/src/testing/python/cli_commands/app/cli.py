@@ -0,0 +1,16 @@
+import click
+
+
+# @ast node: Function "cli"
+@click.group()
+def cli():
+    pass
+
+
+# @ast node: Command "sync-users" [framework=click]
+# @ast edge: Handler -> Function "sync_users" "app/cli.py"
+# @ast node: Function "sync_users"
+@cli.command()
+@click.option("--dry-run", is_flag=True)
+def sync_users(dry_run):
+    click.echo(f"syncing users (dry run: {dry_run})")
@@ -0,0 +1,19 @@
+import click
+
+from app.cli import cli
+
+
+# @ast node: Command "db" [framework=click]
+# @ast edge: Handler -> Function "db" "app/db.py"
+# @ast node: Function "db"
+@cli.group()
+def db():
+    pass
+
+
+# @ast node: Command "db migrate" [framework=click]
+# @ast edge: Handler -> Function "run_migrations" "app/db.py"
+# @ast node: Function "run_migrations"
+@db.command("migrate")
+def run_migrations():
+    click.echo("migrating")
@@ -0,0 +1,10 @@
+from celery import Celery
+from celery.schedules import crontab
+
+app = Celery("jobs")
+
+# @ast node: Job "nightly-cleanup" [scheduler=celery-beat]
+# @ast edge: Handler -> Function "cleanup" "app/tasks.py"
+app.conf.beat_schedule = {
+    "nightly-cleanup": {"task": "app.tasks.cleanup", "schedule": crontab(hour=3, minute=0)},
+}
@@ -0,0 +1,21 @@
+from apscheduler.schedulers.blocking import BlockingScheduler
+
+sched = BlockingScheduler()
+
+
+# @ast node: Job "refresh_cache" [scheduler=apscheduler schedule=interval]
+# @ast edge: Handler -> Function "refresh_cache" "app/scheduler.py"
+# @ast node: Function "refresh_cache"
+@sched.scheduled_job("interval")
+def refresh_cache():
+    return "refreshed"
+
+
+# @ast node: Function "send_digest"
+def send_digest():
+    return "sent"
+
+
+# @ast node: Job "send_digest" [scheduler=apscheduler]
+# @ast edge: Handler -> Function "send_digest" "app/scheduler.py"
+sched.add_job(send_digest, "cron", hour=8)
@@ -0,0 +1,7 @@
+from celery import shared_task
+
+
+# @ast node: Function "cleanup"
+@shared_task
+def cleanup():
+    return "cleaned"
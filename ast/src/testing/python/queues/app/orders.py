@@ -0,0 +1,22 @@
+import pika
+from kafka import KafkaProducer
+
+from app.tasks import send_receipt
+
+producer = KafkaProducer(bootstrap_servers="localhost:9092")
+
+
+# @ast node: Function "place_order"
+# @ast edge: Publishes -> Queue "orders.created" "app/orders.py"
+# @ast edge: Publishes -> Queue "billing" "app/orders.py"
+# @ast edge: Publishes -> Queue "send_receipt" "app/orders.py"
+def place_order(order):
+    producer.send("orders.created", order)
+    channel = pika.BlockingConnection().channel()
+    channel.basic_publish(exchange="", routing_key="billing", body=order)
+    send_receipt.delay(order["id"])
+
+
+# @ast node: Queue "orders.created" [broker=kafka]
+# @ast node: Queue "billing" [broker=rabbitmq]
+# @ast node: Queue "send_receipt" [broker=celery]
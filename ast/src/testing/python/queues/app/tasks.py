@@ -0,0 +1,8 @@
+from celery import shared_task
+
+
+# @ast node: Function "send_receipt"
+# @ast edge: Consumes -> Queue "send_receipt" "app/orders.py"
+@shared_task
+def send_receipt(order_id):
+    return {"order": order_id, "sent": True}
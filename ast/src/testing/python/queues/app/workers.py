@@ -0,0 +1,34 @@
+import boto3
+import pika
+from kafka import KafkaConsumer
+
+
+# @ast node: Function "consume_orders"
+# @ast edge: Consumes -> Queue "orders.created" "app/orders.py"
+def consume_orders():
+    consumer = KafkaConsumer("orders.created")
+    for message in consumer:
+        print(message.value)
+
+
+# @ast node: Function "handle_billing"
+def handle_billing(channel, method, properties, body):
+    print(body)
+
+
+# @ast node: Function "consume_billing"
+# @ast edge: Consumes -> Queue "billing" "app/orders.py"
+def consume_billing():
+    channel = pika.BlockingConnection().channel()
+    channel.basic_consume(queue="billing", on_message_callback=handle_billing)
+    channel.start_consuming()
+
+
+# @ast node: Function "poll_refunds"
+# @ast edge: Consumes -> Queue "refunds" "app/workers.py"
+def poll_refunds():
+    sqs = boto3.client("sqs")
+    return sqs.receive_message(QueueUrl="https://sqs.us-east-1.amazonaws.com/123/refunds")
+
+
+# @ast node: Queue "refunds" [broker=sqs]
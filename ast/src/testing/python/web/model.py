@@ -29,8 +29,10 @@
 # @ast edge: Operand -> Function "species" "model.py"
 # @ast edge: Operand -> Function "is_mammal" "model.py"
 # @ast edge: Operand -> Function "create_puppy" "model.py"
-# @ast node: Function "make_sound"
-# @ast node: Function "move"
+# @ast node: Function "make_sound" [operand=Dog]
+# @ast edge: Overrides -> Function "make_sound" "model.py" [operand=Animal]
+# @ast node: Function "move" [operand=Dog]
+# @ast edge: Overrides -> Function "move" "model.py" [operand=Animal]
 # @ast node: Function "species"
 # @ast node: Function "is_mammal"
 # @ast node: Function "create_puppy"
@@ -0,0 +1,16 @@
+import { Queue } from "bullmq";
+import { Kafka } from "kafkajs";
+
+const kafka = new Kafka({ brokers: ["localhost:9092"] });
+const producer = kafka.producer();
+const emails = new Queue("emails");
+
+// @ast node: Function "placeOrder"
+// @ast edge: Publishes -> Queue "orders.created" "src/worker.ts"
+// @ast edge: Publishes -> Queue "emails" "src/orders.ts"
+export async function placeOrder(orderId: string) {
+  await producer.send({ topic: "orders.created", messages: [{ value: orderId }] });
+  await emails.add("receipt", { orderId });
+}
+
+// @ast node: Queue "emails" [broker=bullmq]
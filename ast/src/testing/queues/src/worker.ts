@@ -0,0 +1,21 @@
+import { Job, Worker } from "bullmq";
+import { Kafka } from "kafkajs";
+
+const kafka = new Kafka({ brokers: ["localhost:9092"] });
+
+// @ast node: Function "processEmail"
+// @ast edge: Consumes -> Queue "emails" "src/orders.ts"
+export async function processEmail(job: Job) {
+  console.log("sending", job.data.orderId);
+}
+
+// @ast node: Function "startOrderConsumer"
+// @ast edge: Consumes -> Queue "orders.created" "src/worker.ts"
+export async function startOrderConsumer() {
+  const consumer = kafka.consumer({ groupId: "billing" });
+  await consumer.subscribe({ topic: "orders.created" });
+}
+
+new Worker("emails", processEmail);
+
+// @ast node: Queue "orders.created" [broker=kafka]
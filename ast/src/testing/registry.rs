@@ -48,6 +48,27 @@ fn test_resolve_method_via_type_def() {
     assert_eq!(reg.resolve_method("UserService", "unrelated"), None);
 }
 
+#[test]
+fn test_resolve_type_from_signature_params() {
+    use crate::lang::signature::{Param, Signature};
+    let mut graph = BTreeMapGraph::new("".to_string(), Language::Typescript);
+    let mut func = ts_method("/repo/src/user.ts", "load", "UserService");
+    func.add_signature(&Signature {
+        params: vec![Param {
+            name: "repo".to_string(),
+            type_name: Some("UserRepo".to_string()),
+            default: None,
+        }],
+        return_type: Some("Promise<User>".to_string()),
+        ..Default::default()
+    });
+    graph.add_node(&NodeType::Function, &func);
+    let reg = TypeScriptRegistry::new(&graph, &[]);
+    assert_eq!(reg.resolve_type("/repo/src/user.ts", "repo"), Some("UserRepo"));
+    // Promise<User> unwraps to the type a chained call resolves against
+    assert_eq!(reg.resolve_field("UserService", "load()"), Some("User"));
+}
+
 #[test]
 fn test_resolve_unknown_returns_none() {
     let graph = BTreeMapGraph::new("".to_string(), Language::Typescript);
@@ -2,8 +2,10 @@
 // @ast node: DataModel "Database"
 // @ast node: DataModel "Wrapper"
 // @ast node: Class "Database"
+// @ast edge: Implements -> Trait "Logger" "traits.rs"
 // @ast edge: Operand -> Function "new_person_impl" "db.rs"
 // @ast edge: Operand -> Function "get_person_by_id_impl" "db.rs"
+// @ast edge: Operand -> Function "error" "db.rs"
 // @ast node: Var "DB_INSTANCE"
 // @ast node: Function "new_person"
 // @ast node: Function "get_person_by_id"
@@ -23,7 +25,10 @@
 // @ast node: Function "multi_attribute_function"
 // @ast node: Function "new_person_impl"
 // @ast node: Function "get_person_by_id_impl"
+// @ast node: Function "error"
+// @ast edge: Overrides -> Function "error" "traits.rs"
 use anyhow::{Context, Result};
+use crate::traits::Logger;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
@@ -138,6 +143,12 @@ impl Database {
     }
 }
 
+impl Logger for Database {
+    fn error(&self, msg: &str) {
+        eprintln!("[db] {}", msg);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,16 @@
+// @ast node: Class "Customer"
+// @ast node: DataModel "Customer"
+package com.example.shop.model;
+
+import jakarta.persistence.Entity;
+import jakarta.persistence.Id;
+import lombok.Data;
+
+@Entity
+@Data
+public class Customer {
+    @Id
+    private Long id;
+    private String email;
+    private String status;
+}
@@ -0,0 +1,35 @@
+// @ast node: Trait "CustomerRepository"
+// @ast edge: Contains -> DataModel "Customer" "Customer.java"
+// @ast node: Function "findByEmail" [operation=find]
+// @ast edge: Contains -> DataModel "Customer" "Customer.java"
+// @ast edge: Reads -> DataModel "Customer" "Customer.java"
+// @ast node: Function "findTop10ByStatusOrderByIdDesc" [operation=find]
+// @ast edge: Contains -> DataModel "Customer" "Customer.java"
+// @ast edge: Reads -> DataModel "Customer" "Customer.java"
+// @ast node: Function "countByStatus" [operation=count]
+// @ast edge: Contains -> DataModel "Customer" "Customer.java"
+// @ast edge: Reads -> DataModel "Customer" "Customer.java"
+// @ast node: Function "existsByEmail" [operation=exists]
+// @ast edge: Contains -> DataModel "Customer" "Customer.java"
+// @ast edge: Reads -> DataModel "Customer" "Customer.java"
+// @ast node: Function "deleteByStatus" [operation=delete]
+// @ast edge: Contains -> DataModel "Customer" "Customer.java"
+// @ast edge: Writes -> DataModel "Customer" "Customer.java"
+package com.example.shop.repository;
+
+import com.example.shop.model.Customer;
+import java.util.List;
+import java.util.Optional;
+import org.springframework.data.jpa.repository.JpaRepository;
+
+public interface CustomerRepository extends JpaRepository<Customer, Long> {
+    Optional<Customer> findByEmail(String email);
+
+    List<Customer> findTop10ByStatusOrderByIdDesc(String status);
+
+    long countByStatus(String status);
+
+    boolean existsByEmail(String email);
+
+    void deleteByStatus(String status);
+}
@@ -0,0 +1,37 @@
+// @ast node: Class "CustomerService"
+// @ast node: Function "CustomerService"
+// @ast node: Function "register" [writes=Customer.email]
+// @ast edge: Contains -> DataModel "Customer" "Customer.java"
+// @ast edge: Writes -> DataModel "Customer" "Customer.java"
+// @ast node: Function "deactivate" [reads=Customer.status writes=Customer.status]
+// @ast edge: Contains -> DataModel "Customer" "Customer.java"
+// @ast edge: Reads -> DataModel "Customer" "Customer.java"
+// @ast edge: Writes -> DataModel "Customer" "Customer.java"
+package com.example.shop.service;
+
+import com.example.shop.model.Customer;
+import com.example.shop.repository.CustomerRepository;
+import org.springframework.stereotype.Service;
+
+@Service
+public class CustomerService {
+    private final CustomerRepository customers;
+
+    public CustomerService(CustomerRepository customers) {
+        this.customers = customers;
+    }
+
+    public Customer register(String email) {
+        Customer customer = new Customer();
+        customer.setEmail(email);
+        return customers.save(customer);
+    }
+
+    public Customer deactivate(Customer customer) {
+        if ("inactive".equals(customer.getStatus())) {
+            return customer;
+        }
+        customer.setStatus("inactive");
+        return customers.save(customer);
+    }
+}
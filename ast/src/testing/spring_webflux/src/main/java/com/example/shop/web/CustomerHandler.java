@@ -0,0 +1,49 @@
+// @ast node: Class "CustomerHandler"
+// @ast node: Function "CustomerHandler"
+// @ast node: Function "getCustomer"
+// @ast node: Function "deleteCustomer"
+// @ast node: Function "customerStats"
+// @ast node: Function "health"
+// @ast node: Function "listCustomers"
+// @ast node: Function "createCustomer"
+package com.example.shop.web;
+
+import com.example.shop.repository.CustomerRepository;
+import org.springframework.stereotype.Component;
+import org.springframework.web.reactive.function.server.ServerRequest;
+import org.springframework.web.reactive.function.server.ServerResponse;
+import reactor.core.publisher.Mono;
+
+@Component
+public class CustomerHandler {
+    private final CustomerRepository customers;
+
+    public CustomerHandler(CustomerRepository customers) {
+        this.customers = customers;
+    }
+
+    public Mono<ServerResponse> getCustomer(ServerRequest request) {
+        return ServerResponse.ok().bodyValue(customers.findById(Long.valueOf(request.pathVariable("id"))));
+    }
+
+    public Mono<ServerResponse> deleteCustomer(ServerRequest request) {
+        customers.deleteById(Long.valueOf(request.pathVariable("id")));
+        return ServerResponse.noContent().build();
+    }
+
+    public Mono<ServerResponse> customerStats(ServerRequest request) {
+        return ServerResponse.ok().bodyValue(customers.countByStatus("active"));
+    }
+
+    public Mono<ServerResponse> health(ServerRequest request) {
+        return ServerResponse.ok().bodyValue("ok");
+    }
+
+    public Mono<ServerResponse> listCustomers(ServerRequest request) {
+        return ServerResponse.ok().bodyValue(customers.findAll());
+    }
+
+    public Mono<ServerResponse> createCustomer(ServerRequest request) {
+        return ServerResponse.accepted().build();
+    }
+}
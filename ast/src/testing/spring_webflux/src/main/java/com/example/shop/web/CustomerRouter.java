@@ -0,0 +1,50 @@
+// @ast node: Class "CustomerRouter"
+// @ast node: Function "customerRoutes"
+// @ast node: Function "legacyRoutes"
+// @ast node: Endpoint "/api/customers/{id}" [verb=GET]
+// @ast edge: Handler -> Function "getCustomer" "CustomerHandler.java"
+// @ast node: Endpoint "/api/customers/{id}" [verb=DELETE]
+// @ast edge: Handler -> Function "deleteCustomer" "CustomerHandler.java"
+// @ast node: Endpoint "/api/stats" [verb=GET]
+// @ast edge: Handler -> Function "customerStats" "CustomerHandler.java"
+// @ast node: Endpoint "/health" [verb=GET]
+// @ast edge: Handler -> Function "health" "CustomerHandler.java"
+// @ast node: Endpoint "/legacy/customers" [verb=GET]
+// @ast edge: Handler -> Function "listCustomers" "CustomerHandler.java"
+// @ast node: Endpoint "/legacy/customers" [verb=POST]
+// @ast edge: Handler -> Function "createCustomer" "CustomerHandler.java"
+package com.example.shop.web;
+
+import static org.springframework.web.reactive.function.server.RequestPredicates.GET;
+import static org.springframework.web.reactive.function.server.RequestPredicates.POST;
+import static org.springframework.web.reactive.function.server.RequestPredicates.path;
+import static org.springframework.web.reactive.function.server.RouterFunctions.nest;
+import static org.springframework.web.reactive.function.server.RouterFunctions.route;
+
+import org.springframework.context.annotation.Bean;
+import org.springframework.context.annotation.Configuration;
+import org.springframework.web.reactive.function.server.RouterFunction;
+import org.springframework.web.reactive.function.server.ServerResponse;
+
+@Configuration
+public class CustomerRouter {
+
+    @Bean
+    public RouterFunction<ServerResponse> customerRoutes(CustomerHandler handler) {
+        return route()
+            .path("/api", api -> api
+                .path("/customers", customers -> customers
+                    .GET("/{id}", handler::getCustomer)
+                    .DELETE("/{id}", handler::deleteCustomer))
+                .GET("/stats", handler::customerStats))
+            .GET("/health", handler::health)
+            .build();
+    }
+
+    @Bean
+    public RouterFunction<ServerResponse> legacyRoutes(CustomerHandler handler) {
+        return nest(path("/legacy"),
+            route(GET("/customers"), handler::listCustomers)
+                .andRoute(POST("/customers"), handler::createCustomer));
+    }
+}
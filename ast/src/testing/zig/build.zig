@@ -0,0 +1,10 @@
+// @ast node: Function "build"
+const std = @import("std");
+
+pub fn build(b: *std.Build) void {
+    const exe = b.addExecutable(.{
+        .name = "bank",
+        .root_source_file = b.path("src/main.zig"),
+    });
+    b.installArtifact(exe);
+}
@@ -0,0 +1,31 @@
+// @ast node: Class "Account"
+// @ast edge: Operand -> Function "init" "src/account.zig"
+// @ast edge: Operand -> Function "deposit" "src/account.zig"
+// @ast node: DataModel "Account"
+// @ast node: Class "Kind"
+// @ast node: DataModel "Kind"
+// @ast node: Function "init"
+// @ast node: Function "deposit"
+// @ast node: UnitTest "deposit adds to balance"
+const std = @import("std");
+
+pub const Kind = enum { checking, savings };
+
+pub const Account = struct {
+    balance: i64,
+    kind: Kind = .checking,
+
+    pub fn init(balance: i64) Account {
+        return .{ .balance = balance };
+    }
+
+    pub fn deposit(self: *Account, amount: i64) void {
+        self.balance += amount;
+    }
+};
+
+test "deposit adds to balance" {
+    var account = Account.init(1);
+    account.deposit(2);
+    try std.testing.expectEqual(@as(i64, 3), account.balance);
+}
@@ -0,0 +1,13 @@
+// @ast node: Function "main"
+// @ast edge: Calls -> Function "init" "src/account.zig"
+// @ast edge: Calls -> Function "deposit" "src/account.zig"
+// @ast edge: Calls -> Function "add" "src/math.zig"
+const std = @import("std");
+const Account = @import("account.zig").Account;
+const math = @import("math.zig");
+
+pub fn main() !void {
+    var account = Account.init(100);
+    account.deposit(math.add(20, 22));
+    std.debug.print("{d}\n", .{account.balance});
+}
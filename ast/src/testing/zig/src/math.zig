@@ -0,0 +1,11 @@
+// @ast node: Function "add"
+// @ast node: UnitTest "add is commutative"
+const std = @import("std");
+
+pub fn add(a: i64, b: i64) i64 {
+    return a + b;
+}
+
+test "add is commutative" {
+    try std.testing.expect(add(1, 2) == add(2, 1));
+}
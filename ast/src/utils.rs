@@ -2,7 +2,7 @@ use std::any::Any;
 use std::env;
 use std::future::Future;
 
-use crate::lang::graphs::{ArrayGraph, Node};
+use crate::lang::graphs::{ArrayGraph, Node, NodeType};
 use crate::lang::{BTreeMapGraph, Graph, NodeRef};
 use serde::Serialize;
 use shared::Result;
@@ -79,6 +79,11 @@ fn truncate_at_char_boundary(s: &str, max: usize) -> &str {
     &s[..idx]
 }
 
+/// Zero-based line of a byte offset into `code`.
+pub fn line_of(code: &str, offset: usize) -> usize {
+    code[..offset.min(code.len())].matches('\n').count()
+}
+
 pub fn create_node_key(node: &Node) -> String {
     let node_type = node.node_type.to_string();
     let node_data = &node.node_data;
@@ -135,6 +140,72 @@ pub fn create_node_key(node: &Node) -> String {
     }
 }
 
+/// Line-independent identity: `type-file-operand.name[-verb][-signature]`.
+/// Unlike `create_node_key`, moving a symbol within its file keeps the same key, so
+/// state attached to it (muted flags, descriptions, embeddings, dynamic edges) can be
+/// carried across re-ingests. Kinds that may repeat a qualified name in one file
+/// (overloads, tests, requests) are disambiguated by a hash of their first line.
+///
+/// This is deliberately not the identity nodes and edges MERGE on: static edges are
+/// rebuilt from source on every ingest and keep matching on `node_key`. `stable_key` only
+/// re-attaches what a rebuild cannot recreate.
+pub fn create_stable_key(node: &Node) -> String {
+    let node_data = &node.node_data;
+    let meta = &node_data.meta;
+
+    let mut path = String::new();
+    if let Some(operand) = meta.get("operand") {
+        path.push_str(&sanitize_string(operand));
+        path.push('.');
+    }
+    path.push_str(truncate_at_char_boundary(
+        &sanitize_string(&node_data.name),
+        2000,
+    ));
+
+    let mut result = String::new();
+    result.push_str(&sanitize_string(&node.node_type.to_string()));
+    result.push('-');
+    result.push_str(&sanitize_string(&node_data.file));
+    result.push('-');
+    result.push_str(&path);
+
+    if let Some(v) = meta.get("verb") {
+        result.push('-');
+        result.push_str(&sanitize_string(v));
+    }
+
+    if needs_signature_disambiguator(&node.node_type) {
+        if let Some(line) = node_data
+            .body
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+        {
+            let signature = line.split_whitespace().collect::<Vec<_>>().join(" ");
+            result.push('-');
+            result.push_str(&sha256::digest(signature)[..12]);
+        }
+    }
+
+    let new_len = truncate_at_char_boundary(&result, 5000).len();
+    result.truncate(new_len);
+    result
+}
+
+fn needs_signature_disambiguator(node_type: &NodeType) -> bool {
+    matches!(
+        node_type,
+        NodeType::Function
+            | NodeType::Request
+            | NodeType::UnitTest
+            | NodeType::IntegrationTest
+            | NodeType::E2eTest
+            | NodeType::Var
+            | NodeType::Instance
+    )
+}
+
 pub fn get_use_lsp() -> bool {
     if let Err(err) = delete_react_testing_node_modules() {
         warn!("failed to clean test node_modules: {err}");
@@ -241,3 +312,41 @@ where
 {
     task::block_in_place(|| Handle::current().block_on(async_fn()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lang::NodeData;
+
+    fn function(name: &str, start: usize, body: &str) -> Node {
+        let mut nd = NodeData::name_file(name, "src/lib.rs");
+        nd.start = start;
+        nd.end = start + body.lines().count();
+        nd.body = body.to_string();
+        Node::new(NodeType::Function, nd)
+    }
+
+    #[test]
+    fn test_stable_key_ignores_line_shift() {
+        let body = "fn parse(input: &str) -> Ast {\n    todo!()\n}";
+        let before = function("parse", 10, body);
+        let after = function("parse", 11, body);
+        assert_ne!(create_node_key(&before), create_node_key(&after));
+        assert_eq!(create_stable_key(&before), create_stable_key(&after));
+    }
+
+    #[test]
+    fn test_stable_key_distinguishes_overloads() {
+        let one = function("add", 3, "int add(int a, int b) {\n    return a + b;\n}");
+        let two = function(
+            "add",
+            7,
+            "double add(double a, double b) {\n    return a + b;\n}",
+        );
+        assert_ne!(create_stable_key(&one), create_stable_key(&two));
+
+        // reformatting the signature line is not a new overload
+        let spaced = function("add", 3, "int  add(int a,   int b) {\n    return a + b;\n}");
+        assert_eq!(create_stable_key(&one), create_stable_key(&spaced));
+    }
+}
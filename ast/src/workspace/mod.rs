@@ -177,6 +177,9 @@ fn detect_language(dir: &Path) -> Option<Language> {
     if dir.join("pom.xml").exists() {
         return Some(Language::Java);
     }
+    if dir.join("build.zig").exists() || dir.join("build.zig.zon").exists() {
+        return Some(Language::Zig);
+    }
     None
 }
 
@@ -214,7 +217,16 @@ fn is_skip_dir(path: &Path) -> bool {
     let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
     matches!(
         name,
-        ".git" | "node_modules" | "target" | "vendor" | "build" | "dist" | ".venv" | "venv"
+        ".git"
+            | "node_modules"
+            | "target"
+            | "vendor"
+            | "build"
+            | "dist"
+            | ".venv"
+            | "venv"
+            | "zig-out"
+            | ".zig-cache"
     )
 }
 
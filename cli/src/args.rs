@@ -166,6 +166,10 @@ pub struct DepsArgs {
     #[arg(long, default_value_t = true, action = ArgAction::Set)]
     pub allow: bool,
 
+    /// Only follow calls resolved with at least this confidence (0.0-1.0); calls with no recorded confidence are kept
+    #[arg(long, value_name = "SCORE", value_parser = confidence_score)]
+    pub min_confidence: Option<f32>,
+
     /// Include only files matching these glob patterns (VS Code-style, repeatable or comma-separated)
     #[arg(long, value_delimiter = ',')]
     pub include: Vec<String>,
@@ -213,6 +217,10 @@ pub struct ImpactArgs {
     #[arg(long, default_value_t = true, action = ArgAction::Set)]
     pub allow: bool,
 
+    /// Only follow calls resolved with at least this confidence (0.0-1.0); calls with no recorded confidence are kept
+    #[arg(long, value_name = "SCORE", value_parser = confidence_score)]
+    pub min_confidence: Option<f32>,
+
     /// Include only files matching these glob patterns (VS Code-style, repeatable or comma-separated)
     #[arg(long, value_delimiter = ',')]
     pub include: Vec<String>,
@@ -291,6 +299,10 @@ pub struct SearchArgs {
     #[arg(long, action = ArgAction::SetTrue)]
     pub context: bool,
 
+    /// Only follow calls resolved with at least this confidence (0.0-1.0); calls with no recorded confidence are kept
+    #[arg(long, value_name = "SCORE", value_parser = confidence_score)]
+    pub min_confidence: Option<f32>,
+
     /// Show associated test nodes for each result
     #[arg(long, action = ArgAction::SetTrue)]
     pub tests: bool,
@@ -304,6 +316,18 @@ pub struct SearchArgs {
     pub files: Vec<String>,
 }
 
+/// `--min-confidence` is checked when the flags are parsed, before any files are.
+fn confidence_score(value: &str) -> std::result::Result<f32, String> {
+    let score: f32 = value
+        .parse()
+        .map_err(|_| format!("`{}` is not a number", value))?;
+    if (0.0..=1.0).contains(&score) {
+        Ok(score)
+    } else {
+        Err("must be between 0.0 and 1.0".to_string())
+    }
+}
+
 impl CliArgs {
     pub fn parse_and_expand() -> Result<Self> {
         let mut args = Self::parse();
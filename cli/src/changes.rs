@@ -5,6 +5,7 @@ use super::git::{
     filter_paths_by_scope, get_changed_files, get_repo_root, get_staged_changes,
     get_working_tree_changes, list_commits_for_paths, read_file_at_rev, validate_rev,
 };
+use ast::lang::fields::{diff_fields, FieldChange};
 use ast::lang::graphs::{ArrayGraph, Edge, EdgeType, Node, NodeType};
 use console::style;
 use lsp::Language;
@@ -56,6 +57,8 @@ struct ChangedNodeSummary {
 struct ModifiedNodeSummary {
     before: ChangedNodeSummary,
     after: ChangedNodeSummary,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    field_changes: Vec<FieldChange>,
 }
 
 #[derive(Serialize)]
@@ -635,6 +638,7 @@ async fn run_diff(
                 .map(|(after, before)| ModifiedNodeSummary {
                     before: json_node_summary(before),
                     after: json_node_summary(after),
+                    field_changes: field_changes(before, after),
                 })
                 .collect(),
             added_edges: added_edges.iter().map(|e| json_edge_summary(e)).collect(),
@@ -722,6 +726,36 @@ fn total_changed_file_count(
         .len()
 }
 
+// added, removed and retyped fields of a modified DataModel
+fn field_changes(before: &Node, after: &Node) -> Vec<FieldChange> {
+    if after.node_type != NodeType::DataModel {
+        return Vec::new();
+    }
+    diff_fields(&before.node_data.fields(), &after.node_data.fields())
+}
+
+fn format_field_change(change: &FieldChange) -> String {
+    let typed = |name: &str, t: &Option<String>| match t {
+        Some(t) => format!("field {}: {}", name, t),
+        None => format!("field {}", name),
+    };
+    match change {
+        FieldChange::Added { name, type_name } => format!("+ {}", typed(name, type_name)),
+        FieldChange::Removed { name, type_name } => format!("- {}", typed(name, type_name)),
+        FieldChange::Retyped {
+            name,
+            before,
+            after,
+        } => format!(
+            "~ field {}: {} → {}",
+            name,
+            before.as_deref().unwrap_or("?"),
+            after.as_deref().unwrap_or("?")
+        ),
+        FieldChange::Changed { name } => format!("~ field {} (default or annotations)", name),
+    }
+}
+
 const MAX_SIG: usize = 100;
 fn node_signature(node: &Node) -> Option<String> {
     let raw = if let Some(iface) = node.node_data.meta.get("interface") {
@@ -1000,6 +1034,19 @@ fn print_delta(
                     style(&after_node.node_data.name).bold(),
                     line_range
                 ))?;
+                let changes = field_changes(before_node, after_node);
+                if !changes.is_empty() {
+                    for change in &changes {
+                        let line = format_field_change(change);
+                        let line = match change {
+                            FieldChange::Added { .. } => style(line).green(),
+                            FieldChange::Removed { .. } => style(line).red(),
+                            _ => style(line).yellow(),
+                        };
+                        out.writeln(format!("    {}", line))?;
+                    }
+                    continue;
+                }
                 let before_sig = node_signature(&before_node);
                 let after_sig = node_signature(&after_node);
                 match (before_sig, after_sig) {
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use ast::lang::graphs::EdgeType;
+use ast::lang::graphs::{merge_call_sites, CallResolution, CallSite, EdgeType};
 use console::style;
 use serde::Serialize;
 use shared::{Error, Result};
@@ -8,9 +8,10 @@ use shared::{Error, Result};
 use super::args::DepsArgs;
 use super::output::{write_json_success, JsonWarning, Output, OutputMode};
 use super::progress::CliSpinner;
+use super::render::format_call_sites;
 use super::utils::{
-    build_graph_for_files_with_options, expand_dirs_for_parse_with_globs, parse_node_types,
-    rel_path_from_cwd,
+    apply_min_confidence, build_graph_for_files_with_options, expand_dirs_for_parse_with_globs,
+    parse_node_types, rel_path_from_cwd,
 };
 
 #[derive(Serialize)]
@@ -29,6 +30,20 @@ struct DependencyEdge {
     target_file: String,
     depth: usize,
     verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strategy: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    call_sites: Vec<CallSite>,
+}
+
+/// A function called from a dependency-tree node, with every site it is called from.
+struct Callee {
+    name: String,
+    file: String,
+    resolution: Option<CallResolution>,
+    call_sites: Vec<CallSite>,
 }
 
 #[derive(Serialize)]
@@ -37,6 +52,8 @@ struct DependencyTreeData {
     files: Vec<String>,
     depth: usize,
     allow_unverified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_confidence: Option<f32>,
     seeds: Vec<DependencySeed>,
     edges: Vec<DependencyEdge>,
 }
@@ -75,7 +92,8 @@ pub async fn run(
         None
     };
 
-    let graph = build_graph_for_files_with_options(&files, args.allow).await?;
+    let mut graph = build_graph_for_files_with_options(&files, args.allow).await?;
+    apply_min_confidence(&mut graph, args.min_confidence);
 
     if let Some(sp) = &spinner {
         sp.finish_and_clear();
@@ -111,6 +129,7 @@ pub async fn run(
             files: files.clone(),
             depth: args.depth,
             allow_unverified: args.allow,
+            min_confidence: args.min_confidence,
             seeds: seeds
                 .iter()
                 .map(|seed| DependencySeed {
@@ -151,6 +170,7 @@ pub async fn run(
         struct StackItem {
             name: String,
             file: String,
+            call_sites: Vec<CallSite>,
             depth: usize,
             prefix: String,
             is_last: bool,
@@ -162,14 +182,15 @@ pub async fn run(
 
         // Push seed callees in reverse so the first callee is popped first.
         let callees = direct_callees(&graph, &args.name, file, args.allow);
-        for (i, (callee_name, callee_file)) in callees.iter().enumerate() {
+        for (i, callee) in callees.iter().enumerate() {
             let is_last = i == callees.len() - 1;
             // Insert into visited at enqueue time to prevent duplicates.
-            let key = (callee_name.clone(), callee_file.clone());
+            let key = (callee.name.clone(), callee.file.clone());
             if visited.insert(key) {
                 stack.push(StackItem {
-                    name: callee_name.clone(),
-                    file: callee_file.clone(),
+                    name: callee.name.clone(),
+                    file: callee.file.clone(),
+                    call_sites: callee.call_sites.clone(),
                     depth: 1,
                     prefix: String::new(),
                     is_last,
@@ -184,6 +205,11 @@ pub async fn run(
             } else {
                 "├── "
             };
+            let sites = if item.call_sites.is_empty() {
+                String::new()
+            } else {
+                format!("  {}", style(format_call_sites(&item.call_sites)).dim())
+            };
             let node_line = if item.file == "unverified" {
                 format!("{}{}{}", item.prefix, connector, style(&item.name).white())
             } else {
@@ -203,7 +229,7 @@ pub async fn run(
                     style(display_line).dim()
                 )
             };
-            out.writeln(node_line)?;
+            out.writeln(format!("{}{}", node_line, sites))?;
 
             let max_depth = args.depth;
             if (max_depth == 0 || item.depth < max_depth) && item.file != "unverified" {
@@ -217,13 +243,14 @@ pub async fn run(
                 let mut to_push: Vec<StackItem> = child_callees
                     .iter()
                     .enumerate()
-                    .filter_map(|(i, (cn, cf))| {
+                    .filter_map(|(i, callee)| {
                         let is_last = i == child_callees.len() - 1;
-                        let key = (cn.clone(), cf.clone());
+                        let key = (callee.name.clone(), callee.file.clone());
                         if visited.insert(key) {
                             Some(StackItem {
-                                name: cn.clone(),
-                                file: cf.clone(),
+                                name: callee.name.clone(),
+                                file: callee.file.clone(),
+                                call_sites: callee.call_sites.clone(),
                                 depth: item.depth + 1,
                                 prefix: child_prefix.clone(),
                                 is_last,
@@ -258,20 +285,21 @@ fn collect_dependency_edges(
     let seed_file = seed.node_data.file.clone();
     visited.insert((seed_name.clone(), seed_file.clone()));
 
-    for (callee_name, callee_file) in
-        direct_callees(graph, &seed_name, &seed_file, allow_unverified)
-    {
-        let key = (callee_name.clone(), callee_file.clone());
+    for callee in direct_callees(graph, &seed_name, &seed_file, allow_unverified) {
+        let key = (callee.name.clone(), callee.file.clone());
         if visited.insert(key) {
             result_edges.push(DependencyEdge {
                 source_name: seed_name.clone(),
                 source_file: seed_file.clone(),
-                target_name: callee_name.clone(),
-                target_file: callee_file.clone(),
+                target_name: callee.name.clone(),
+                target_file: callee.file.clone(),
                 depth: 1,
-                verified: callee_file != "unverified",
+                verified: callee.file != "unverified",
+                confidence: callee.resolution.as_ref().map(|r| r.confidence),
+                strategy: callee.resolution.map(|r| r.strategy),
+                call_sites: callee.call_sites,
             });
-            queue.push_back((callee_name, callee_file, 1));
+            queue.push_back((callee.name, callee.file, 1));
         }
     }
 
@@ -283,18 +311,21 @@ fn collect_dependency_edges(
             continue;
         }
 
-        for (callee_name, callee_file) in direct_callees(graph, &name, &file, allow_unverified) {
-            let key = (callee_name.clone(), callee_file.clone());
+        for callee in direct_callees(graph, &name, &file, allow_unverified) {
+            let key = (callee.name.clone(), callee.file.clone());
             if visited.insert(key) {
                 result_edges.push(DependencyEdge {
                     source_name: name.clone(),
                     source_file: file.clone(),
-                    target_name: callee_name.clone(),
-                    target_file: callee_file.clone(),
+                    target_name: callee.name.clone(),
+                    target_file: callee.file.clone(),
                     depth: depth + 1,
-                    verified: callee_file != "unverified",
+                    verified: callee.file != "unverified",
+                    confidence: callee.resolution.as_ref().map(|r| r.confidence),
+                    strategy: callee.resolution.map(|r| r.strategy),
+                    call_sites: callee.call_sites,
                 });
-                queue.push_back((callee_name, callee_file, depth + 1));
+                queue.push_back((callee.name, callee.file, depth + 1));
             }
         }
     }
@@ -305,9 +336,11 @@ fn direct_callees(
     name: &str,
     file: &str,
     allow_unverified: bool,
-) -> Vec<(String, String)> {
-    // Group all call targets by callee name, collecting all unique files
-    let mut by_name: HashMap<String, HashSet<String>> = HashMap::new();
+) -> Vec<Callee> {
+    // Group all call targets by callee name, collecting all unique files with the
+    // most confident resolution and every call site seen for each
+    let mut by_name: HashMap<String, HashMap<String, (Option<CallResolution>, Vec<CallSite>)>> =
+        HashMap::new();
     for e in graph.edges.iter().filter(|e| {
         e.edge == EdgeType::Calls
             && e.source.node_data.name == name
@@ -317,26 +350,38 @@ fn direct_callees(
         if !allow_unverified && callee_file == "unverified" {
             continue;
         }
-        by_name
+        let (best, sites) = by_name
             .entry(e.target.node_data.name.clone())
             .or_default()
-            .insert(callee_file);
+            .entry(callee_file)
+            .or_default();
+        if e.resolution > *best {
+            *best = e.resolution.clone();
+        }
+        merge_call_sites(sites, &e.call_sites);
     }
     // For each callee name: if any verified resolution exists, emit only the verified ones;
     // otherwise emit a single unverified entry.
     let mut result = Vec::new();
-    for (callee_name, files) in by_name {
-        let verified: Vec<_> = files
-            .iter()
-            .filter(|f| *f != "unverified")
-            .cloned()
-            .collect();
-        if !verified.is_empty() {
-            for f in verified {
-                result.push((callee_name.clone(), f));
+    for (callee_name, mut files) in by_name {
+        let unverified = files.remove("unverified");
+        if !files.is_empty() {
+            for (f, (resolution, call_sites)) in files {
+                result.push(Callee {
+                    name: callee_name.clone(),
+                    file: f,
+                    resolution,
+                    call_sites,
+                });
             }
         } else if allow_unverified {
-            result.push((callee_name, "unverified".to_string()));
+            let (resolution, call_sites) = unverified.unwrap_or_default();
+            result.push(Callee {
+                name: callee_name,
+                file: "unverified".to_string(),
+                resolution,
+                call_sites,
+            });
         }
     }
     result
@@ -1,9 +1,12 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::path::Path;
 
-use ast::lang::graphs::{EdgeType, NodeType};
+use ast::infra::ci;
+use ast::lang::errors::EscapingErrors;
+use ast::lang::graphs::{ArrayGraph, CallResolution, EdgeType, Graph, NodeType};
+use ast::lang::NodeData;
 use console::style;
-use lsp::Language;
+use lsp::{strip_tmp, Language};
 use serde::Serialize;
 use shared::{Error, Result};
 
@@ -12,7 +15,7 @@ use super::git::{get_changed_files, get_repo_root, get_staged_changes, get_worki
 use super::output::{write_json_success, JsonWarning, Output, OutputMode};
 use super::progress::CliSpinner;
 use super::utils::{
-    apply_glob_filters, build_graph_for_files_with_options, expand_dirs_for_parse_with_globs,
+    apply_glob_filters, apply_min_confidence, build_graph_for_files_with_options, expand_dirs_for_parse_with_globs,
     parse_node_types, path_suffix_matches,
     rel_path_from_cwd,
 };
@@ -24,6 +27,10 @@ const REVERSE_EDGE_TYPES: &[EdgeType] = &[
     EdgeType::Renders,
     EdgeType::Imports,
     EdgeType::Operand,
+    EdgeType::Overrides,
+    EdgeType::Reads,
+    EdgeType::Throws,
+    EdgeType::Catches,
 ];
 
 #[derive(Serialize)]
@@ -38,6 +45,10 @@ struct ImpactSeed {
 struct ChainHop {
     edge: String,
     via: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strategy: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -48,6 +59,17 @@ struct AffectedNode {
     line: usize,
     depth: usize,
     edge_chain: Vec<ChainHop>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    unhandled_errors: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct CiJobHit {
+    workflow: String,
+    job: String,
+    file: String,
+    line: usize,
+    step: String,
 }
 
 #[derive(Serialize)]
@@ -65,6 +87,8 @@ struct ImpactData {
     depth: usize,
     summary: ImpactSummary,
     affected: Vec<AffectedNode>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ci_jobs: Vec<CiJobHit>,
 }
 
 pub async fn run(
@@ -93,7 +117,7 @@ pub async fn run(
         })
         .transpose()?;
 
-    let (files, git_changed_files, mode_description) = if git_mode {
+    let (files, git_changed_files, mode_description, repo_root) = if git_mode {
         let cwd = std::env::current_dir()
             .map_err(|e| Error::internal(format!("Failed to get current directory: {}", e)))?;
         let cwd_str = cwd.to_string_lossy().to_string();
@@ -129,11 +153,18 @@ pub async fn run(
             .collect();
 
         if abs_files.is_empty() {
+            // scripts and CI config are not parsed but can still be run by CI jobs
+            let touched: Vec<String> = changed
+                .iter()
+                .map(|f| Path::new(&repo_root).join(f).to_string_lossy().to_string())
+                .collect();
+            let ci_jobs = affected_ci_jobs(Some(repo_root.as_str()), touched);
             if !output_mode.is_json() {
                 out.writeln(format!(
                     "{}",
                     style(format!("No parseable files changed in {}", mode_desc)).yellow()
                 ))?;
+                print_ci_jobs(out, &ci_jobs)?;
             }
             if output_mode.is_json() {
                 let data = ImpactData {
@@ -146,6 +177,7 @@ pub async fn run(
                         by_type: HashMap::new(),
                     },
                     affected: Vec::new(),
+                    ci_jobs,
                 };
                 write_json_success(out, "impact", data, vec![
                     JsonWarning::new("no_changes", "No parseable files changed"),
@@ -178,7 +210,7 @@ pub async fn run(
             filtered
         };
 
-        (parse_scope, Some(changed_rel), Some(mode_desc))
+        (parse_scope, Some(changed_rel), Some(mode_desc), Some(repo_root))
     } else {
         let files = expand_dirs_for_parse_with_globs(&args.files, &args.include, &args.exclude)?;
         if files.is_empty() {
@@ -186,7 +218,10 @@ pub async fn run(
                 "no parseable files found in the given paths",
             ));
         }
-        (files, None, None)
+        let repo_root = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| get_repo_root(&cwd.to_string_lossy()).ok());
+        (files, None, None, repo_root)
     };
 
     let spinner = if show_progress {
@@ -198,7 +233,8 @@ pub async fn run(
         None
     };
 
-    let graph = build_graph_for_files_with_options(&files, args.allow).await?;
+    let mut graph = build_graph_for_files_with_options(&files, args.allow).await?;
+    apply_min_confidence(&mut graph, args.min_confidence);
 
     if let Some(sp) = &spinner {
         sp.finish_and_clear();
@@ -207,6 +243,9 @@ pub async fn run(
     let seed_node_types: HashSet<NodeType> = [
         NodeType::Function,
         NodeType::Endpoint,
+        NodeType::Command,
+        NodeType::Job,
+        NodeType::ConfigKey,
         NodeType::Class,
         NodeType::Trait,
         NodeType::DataModel,
@@ -283,6 +322,7 @@ pub async fn run(
         );
     }
 
+    fill_unhandled_errors(&graph, &mut all_affected);
     all_affected.sort_by(|a, b| a.node_type.cmp(&b.node_type).then(a.name.cmp(&b.name)));
 
     let mut by_type: HashMap<String, usize> = HashMap::new();
@@ -290,6 +330,20 @@ pub async fn run(
         *by_type.entry(a.node_type.clone()).or_default() += 1;
     }
 
+    let mut touched: Vec<String> = seeds
+        .iter()
+        .map(|s| s.node_data.file.clone())
+        .chain(all_affected.iter().map(|a| a.file.clone()))
+        .collect();
+    if let (Some(root), Some(changed)) = (&repo_root, &git_changed_files) {
+        touched.extend(
+            changed
+                .iter()
+                .map(|f| Path::new(root).join(f).to_string_lossy().to_string()),
+        );
+    }
+    let ci_jobs = affected_ci_jobs(repo_root.as_deref(), touched);
+
     if output_mode.is_json() {
         let data = ImpactData {
             mode: mode_description.clone(),
@@ -309,6 +363,7 @@ pub async fn run(
                 by_type: by_type.clone(),
             },
             affected: all_affected,
+            ci_jobs,
         };
         let warnings = if data.affected.is_empty() {
             vec![JsonWarning::new(
@@ -351,12 +406,15 @@ pub async fn run(
             "  {}",
             style("No upstream dependents found.").dim()
         ))?;
+        print_ci_jobs(out, &ci_jobs)?;
         return Ok(());
     }
 
     let summary_parts: Vec<String> = {
         let order = [
             "Endpoint",
+            "Command",
+            "Job",
             "UnitTest",
             "IntegrationTest",
             "E2eTest",
@@ -393,6 +451,8 @@ pub async fn run(
     let grouped = group_by_type(&all_affected);
     let display_order = [
         "Endpoint",
+        "Command",
+        "Job",
         "UnitTest",
         "IntegrationTest",
         "E2eTest",
@@ -411,7 +471,54 @@ pub async fn run(
             print_group(out, type_name, nodes)?;
         }
     }
+    print_ci_jobs(out, &ci_jobs)?;
+
+    Ok(())
+}
+
+/// CI jobs (GitHub Actions, GitLab CI) whose steps run any of the touched files,
+/// directly or through a Makefile target.
+fn affected_ci_jobs(repo_root: Option<&str>, touched: Vec<String>) -> Vec<CiJobHit> {
+    let Some(root) = repo_root else {
+        return Vec::new();
+    };
+    let workflows = ci::collect_workflows(Path::new(root));
+    if workflows.is_empty() {
+        return Vec::new();
+    }
+    let touched: Vec<String> = touched
+        .iter()
+        .map(|f| strip_tmp(Path::new(f)).display().to_string())
+        .collect();
+    ci::affected_jobs(&workflows, &touched)
+        .into_iter()
+        .map(|hit| CiJobHit {
+            workflow: hit.workflow.name.clone(),
+            job: hit.job.name.clone(),
+            file: hit.workflow.file.clone(),
+            line: hit.job.start + 1,
+            step: hit.step.name.clone(),
+        })
+        .collect()
+}
 
+fn print_ci_jobs(out: &mut Output, jobs: &[CiJobHit]) -> Result<()> {
+    if jobs.is_empty() {
+        return Ok(());
+    }
+    out.writeln(style("  CI jobs:").bold().to_string())?;
+    for job in jobs {
+        out.writeln(format!(
+            "    {} / {}  [{}:{}]  {} {}",
+            style(&job.workflow).white(),
+            style(&job.job).white().bold(),
+            style(rel_path_from_cwd(&job.file)).dim(),
+            style(job.line).dim(),
+            style("←").dim(),
+            style(&job.step).dim()
+        ))?;
+    }
+    out.newline()?;
     Ok(())
 }
 
@@ -423,13 +530,16 @@ fn style_edge(edge: &str) -> String {
         "RENDERS" => style(edge).green().to_string(),
         "IMPORTS" => style(edge).magenta().to_string(),
         "OPERAND" => style(edge).blue().to_string(),
+        "OVERRIDES" => style(edge).blue().dim().to_string(),
+        "THROWS" => style(edge).red().to_string(),
+        "CATCHES" => style(edge).green().dim().to_string(),
         _ => style(edge).dim().to_string(),
     }
 }
 
 fn print_group(out: &mut Output, type_name: &str, nodes: &[&AffectedNode]) -> Result<()> {
     let type_style = match type_name {
-        "Endpoint" => style(format!("  {}:", type_name)).yellow().bold(),
+        "Endpoint" | "Command" | "Job" => style(format!("  {}:", type_name)).yellow().bold(),
         "Function" => style(format!("  {}:", type_name)).green().bold(),
         "Class" => style(format!("  {}:", type_name)).blue().bold(),
         "DataModel" => style(format!("  {}:", type_name)).magenta().bold(),
@@ -466,6 +576,13 @@ fn print_group(out: &mut Output, type_name: &str, nodes: &[&AffectedNode]) -> Re
             style(node.line).dim(),
             chain,
         ))?;
+        if !node.unhandled_errors.is_empty() {
+            out.writeln(format!(
+                "      {} {}",
+                style("unhandled:").red(),
+                style(node.unhandled_errors.join(", ")).red()
+            ))?;
+        }
     }
     out.newline()?;
     Ok(())
@@ -479,8 +596,32 @@ fn group_by_type<'a>(affected: &'a [AffectedNode]) -> HashMap<String, Vec<&'a Af
     groups
 }
 
+// errors escaping an endpoint's handler surface to the client as server errors
+fn fill_unhandled_errors(graph: &ArrayGraph, affected: &mut [AffectedNode]) {
+    let endpoint_type = NodeType::Endpoint.to_string();
+    if !affected.iter().any(|a| a.node_type == endpoint_type) {
+        return;
+    }
+    let escaping = EscapingErrors::new(graph);
+    let mut by_endpoint: HashMap<(String, String, usize), BTreeSet<String>> = HashMap::new();
+    for (endpoint, handler) in
+        graph.find_nodes_with_edge_type(NodeType::Endpoint, NodeType::Function, EdgeType::Handler)
+    {
+        by_endpoint
+            .entry((endpoint.name, endpoint.file, endpoint.start + 1))
+            .or_default()
+            .extend(escaping.of(&handler));
+    }
+    for node in affected.iter_mut().filter(|a| a.node_type == endpoint_type) {
+        let key = (node.name.clone(), node.file.clone(), node.line);
+        if let Some(errors) = by_endpoint.get(&key) {
+            node.unhandled_errors = errors.iter().cloned().collect();
+        }
+    }
+}
+
 fn collect_impact(
-    graph: &ast::lang::graphs::ArrayGraph,
+    graph: &ArrayGraph,
     seed_name: &str,
     seed_file: &str,
     max_depth: usize,
@@ -491,13 +632,15 @@ fn collect_impact(
         name: String,
         file: String,
         depth: usize,
-        // each hop: (edge_type_str, via_node_name) — path from this node back to seed
-        edge_chain: Vec<(String, String)>,
+        // each hop: (edge_type_str, via_node_name, resolution) — path from this node back to seed
+        edge_chain: Vec<(String, String, Option<CallResolution>)>,
     }
 
     let mut queue: VecDeque<BfsItem> = VecDeque::new();
 
-    for (source_ref, edge_type) in graph.find_dependents(seed_name, seed_file, REVERSE_EDGE_TYPES) {
+    for (source_ref, edge_type, resolution) in
+        graph.find_dependents(seed_name, seed_file, REVERSE_EDGE_TYPES)
+    {
         let key = (
             source_ref.node_data.name.clone(),
             source_ref.node_data.file.clone(),
@@ -510,7 +653,7 @@ fn collect_impact(
             name: source_ref.node_data.name.clone(),
             file: source_ref.node_data.file.clone(),
             depth: 1,
-            edge_chain: vec![(format!("{}", edge_type), seed_name.to_string())],
+            edge_chain: vec![(format!("{}", edge_type), seed_name.to_string(), resolution)],
         });
     }
 
@@ -534,15 +677,18 @@ fn collect_impact(
             edge_chain: item
                 .edge_chain
                 .iter()
-                .map(|(e, v)| ChainHop {
+                .map(|(e, v, resolution)| ChainHop {
                     edge: e.clone(),
                     via: v.clone(),
+                    confidence: resolution.as_ref().map(|r| r.confidence),
+                    strategy: resolution.as_ref().map(|r| r.strategy.clone()),
                 })
                 .collect(),
+            unhandled_errors: Vec::new(),
         });
 
         if max_depth == 0 || item.depth < max_depth {
-            for (source_ref, edge_type) in
+            for (source_ref, edge_type, resolution) in
                 graph.find_dependents(&item.name, &item.file, REVERSE_EDGE_TYPES)
             {
                 let key = (
@@ -554,7 +700,7 @@ fn collect_impact(
                 }
                 visited.insert(key);
                 let mut chain = item.edge_chain.clone();
-                chain.push((format!("{}", edge_type), item.name.clone()));
+                chain.push((format!("{}", edge_type), item.name.clone(), resolution));
                 queue.push_back(BfsItem {
                     name: source_ref.node_data.name.clone(),
                     file: source_ref.node_data.file.clone(),
@@ -7,6 +7,9 @@ use lsp::Language;
 use serde::Serialize;
 use shared::{Error, Result};
 
+use ast::infra::ci::{self, WorkflowSpec};
+use ast::lang::commands::collect_commands;
+use ast::lang::jobs::{collect_jobs, JobDef};
 use ast::lang::queries::skips::summary::{
     is_junk_file, is_test_file, should_skip_dir, ALWAYS_EXPAND_DIRS, COLLAPSE_DIRS,
     ENTRY_POINT_NAMES, PRIORITY_ROOT_FILES, PRIORITY_SOURCE_DIRS,
@@ -38,10 +41,46 @@ struct OverviewJsonData {
     root: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ci: Vec<OverviewCiWorkflow>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    commands: Vec<OverviewCommand>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    jobs: Vec<OverviewJob>,
     tree: String,
     stats: OverviewJsonStats,
 }
 
+#[derive(Default, Serialize)]
+struct OverviewCiWorkflow {
+    name: String,
+    file: String,
+    platform: String,
+    triggers: Vec<String>,
+    jobs: Vec<OverviewCiJob>,
+}
+
+#[derive(Default, Serialize)]
+struct OverviewCiJob {
+    name: String,
+    runs: Vec<String>,
+}
+
+#[derive(Default, Serialize)]
+struct OverviewCommand {
+    name: String,
+    file: String,
+    framework: String,
+}
+
+#[derive(Default, Serialize)]
+struct OverviewJob {
+    name: String,
+    file: String,
+    schedule: String,
+    scheduler: String,
+}
+
 #[derive(Default, Serialize)]
 struct OverviewJsonStats {
     total_files: usize,
@@ -773,10 +812,15 @@ pub fn run(args: &OverviewArgs, out: &mut Output, output_mode: OutputMode) -> Re
         }
     };
 
-    let fingerprint = if zoom_target.is_none() && args.grep.is_none() {
-        detect_fingerprint(&scan_root)
+    let (fingerprint, workflows, commands, jobs) = if zoom_target.is_none() && args.grep.is_none() {
+        (
+            detect_fingerprint(&scan_root),
+            ci::collect_workflows(&scan_root),
+            collect_commands(&scan_root),
+            collect_jobs(&scan_root),
+        )
     } else {
-        None
+        (None, Vec::new(), Vec::new(), Vec::new())
     };
 
     if output_mode.is_json() {
@@ -784,6 +828,40 @@ pub fn run(args: &OverviewArgs, out: &mut Output, output_mode: OutputMode) -> Re
             mode: "overview".to_string(),
             root: display_path,
             fingerprint: fingerprint.clone(),
+            ci: workflows
+                .iter()
+                .map(|wf| OverviewCiWorkflow {
+                    name: wf.name.clone(),
+                    file: rel_to(&scan_root, &wf.file),
+                    platform: wf.platform.clone(),
+                    triggers: wf.triggers.clone(),
+                    jobs: wf
+                        .jobs
+                        .iter()
+                        .map(|j| OverviewCiJob {
+                            name: j.name.clone(),
+                            runs: job_commands(j),
+                        })
+                        .collect(),
+                })
+                .collect(),
+            commands: commands
+                .iter()
+                .map(|(file, name, framework)| OverviewCommand {
+                    name: name.clone(),
+                    file: rel_to(&scan_root, file),
+                    framework: framework.to_string(),
+                })
+                .collect(),
+            jobs: jobs
+                .iter()
+                .map(|(file, job)| OverviewJob {
+                    name: job.label(),
+                    file: rel_to(&scan_root, file),
+                    schedule: job.schedule.clone(),
+                    scheduler: job.scheduler.to_string(),
+                })
+                .collect(),
             tree: tree_str,
             stats: OverviewJsonStats {
                 total_files,
@@ -803,6 +881,27 @@ pub fn run(args: &OverviewArgs, out: &mut Output, output_mode: OutputMode) -> Re
         out.newline()?;
     }
 
+    if !workflows.is_empty() {
+        for line in format_ci_summary(&scan_root, &workflows) {
+            out.writeln(line)?;
+        }
+        out.newline()?;
+    }
+
+    if !commands.is_empty() {
+        for line in format_command_summary(&commands) {
+            out.writeln(line)?;
+        }
+        out.newline()?;
+    }
+
+    if !jobs.is_empty() {
+        for line in format_job_summary(&jobs) {
+            out.writeln(line)?;
+        }
+        out.newline()?;
+    }
+
     out.writeln(&tree_str)?;
     if state.hidden_files > 0 || state.collapsed_dirs > 0 {
         out.newline()?;
@@ -818,6 +917,128 @@ pub fn run(args: &OverviewArgs, out: &mut Output, output_mode: OutputMode) -> Re
     Ok(())
 }
 
+/// How the repo is built and tested: one line per CI workflow or Makefile,
+/// followed by the commands each CI job runs.
+fn format_ci_summary(root: &Path, workflows: &[WorkflowSpec]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for wf in workflows {
+        let file = rel_to(root, &wf.file);
+        if wf.platform == "make" {
+            let targets: Vec<&str> = wf.jobs.iter().map(|j| j.name.as_str()).collect();
+            lines.push(format!(
+                "{} {}",
+                style(format!("{} targets:", file)).cyan().bold(),
+                targets.join(", ")
+            ));
+            continue;
+        }
+        let on = if wf.triggers.is_empty() {
+            String::new()
+        } else {
+            format!(" on {}", wf.triggers.join(", "))
+        };
+        lines.push(format!(
+            "{} {}{}",
+            style(format!("CI {}:", wf.name)).cyan().bold(),
+            style(&file).dim(),
+            on
+        ));
+        for job in &wf.jobs {
+            let runs = job_commands(job);
+            let shown: Vec<&str> = runs.iter().take(3).map(|r| r.as_str()).collect();
+            let more = if runs.len() > shown.len() {
+                format!(" (+{} more)", runs.len() - shown.len())
+            } else {
+                String::new()
+            };
+            lines.push(format!(
+                "  {} {}{}",
+                style(format!("{}:", job.name)).bold(),
+                shown.join("; "),
+                more
+            ));
+        }
+    }
+    lines
+}
+
+/// The CLI surface: one line per framework listing its command paths.
+fn format_command_summary(commands: &[(String, String, &'static str)]) -> Vec<String> {
+    let mut by_framework: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (_, name, framework) in commands {
+        by_framework.entry(*framework).or_default().push(name.as_str());
+    }
+    by_framework
+        .into_iter()
+        .map(|(framework, mut names)| {
+            names.sort();
+            names.dedup();
+            let more = if names.len() > 12 {
+                format!(" (+{} more)", names.len() - 12)
+            } else {
+                String::new()
+            };
+            names.truncate(12);
+            format!(
+                "{} {}{}",
+                style(format!("CLI commands ({}):", framework)).cyan().bold(),
+                names.join(", "),
+                more
+            )
+        })
+        .collect()
+}
+
+/// Scheduled entry points: one line per scheduler listing its jobs and their schedules.
+fn format_job_summary(jobs: &[(String, JobDef)]) -> Vec<String> {
+    let mut by_scheduler: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for (_, job) in jobs {
+        by_scheduler.entry(job.scheduler).or_default().push(format!(
+            "{} ({})",
+            job.label(),
+            job.schedule
+        ));
+    }
+    by_scheduler
+        .into_iter()
+        .map(|(scheduler, mut entries)| {
+            entries.sort();
+            entries.dedup();
+            let more = if entries.len() > 12 {
+                format!(" (+{} more)", entries.len() - 12)
+            } else {
+                String::new()
+            };
+            entries.truncate(12);
+            format!(
+                "{} {}{}",
+                style(format!("Scheduled jobs ({}):", scheduler))
+                    .cyan()
+                    .bold(),
+                entries.join(", "),
+                more
+            )
+        })
+        .collect()
+}
+
+/// First line of each `run` command, skipping steps that only `uses` an action.
+fn job_commands(job: &ci::JobSpec) -> Vec<String> {
+    job.steps
+        .iter()
+        .filter_map(|s| s.run.as_deref())
+        .filter_map(|r| r.lines().map(str::trim).find(|l| !l.is_empty()))
+        .map(|l| l.to_string())
+        .collect()
+}
+
+fn rel_to(root: &Path, file: &str) -> String {
+    Path::new(file)
+        .strip_prefix(root)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| file.to_string())
+}
+
 fn has_manifest(path: &Path) -> bool {
     MANIFEST_FILES
         .iter()
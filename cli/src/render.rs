@@ -6,6 +6,7 @@ use console::{style, Style};
 use serde::Serialize;
 use shared::Result;
 
+use ast::lang::graphs::CallSite;
 use ast::lang::graphs::EdgeType;
 use ast::lang::graphs::NodeType;
 use ast::lang::ArrayGraph;
@@ -98,6 +99,17 @@ pub struct CallRef {
     pub line: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub call_sites: Vec<CallSite>,
+}
+
+/// One-based `L12:5, L30:9` list of where a call is made, for text output.
+pub fn format_call_sites(sites: &[CallSite]) -> String {
+    sites
+        .iter()
+        .map(|site| format!("L{}:{}", site.line + 1, site.column + 1))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 pub fn resolve_call_ref(edge: &Edge, source_file: &str, graph: &ArrayGraph) -> CallRef {
@@ -140,6 +152,7 @@ pub fn resolve_call_ref(edge: &Edge, source_file: &str, graph: &ArrayGraph) -> C
         name,
         line: edge.target.node_data.start + 1,
         file,
+        call_sites: edge.call_sites.clone(),
     }
 }
 
@@ -199,10 +212,16 @@ fn print_function_edges(
             let arrow = style("→").dim();
             let line_num = style(format!("L{}", cr.line)).dim();
             let file_info_styled = style(file_info).dim();
+            let sites = if cr.call_sites.is_empty() {
+                String::new()
+            } else {
+                format!(" at {}", format_call_sites(&cr.call_sites))
+            };
+            let sites_styled = style(sites).dim();
 
             out.writeln(format!(
-                "  {} {} ({}){}",
-                arrow, cr.name, line_num, file_info_styled
+                "  {} {} ({}){}{}",
+                arrow, cr.name, line_num, file_info_styled, sites_styled
             ))?;
         }
     }
@@ -1,6 +1,8 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
+use ast::lang::asg::NodeKeys;
 use ast::lang::graphs::{EdgeType, NodeType};
+use ast::lang::modules::qualified_names;
 use ast::lang::ArrayGraph;
 use ast::lang::Node;
 use console::style;
@@ -12,8 +14,8 @@ use super::output::{write_json_success, JsonWarning, Output, OutputMode};
 use super::progress::CliSpinner;
 use super::render::{node_display_name, style_for_node_type};
 use super::utils::{
-    build_graph_for_files_with_options, expand_dirs_for_parse_with_globs, parse_node_types,
-    rel_path_from_cwd,
+    apply_min_confidence, build_graph_for_files_with_options, expand_dirs_for_parse_with_globs,
+    parse_node_types, rel_path_from_cwd,
 };
 
 const SEARCHABLE_TYPES: &[NodeType] = &[
@@ -30,6 +32,7 @@ const SEARCHABLE_TYPES: &[NodeType] = &[
     NodeType::E2eTest,
     NodeType::Page,
     NodeType::Concept,
+    NodeType::Module,
 ];
 
 const TEST_TYPES: &[NodeType] = &[
@@ -52,6 +55,10 @@ struct ContextRef {
     node_type: String,
     file: String,
     line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strategy: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -71,6 +78,8 @@ struct SearchResultNode {
     lines: String,
     score: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
+    fqn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     body: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     tests: Vec<RelatedRef>,
@@ -90,12 +99,14 @@ struct SearchData {
 
 // Score a node against the query terms.
 // Returns 0 if no term matches at all (node should be excluded).
-fn score_node(node: &Node, terms: &[&str], search_body: bool) -> u32 {
+// Qualified terms (`graphs::Graph`, `app.models.User`) are matched against the node's FQN.
+fn score_node(node: &Node, fqn: Option<&str>, terms: &[&str], search_body: bool) -> u32 {
     if terms.is_empty() {
         return 1;
     }
 
     let name_lower = node.node_data.name.to_lowercase();
+    let fqn_lower = fqn.map(str::to_lowercase);
     let file_lower = node.node_data.file.to_lowercase();
     let docs_lower = node.node_data.docs.as_deref().unwrap_or("").to_lowercase();
     let meta_values: Vec<String> = node
@@ -115,6 +126,13 @@ fn score_node(node: &Node, terms: &[&str], search_body: bool) -> u32 {
         } else if name_lower.contains(t.as_str()) {
             score += 50;
         }
+        if let Some(fqn) = fqn_lower.as_deref().filter(|_| is_qualified(&t)) {
+            if fqn == t {
+                score += 100;
+            } else if fqn.ends_with(t.as_str()) {
+                score += 70;
+            }
+        }
         if docs_lower.contains(t.as_str()) {
             score += 25;
         }
@@ -132,6 +150,19 @@ fn score_node(node: &Node, terms: &[&str], search_body: bool) -> u32 {
     score
 }
 
+fn is_qualified(term: &str) -> bool {
+    term.contains("::") || term.contains('.') || term.contains('/')
+}
+
+/// Fully-qualified name of a node: its own name for a Module, its module path otherwise.
+fn node_fqn<'a>(node: &'a Node, fqns: &'a HashMap<NodeKeys, String>) -> Option<&'a str> {
+    if node.node_type == NodeType::Module {
+        return Some(node.node_data.name.as_str());
+    }
+    fqns.get(&NodeKeys::from(&node.node_data))
+        .map(String::as_str)
+}
+
 fn collect_callers(graph: &ArrayGraph, node: &Node) -> Vec<ContextRef> {
     let target_name = &node.node_data.name;
     let target_file = &node.node_data.file;
@@ -169,6 +200,8 @@ fn collect_callers(graph: &ArrayGraph, node: &Node) -> Vec<ContextRef> {
             node_type,
             file: rel_path_from_cwd(&edge.source.node_data.file),
             line,
+            confidence: edge.confidence(),
+            strategy: edge.resolution.as_ref().map(|r| r.strategy.clone()),
         });
     }
 
@@ -224,6 +257,8 @@ fn collect_callees(graph: &ArrayGraph, node: &Node) -> Vec<ContextRef> {
             node_type,
             file,
             line,
+            confidence: edge.confidence(),
+            strategy: edge.resolution.as_ref().map(|r| r.strategy.clone()),
         });
     }
 
@@ -314,13 +349,15 @@ pub async fn run(
         None
     };
 
-    let graph = build_graph_for_files_with_options(&files, true).await?;
+    let mut graph = build_graph_for_files_with_options(&files, true).await?;
+    apply_min_confidence(&mut graph, args.min_confidence);
 
     if let Some(sp) = &spinner {
         sp.finish_and_clear();
     }
 
     let terms: Vec<&str> = args.query.split_whitespace().collect();
+    let fqns = qualified_names(&graph);
 
     let mut scored: Vec<(&Node, u32)> = graph
         .nodes
@@ -342,7 +379,7 @@ pub async fn run(
             true
         })
         .filter_map(|n| {
-            let s = score_node(n, &terms, args.body);
+            let s = score_node(n, node_fqn(n, &fqns), &terms, args.body);
             if s > 0 {
                 Some((n, s))
             } else {
@@ -366,6 +403,7 @@ pub async fn run(
                     file: rel_path_from_cwd(&nd.file),
                     lines: format_lines(nd.start, nd.end),
                     score: *score,
+                    fqn: node_fqn(node, &fqns).map(str::to_string),
                     body: args.code.then(|| nd.body.clone()),
                     callers: if args.context {
                         collect_callers(&graph, node)
@@ -444,12 +482,17 @@ pub async fn run(
         let lines = format_lines(nd.start, nd.end);
         let rel_file = rel_path_from_cwd(&nd.file);
 
-        // Summary line: Type: name  [file:lines]
+        // Summary line: Type: name  fqn  [file:lines]
         let type_styled = style_for_node_type(&node.node_type).apply_to(&node.node_type);
+        let fqn = node_fqn(node, &fqns)
+            .filter(|f| *f != display_name)
+            .map(|f| format!("  {}", style(f).dim()))
+            .unwrap_or_default();
         out.writeln(format!(
-            "{}: {}  [{}:{}]",
+            "{}: {}{}  [{}:{}]",
             type_styled,
             style(&display_name).bold().white(),
+            fqn,
             style(&rel_file).dim(),
             style(&lines).dim(),
         ))?;
@@ -25,6 +25,17 @@ const NODE_TYPES: &[&str] = &[
     "IntegrationTest",
     "E2eTest",
     "Mock",
+    "Resource",
+    "Service",
+    "Workflow",
+    "WorkflowJob",
+    "WorkflowStep",
+    "Queue",
+    "Job",
+    "Command",
+    "Channel",
+    "ConfigKey",
+    "Module",
 ];
 
 const EDGE_TYPES: &[&str] = &[
@@ -41,6 +52,17 @@ const EDGE_TYPES: &[&str] = &[
     "ParentOf",
     "Implements",
     "NestedIn",
+    "DependsOn",
+    "ConnectsTo",
+    "Runs",
+    "Publishes",
+    "Consumes",
+    "Emits",
+    "Reads",
+    "Writes",
+    "Overrides",
+    "Throws",
+    "Catches",
 ];
 
 #[derive(Serialize)]
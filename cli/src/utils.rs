@@ -171,6 +171,17 @@ pub fn parse_node_types(raw: &[String]) -> Result<Vec<NodeType>> {
             "integrationtest" => "IntegrationTest",
             "e2etest" => "E2etest",
             "mock" => "Mock",
+            "resource" => "Resource",
+            "service" => "Service",
+            "workflow" => "Workflow",
+            "workflowjob" => "WorkflowJob",
+            "workflowstep" => "WorkflowStep",
+            "queue" => "Queue",
+            "job" => "Job",
+            "command" => "Command",
+            "channel" => "Channel",
+            "configkey" => "ConfigKey",
+            "module" => "Module",
             other => {
                 return Err(Error::validation(format!("Unknown node type: '{}'", other)));
             }
@@ -278,6 +289,15 @@ pub async fn build_graph_for_files_with_options(
     repos.build_graphs_array().await
 }
 
+/// Drop resolved calls below `--min-confidence` so traversals only follow calls the
+/// resolver was at least that sure about. Calls with no resolution never went through a
+/// resolver and are kept. The range is validated by clap.
+pub fn apply_min_confidence(graph: &mut ArrayGraph, min_confidence: Option<f32>) {
+    if let Some(min_confidence) = min_confidence {
+        graph.retain_calls_with_min_confidence(min_confidence);
+    }
+}
+
 /// Returns true when `full` equals `suffix` or `full` ends with `/<suffix>`.
 /// Avoids false positives like "src/lib.rs" matching "othersrc/lib.rs".
 pub fn path_suffix_matches(full: &str, suffix: &str) -> bool {
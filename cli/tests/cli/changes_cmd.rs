@@ -243,3 +243,86 @@ fn changes_diff_json_outputs_machine_readable_payload() {
     assert!(payload["data"]["summary"].is_object());
     assert!(payload["data"]["files"].is_array());
 }
+
+fn init_git_repo_with_model_change() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().expect("tempdir failed");
+    let root = dir.path();
+
+    run_cmd(root, &["git", "init"]);
+    run_cmd(root, &["git", "config", "user.email", "test@example.com"]);
+    run_cmd(root, &["git", "config", "user.name", "Test User"]);
+
+    write_file(
+        root,
+        "src/lib.rs",
+        "pub struct User {\n    pub id: u64,\n    pub email: String,\n    pub age: u32,\n}\n",
+    );
+    run_cmd(root, &["git", "add", "."]);
+    run_cmd(root, &["git", "commit", "-m", "initial"]);
+
+    write_file(
+        root,
+        "src/lib.rs",
+        "pub struct User {\n    pub id: u64,\n    pub email: Option<String>,\n    pub name: String,\n}\n",
+    );
+
+    dir
+}
+
+#[test]
+fn changes_diff_reports_field_changes() {
+    let repo = init_git_repo_with_model_change();
+    let cwd = repo.path().to_string_lossy().to_string();
+    let out = run_stakgraph_in_cwd(&cwd, &["changes", "diff"]);
+
+    assert_eq!(out.exit_code, 0, "stderr: {}", out.stderr);
+    for line in [
+        "+ field name: String",
+        "- field age: u32",
+        "~ field email: String → Option<String>",
+    ] {
+        assert!(
+            out.stdout.contains(line),
+            "expected {:?}; stdout: {}\nstderr: {}",
+            line,
+            out.stdout,
+            out.stderr
+        );
+    }
+    assert!(!out.stdout.contains("field id"), "stdout: {}", out.stdout);
+}
+
+#[test]
+fn changes_diff_json_includes_field_changes() {
+    let repo = init_git_repo_with_model_change();
+    let cwd = repo.path().to_string_lossy().to_string();
+    let out = run_stakgraph_in_cwd(&cwd, &["--json", "changes", "diff"]);
+
+    assert_eq!(out.exit_code, 0, "stderr: {}", out.stderr);
+    let payload: Value = serde_json::from_str(&out.stdout).expect("valid json stdout");
+    let modified = payload["data"]["modified_nodes"]
+        .as_array()
+        .expect("modified_nodes array");
+    let user = modified
+        .iter()
+        .find(|m| m["after"]["node_type"] == "DataModel" && m["after"]["name"] == "User")
+        .expect("User data model modified");
+    let changes = user["field_changes"]
+        .as_array()
+        .expect("field_changes array");
+
+    let change = |name: &str| {
+        changes
+            .iter()
+            .find(|c| c["name"] == name)
+            .unwrap_or_else(|| panic!("no change for {}: {:?}", name, changes))
+    };
+    assert_eq!(change("name")["change"], "added");
+    assert_eq!(change("name")["type"], "String");
+    assert_eq!(change("age")["change"], "removed");
+    assert_eq!(change("age")["type"], "u32");
+    assert_eq!(change("email")["change"], "retyped");
+    assert_eq!(change("email")["before"], "String");
+    assert_eq!(change("email")["after"], "Option<String>");
+    assert_eq!(changes.len(), 3);
+}
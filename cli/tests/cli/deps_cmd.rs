@@ -160,3 +160,67 @@ fn deps_json_outputs_machine_readable_payload() {
     assert!(payload["data"]["seeds"].is_array());
     assert!(payload["data"]["edges"].is_array());
 }
+
+#[test]
+fn deps_json_reports_call_confidence_and_strategy() {
+    let file = fixture_path("src/testing/python/web/main.py");
+    let out = run_stakgraph(&["--json", "deps", "run_servers", &file]);
+
+    assert_eq!(out.exit_code, 0, "stderr: {}", out.stderr);
+    let payload: Value = serde_json::from_str(&out.stdout).expect("valid json stdout");
+    let edges = payload["data"]["edges"].as_array().expect("edges array");
+    let cleanup = edges
+        .iter()
+        .find(|e| e["target_name"] == "cleanup")
+        .expect("cleanup edge");
+    assert!(cleanup["confidence"].as_f64().unwrap_or(0.0) > 0.0);
+    assert!(cleanup["strategy"].is_string());
+}
+
+#[test]
+fn deps_min_confidence_drops_low_confidence_calls() {
+    let file = fixture_path("src/testing/python/web/main.py");
+    let out = run_stakgraph(&["deps", "run_servers", "--min-confidence", "0.5", &file]);
+    assert_eq!(out.exit_code, 0, "stderr: {}", out.stderr);
+    assert!(out.stdout.contains("cleanup"), "stdout: {}", out.stdout);
+    assert!(!out.stdout.contains("chdir"), "stdout: {}", out.stdout);
+}
+
+#[test]
+fn deps_min_confidence_out_of_range_fails() {
+    let traits = fixture_path("src/testing/rust/src/traits.rs");
+    let out = run_stakgraph(&["deps", "batch_process", "--min-confidence", "2", &traits]);
+    assert_ne!(out.exit_code, 0);
+    assert!(out.stderr.contains("--min-confidence"), "stderr: {}", out.stderr);
+}
+
+#[test]
+fn deps_json_aggregates_call_sites() {
+    let file = fixture_path("src/testing/python/web/main.py");
+    let out = run_stakgraph(&["--json", "deps", "signal_handler", &file]);
+
+    assert_eq!(out.exit_code, 0, "stderr: {}", out.stderr);
+    let payload: Value = serde_json::from_str(&out.stdout).expect("valid json stdout");
+    let edges = payload["data"]["edges"].as_array().expect("edges array");
+    let cleanup = edges
+        .iter()
+        .find(|e| e["target_name"] == "cleanup")
+        .expect("cleanup edge");
+    let sites = cleanup["call_sites"].as_array().expect("call_sites array");
+    assert_eq!(sites.len(), 2, "sites: {:?}", sites);
+    assert_eq!(sites[0]["line"], 56);
+    assert_eq!(sites[1]["line"], 57);
+    assert_eq!(sites[0]["arg_count"], 0);
+}
+
+#[test]
+fn deps_shows_call_site_locations() {
+    let file = fixture_path("src/testing/python/web/main.py");
+    let out = run_stakgraph(&["deps", "signal_handler", &file]);
+    assert_eq!(out.exit_code, 0, "stderr: {}", out.stderr);
+    assert!(
+        out.stdout.contains("L57:5, L58:5"),
+        "stdout: {}",
+        out.stdout
+    );
+}
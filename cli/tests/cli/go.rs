@@ -182,6 +182,17 @@ fn search_go_json_total() {
     assert_eq!(v["data"]["total"], 3);
 }
 
+#[test]
+fn search_go_package_by_import_path() {
+    let dir = fixture_path("src/testing/go");
+    let out = run_stakgraph(&["--json", "search", "gogo/tests", "--type", "Module", &dir]);
+
+    assert_eq!(out.exit_code, 0, "stderr: {}", out.stderr);
+    let v: Value = serde_json::from_str(&out.stdout).expect("invalid JSON");
+    assert_eq!(v["data"]["total"], 1);
+    assert_eq!(v["data"]["results"][0]["fqn"], "gogo/tests");
+}
+
 // ── overview ──────────────────────────────────────────────────────────────────
 
 #[test]
@@ -20,9 +20,11 @@ pub enum Language {
     Cpp,
     Php,
     CSharp,
+    Lua,
+    Zig,
 }
 
-pub const PROGRAMMING_LANGUAGES: [Language; 14] = [
+pub const PROGRAMMING_LANGUAGES: [Language; 16] = [
     Language::Rust,
     Language::Go,
     Language::Typescript,
@@ -37,12 +39,15 @@ pub const PROGRAMMING_LANGUAGES: [Language; 14] = [
     Language::Cpp,
     Language::Php,
     Language::CSharp,
+    Language::Lua,
+    Language::Zig,
 ];
 
 impl Language {
     pub fn annotation_prefix(&self) -> &'static str {
         match self {
             Self::Python | Self::Ruby => "# @ast ",
+            Self::Lua => "-- @ast ",
             _ => "// @ast ",
         }
     }
@@ -72,6 +77,8 @@ impl Language {
             Self::Cpp => vec!["CMakeLists.txt", "Makefile", "meson.build"],
             Self::Php => vec!["composer.json"],
             Self::CSharp => vec![".csproj", ".sln"],
+            Self::Lua => vec![],
+            Self::Zig => vec!["build.zig", "build.zig.zon"],
         }
     }
 
@@ -93,6 +100,8 @@ impl Language {
             Self::Cpp => vec!["cpp", "hpp", "cc", "cxx", "hxx", "h", "cu"],
             Self::Php => vec!["php"],
             Self::CSharp => vec!["cs"],
+            Self::Lua => vec!["lua"],
+            Self::Zig => vec!["zig"],
         }
     }
 
@@ -131,6 +140,8 @@ impl Language {
             Self::Cpp => vec![".git", "build", "out", "CMakeFiles"],
             Self::Php => vec![".git", "vendor"],
             Self::CSharp => vec![".git", "bin", "obj", "packages", ".vs"],
+            Self::Lua => vec![".git", "lua_modules"],
+            Self::Zig => vec![".git", "zig-out", ".zig-cache", "zig-cache"],
         }
     }
 
@@ -162,6 +173,8 @@ impl Language {
             Self::Cpp => Vec::new(),
             Self::Php => Vec::new(),
             Self::CSharp => Vec::new(),
+            Self::Lua => Vec::new(),
+            Self::Zig => Vec::new(),
         }
     }
 
@@ -196,6 +209,8 @@ impl Language {
             Self::Cpp => "",
             Self::Php => "",
             Self::CSharp => "",
+            Self::Lua => "",
+            Self::Zig => "",
         }
         .to_string()
     }
@@ -218,6 +233,8 @@ impl Language {
             Self::Cpp => "--version",
             Self::Php => "--version",
             Self::CSharp => "--version",
+            Self::Lua => "",
+            Self::Zig => "",
         }
         .to_string()
     }
@@ -249,6 +266,8 @@ impl Language {
             Self::Cpp => Vec::new(),
             Self::Php => Vec::new(),
             Self::CSharp => Vec::new(),
+            Self::Lua => Vec::new(),
+            Self::Zig => Vec::new(),
         }
     }
 
@@ -289,6 +308,8 @@ impl Language {
             Self::Php => Vec::new(),
             Self::C => Vec::new(),
             Self::CSharp => Vec::new(),
+            Self::Lua => Vec::new(),
+            Self::Zig => Vec::new(),
         }
     }
 
@@ -360,6 +381,8 @@ impl Display for Language {
             Self::Cpp => "cpp",
             Self::Php => "php",
             Self::CSharp => "csharp",
+            Self::Lua => "lua",
+            Self::Zig => "zig",
         };
         write!(f, "{}", s)
     }
@@ -417,6 +440,10 @@ impl FromStr for Language {
             "CSharp" => Ok(Language::CSharp),
             "c#" => Ok(Language::CSharp),
             "C#" => Ok(Language::CSharp),
+            "lua" => Ok(Language::Lua),
+            "Lua" => Ok(Language::Lua),
+            "zig" => Ok(Language::Zig),
+            "Zig" => Ok(Language::Zig),
 
             _ => Err(Error::validation("unsupported language")),
         }
@@ -1107,6 +1107,8 @@ WHERE ALL(node IN nodes(path) WHERE
     node:Function OR
     node:Request OR
     node:Endpoint OR
+    node:Job OR
+    node:Command OR
     node:Datamodel)
 RETURN path
 `;
@@ -1125,6 +1127,8 @@ WHERE ALL(node IN nodes(path) WHERE
     node:Function OR
     node:Request OR
     node:Endpoint OR
+    node:Job OR
+    node:Command OR
     node:Datamodel)
 RETURN path
 `;
@@ -1345,11 +1349,11 @@ RETURN path
 
 export const IMPORTANCE_GRAPH_PROJECT_QUERY = `
 MATCH (n)
-WHERE n:Function OR n:Class OR n:Trait OR n:Endpoint OR n:Datamodel OR n:Request OR n:Page
+WHERE n:Function OR n:Class OR n:Trait OR n:Endpoint OR n:Job OR n:Command OR n:Datamodel OR n:Request OR n:Page
 WITH collect(n) AS nodes
 UNWIND nodes AS n
 OPTIONAL MATCH (n)-[r:CALLS|HANDLER|RENDERS]->(m)
-WHERE m:Function OR m:Class OR m:Trait OR m:Endpoint OR m:Datamodel OR m:Request OR m:Page
+WHERE m:Function OR m:Class OR m:Trait OR m:Endpoint OR m:Job OR m:Command OR m:Datamodel OR m:Request OR m:Page
 WITH gds.graph.project(
   $graphName,
   n,
@@ -1371,12 +1375,12 @@ RETURN n.ref_id AS ref_id, score
 
 export const IMPORTANCE_DEGREE_QUERY = `
 MATCH (n)
-WHERE n:Function OR n:Class OR n:Trait OR n:Endpoint OR n:Datamodel OR n:Request OR n:Page
+WHERE n:Function OR n:Class OR n:Trait OR n:Endpoint OR n:Job OR n:Command OR n:Datamodel OR n:Request OR n:Page
 OPTIONAL MATCH (caller)-[:CALLS|HANDLER|RENDERS]->(n)
-WHERE caller:Function OR caller:Class OR caller:Trait OR caller:Endpoint OR caller:Datamodel OR caller:Request OR caller:Page
+WHERE caller:Function OR caller:Class OR caller:Trait OR caller:Endpoint OR caller:Job OR caller:Command OR caller:Datamodel OR caller:Request OR caller:Page
 WITH n, count(DISTINCT caller) AS in_degree
 OPTIONAL MATCH (n)-[:CALLS|HANDLER|RENDERS]->(callee)
-WHERE callee:Function OR callee:Class OR callee:Trait OR callee:Endpoint OR callee:Datamodel OR callee:Request OR callee:Page
+WHERE callee:Function OR callee:Class OR callee:Trait OR callee:Endpoint OR callee:Job OR callee:Command OR callee:Datamodel OR callee:Request OR callee:Page
 WITH n, in_degree, count(DISTINCT callee) AS out_degree
 RETURN n.ref_id AS ref_id, in_degree, out_degree, [l IN labels(n) WHERE l <> 'Data_Bank'][0] AS node_type
 `;
@@ -1402,7 +1406,7 @@ SET n.pagerank      = item.pagerank,
 export const GET_NODES_BY_IMPORTANCE_TAG_QUERY = `
 MATCH (n)
 WHERE n.importance_tag = $tag
-  AND (n:Function OR n:Class OR n:Trait OR n:Endpoint OR n:Datamodel OR n:Request OR n:Page)
+  AND (n:Function OR n:Class OR n:Trait OR n:Endpoint OR n:Job OR n:Command OR n:Datamodel OR n:Request OR n:Page)
 RETURN n.ref_id AS ref_id, n.name AS name, n.file AS file,
        [l IN labels(n) WHERE l <> 'Data_Bank'][0] AS label,
        n.pagerank AS pagerank,
@@ -1424,7 +1428,7 @@ YIELD graphName
 export const GET_TOP_NODES_BY_IMPORTANCE_QUERY = `
 MATCH (n)
 WHERE n.pagerank IS NOT NULL
-  AND (n:Function OR n:Class OR n:Trait OR n:Endpoint OR n:Datamodel OR n:Request OR n:Page)
+  AND (n:Function OR n:Class OR n:Trait OR n:Endpoint OR n:Job OR n:Command OR n:Datamodel OR n:Request OR n:Page)
 RETURN n.ref_id AS ref_id, n.name AS name, n.file AS file,
        [l IN labels(n) WHERE l <> 'Data_Bank'][0] AS label,
        n.pagerank AS pagerank,
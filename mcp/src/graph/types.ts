@@ -71,7 +71,18 @@ export type NodeType =
   | "Mock"
   | "Learning"
   | "Scope"
-  | "UserObjective";
+  | "UserObjective"
+  | "Resource"
+  | "Service"
+  | "Workflow"
+  | "WorkflowJob"
+  | "WorkflowStep"
+  | "Queue"
+  | "Job"
+  | "Command"
+  | "Channel"
+  | "ConfigKey"
+  | "Module";
 
 export type EdgeType =
   | "CALLS"
@@ -87,6 +98,17 @@ export type EdgeType =
   | "PARENT_OF"
   | "IMPLEMENTS"
   | "NESTED_IN"
+  | "DEPENDS_ON"
+  | "CONNECTS_TO"
+  | "RUNS"
+  | "PUBLISHES"
+  | "CONSUMES"
+  | "EMITS"
+  | "READS"
+  | "WRITES"
+  | "OVERRIDES"
+  | "THROWS"
+  | "CATCHES"
   | "HAS_CONTRIBUTOR"
   | "HAS_STARS"
   | "HAS_COMMITS"
@@ -229,6 +251,17 @@ export function all_node_types(): NodeType[] {
     "Learning",
     "Scope",
     "UserObjective",
+    "Resource",
+    "Service",
+    "Workflow",
+    "WorkflowJob",
+    "WorkflowStep",
+    "Queue",
+    "Job",
+    "Command",
+    "Channel",
+    "ConfigKey",
+    "Module",
   ];
 }
 
@@ -247,6 +280,17 @@ export function all_edge_types(): EdgeType[] {
     "PARENT_OF",
     "IMPLEMENTS",
     "NESTED_IN",
+    "DEPENDS_ON",
+    "CONNECTS_TO",
+    "RUNS",
+    "PUBLISHES",
+    "CONSUMES",
+    "EMITS",
+    "READS",
+    "WRITES",
+    "OVERRIDES",
+    "THROWS",
+    "CATCHES",
     "HAS_CONTRIBUTOR",
     "HAS_STARS",
     "HAS_COMMITS",
@@ -319,6 +363,17 @@ export function node_type_descriptions(): { [k in NodeType]: string } {
     Learning: "A rule or guideline capturing knowledge about the codebase, linked to scopes that define where it applies.",
     Scope: "A scope label that groups related learnings, such as a technology, pattern, or area of the codebase.",
     UserObjective: "A specific goal or objective that a user wants to achieve within the application.",
+    Resource: "An infrastructure-as-code block (Terraform resource, data source, module, variable or output).",
+    Service: "A deployable service defined by a Dockerfile, docker-compose file or Kubernetes manifest.",
+    Workflow: "A CI workflow, pipeline or Makefile that groups jobs",
+    WorkflowJob: "A CI job or Makefile target",
+    WorkflowStep: "A single step or command run by a CI job",
+    Queue: "A message queue or topic that functions publish to or consume from",
+    Job: "A scheduled job (cron, periodic task or Kubernetes CronJob) whose handler runs on a schedule",
+    Command: "A CLI command or subcommand whose handler runs when it is invoked",
+    Channel: "A realtime event channel (socket.io event, Phoenix or ActionCable channel, WebSocket or SSE stream)",
+    ConfigKey: "A configuration key or environment variable read by code and defined in .env.example, docker-compose or Kubernetes manifests",
+    Module: "A module, package or namespace (Rust mod, Python package, Go/Java/Kotlin package, C# namespace) that contains functions, classes and submodules",
   };
 }
 
@@ -264,6 +264,8 @@ export function getExtensionsForLanguage(language: string): string[] {
     cpp: [".cpp", ".cc", ".cxx", ".hpp", ".hxx", ".h"],
     csharp: [".cs"],
     php: [".php"],
+    lua: [".lua"],
+    zig: [".zig"],
     html: [".html", ".htm"],
     css: [".css", ".scss", ".sass", ".less"],
     shell: [".sh", ".bash", ".zsh"],
@@ -25,7 +25,12 @@ function assignTag(
   thresholds: ImportanceThresholds,
 ): ImportanceTag {
   // Structural overrides — node types that are entry points by definition
-  if (node_type === "Request" || node_type === "Page") {
+  if (
+    node_type === "Request" ||
+    node_type === "Page" ||
+    node_type === "Job" ||
+    node_type === "Command"
+  ) {
     return ImportanceTag.EntryPoint;
   }
   // Endpoints with no callers in the projected graph are pure API entry points
@@ -91,6 +96,8 @@ export async function runImportanceScoring(): Promise<ImportanceResult> {
       (n) =>
         n.node_type !== "Request" &&
         n.node_type !== "Page" &&
+        n.node_type !== "Job" &&
+        n.node_type !== "Command" &&
         !(n.node_type === "Endpoint" && n.in_degree === 0),
     );
     const sortedEntry = nonStructural